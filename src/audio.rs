@@ -1715,11 +1715,12 @@ enum SfxKind {
     LevelClear,
     MaskCrack,
     Elevator,
+    GlassShatter,
 }
 
 /// All kinds, in pre-render order (the combat sounds first — they are the
 /// expensive ones and the ones a firefight needs early).
-const SFX_KINDS: [SfxKind; 16] = [
+const SFX_KINDS: [SfxKind; 17] = [
     SfxKind::AttackGun,
     SfxKind::AttackMachinegun,
     SfxKind::AttackShotgun,
@@ -1736,6 +1737,7 @@ const SFX_KINDS: [SfxKind; 16] = [
     SfxKind::LevelClear,
     SfxKind::MaskCrack,
     SfxKind::Elevator,
+    SfxKind::GlassShatter,
 ];
 
 /// Where a pre-rendered voice plugs back into the live bus at play time: the
@@ -1787,6 +1789,7 @@ impl SfxKind {
             SfxKind::LevelClear => (SfxRoute::Room, 0.6, 0.0),
             SfxKind::MaskCrack => (SfxRoute::Room, 0.55, 0.0),
             SfxKind::Elevator => (SfxRoute::Room, 1.5, 0.0),
+            SfxKind::GlassShatter => (SfxRoute::Room, 0.7, 0.05),
         };
         SfxSpec {
             route,
@@ -2932,6 +2935,35 @@ impl AudioEngine {
         self.tone(1318.5, 1318.5, t + 0.78, 0.45, 0.11, OscillatorType::Sine);
     }
 
+    /// A pane of glass bursting — a bright crack, then a shower of tinkling
+    /// shards settling over half a second.
+    pub fn play_glass_shatter(&self) {
+        if !self.enabled.get() {
+            return; // sound off: build NO nodes (the context is suspended anyway)
+        }
+        if self.play_baked(SfxKind::GlassShatter) {
+            return;
+        }
+        self.synth_glass_shatter();
+    }
+
+    /// Live synthesis of [`Self::play_glass_shatter`] (also pre-rendered).
+    fn synth_glass_shatter(&self) {
+        let t = self.t0();
+        // The crack: a hard high-passed burst sweeping down.
+        self.noise(t, 0.12, 0.45, BiquadFilterType::Highpass, 7000.0, 2500.0);
+        // The shards: short band-passed ticks falling off in pitch and level.
+        for (i, f) in [5200.0, 6100.0, 4300.0, 5600.0, 3900.0, 4800.0]
+            .into_iter()
+            .enumerate()
+        {
+            let at = t + (0.04 + i as f64 * 0.07) * self.jit(0.2);
+            let peak = 0.18 * (1.0 - i as f64 * 0.12);
+            self.noise(at, 0.05, peak, BiquadFilterType::Bandpass, f, f * 0.8);
+            self.tone(f * 0.5, f * 0.5, at, 0.04, peak * 0.3, OscillatorType::Sine);
+        }
+    }
+
     // --- pre-rendered voices -----------------------------------------------
     //
     // Building a fresh Web Audio graph per shot (oscillators + envelopes +
@@ -3009,6 +3041,7 @@ impl AudioEngine {
             SfxKind::LevelClear => self.synth_level_clear(),
             SfxKind::MaskCrack => self.synth_mask_crack(),
             SfxKind::Elevator => self.synth_elevator(),
+            SfxKind::GlassShatter => self.synth_glass_shatter(),
         }
     }
 
@@ -0,0 +1,176 @@
+//! `open-miami-sim`: run a floor or the whole campaign headlessly — with the
+//! bot or a recorded replay — and print a JSON report (see
+//! `open_miami::report`), or estimate every floor's difficulty over many
+//! seeds and bot skills (see `open_miami::difficulty`).
+
+use open_miami::difficulty::{estimate, DifficultyOptions};
+use open_miami::ecs::DEFAULT_SEED;
+use open_miami::levels::{level_index_for_floor_id, LEVEL_COUNT};
+use open_miami::replay::Replay;
+use open_miami::report::{run_bot, run_replay, RunOptions};
+use open_miami::sim::BotSkill;
+use std::process::ExitCode;
+use std::time::Duration;
+
+const USAGE: &str = "\
+usage: open-miami-sim [--floor ID | --campaign] [--replay FILE | --difficulty] [options]
+
+  --floor ID         play one floor (its number, as in ?floor=ID)
+  --campaign         play every floor in order with the bot (the default)
+  --replay FILE      re-run a recorded floor attempt instead of the bot
+  --difficulty       play each floor once per seed and skill and rank the
+                     floors (a table and death heatmaps; fails if a floor
+                     was never cleared)
+  --seed N           seed of the first floor (default 12345)
+  --dt SECS          seconds per frame (default 1/60)
+  --max-seconds S    bot: give up on a floor attempt after S game seconds
+  --attempts N       bot: tries per floor before the run stops (default 3)
+  --speed X          run at X times real time (default: as fast as possible)
+  --seeds N          difficulty: seeds per skill (default 20)
+  --skill R:A        difficulty: a bot skill, reaction seconds and aim error
+                     in radians; repeat for several (default 0:0 and 0.25:0.08)";
+
+struct Args {
+    floor: Option<usize>,
+    replay: Option<String>,
+    seed: u32,
+    opts: RunOptions,
+    speed: Option<f32>,
+    difficulty: Option<DifficultyOptions>,
+}
+
+/// The parsed command line, or `None` for `--help`.
+fn parse_args(mut argv: impl Iterator<Item = String>) -> Result<Option<Args>, String> {
+    let mut args = Args {
+        floor: None,
+        replay: None,
+        seed: DEFAULT_SEED,
+        opts: RunOptions::default(),
+        speed: None,
+        difficulty: None,
+    };
+    let mut max_seconds = None;
+    let mut seeds = None;
+    let mut skills = Vec::new();
+    while let Some(flag) = argv.next() {
+        let mut value = |name: &str| argv.next().ok_or(format!("{name} needs a value"));
+        match flag.as_str() {
+            "--floor" => args.floor = Some(parse(&value("--floor")?)?),
+            "--campaign" => args.floor = None,
+            "--replay" => args.replay = Some(value("--replay")?),
+            "--seed" => args.seed = parse(&value("--seed")?)?,
+            "--dt" => args.opts.dt = parse(&value("--dt")?)?,
+            "--max-seconds" => max_seconds = Some(parse::<f32>(&value("--max-seconds")?)?),
+            "--attempts" => args.opts.attempts = parse(&value("--attempts")?)?,
+            "--speed" => args.speed = Some(parse(&value("--speed")?)?),
+            "--difficulty" => args.difficulty = Some(DifficultyOptions::default()),
+            "--seeds" => seeds = Some(parse(&value("--seeds")?)?),
+            "--skill" => skills.push(parse_skill(&value("--skill")?)?),
+            "-h" | "--help" => return Ok(None),
+            other => return Err(format!("unknown argument `{other}`")),
+        }
+    }
+    if args.opts.dt.is_nan() || args.opts.dt <= 0.0 {
+        return Err("--dt must be positive".to_string());
+    }
+    if let Some(speed) = args.speed {
+        if speed.is_nan() || speed <= 0.0 {
+            return Err("--speed must be positive".to_string());
+        }
+    }
+    if args.replay.is_some() && args.floor.is_some() {
+        return Err("a replay plays its own floor: drop --floor".to_string());
+    }
+    if let Some(secs) = max_seconds {
+        args.opts.max_frames = (secs / args.opts.dt).ceil() as usize;
+    }
+    if let Some(difficulty) = &mut args.difficulty {
+        if args.replay.is_some() {
+            return Err("--difficulty plays the bot: drop --replay".to_string());
+        }
+        difficulty.dt = args.opts.dt;
+        difficulty.first_seed = args.seed;
+        if max_seconds.is_some() {
+            difficulty.max_frames = args.opts.max_frames;
+        }
+        if let Some(n) = seeds {
+            difficulty.seeds = n;
+        }
+        if !skills.is_empty() {
+            difficulty.skills = skills;
+        }
+    } else if seeds.is_some() || !skills.is_empty() {
+        return Err("--seeds and --skill need --difficulty".to_string());
+    }
+    Ok(Some(args))
+}
+
+/// `REACTION:AIM_ERROR`, e.g. `0.25:0.08`.
+fn parse_skill(s: &str) -> Result<BotSkill, String> {
+    let (reaction, aim_error) = s.split_once(':').ok_or(format!("bad skill `{s}`"))?;
+    Ok(BotSkill {
+        reaction: parse(reaction)?,
+        aim_error: parse(aim_error)?,
+    })
+}
+
+fn parse<T: std::str::FromStr>(s: &str) -> Result<T, String> {
+    s.parse().map_err(|_| format!("bad value `{s}`"))
+}
+
+/// The text to print, and whether the run passed.
+fn run(args: Args) -> Result<(String, bool), String> {
+    // Sleep each frame's share of real time at the chosen speed (a speed so
+    // slow the share overflows a Duration sleeps as long as one can).
+    let mut pace = |dt: f32| {
+        if let Some(speed) = args.speed {
+            let share = Duration::try_from_secs_f32(dt / speed).unwrap_or(Duration::MAX);
+            std::thread::sleep(share);
+        }
+    };
+    let levels: Vec<usize> = match args.floor {
+        Some(id) => vec![level_index_for_floor_id(id).ok_or(format!("unknown floor {id}"))?],
+        None => (0..LEVEL_COUNT).collect(),
+    };
+    if let Some(difficulty) = &args.difficulty {
+        let report = estimate(&levels, difficulty);
+        return Ok((report.to_text(), report.unwinnable().is_empty()));
+    }
+    let report = if let Some(path) = &args.replay {
+        let text = std::fs::read_to_string(path).map_err(|e| format!("{path}: {e}"))?;
+        let replay = Replay::parse(&text).map_err(|e| format!("{path}: {e}"))?;
+        run_replay(&replay, &mut pace).map_err(|e| e.to_string())?
+    } else {
+        run_bot(&levels, args.seed, &args.opts, &mut pace)
+    };
+    Ok((report.to_json(), true))
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(msg) => {
+            eprintln!("open-miami-sim: {msg}\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+    match run(args) {
+        Ok((out, passed)) => {
+            println!("{out}");
+            if passed {
+                ExitCode::SUCCESS
+            } else {
+                eprintln!("open-miami-sim: a floor was never cleared");
+                ExitCode::FAILURE
+            }
+        }
+        Err(msg) => {
+            eprintln!("open-miami-sim: {msg}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -32,6 +32,36 @@ pub fn swept_circle_circle_collision(
     cx * cx + cy * cy < r * r
 }
 
+/// Swept circle-vs-circle time of impact: the fraction (`0..=1`) of the move
+/// `from -> to` at which a circle of `radius` first touches the circle at
+/// `center` — `0.0` if they already overlap, `None` if they never touch.
+pub fn swept_circle_circle_toi(
+    from: Vec2,
+    to: Vec2,
+    radius: f32,
+    center: Vec2,
+    target_radius: f32,
+) -> Option<f32> {
+    let r = radius + target_radius;
+    let d = to - from;
+    let f = from - center;
+    let c = f.dot(f) - r * r;
+    if c < 0.0 {
+        return Some(0.0);
+    }
+    let a = d.dot(d);
+    if a <= f32::EPSILON {
+        return None;
+    }
+    let b = f.dot(d);
+    let disc = b * b - a * c;
+    if disc < 0.0 {
+        return None;
+    }
+    let t = (-b - disc.sqrt()) / a;
+    (0.0..=1.0).contains(&t).then_some(t)
+}
+
 pub fn circle_rect_collision(
     circle_pos: Vec2,
     radius: f32,
@@ -72,6 +102,30 @@ pub fn swept_circle_rect_collision(
         || line_rect_intersection(from, to, ix, iy, iw, ih)
 }
 
+/// Swept circle-vs-rect time of impact: the fraction (`0..=1`) of the move
+/// `from -> to` at which a circle of `radius` first touches the rectangle
+/// (inflated by `radius`, as in [`swept_circle_rect_collision`]) — `0.0` if
+/// it starts inside, `None` if it never touches.
+pub fn swept_circle_rect_toi(
+    from: Vec2,
+    to: Vec2,
+    radius: f32,
+    rect_x: f32,
+    rect_y: f32,
+    rect_w: f32,
+    rect_h: f32,
+) -> Option<f32> {
+    ray_rect_distance(
+        from,
+        to - from,
+        rect_x - radius,
+        rect_y - radius,
+        rect_w + radius * 2.0,
+        rect_h + radius * 2.0,
+    )
+    .filter(|&t| t <= 1.0)
+}
+
 pub fn point_in_rect(point: Vec2, rect_x: f32, rect_y: f32, rect_w: f32, rect_h: f32) -> bool {
     point.x >= rect_x
         && point.x <= rect_x + rect_w
@@ -116,14 +170,113 @@ fn line_rect_intersection(
         || line_segment_intersection(line_start, line_end, bottom_left, top_left)
 }
 
-/// Check if there's a clear line of sight between two points (no walls blocking)
-pub fn has_line_of_sight(from: Vec2, to: Vec2, walls: &[Wall]) -> bool {
-    for wall in walls {
+/// A swinging door's leaf as sight sees it: the segment from its hinge to
+/// its tip (see `components::Door`). Sight and bullets stop at it; movement
+/// and the navigation grids do not treat it as a wall — walking into it
+/// pushes it open.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DoorLeaf {
+    pub hinge: Vec2,
+    pub tip: Vec2,
+}
+
+impl DoorLeaf {
+    /// Whether the segment `from -> to` crosses the leaf.
+    pub fn crosses(&self, from: Vec2, to: Vec2) -> bool {
+        line_segment_intersection(from, to, self.hinge, self.tip)
+    }
+
+    /// The fraction (`0..=1`) of the segment `from -> to` at which it
+    /// crosses the leaf, if it does.
+    pub fn crossing(&self, from: Vec2, to: Vec2) -> Option<f32> {
+        self.ray_distance(from, to - from).filter(|&t| t <= 1.0)
+    }
+
+    /// Distance from `origin` along the unit direction `dir` to where the
+    /// ray meets the leaf, if it does (in units of `dir` when it is not a
+    /// unit vector).
+    pub fn ray_distance(&self, origin: Vec2, dir: Vec2) -> Option<f32> {
+        let cross = |a: Vec2, b: Vec2| a.x * b.y - a.y * b.x;
+        let edge = self.tip - self.hinge;
+        let denom = cross(dir, edge);
+        if denom.abs() <= f32::EPSILON {
+            return None;
+        }
+        let w = self.hinge - origin;
+        let t = cross(w, edge) / denom;
+        let s = cross(w, dir) / denom;
+        (t >= 0.0 && (0.0..=1.0).contains(&s)).then_some(t)
+    }
+
+    /// The point of the leaf nearest `point`, and how far along the leaf
+    /// (from the hinge) it lies.
+    pub fn closest_point(&self, point: Vec2) -> (Vec2, f32) {
+        let d = self.tip - self.hinge;
+        let len = d.length();
+        if len <= f32::EPSILON {
+            return (self.hinge, 0.0);
+        }
+        let along = ((point - self.hinge).dot(d) / len).clamp(0.0, len);
+        (self.hinge + d * (along / len), along)
+    }
+}
+
+/// Check if there's a clear line of sight between two points: no solid wall
+/// and no door leaf in the way (glass walls let sight through).
+pub fn has_line_of_sight(from: Vec2, to: Vec2, walls: &[Wall], doors: &[DoorLeaf]) -> bool {
+    for wall in walls.iter().filter(|w| !w.is_glass()) {
         if line_rect_intersection(from, to, wall.x, wall.y, wall.width, wall.height) {
             return false; // Wall blocks line of sight
         }
     }
-    true // No walls blocking
+    !doors.iter().any(|door| door.crosses(from, to))
+}
+
+/// Distance from `origin` along the unit direction `dir` to the first wall
+/// the ray enters or door leaf it meets, capped at `max` (`0.0` when
+/// `origin` is inside a wall).
+pub fn raycast_walls(origin: Vec2, dir: Vec2, max: f32, walls: &[Wall], doors: &[DoorLeaf]) -> f32 {
+    walls
+        .iter()
+        .filter_map(|w| ray_rect_distance(origin, dir, w.x, w.y, w.width, w.height))
+        .chain(
+            doors
+                .iter()
+                .filter_map(|door| door.ray_distance(origin, dir)),
+        )
+        .fold(max, f32::min)
+}
+
+/// Ray vs rectangle (slab test): the ray parameter where it enters the
+/// rectangle, if it does.
+fn ray_rect_distance(
+    origin: Vec2,
+    dir: Vec2,
+    rect_x: f32,
+    rect_y: f32,
+    rect_w: f32,
+    rect_h: f32,
+) -> Option<f32> {
+    let mut near = 0.0f32;
+    let mut far = f32::INFINITY;
+    for (o, d, lo, hi) in [
+        (origin.x, dir.x, rect_x, rect_x + rect_w),
+        (origin.y, dir.y, rect_y, rect_y + rect_h),
+    ] {
+        if d == 0.0 {
+            if o < lo || o > hi {
+                return None;
+            }
+        } else {
+            let (a, b) = ((lo - o) / d, (hi - o) / d);
+            near = near.max(a.min(b));
+            far = far.min(a.max(b));
+            if near > far {
+                return None;
+            }
+        }
+    }
+    Some(near)
 }
 
 /// Check if there's a clear line of sight with inflated wall boundaries
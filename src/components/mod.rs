@@ -1,5 +1,7 @@
 // Game Components - Pure data structures
+use crate::ecs::Entity;
 use crate::math::Vec2;
+use std::fmt;
 
 /// Position in 2D space
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -45,7 +47,8 @@ impl Velocity {
     }
 }
 
-/// Health component
+/// Health component. Game code writes it through `World::update_component`,
+/// so `Changed<Health>` means the value moved (a hit on a corpse is quiet).
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Health {
     pub current: i32,
@@ -160,6 +163,10 @@ pub struct AI {
     pub attack_range: f32,
     pub attack_cooldown: f32,
     pub attack_timer: f32,
+    /// Seconds a gun-carrying rogue has held the player in its sights (0 =
+    /// not aiming). It fires once this passes its gun's wind-up (see
+    /// [`crate::systems::combat::enemy_gun`]); the climb is the telegraph.
+    pub aim_timer: f32,
 
     // State transition timers
     pub state_timer: f32,
@@ -202,9 +209,6 @@ pub struct PassiveAI {
     pub fidget_heading: f32,
     /// True once the bot has arrived inside its zone (it then only fidgets).
     pub arrived: bool,
-    /// Health at the previous tick: a drop means the bot was hurt, which
-    /// flips every passive on the floor hostile.
-    pub last_health: i32,
 }
 
 impl PassiveAI {
@@ -221,7 +225,6 @@ impl PassiveAI {
             fidget_timer: 0.0,
             fidget_heading: face.unwrap_or(0.0),
             arrived: false,
-            last_health: i32::MAX,
         }
     }
 }
@@ -245,6 +248,7 @@ impl AI {
             attack_range: 40.0,
             attack_cooldown: 1.0,
             attack_timer: 0.0,
+            aim_timer: 0.0,
             state_timer: 0.0,
             spot_duration: 0.3,
             unsure_check_duration: 2.0,
@@ -276,6 +280,15 @@ impl AI {
     pub fn reset_attack_timer(&mut self) {
         self.attack_timer = self.attack_cooldown;
     }
+
+    /// Unsure, and on its way to check a noise it heard (see
+    /// `systems::noise`): the spot it checks is where it was sent, not where
+    /// it first saw the player.
+    pub fn investigating(&self) -> bool {
+        self.state == AIState::SpottedUnsure
+            && self.check_position.is_some()
+            && self.check_position == self.last_known_player_position
+    }
 }
 
 impl Default for AI {
@@ -286,30 +299,16 @@ impl Default for AI {
 
 impl Copy for AI {}
 
-/// Weapon type enum
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub enum WeaponType {
-    Pistol,
-    Shotgun,
-    MachineGun,
-    Melee,
-}
+/// A weapon: an index into the weapons table ([`crate::weapons::WEAPONS`],
+/// compiled from `weapons/weapons.json`). Each entry has a constant
+/// (`WeaponType::PISTOL`...) generated with the table; its stats are on
+/// [`WeaponType::def`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WeaponType(pub(crate) u8);
 
-impl WeaponType {
-    /// Rounds in a full magazine. Melee has no magazine (see
-    /// [`WeaponType::is_melee`]); its value here is only a nominal cap.
-    pub const fn magazine(self) -> i32 {
-        match self {
-            WeaponType::Pistol => 12,
-            WeaponType::Shotgun => 6,
-            WeaponType::MachineGun => 30,
-            WeaponType::Melee => 999,
-        }
-    }
-
-    /// Melee weapons never run dry.
-    pub const fn is_melee(self) -> bool {
-        matches!(self, WeaponType::Melee)
+impl fmt::Debug for WeaponType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.id())
     }
 }
 
@@ -478,7 +477,7 @@ impl Fists {
 pub enum FinisherKind {
     /// Unarmed: drop onto the downed bot and pound — three quick hits.
     Pound,
-    /// Metal bar (or an empty gun swung like one): one overhead blow.
+    /// Bar or blade (or an empty gun swung like one): one overhead blow.
     Overhead,
     /// A loaded gun: one point-blank shot straight down. Costs a round.
     Execute(WeaponType),
@@ -601,8 +600,15 @@ pub struct Bullet {
     pub speed: f32,
     pub lifetime: f32,
     pub max_lifetime: f32,
+    /// The rogue that fired it, or `None` for the player's rounds. A player
+    /// round only hits enemies; a rogue's only hits the player.
+    pub shooter: Option<Entity>,
 }
 
+/// Muzzle speed of a rogue's round (px/s): slower than the player's so a
+/// telegraphed shot can still be sidestepped.
+pub const ENEMY_BULLET_SPEED: f32 = 560.0;
+
 impl Bullet {
     pub fn new(weapon_type: WeaponType, damage: i32) -> Self {
         Bullet {
@@ -611,6 +617,16 @@ impl Bullet {
             speed: 800.0,  // pixels per second
             lifetime: 3.0, // 3 seconds max lifetime
             max_lifetime: 3.0,
+            shooter: None,
+        }
+    }
+
+    /// A round fired by the rogue `shooter`.
+    pub fn enemy(weapon_type: WeaponType, damage: i32, shooter: Entity) -> Self {
+        Bullet {
+            speed: ENEMY_BULLET_SPEED,
+            shooter: Some(shooter),
+            ..Bullet::new(weapon_type, damage)
         }
     }
 
@@ -627,20 +643,13 @@ impl Weapon {
 
     /// A weapon holding `ammo` rounds (clamped to its magazine).
     pub fn with_ammo(weapon_type: WeaponType, ammo: i32) -> Self {
-        let (damage, fire_rate) = match weapon_type {
-            WeaponType::Pistol => (50, 0.5),
-            WeaponType::Shotgun => (80, 1.0),
-            WeaponType::MachineGun => (30, 0.1),
-            WeaponType::Melee => (100, 0.5),
-        };
-        let max_ammo = weapon_type.magazine();
-
+        let def = weapon_type.def();
         Weapon {
             weapon_type,
-            damage,
-            ammo: ammo.clamp(0, max_ammo),
-            max_ammo,
-            fire_rate,
+            damage: def.damage,
+            ammo: ammo.clamp(0, def.magazine),
+            max_ammo: def.magazine,
+            fire_rate: def.fire_rate,
             fire_timer: 0.0,
         }
     }
@@ -692,14 +701,18 @@ pub struct Downed;
 pub enum GameEvent {
     /// The player fired (or swung) their weapon; one per round.
     PlayerFired(WeaponType),
+    /// A rogue fired its gun; one per trigger pull (a shotgun's pellets
+    /// count once).
+    EnemyFired(WeaponType),
     /// The player pulled the trigger on an empty gun.
     DryFire,
     /// A player attack connected with an enemy.
     EnemyHit { by: WeaponType },
     /// An enemy's health just reached zero.
     EnemyDown,
-    /// The player took damage (and survived the frame or not).
-    PlayerHurt,
+    /// The player took damage (and survived the frame or not) from enemy
+    /// `by`.
+    PlayerHurt { by: Entity },
     /// The player picked a weapon up off the floor.
     Pickup,
     /// The player threw their weapon.
@@ -715,6 +728,33 @@ pub enum GameEvent {
     /// A finisher ran to completion (the victim died at the final impact).
     /// Drives the tutorial `finish` gate.
     FinisherDone,
+    /// A swinging door slammed into a rogue (knockdown).
+    DoorSlam,
+    /// A glass wall shattered (it is gone from the world): the pane, for the
+    /// debris.
+    GlassShatter(crate::ecs::Wall),
+}
+
+impl GameEvent {
+    /// The variant's name, payload left out (report / histogram keys).
+    pub const fn name(&self) -> &'static str {
+        match self {
+            GameEvent::PlayerFired(_) => "PlayerFired",
+            GameEvent::EnemyFired(_) => "EnemyFired",
+            GameEvent::DryFire => "DryFire",
+            GameEvent::EnemyHit { .. } => "EnemyHit",
+            GameEvent::EnemyDown => "EnemyDown",
+            GameEvent::PlayerHurt { .. } => "PlayerHurt",
+            GameEvent::Pickup => "Pickup",
+            GameEvent::Throw => "Throw",
+            GameEvent::ThrownImpact => "ThrownImpact",
+            GameEvent::PunchLanded => "PunchLanded",
+            GameEvent::StrikeLanded => "StrikeLanded",
+            GameEvent::FinisherDone => "FinisherDone",
+            GameEvent::DoorSlam => "DoorSlam",
+            GameEvent::GlassShatter(_) => "GlassShatter",
+        }
+    }
 }
 
 /// Cached pathfinding result for an entity that navigates around walls (a
@@ -885,6 +925,59 @@ impl Zone {
     }
 }
 
+/// A swinging door: a leaf pivoting on its hinge at `(x, y)`, pushed open by
+/// whoever walks into it and carrying the shove on as spin (see
+/// `systems::door::DoorSystem`). The leaf blocks sight and bullets; a fast
+/// swing into a rogue knocks it down.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Door {
+    pub x: f32,
+    pub y: f32,
+    /// Heading (radians) of the leaf when shut.
+    pub closed: f32,
+    pub length: f32,
+    /// Radians the leaf has swung from shut, within
+    /// `±systems::door::DOOR_MAX_SWING`.
+    pub swing: f32,
+    /// Angular velocity (rad/s) left over from the last shove.
+    pub spin: f32,
+}
+
+impl Door {
+    pub fn from_def(def: &crate::scenario::DoorDef) -> Self {
+        Door {
+            x: def.x,
+            y: def.y,
+            closed: def.angle.to_radians(),
+            length: def.length,
+            swing: 0.0,
+            spin: 0.0,
+        }
+    }
+
+    pub fn hinge(&self) -> Vec2 {
+        Vec2::new(self.x, self.y)
+    }
+
+    /// Where the leaf lies right now.
+    pub fn leaf(&self) -> crate::collision::DoorLeaf {
+        self.leaf_at(self.closed + self.swing)
+    }
+
+    /// Where the leaf lies when shut: the doorway it fills.
+    pub fn doorway(&self) -> crate::collision::DoorLeaf {
+        self.leaf_at(self.closed)
+    }
+
+    fn leaf_at(&self, heading: f32) -> crate::collision::DoorLeaf {
+        let hinge = self.hinge();
+        crate::collision::DoorLeaf {
+            hinge,
+            tip: hinge + Vec2::new(heading.cos(), heading.sin()) * self.length,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -948,7 +1041,7 @@ mod tests {
 
     #[test]
     fn test_weapon_pistol_stats() {
-        let weapon = Weapon::new(WeaponType::Pistol);
+        let weapon = Weapon::new(WeaponType::PISTOL);
         assert_eq!(weapon.damage, 50);
         assert_eq!(weapon.max_ammo, 12);
         assert_eq!(weapon.ammo, 12);
@@ -957,14 +1050,14 @@ mod tests {
 
     #[test]
     fn test_weapon_shotgun_stats() {
-        let weapon = Weapon::new(WeaponType::Shotgun);
+        let weapon = Weapon::new(WeaponType::SHOTGUN);
         assert_eq!(weapon.damage, 80);
         assert_eq!(weapon.max_ammo, 6);
     }
 
     #[test]
     fn test_weapon_fire() {
-        let mut weapon = Weapon::new(WeaponType::Pistol);
+        let mut weapon = Weapon::new(WeaponType::PISTOL);
         assert!(weapon.can_fire());
 
         weapon.fire();
@@ -977,14 +1070,14 @@ mod tests {
 
     #[test]
     fn test_weapon_empty_ammo() {
-        let mut weapon = Weapon::new(WeaponType::Pistol);
+        let mut weapon = Weapon::new(WeaponType::PISTOL);
         weapon.ammo = 0;
         assert!(!weapon.can_fire());
     }
 
     #[test]
     fn test_weapon_update_timer() {
-        let mut weapon = Weapon::new(WeaponType::Pistol);
+        let mut weapon = Weapon::new(WeaponType::PISTOL);
         weapon.fire();
 
         assert_eq!(weapon.fire_timer, 0.5);
@@ -999,27 +1092,27 @@ mod tests {
 
     #[test]
     fn test_weapon_melee_never_runs_dry() {
-        let mut weapon = Weapon::new(WeaponType::Melee);
+        let mut weapon = Weapon::new(WeaponType::MELEE);
         let before = weapon.ammo;
         weapon.fire();
         assert_eq!(weapon.ammo, before); // no round spent
         weapon.fire_timer = 0.0;
         assert!(weapon.can_fire());
-        assert!(!Weapon::with_ammo(WeaponType::Melee, 0).is_dry());
+        assert!(!Weapon::with_ammo(WeaponType::MELEE, 0).is_dry());
     }
 
     #[test]
     fn test_weapon_with_ammo_clamps_to_magazine() {
-        assert_eq!(Weapon::with_ammo(WeaponType::Pistol, 5).ammo, 5);
-        assert_eq!(Weapon::with_ammo(WeaponType::Pistol, 99).ammo, 12);
-        assert_eq!(Weapon::with_ammo(WeaponType::Shotgun, -3).ammo, 0);
-        assert_eq!(WeaponPickup::with_ammo(WeaponType::MachineGun, 7).ammo, 7);
-        assert_eq!(WeaponPickup::new(WeaponType::Shotgun).ammo, 6);
+        assert_eq!(Weapon::with_ammo(WeaponType::PISTOL, 5).ammo, 5);
+        assert_eq!(Weapon::with_ammo(WeaponType::PISTOL, 99).ammo, 12);
+        assert_eq!(Weapon::with_ammo(WeaponType::SHOTGUN, -3).ammo, 0);
+        assert_eq!(WeaponPickup::with_ammo(WeaponType::MACHINEGUN, 7).ammo, 7);
+        assert_eq!(WeaponPickup::new(WeaponType::SHOTGUN).ammo, 6);
     }
 
     #[test]
     fn test_weapon_is_dry() {
-        let mut weapon = Weapon::with_ammo(WeaponType::Pistol, 1);
+        let mut weapon = Weapon::with_ammo(WeaponType::PISTOL, 1);
         assert!(!weapon.is_dry());
         weapon.fire();
         assert!(!weapon.is_dry()); // still on cooldown
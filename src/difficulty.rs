@@ -0,0 +1,433 @@
+//! Monte-Carlo difficulty estimates: every floor played many times by the
+//! campaign bot ([`FloorRun::bot_frame`]) over a spread of RNG seeds and bot
+//! skills ([`BotSkill`]), one attempt per trial.
+//!
+//! Per floor it reports the win rate, the median clear time, where the
+//! player died and which rogue did it (by spawn point, so the level JSON can
+//! be looked up), then ranks the floors hardest first and draws a death
+//! heatmap for each. A floor no trial won is flagged: that is how an edit
+//! that made a floor unwinnable shows up. `open-miami-sim --difficulty`
+//! prints the report.
+
+use crate::components::{Boss, GameEvent, AI};
+use crate::ecs::Entity;
+use crate::levels::{floor_def, floor_title};
+use crate::math::Vec2;
+use crate::report::Outcome;
+use crate::sim::{BotSkill, FloorRun};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// Side of a heatmap cell, in world pixels.
+pub const HEAT_CELL: f32 = 100.0;
+
+/// Knobs for a batch.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DifficultyOptions {
+    /// Seconds per frame.
+    pub dt: f32,
+    /// Frame budget per trial; running out counts as a timeout.
+    pub max_frames: usize,
+    /// Seeds per skill.
+    pub seeds: usize,
+    /// The first seed; the others are derived from it.
+    pub first_seed: u32,
+    /// Every seed is played at each of these skills.
+    pub skills: Vec<BotSkill>,
+}
+
+impl Default for DifficultyOptions {
+    fn default() -> Self {
+        DifficultyOptions {
+            dt: 1.0 / 60.0,
+            max_frames: 60 * 180,
+            seeds: 20,
+            first_seed: 1,
+            skills: vec![
+                BotSkill::default(),
+                BotSkill {
+                    reaction: 0.25,
+                    aim_error: 0.08,
+                },
+            ],
+        }
+    }
+}
+
+impl DifficultyOptions {
+    /// The world seeds the trials start from.
+    pub fn seed_list(&self) -> Vec<u32> {
+        (0..self.seeds as u32)
+            .map(|i| self.first_seed.wrapping_add(i).wrapping_mul(2_654_435_761))
+            .collect()
+    }
+}
+
+/// Where and how one trial ended in a death.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Death {
+    pub pos: Vec2,
+    /// Simulated seconds into the attempt.
+    pub seconds: f32,
+    /// Who landed the hit (see [`killer_label`]).
+    pub killer: String,
+}
+
+/// One floor attempt.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trial {
+    pub seed: u32,
+    pub skill: BotSkill,
+    /// Cleared (extracted), died or timed out.
+    pub outcome: Outcome,
+    pub seconds: f32,
+    pub death: Option<Death>,
+}
+
+/// Play `level` once from `seed` with the bot at `skill`.
+pub fn trial(level: usize, seed: u32, skill: BotSkill, opts: &DifficultyOptions) -> Trial {
+    let mut run = FloorRun::start(level, seed);
+    run.sim.bot_skill = skill;
+    let mut trial = Trial {
+        seed,
+        skill,
+        outcome: Outcome::Timeout,
+        seconds: 0.0,
+        death: None,
+    };
+    let mut last_hit = None;
+    for _ in 0..opts.max_frames {
+        run.bot_frame(opts.dt);
+        trial.seconds += opts.dt;
+        for event in run.sim.frame_events() {
+            if let GameEvent::PlayerHurt { by } = *event {
+                last_hit = Some(by);
+            }
+        }
+        if run.extracted.is_some() {
+            trial.outcome = Outcome::Cleared;
+            break;
+        }
+        if !run.sim.player_alive() {
+            trial.outcome = Outcome::Died;
+            trial.death = Some(Death {
+                pos: run.sim.player_position().unwrap_or(Vec2::zero()),
+                seconds: trial.seconds,
+                killer: last_hit.map_or("other".to_string(), |e| killer_label(&run, e)),
+            });
+            break;
+        }
+    }
+    trial
+}
+
+/// A rogue as a level author knows it: `"boss"`, or its behaviour and spawn
+/// point, e.g. `"wandering rogue at (820, 410)"` (`"civilian"` for a crowd
+/// bot turned hostile).
+pub fn killer_label(run: &FloorRun, enemy: Entity) -> String {
+    let world = &run.sim.world;
+    if world.has_component::<Boss>(enemy) {
+        return "boss".to_string();
+    }
+    match world.get_component::<AI>(enemy) {
+        Some(ai) => {
+            let kind = if ai.passive.is_some() {
+                "civilian".to_string()
+            } else {
+                format!("{:?} rogue", ai.initial_type).to_lowercase()
+            };
+            format!(
+                "{kind} at ({:.0}, {:.0})",
+                ai.spawn_position.x, ai.spawn_position.y
+            )
+        }
+        None => "other".to_string(),
+    }
+}
+
+/// Every trial of one floor, summed up.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FloorDifficulty {
+    pub level: usize,
+    pub floor_id: usize,
+    pub title: String,
+    pub trials: Vec<Trial>,
+}
+
+impl FloorDifficulty {
+    pub fn wins(&self) -> usize {
+        self.count(Outcome::Cleared)
+    }
+
+    pub fn timeouts(&self) -> usize {
+        self.count(Outcome::Timeout)
+    }
+
+    fn count(&self, outcome: Outcome) -> usize {
+        self.trials.iter().filter(|t| t.outcome == outcome).count()
+    }
+
+    pub fn deaths(&self) -> impl Iterator<Item = &Death> {
+        self.trials.iter().filter_map(|t| t.death.as_ref())
+    }
+
+    /// Share of trials cleared, `0.0..=1.0`.
+    pub fn win_rate(&self) -> f32 {
+        if self.trials.is_empty() {
+            return 0.0;
+        }
+        self.wins() as f32 / self.trials.len() as f32
+    }
+
+    /// Median seconds to extract, over the cleared trials.
+    pub fn median_clear_time(&self) -> Option<f32> {
+        let mut times: Vec<f32> = self
+            .trials
+            .iter()
+            .filter(|t| t.outcome == Outcome::Cleared)
+            .map(|t| t.seconds)
+            .collect();
+        times.sort_by(f32::total_cmp);
+        let n = times.len();
+        match n {
+            0 => None,
+            _ if n % 2 == 1 => Some(times[n / 2]),
+            _ => Some((times[n / 2 - 1] + times[n / 2]) / 2.0),
+        }
+    }
+
+    /// Deaths per killer, most first.
+    pub fn killers(&self) -> Vec<(&str, usize)> {
+        let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+        for death in self.deaths() {
+            *counts.entry(death.killer.as_str()).or_default() += 1;
+        }
+        let mut killers: Vec<(&str, usize)> = counts.into_iter().collect();
+        killers.sort_by_key(|&(_, n)| std::cmp::Reverse(n));
+        killers
+    }
+
+    /// Deaths per [`HEAT_CELL`] square, row by row over the floor.
+    pub fn heatmap(&self) -> Vec<Vec<usize>> {
+        let floor = floor_def(self.level);
+        let cols = (floor.width / HEAT_CELL).ceil().max(1.0) as usize;
+        let rows = (floor.height / HEAT_CELL).ceil().max(1.0) as usize;
+        let mut grid = vec![vec![0; cols]; rows];
+        for death in self.deaths() {
+            let c = ((death.pos.x / HEAT_CELL).max(0.0) as usize).min(cols - 1);
+            let r = ((death.pos.y / HEAT_CELL).max(0.0) as usize).min(rows - 1);
+            grid[r][c] += 1;
+        }
+        grid
+    }
+}
+
+/// A whole batch, floors in play order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DifficultyReport {
+    pub floors: Vec<FloorDifficulty>,
+}
+
+impl DifficultyReport {
+    /// Hardest first: lowest win rate, then slowest median clear.
+    pub fn ranked(&self) -> Vec<&FloorDifficulty> {
+        let mut floors: Vec<&FloorDifficulty> = self.floors.iter().collect();
+        floors.sort_by(|a, b| {
+            a.win_rate().total_cmp(&b.win_rate()).then_with(|| {
+                let slow = |f: &FloorDifficulty| f.median_clear_time().unwrap_or(f32::INFINITY);
+                slow(b).total_cmp(&slow(a))
+            })
+        });
+        floors
+    }
+
+    /// Floors no trial cleared.
+    pub fn unwinnable(&self) -> Vec<&FloorDifficulty> {
+        self.floors.iter().filter(|f| f.wins() == 0).collect()
+    }
+
+    /// The ranking table, then a death heatmap per floor that has deaths.
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "rank  floor       trials   win%  median  deaths  timeouts  top killer"
+        );
+        for (i, f) in self.ranked().into_iter().enumerate() {
+            let median = f
+                .median_clear_time()
+                .map_or("-".to_string(), |s| format!("{s:.1}s"));
+            let top = f
+                .killers()
+                .first()
+                .map_or("-".to_string(), |(who, n)| format!("{who} ({n})"));
+            let _ = writeln!(
+                out,
+                "{:>4}  {:<10}  {:>6}  {:>5.1}  {:>6}  {:>6}  {:>8}  {}",
+                i + 1,
+                f.title,
+                f.trials.len(),
+                f.win_rate() * 100.0,
+                median,
+                f.deaths().count(),
+                f.timeouts(),
+                top
+            );
+        }
+        for f in &self.floors {
+            let deaths = f.deaths().count();
+            if deaths == 0 {
+                continue;
+            }
+            let _ = writeln!(
+                out,
+                "\n{}: {deaths} deaths ({HEAT_CELL:.0}px cells; . none, 1-9, + ten or more)",
+                f.title
+            );
+            let grid = f.heatmap();
+            let border = format!("+{}+", "-".repeat(grid[0].len()));
+            let _ = writeln!(out, "{border}");
+            for row in &grid {
+                let cells: String = row
+                    .iter()
+                    .map(|&n| match n {
+                        0 => '.',
+                        1..=9 => char::from(b'0' + n as u8),
+                        _ => '+',
+                    })
+                    .collect();
+                let _ = writeln!(out, "|{cells}|");
+            }
+            let _ = writeln!(out, "{border}");
+        }
+        out
+    }
+}
+
+/// Play every level in `levels` once per seed and skill.
+pub fn estimate(levels: &[usize], opts: &DifficultyOptions) -> DifficultyReport {
+    let seeds = opts.seed_list();
+    let floors = levels
+        .iter()
+        .map(|&level| {
+            let mut trials = Vec::new();
+            for &skill in &opts.skills {
+                for &seed in &seeds {
+                    trials.push(trial(level, seed, skill, opts));
+                }
+            }
+            FloorDifficulty {
+                level,
+                floor_id: floor_def(level).id,
+                title: floor_title(level),
+                trials,
+            }
+        })
+        .collect();
+    DifficultyReport { floors }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn opts(seeds: usize, max_frames: usize) -> DifficultyOptions {
+        DifficultyOptions {
+            seeds,
+            max_frames,
+            ..DifficultyOptions::default()
+        }
+    }
+
+    fn cleared(seconds: f32) -> Trial {
+        Trial {
+            seed: 0,
+            skill: BotSkill::default(),
+            outcome: Outcome::Cleared,
+            seconds,
+            death: None,
+        }
+    }
+
+    fn died(pos: Vec2, killer: &str) -> Trial {
+        Trial {
+            outcome: Outcome::Died,
+            death: Some(Death {
+                pos,
+                seconds: 1.0,
+                killer: killer.to_string(),
+            }),
+            ..cleared(1.0)
+        }
+    }
+
+    #[test]
+    fn test_floor_stats_median_killers_and_heatmap() {
+        let floor = FloorDifficulty {
+            level: 1,
+            floor_id: 1,
+            title: floor_title(1),
+            trials: vec![
+                cleared(30.0),
+                cleared(10.0),
+                cleared(20.0),
+                cleared(40.0),
+                died(Vec2::new(150.0, 50.0), "boss"),
+                died(Vec2::new(160.0, 20.0), "boss"),
+                died(Vec2::new(50.0, 250.0), "idle rogue at (1, 2)"),
+            ],
+        };
+        assert_eq!(floor.wins(), 4);
+        assert!((floor.win_rate() - 4.0 / 7.0).abs() < 1e-6);
+        assert_eq!(floor.median_clear_time(), Some(25.0));
+        assert_eq!(floor.killers()[0], ("boss", 2));
+        let grid = floor.heatmap();
+        assert_eq!(grid[0][1], 2);
+        assert_eq!(grid[2][0], 1);
+        assert_eq!(grid.iter().flatten().sum::<usize>(), 3);
+        let text = DifficultyReport {
+            floors: vec![floor],
+        }
+        .to_text();
+        assert!(text.contains("|.2"), "{text}");
+    }
+
+    #[test]
+    fn test_estimate_is_deterministic_and_ranks() {
+        let opts = opts(2, 60 * 60);
+        let report = estimate(&[1, 2], &opts);
+        assert_eq!(report.floors.len(), 2);
+        for f in &report.floors {
+            assert_eq!(f.trials.len(), 2 * opts.skills.len());
+            assert_eq!(f.wins() + f.deaths().count() + f.timeouts(), f.trials.len());
+        }
+        let ranked = report.ranked();
+        assert!(ranked[0].win_rate() <= ranked[1].win_rate());
+        assert_eq!(estimate(&[1, 2], &opts), report);
+    }
+
+    #[test]
+    fn test_flags_a_floor_nobody_clears() {
+        // One second is never enough to reach the exit.
+        let report = estimate(&[1], &opts(2, 60));
+        assert_eq!(report.unwinnable().len(), 1);
+        assert_eq!(report.floors[0].timeouts(), report.floors[0].trials.len());
+        assert_eq!(report.floors[0].median_clear_time(), None);
+    }
+
+    #[test]
+    fn test_death_names_the_killer() {
+        let seeds = opts(20, 60 * 120).seed_list();
+        // A bot that never fires on a rogue floor: it dies to
+        // somebody.
+        let hopeless = BotSkill {
+            reaction: f32::INFINITY,
+            aim_error: 0.0,
+        };
+        let death = seeds
+            .iter()
+            .find_map(|&seed| trial(3, seed, hopeless, &opts(1, 60 * 120)).death)
+            .expect("an unarmed bot dies");
+        assert!(death.killer.contains("rogue at ("), "{}", death.killer);
+    }
+}
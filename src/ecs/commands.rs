@@ -0,0 +1,126 @@
+use super::{Component, Entity, World};
+
+type Command = Box<dyn FnOnce(&mut World)>;
+
+/// A buffer of structural world changes — spawn / insert / remove / despawn —
+/// recorded while a system is iterating and applied later, in push order.
+///
+/// A system fills a local buffer while it walks a query (which borrows the
+/// world), then hands it over with [`World::defer`]; the [`super::Schedule`]
+/// applies everything deferred at the end of each stage
+/// ([`World::apply_deferred`]). Nothing structural changes under a running
+/// iteration, and the next stage sees the final shape.
+///
+/// Inserts and removes aimed at an entity that is gone by the time the buffer
+/// is applied (despawned earlier in the same buffer, say) are dropped.
+#[derive(Default)]
+pub struct Commands {
+    queue: Vec<Command>,
+}
+
+impl Commands {
+    pub fn new() -> Self {
+        Commands { queue: Vec::new() }
+    }
+
+    /// Spawn an entity and let `build` attach its components.
+    pub fn spawn(&mut self, build: impl FnOnce(&mut World, Entity) + 'static) {
+        self.add(move |world| {
+            let entity = world.spawn();
+            build(world, entity);
+        });
+    }
+
+    pub fn insert<T: Component + Clone>(&mut self, entity: Entity, component: T) {
+        self.add(move |world| {
+            if world.is_alive(entity) {
+                world.add_component(entity, component);
+            }
+        });
+    }
+
+    pub fn remove<T: Component>(&mut self, entity: Entity) {
+        self.add(move |world| {
+            world.remove_component::<T>(entity);
+        });
+    }
+
+    pub fn despawn(&mut self, entity: Entity) {
+        self.add(move |world| world.despawn(entity));
+    }
+
+    /// Any other deferred world change (e.g. one that draws from the world
+    /// RNG, which must happen in apply order).
+    pub fn add(&mut self, command: impl FnOnce(&mut World) + 'static) {
+        self.queue.push(Box::new(command));
+    }
+
+    /// Move every command of `other` to the end of this buffer.
+    pub fn append(&mut self, other: &mut Commands) {
+        self.queue.append(&mut other.queue);
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Apply every command now, in push order.
+    pub fn apply(self, world: &mut World) {
+        for command in self.queue {
+            command(world);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Tag(u32);
+
+    #[test]
+    fn test_commands_apply_in_push_order() {
+        let mut world = World::new();
+        let a = world.spawn();
+        let b = world.spawn();
+        world.add_component(a, Tag(1));
+
+        let mut commands = Commands::new();
+        commands.despawn(a);
+        // Stale by the time it applies: dropped, not an assertion.
+        commands.insert(a, Tag(2));
+        commands.insert(b, Tag(3));
+        commands.spawn(|world, e| world.add_component(e, Tag(4)));
+        assert_eq!(commands.len(), 4);
+        assert_eq!(world.entities().len(), 2, "nothing applied yet");
+
+        commands.apply(&mut world);
+        assert!(!world.is_alive(a));
+        assert_eq!(world.get_component::<Tag>(b), Some(&Tag(3)));
+        let tags: Vec<u32> = world.query_ref::<&Tag>().map(|t| t.0).collect();
+        // The spawn reused `a`'s index, so it sorts first.
+        assert_eq!(tags, vec![4, 3]);
+    }
+
+    #[test]
+    fn test_deferred_commands_wait_for_apply() {
+        let mut world = World::new();
+        let e = world.spawn();
+        world.add_component(e, Tag(1));
+
+        let mut commands = Commands::new();
+        for (entity, _) in world.query_ref::<(Entity, &Tag)>() {
+            commands.remove::<Tag>(entity);
+        }
+        world.defer(commands);
+        assert!(world.has_component::<Tag>(e));
+
+        world.apply_deferred();
+        assert!(!world.has_component::<Tag>(e));
+    }
+}
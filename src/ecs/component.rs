@@ -18,46 +18,6 @@ impl<T: 'static> Component for T {
     }
 }
 
-/// Object-safe clonable `Any`: what the world's component storage actually
-/// boxes. Every stored component must be `Clone` so the whole [`World`] can
-/// be snapshotted (the mid-floor `checkpoint` scenario action clones the
-/// world and restores it on death).
-///
-/// [`World`]: crate::ecs::World
-pub trait AnyComponent: Any {
-    fn clone_box(&self) -> Box<dyn AnyComponent>;
-    fn as_any(&self) -> &dyn Any;
-    fn as_any_mut(&mut self) -> &mut dyn Any;
-    fn into_any(self: Box<Self>) -> Box<dyn Any>;
-}
-
-impl<T: Any + Clone> AnyComponent for T {
-    fn clone_box(&self) -> Box<dyn AnyComponent> {
-        Box::new(self.clone())
-    }
-
-    fn as_any(&self) -> &dyn Any {
-        self
-    }
-
-    fn as_any_mut(&mut self) -> &mut dyn Any {
-        self
-    }
-
-    fn into_any(self: Box<Self>) -> Box<dyn Any> {
-        self
-    }
-}
-
-impl Clone for Box<dyn AnyComponent> {
-    fn clone(&self) -> Self {
-        // Explicit deref: `Box<dyn AnyComponent>` is itself `Any + Clone`,
-        // so `self.clone_box()` would resolve to the blanket impl ON THE BOX
-        // and recurse forever. Dispatch on the inner trait object instead.
-        (**self).clone_box()
-    }
-}
-
 /// Type-safe wrapper for component type IDs
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct ComponentId(pub TypeId);
@@ -113,18 +73,4 @@ mod tests {
         downcast_mut.x = 30.0;
         assert_eq!(pos.x, 30.0);
     }
-
-    #[test]
-    fn test_boxed_component_clones() {
-        #[derive(Debug, Clone, PartialEq)]
-        struct Tag(u32);
-        let boxed: Box<dyn AnyComponent> = Box::new(Tag(7));
-        let cloned = boxed.clone();
-        assert_eq!(
-            AnyComponent::as_any(cloned.as_ref()).downcast_ref::<Tag>(),
-            Some(&Tag(7))
-        );
-        let back = cloned.into_any().downcast::<Tag>().unwrap();
-        assert_eq!(*back, Tag(7));
-    }
 }
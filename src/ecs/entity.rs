@@ -1,14 +1,40 @@
-/// Entity is just a unique identifier
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub struct Entity(pub u64);
+use std::fmt;
+
+/// Entity is a generational handle: a slot `index` the world recycles once
+/// the entity is despawned, plus the slot's `generation` at spawn time. A
+/// despawn bumps the slot's generation, so every handle still pointing at the
+/// old occupant goes stale instead of silently aliasing whatever gets spawned
+/// into the slot next (see `World::is_alive`).
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Entity {
+    index: u32,
+    generation: u32,
+}
 
 impl Entity {
-    pub fn new(id: u64) -> Self {
-        Entity(id)
+    pub fn new(index: u32, generation: u32) -> Self {
+        Entity { index, generation }
     }
 
-    pub fn id(&self) -> u64 {
-        self.0
+    /// Slot index: dense, reused after a despawn. What component storages
+    /// are keyed by.
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+
+    /// Both halves packed into one `u64` (generation high, index low).
+    pub fn to_bits(&self) -> u64 {
+        ((self.generation as u64) << 32) | self.index as u64
+    }
+}
+
+impl fmt::Debug for Entity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Entity({}v{})", self.index, self.generation)
     }
 }
 
@@ -18,23 +44,27 @@ mod tests {
 
     #[test]
     fn test_entity_creation() {
-        let entity = Entity::new(42);
-        assert_eq!(entity.id(), 42);
+        let entity = Entity::new(42, 3);
+        assert_eq!(entity.index(), 42);
+        assert_eq!(entity.generation(), 3);
+        assert_eq!(entity.to_bits(), (3 << 32) | 42);
     }
 
     #[test]
     fn test_entity_equality() {
-        let e1 = Entity::new(1);
-        let e2 = Entity::new(1);
-        let e3 = Entity::new(2);
+        let e1 = Entity::new(1, 0);
+        let e2 = Entity::new(1, 0);
+        let e3 = Entity::new(2, 0);
+        let e1_reused = Entity::new(1, 1);
 
         assert_eq!(e1, e2);
         assert_ne!(e1, e3);
+        assert_ne!(e1, e1_reused);
     }
 
     #[test]
     fn test_entity_copy() {
-        let e1 = Entity::new(5);
+        let e1 = Entity::new(5, 0);
         let e2 = e1; // Should copy, not move
         assert_eq!(e1, e2);
     }
@@ -1,17 +1,25 @@
 // Custom ECS Engine
 // Simple, testable architecture with minimal dependencies
 
+pub mod commands;
 pub mod component;
 pub mod entity;
 pub mod query;
+pub mod resource;
+pub mod rng;
+pub mod storage;
 pub mod system;
 pub mod world;
 
+pub use commands::Commands;
 pub use component::Component;
 pub use entity::Entity;
-pub use query::{Query, QueryMut};
-pub use system::System;
-pub use world::{Wall, World};
+pub use query::{
+    Added, Changed, Query, QueryData, QueryFilter, QueryMut, ReadOnlyQueryData, With, Without,
+};
+pub use rng::{Rng, RngStream, RngStreams};
+pub use system::{RunCondition, Schedule, System};
+pub use world::{RemovedComponents, Wall, WallMaterial, World, WorldDelta, DEFAULT_SEED};
 
 // Re-export common types
 pub use crate::math::Vec2;
@@ -1,56 +1,475 @@
+use super::storage::SparseSet;
 use super::{Component, Entity, World};
+use std::any::{type_name, TypeId};
+use std::marker::PhantomData;
+use std::ptr::NonNull;
 
-/// Query provides iteration over entities with specific components
-pub struct Query<'a, T: Component> {
-    entities: Vec<Entity>,
-    world: &'a World,
-    _phantom: std::marker::PhantomData<T>,
+/// Most component accesses a single query may declare. Tuples go up to eight
+/// elements, so this only trips on deeply nested tuples.
+const MAX_ACCESSES: usize = 16;
+
+/// The components a query reads and writes, checked once when the query is
+/// built: two accesses to the same component where either one writes would
+/// hand out aliasing references, so that panics instead.
+pub struct Access {
+    items: [(TypeId, &'static str, bool); MAX_ACCESSES],
+    len: usize,
+}
+
+impl Access {
+    fn new() -> Self {
+        Access {
+            items: [(TypeId::of::<()>(), "", false); MAX_ACCESSES],
+            len: 0,
+        }
+    }
+
+    fn add<T: Component>(&mut self, write: bool) {
+        let id = TypeId::of::<T>();
+        for &(other, name, other_write) in &self.items[..self.len] {
+            if other == id && (write || other_write) {
+                panic!("query borrows `{name}` mutably alongside another borrow of it");
+            }
+        }
+        assert!(self.len < MAX_ACCESSES, "query touches too many components");
+        self.items[self.len] = (id, type_name::<T>(), write);
+        self.len += 1;
+    }
+
+    pub fn read<T: Component>(&mut self) {
+        self.add::<T>(false);
+    }
+
+    pub fn write<T: Component>(&mut self) {
+        self.add::<T>(true);
+    }
+}
+
+/// What a query yields per entity: `&T`, `&mut T`, `Option<&T>`,
+/// `Option<&mut T>`, [`Entity`], or a tuple of those.
+///
+/// Fetches work on raw pointers to the per-type sparse sets, so several
+/// `&mut` borrows of *different* component types can be live at once; the
+/// [`Access`] check rules out two borrows of the same one. Every pointer is
+/// taken off the query's one world pointer through `World::storage_ptr` /
+/// `World::storage_ptr_mut`, never through a fresh `&World` or `&mut World`
+/// (`make check-miri` runs the query tests under Miri).
+///
+/// # Safety
+///
+/// `access` must declare every component `fetch` touches, as a write for
+/// every one it hands out `&mut` to, and `fetch` must not touch anything else
+/// of the world.
+pub unsafe trait QueryData {
+    type Item<'w>;
+    type State: Copy;
+
+    fn access(access: &mut Access);
+
+    /// Look the storages up once. `None` when a required component has no
+    /// storage at all, i.e. nothing can match.
+    ///
+    /// # Safety
+    ///
+    /// `world` must be valid for the query's lifetime, and writable when the
+    /// data is not [`ReadOnlyQueryData`].
+    unsafe fn init(world: NonNull<World>) -> Option<Self::State>;
+
+    /// Narrow `driver` to the shortest entity list this data requires.
+    ///
+    /// # Safety
+    ///
+    /// `state` must come from [`QueryData::init`] on a still-live world.
+    unsafe fn narrow(state: &Self::State, driver: &mut Option<*const [Entity]>);
+
+    /// # Safety
+    ///
+    /// `state` must come from [`QueryData::init`] on a still-live world, and
+    /// no item previously fetched for `entity` may still be alive.
+    unsafe fn fetch<'w>(state: Self::State, entity: Entity) -> Option<Self::Item<'w>>;
+}
+
+/// Query data that never writes: usable through a shared `&World`.
+///
+/// # Safety
+///
+/// `fetch` must only read.
+pub unsafe trait ReadOnlyQueryData: QueryData {}
+
+/// Narrows a query without fetching anything: [`With`], [`Without`],
+/// [`Added`], [`Changed`], `()`, or a tuple of those (all must hold).
+pub trait QueryFilter {
+    type State: Copy;
+
+    /// # Safety
+    ///
+    /// `world` must be valid for reads for the query's lifetime.
+    unsafe fn init(world: NonNull<World>) -> Self::State;
+
+    /// # Safety
+    ///
+    /// `state` must come from [`QueryFilter::init`] on a still-live world.
+    unsafe fn matches(state: Self::State, entity: Entity) -> bool;
+}
+
+/// Only entities that hold a `T`.
+pub struct With<T>(PhantomData<T>);
+
+/// Only entities that do not hold a `T` (e.g. `Without<Stunned>`).
+pub struct Without<T>(PhantomData<T>);
+
+/// Only entities whose `T` was inserted since the running reader's baseline
+/// (see `World::run_tracked`). Overwriting an existing `T` is a change, not
+/// an addition.
+pub struct Added<T>(PhantomData<T>);
+
+/// Only entities whose `T` was inserted or mutably borrowed since the
+/// running reader's baseline (e.g. `Changed<Health>`: hit this tick).
+pub struct Changed<T>(PhantomData<T>);
+
+fn narrow_to(driver: &mut Option<*const [Entity]>, candidate: *const [Entity]) {
+    if driver.is_none_or(|current| candidate.len() < current.len()) {
+        *driver = Some(candidate);
+    }
+}
+
+unsafe impl<T: Component> QueryData for &T {
+    type Item<'w> = &'w T;
+    type State = *const SparseSet<T>;
+
+    fn access(access: &mut Access) {
+        access.read::<T>();
+    }
+
+    unsafe fn init(world: NonNull<World>) -> Option<Self::State> {
+        World::storage_ptr::<T>(world)
+    }
+
+    unsafe fn narrow(state: &Self::State, driver: &mut Option<*const [Entity]>) {
+        narrow_to(driver, (**state).entities());
+    }
+
+    unsafe fn fetch<'w>(state: Self::State, entity: Entity) -> Option<&'w T> {
+        (*state).get(entity)
+    }
+}
+
+unsafe impl<T: Component> ReadOnlyQueryData for &T {}
+
+// Mutable fetches carry the world's change tick: every row handed out as
+// `&mut` is marked changed, whether or not the caller writes through it.
+unsafe impl<T: Component> QueryData for &mut T {
+    type Item<'w> = &'w mut T;
+    type State = (*mut SparseSet<T>, u64);
+
+    fn access(access: &mut Access) {
+        access.write::<T>();
+    }
+
+    unsafe fn init(world: NonNull<World>) -> Option<Self::State> {
+        let (tick, _) = World::ticks_ptr(world);
+        World::storage_ptr_mut::<T>(world).map(|s| (s, tick))
+    }
+
+    unsafe fn narrow(state: &Self::State, driver: &mut Option<*const [Entity]>) {
+        narrow_to(driver, (*state.0).entities());
+    }
+
+    unsafe fn fetch<'w>((set, tick): Self::State, entity: Entity) -> Option<&'w mut T> {
+        SparseSet::row_ptr(set, entity, tick).map(|row| &mut *row)
+    }
+}
+
+unsafe impl<T: Component> QueryData for Option<&T> {
+    type Item<'w> = Option<&'w T>;
+    type State = Option<*const SparseSet<T>>;
+
+    fn access(access: &mut Access) {
+        access.read::<T>();
+    }
+
+    unsafe fn init(world: NonNull<World>) -> Option<Self::State> {
+        Some(World::storage_ptr::<T>(world))
+    }
+
+    unsafe fn narrow(_: &Self::State, _: &mut Option<*const [Entity]>) {}
+
+    unsafe fn fetch<'w>(state: Self::State, entity: Entity) -> Option<Option<&'w T>> {
+        Some(state.and_then(|s| (*s).get(entity)))
+    }
+}
+
+unsafe impl<T: Component> ReadOnlyQueryData for Option<&T> {}
+
+unsafe impl<T: Component> QueryData for Option<&mut T> {
+    type Item<'w> = Option<&'w mut T>;
+    type State = (Option<*mut SparseSet<T>>, u64);
+
+    fn access(access: &mut Access) {
+        access.write::<T>();
+    }
+
+    unsafe fn init(world: NonNull<World>) -> Option<Self::State> {
+        let (tick, _) = World::ticks_ptr(world);
+        Some((World::storage_ptr_mut::<T>(world), tick))
+    }
+
+    unsafe fn narrow(_: &Self::State, _: &mut Option<*const [Entity]>) {}
+
+    unsafe fn fetch<'w>((set, tick): Self::State, entity: Entity) -> Option<Option<&'w mut T>> {
+        Some(set.and_then(|s| SparseSet::row_ptr(s, entity, tick).map(|row| &mut *row)))
+    }
+}
+
+unsafe impl QueryData for Entity {
+    type Item<'w> = Entity;
+    type State = ();
+
+    fn access(_: &mut Access) {}
+
+    unsafe fn init(_: NonNull<World>) -> Option<()> {
+        Some(())
+    }
+
+    unsafe fn narrow(_: &(), _: &mut Option<*const [Entity]>) {}
+
+    unsafe fn fetch<'w>(_: (), entity: Entity) -> Option<Self::Item<'w>> {
+        Some(entity)
+    }
+}
+
+unsafe impl ReadOnlyQueryData for Entity {}
+
+impl QueryFilter for () {
+    type State = ();
+
+    unsafe fn init(_: NonNull<World>) {}
+
+    unsafe fn matches(_: (), _: Entity) -> bool {
+        true
+    }
+}
+
+impl<T: Component> QueryFilter for With<T> {
+    type State = Option<*const SparseSet<T>>;
+
+    unsafe fn init(world: NonNull<World>) -> Self::State {
+        World::storage_ptr::<T>(world)
+    }
+
+    unsafe fn matches(state: Self::State, entity: Entity) -> bool {
+        state.is_some_and(|s| (*s).contains(entity))
+    }
+}
+
+impl<T: Component> QueryFilter for Without<T> {
+    type State = Option<*const SparseSet<T>>;
+
+    unsafe fn init(world: NonNull<World>) -> Self::State {
+        World::storage_ptr::<T>(world)
+    }
+
+    unsafe fn matches(state: Self::State, entity: Entity) -> bool {
+        !state.is_some_and(|s| (*s).contains(entity))
+    }
+}
+
+impl<T: Component> QueryFilter for Added<T> {
+    type State = (Option<*const SparseSet<T>>, u64);
+
+    unsafe fn init(world: NonNull<World>) -> Self::State {
+        (World::storage_ptr::<T>(world), World::ticks_ptr(world).1)
+    }
+
+    unsafe fn matches((set, since): Self::State, entity: Entity) -> bool {
+        set.and_then(|s| (*s).ticks(entity))
+            .is_some_and(|t| t.added > since)
+    }
+}
+
+impl<T: Component> QueryFilter for Changed<T> {
+    type State = (Option<*const SparseSet<T>>, u64);
+
+    unsafe fn init(world: NonNull<World>) -> Self::State {
+        (World::storage_ptr::<T>(world), World::ticks_ptr(world).1)
+    }
+
+    unsafe fn matches((set, since): Self::State, entity: Entity) -> bool {
+        set.and_then(|s| (*s).ticks(entity))
+            .is_some_and(|t| t.changed > since)
+    }
+}
+
+macro_rules! impl_query_tuple {
+    ($($name:ident),+) => {
+        #[allow(non_snake_case)]
+        unsafe impl<$($name: QueryData),+> QueryData for ($($name,)+) {
+            type Item<'w> = ($($name::Item<'w>,)+);
+            type State = ($($name::State,)+);
+
+            fn access(access: &mut Access) {
+                $($name::access(access);)+
+            }
+
+            unsafe fn init(world: NonNull<World>) -> Option<Self::State> {
+                Some(($($name::init(world)?,)+))
+            }
+
+            unsafe fn narrow(state: &Self::State, driver: &mut Option<*const [Entity]>) {
+                let ($($name,)+) = state;
+                $($name::narrow($name, driver);)+
+            }
+
+            unsafe fn fetch<'w>(state: Self::State, entity: Entity) -> Option<Self::Item<'w>> {
+                let ($($name,)+) = state;
+                Some(($($name::fetch($name, entity)?,)+))
+            }
+        }
+
+        unsafe impl<$($name: ReadOnlyQueryData),+> ReadOnlyQueryData for ($($name,)+) {}
+
+        #[allow(non_snake_case)]
+        impl<$($name: QueryFilter),+> QueryFilter for ($($name,)+) {
+            type State = ($($name::State,)+);
+
+            unsafe fn init(world: NonNull<World>) -> Self::State {
+                ($($name::init(world),)+)
+            }
+
+            unsafe fn matches(state: Self::State, entity: Entity) -> bool {
+                let ($($name,)+) = state;
+                $($name::matches($name, entity))&&+
+            }
+        }
+    };
+}
+
+impl_query_tuple!(A);
+impl_query_tuple!(A, B);
+impl_query_tuple!(A, B, C);
+impl_query_tuple!(A, B, C, D);
+impl_query_tuple!(A, B, C, D, E);
+impl_query_tuple!(A, B, C, D, E, G);
+impl_query_tuple!(A, B, C, D, E, G, H);
+impl_query_tuple!(A, B, C, D, E, G, H, I);
+
+/// Shared iteration state: walk the shortest required entity list (or every
+/// live entity when nothing is required) and keep the rows that match.
+struct Cursor<D: QueryData, F: QueryFilter> {
+    state: Option<(D::State, F::State)>,
+    driver: *const [Entity],
+    next: usize,
+}
+
+impl<D: QueryData, F: QueryFilter> Cursor<D, F> {
+    /// # Safety
+    ///
+    /// As [`QueryData::init`].
+    unsafe fn new(world: NonNull<World>) -> Self {
+        let mut access = Access::new();
+        D::access(&mut access);
+        let state = D::init(world).map(|d| (d, F::init(world)));
+        let mut driver = None;
+        if let Some((d, _)) = &state {
+            D::narrow(d, &mut driver);
+        }
+        Cursor {
+            state,
+            driver: driver.unwrap_or_else(|| World::entities_ptr(world)),
+            next: 0,
+        }
+    }
+
+    /// # Safety
+    ///
+    /// The world the cursor was built on must still be live and unchanged.
+    unsafe fn next<'w>(&mut self) -> Option<D::Item<'w>> {
+        let (d, f) = self.state?;
+        while self.next < self.driver.len() {
+            let entity = (*self.driver)[self.next];
+            self.next += 1;
+            if F::matches(f, entity) {
+                if let Some(item) = D::fetch(d, entity) {
+                    return Some(item);
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Read-only typed query: iterates `D::Item` for every entity matching `D`
+/// and the filter `F`, in entity id (spawn) order.
+///
+/// ```ignore
+/// for (entity, pos, hp) in world.query_ref::<(Entity, &Position, &Health)>() { .. }
+/// ```
+pub struct Query<'w, D: ReadOnlyQueryData, F: QueryFilter = ()> {
+    cursor: Cursor<D, F>,
+    _world: PhantomData<&'w World>,
 }
 
-impl<'a, T: Component> Query<'a, T> {
-    pub fn new(world: &'a World) -> Self {
+impl<'w, D: ReadOnlyQueryData, F: QueryFilter> Query<'w, D, F> {
+    pub fn new(world: &'w World) -> Self {
         Query {
-            entities: world.query::<T>(),
-            world,
-            _phantom: std::marker::PhantomData,
+            // SAFETY: the shared borrow outlives the query and D only reads.
+            cursor: unsafe { Cursor::new(NonNull::from(world)) },
+            _world: PhantomData,
         }
     }
+}
+
+impl<'w, D: ReadOnlyQueryData, F: QueryFilter> Iterator for Query<'w, D, F> {
+    type Item = D::Item<'w>;
 
-    pub fn iter(&self) -> impl Iterator<Item = (Entity, &T)> {
-        self.entities.iter().filter_map(|&entity| {
-            self.world
-                .get_component::<T>(entity)
-                .map(|comp| (entity, comp))
-        })
+    fn next(&mut self) -> Option<Self::Item> {
+        // SAFETY: the world is borrowed for 'w, so it cannot change under us.
+        unsafe { self.cursor.next() }
     }
 }
 
-/// QueryMut provides mutable iteration over entities with specific components
-pub struct QueryMut<'a, T: Component> {
-    entities: Vec<Entity>,
-    world: &'a mut World,
-    _phantom: std::marker::PhantomData<T>,
+/// Mutable typed query: like [`Query`], but `D` may hold `&mut T` /
+/// `Option<&mut T>` for any number of distinct component types.
+///
+/// ```ignore
+/// for (pos, vel) in world.query_mut_filtered::<(&mut Position, &Velocity), Without<Stunned>>() {
+///     pos.x += vel.x * dt;
+/// }
+/// ```
+pub struct QueryMut<'w, D: QueryData, F: QueryFilter = ()> {
+    cursor: Cursor<D, F>,
+    _world: PhantomData<&'w mut World>,
 }
 
-impl<'a, T: Component> QueryMut<'a, T> {
-    pub fn new(world: &'a mut World) -> Self {
-        let entities = world.query::<T>();
+impl<'w, D: QueryData, F: QueryFilter> QueryMut<'w, D, F> {
+    pub fn new(world: &'w mut World) -> Self {
+        QueryMut {
+            // SAFETY: the exclusive borrow outlives the query.
+            cursor: unsafe { Cursor::new(NonNull::from(world)) },
+            _world: PhantomData,
+        }
+    }
+
+    /// # Safety
+    ///
+    /// `world` must be exclusively borrowed for `'w`, apart from fields no
+    /// query data touches (the walls).
+    pub(super) unsafe fn from_ptr(world: NonNull<World>) -> Self {
         QueryMut {
-            entities,
-            world,
-            _phantom: std::marker::PhantomData,
+            cursor: Cursor::new(world),
+            _world: PhantomData,
         }
     }
+}
+
+impl<'w, D: QueryData, F: QueryFilter> Iterator for QueryMut<'w, D, F> {
+    type Item = D::Item<'w>;
 
-    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Entity, &mut T)> {
-        // We need to use unsafe here to work around Rust's borrow checker
-        // This is safe because we're not allowing overlapping mutable borrows
-        let world_ptr = self.world as *mut World;
-        self.entities.iter().filter_map(move |&entity| unsafe {
-            (*world_ptr)
-                .get_component_mut::<T>(entity)
-                .map(|comp| (entity, comp))
-        })
+    fn next(&mut self) -> Option<Self::Item> {
+        // SAFETY: every entity is visited once and the access check forbids
+        // two borrows of one component, so no two live items alias; the
+        // world is exclusively borrowed for 'w.
+        unsafe { self.cursor.next() }
     }
 }
 
@@ -64,6 +483,54 @@ mod tests {
         y: f32,
     }
 
+    #[derive(Debug, Clone, PartialEq)]
+    struct Velocity {
+        x: f32,
+        y: f32,
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Frozen;
+
+    #[test]
+    fn test_added_and_changed_filters() {
+        let mut world = World::new();
+        let a = world.spawn();
+        world.add_component(a, Position { x: 0.0, y: 0.0 });
+        let mut last_run = 0;
+        let seen = |world: &mut World, last_run: &mut u64| {
+            world.run_tracked(last_run, |w| {
+                let added: Vec<_> = w.query_ref_filtered::<Entity, Added<Position>>().collect();
+                let changed: Vec<_> = w
+                    .query_ref_filtered::<Entity, Changed<Position>>()
+                    .collect();
+                (added, changed)
+            })
+        };
+        assert_eq!(seen(&mut world, &mut last_run), (vec![a], vec![a]));
+        assert_eq!(seen(&mut world, &mut last_run), (vec![], vec![]));
+
+        let b = world.spawn();
+        world.add_component(b, Position { x: 1.0, y: 0.0 });
+        // Handing out `&mut` marks the row, read-only queries do not.
+        for pos in world.query_mut::<&mut Position>() {
+            pos.x += 1.0;
+        }
+        assert_eq!(world.query_ref::<&Position>().count(), 2);
+        assert_eq!(seen(&mut world, &mut last_run), (vec![b], vec![a, b]));
+
+        world.get_component_mut::<Position>(a).unwrap().y = 5.0;
+        assert_eq!(seen(&mut world, &mut last_run), (vec![], vec![a]));
+
+        // `update_component` only marks a row whose value moved.
+        world.update_component::<Position, _>(a, |p| p.y = 5.0);
+        assert_eq!(seen(&mut world, &mut last_run), (vec![], vec![]));
+        let tick = world.change_tick();
+        world.update_component::<Position, _>(b, |p| p.y = 1.0);
+        assert!(world.changed_since::<Position>(tick - 1));
+        assert_eq!(seen(&mut world, &mut last_run), (vec![], vec![b]));
+    }
+
     #[test]
     fn test_query_iter() {
         let mut world = World::new();
@@ -74,10 +541,11 @@ mod tests {
         let e2 = world.spawn();
         world.add_component(e2, Position { x: 3.0, y: 4.0 });
 
-        let query = Query::<Position>::new(&world);
-        let results: Vec<_> = query.iter().collect();
+        let query = Query::<(Entity, &Position)>::new(&world);
+        let results: Vec<_> = query.collect();
 
         assert_eq!(results.len(), 2);
+        assert_eq!(results[0].0, e1);
     }
 
     #[test]
@@ -90,11 +558,8 @@ mod tests {
         let e2 = world.spawn();
         world.add_component(e2, Position { x: 3.0, y: 4.0 });
 
-        {
-            let mut query = QueryMut::<Position>::new(&mut world);
-            for (_entity, pos) in query.iter_mut() {
-                pos.x += 10.0;
-            }
+        for (_entity, pos) in QueryMut::<(Entity, &mut Position)>::new(&mut world) {
+            pos.x += 10.0;
         }
 
         let pos1 = world.get_component::<Position>(e1).unwrap();
@@ -103,4 +568,98 @@ mod tests {
         assert_eq!(pos1.x, 11.0);
         assert_eq!(pos2.x, 13.0);
     }
+
+    #[test]
+    fn test_query_mut_two_components_and_filter() {
+        let mut world = World::new();
+        let moving = world.spawn();
+        world.add_component(moving, Position { x: 0.0, y: 0.0 });
+        world.add_component(moving, Velocity { x: 1.0, y: 2.0 });
+        let frozen = world.spawn();
+        world.add_component(frozen, Position { x: 0.0, y: 0.0 });
+        world.add_component(frozen, Velocity { x: 5.0, y: 5.0 });
+        world.add_component(frozen, Frozen);
+        let still = world.spawn();
+        world.add_component(still, Position { x: 7.0, y: 7.0 });
+
+        for (pos, vel) in
+            world.query_mut_filtered::<(&mut Position, &mut Velocity), Without<Frozen>>()
+        {
+            pos.x += vel.x;
+            pos.y += vel.y;
+            vel.x = 0.0;
+        }
+
+        assert_eq!(
+            world.get_component::<Position>(moving),
+            Some(&Position { x: 1.0, y: 2.0 })
+        );
+        assert_eq!(world.get_component::<Velocity>(moving).unwrap().x, 0.0);
+        assert_eq!(
+            world.get_component::<Position>(frozen),
+            Some(&Position { x: 0.0, y: 0.0 })
+        );
+        let frozen_only: Vec<Entity> = world.query_ref_filtered::<Entity, With<Frozen>>().collect();
+        assert_eq!(frozen_only, vec![frozen]);
+    }
+
+    #[test]
+    fn test_optional_fetch_does_not_narrow() {
+        let mut world = World::new();
+        let a = world.spawn();
+        world.add_component(a, Position { x: 1.0, y: 0.0 });
+        let b = world.spawn();
+        world.add_component(b, Position { x: 2.0, y: 0.0 });
+        world.add_component(b, Velocity { x: 3.0, y: 0.0 });
+
+        let rows: Vec<(f32, Option<f32>)> = world
+            .query_ref::<(&Position, Option<&Velocity>)>()
+            .map(|(p, v)| (p.x, v.map(|v| v.x)))
+            .collect();
+        assert_eq!(rows, vec![(1.0, None), (2.0, Some(3.0))]);
+
+        // A required component nobody holds matches nothing.
+        assert_eq!(world.query_ref::<(&Position, &Frozen)>().count(), 0);
+    }
+
+    #[test]
+    fn test_mixed_shared_and_mutable_elements() {
+        // Reads before and after a write, and an optional write, in one
+        // tuple: the pointers taken for the earlier elements must stay valid
+        // once the later ones are taken (this is what Miri checks).
+        let mut world = World::new();
+        let a = world.spawn();
+        world.add_component(a, Velocity { x: 1.0, y: 1.0 });
+        world.add_component(a, Position { x: 0.0, y: 0.0 });
+        world.add_component(a, Frozen);
+        let b = world.spawn();
+        world.add_component(b, Velocity { x: 2.0, y: 0.0 });
+        world.add_component(b, Position { x: 0.0, y: 0.0 });
+
+        for (vel, pos, frozen, entity) in
+            world.query_mut::<(&Velocity, &mut Position, Option<&mut Frozen>, Entity)>()
+        {
+            if frozen.is_none() {
+                pos.x += vel.x;
+            }
+            pos.y = entity.index() as f32;
+        }
+        assert_eq!(
+            world.get_component::<Position>(a),
+            Some(&Position { x: 0.0, y: 0.0 })
+        );
+        assert_eq!(
+            world.get_component::<Position>(b),
+            Some(&Position { x: 2.0, y: 1.0 })
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "mutably alongside")]
+    fn test_aliasing_mutable_access_panics() {
+        let mut world = World::new();
+        let e = world.spawn();
+        world.add_component(e, Position { x: 0.0, y: 0.0 });
+        let _ = world.query_mut::<(&mut Position, &Position)>();
+    }
 }
@@ -0,0 +1,43 @@
+use super::storage::TypeIdHasher;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::hash::BuildHasherDefault;
+
+/// Type-erased singleton held by the world (see `World::insert_resource`).
+/// Clonable like the component storages, so a world snapshot carries its
+/// resources along.
+pub trait AnyResource: Any {
+    fn clone_box(&self) -> Box<dyn AnyResource>;
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+}
+
+impl<T: Any + Clone> AnyResource for T {
+    fn clone_box(&self) -> Box<dyn AnyResource> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+impl Clone for Box<dyn AnyResource> {
+    fn clone(&self) -> Self {
+        // Explicit deref, as for `Box<dyn AnyStorage>`: the box itself is
+        // `Any + Clone` and would otherwise match the blanket impl.
+        (**self).clone_box()
+    }
+}
+
+/// The world's `TypeId -> resource` map.
+pub type ResourceMap = HashMap<TypeId, Box<dyn AnyResource>, BuildHasherDefault<TypeIdHasher>>;
@@ -0,0 +1,189 @@
+//! Named random streams (see `World::rng`).
+//!
+//! The world draws from one stream per subsystem instead of a single shared
+//! generator, so a draw in one never shifts another: giving the passive crowd
+//! one more fidget no longer reshuffles every rogue's wander and lunge
+//! timing, and recorded replays and determinism baselines survive changes to
+//! unrelated systems. All streams derive from one seed (`World::seed_rng`).
+
+/// The subsystems that draw random numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RngStream {
+    /// Rogue AI: wander headings and timers, feral lunges, confusion looks.
+    Ai,
+    /// The passive (civilian) crowd: stroll targets and fidgets.
+    Crowd,
+    /// Weapon drops: rounds left in a downed rogue's gun.
+    Loot,
+    /// Weapon spread: where each round leaves a gun's cone, the player's
+    /// and the rogues' alike.
+    Spread,
+}
+
+impl RngStream {
+    pub const COUNT: usize = 4;
+
+    /// Every stream, in save / hash order.
+    pub const ALL: [RngStream; RngStream::COUNT] = [
+        RngStream::Ai,
+        RngStream::Crowd,
+        RngStream::Loot,
+        RngStream::Spread,
+    ];
+
+    /// The stream's name in saves and desync reports (`"rng:ai"`...).
+    pub const fn name(self) -> &'static str {
+        match self {
+            RngStream::Ai => "rng:ai",
+            RngStream::Crowd => "rng:crowd",
+            RngStream::Loot => "rng:loot",
+            RngStream::Spread => "rng:spread",
+        }
+    }
+
+    const fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// One stream: the world's long-standing LCG (Numerical Recipes constants).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rng {
+    state: u32,
+}
+
+impl Rng {
+    pub const fn from_state(state: u32) -> Self {
+        Rng { state }
+    }
+
+    /// The raw state (saves, hashes).
+    pub const fn state(self) -> u32 {
+        self.state
+    }
+
+    /// Advance and return the next pseudo-random `u32`.
+    pub fn next_u32(&mut self) -> u32 {
+        self.state = self.state.wrapping_mul(1664525).wrapping_add(1013904223);
+        self.state
+    }
+
+    /// Random float in `[min, max)` (or `[min, max]` at the endpoint).
+    pub fn range(&mut self, min: f32, max: f32) -> f32 {
+        let r = self.next_u32() as f32 / u32::MAX as f32;
+        r * (max - min) + min
+    }
+
+    /// Random integer in `[min, max]` (inclusive).
+    pub fn int_range(&mut self, min: i32, max: i32) -> i32 {
+        let range = (max - min + 1) as u32;
+        min + (self.next_u32() % range) as i32
+    }
+
+    /// A child stream, independent of this one from here on (one draw here
+    /// seeds it).
+    pub fn split(&mut self) -> Rng {
+        Rng::from_state(mix(self.next_u32()))
+    }
+}
+
+/// Murmur3's finaliser: spreads nearby seeds (`1`, `2`...) far apart.
+const fn mix(mut x: u32) -> u32 {
+    x ^= x >> 16;
+    x = x.wrapping_mul(0x85eb_ca6b);
+    x ^= x >> 13;
+    x = x.wrapping_mul(0xc2b2_ae35);
+    x ^ (x >> 16)
+}
+
+/// Every [`RngStream`] of a world.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RngStreams {
+    streams: [Rng; RngStream::COUNT],
+}
+
+impl RngStreams {
+    /// Every stream derived from `seed`, each with its own salt.
+    pub fn from_seed(seed: u32) -> Self {
+        let mut streams = [Rng::from_state(0); RngStream::COUNT];
+        for stream in RngStream::ALL {
+            let salt = mix(stream.index() as u32 + 1);
+            streams[stream.index()] = Rng::from_state(mix(seed ^ salt));
+        }
+        RngStreams { streams }
+    }
+
+    /// Streams with exactly these raw states (a loaded save).
+    pub fn from_states(states: [u32; RngStream::COUNT]) -> Self {
+        RngStreams {
+            streams: states.map(Rng::from_state),
+        }
+    }
+
+    /// Raw states in [`RngStream::ALL`] order.
+    pub fn states(&self) -> [u32; RngStream::COUNT] {
+        self.streams.map(Rng::state)
+    }
+
+    pub fn get(&self, stream: RngStream) -> Rng {
+        self.streams[stream.index()]
+    }
+
+    pub fn get_mut(&mut self, stream: RngStream) -> &mut Rng {
+        &mut self.streams[stream.index()]
+    }
+
+    /// Every stream folded into one number: a seed that carries a run on
+    /// (the next floor, a retry) from where these streams stand.
+    pub fn fold(&self) -> u32 {
+        self.streams
+            .iter()
+            .fold(0, |acc, rng| mix(acc ^ rng.state()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_streams_are_independent() {
+        let mut a = RngStreams::from_seed(7);
+        let mut b = RngStreams::from_seed(7);
+        // Extra draws on the crowd stream in `b` only...
+        for _ in 0..10 {
+            b.get_mut(RngStream::Crowd).next_u32();
+        }
+        // ...leave the AI stream's sequence untouched.
+        for _ in 0..5 {
+            assert_eq!(
+                a.get_mut(RngStream::Ai).next_u32(),
+                b.get_mut(RngStream::Ai).next_u32()
+            );
+        }
+        assert_ne!(a.get(RngStream::Crowd), b.get(RngStream::Crowd));
+    }
+
+    #[test]
+    fn test_seeds_and_streams_differ() {
+        let a = RngStreams::from_seed(1);
+        let b = RngStreams::from_seed(2);
+        assert_ne!(a.get(RngStream::Ai), b.get(RngStream::Ai));
+        assert_ne!(a.get(RngStream::Ai), a.get(RngStream::Loot));
+        assert_ne!(a.fold(), b.fold());
+        assert_eq!(RngStreams::from_states(a.states()), a);
+    }
+
+    #[test]
+    fn test_split_and_ranges() {
+        let mut parent = Rng::from_state(99);
+        let mut child = parent.split();
+        assert_ne!(child.next_u32(), parent.next_u32());
+        for _ in 0..100 {
+            let f = parent.range(-2.0, 3.0);
+            assert!((-2.0..=3.0).contains(&f));
+            let i = parent.int_range(30, 100);
+            assert!((30..=100).contains(&i));
+        }
+    }
+}
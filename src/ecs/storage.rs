@@ -0,0 +1,432 @@
+use super::Entity;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::hash::{BuildHasherDefault, Hasher};
+
+/// Sentinel in [`SparseSet::sparse`]: the entity has no component here.
+const ABSENT: u32 = u32::MAX;
+
+/// Dense storage for every component of one type, keyed by entity index.
+///
+/// * `sparse[index]` holds the entity's slot in the dense arrays (or
+///   [`ABSENT`]), so a lookup is one bounds-checked index plus one read —
+///   no hashing, no boxing, no per-component allocation. The packed
+///   `entities[slot]` keeps the full handle, so a stale handle (same index,
+///   older generation) misses instead of reading the slot's new occupant.
+/// * `entities[slot]` / `data[slot]` are packed and kept sorted by entity
+///   index. That makes [`SparseSet::entities`] the deterministic list
+///   `World::query` used to build by collecting and sorting `HashMap` keys on
+///   every call — it is now a plain slice copy.
+///
+/// Every row also carries its [`ComponentTicks`] — the world change tick it
+/// was added at and last mutably borrowed at — for the `Added` / `Changed`
+/// query filters, and the set keeps the newest tick any of its rows was
+/// inserted, borrowed or removed at, so "did any `T` change" is one read.
+///
+/// Keeping the packed arrays sorted costs a shift on an out-of-order insert
+/// (adding `Stunned` to an old rogue, or filling a recycled index) or a
+/// removal; the sets in this game hold at most a few hundred entries, and the
+/// common case — components attached to a freshly spawned entity (the highest
+/// index) — is a push.
+#[derive(Clone)]
+pub struct SparseSet<T> {
+    sparse: Vec<u32>,
+    entities: Vec<Entity>,
+    data: Vec<T>,
+    ticks: Vec<ComponentTicks>,
+    changed: u64,
+}
+
+/// When a component row was inserted and when it was last handed out
+/// mutably, in world change ticks (see `World::change_tick`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ComponentTicks {
+    pub added: u64,
+    pub changed: u64,
+}
+
+impl ComponentTicks {
+    fn new(tick: u64) -> Self {
+        ComponentTicks {
+            added: tick,
+            changed: tick,
+        }
+    }
+}
+
+impl<T> SparseSet<T> {
+    pub fn new() -> Self {
+        SparseSet {
+            sparse: Vec::new(),
+            entities: Vec::new(),
+            data: Vec::new(),
+            ticks: Vec::new(),
+            changed: 0,
+        }
+    }
+
+    /// Packed slot of whatever occupies `entity`'s index, any generation.
+    fn index_slot(&self, entity: Entity) -> Option<usize> {
+        match self.sparse.get(entity.index() as usize) {
+            Some(&slot) if slot != ABSENT => Some(slot as usize),
+            _ => None,
+        }
+    }
+
+    fn slot(&self, entity: Entity) -> Option<usize> {
+        self.index_slot(entity)
+            .filter(|&slot| self.entities[slot] == entity)
+    }
+
+    /// Re-point `sparse` at every packed entry from `from` on (after a shift).
+    fn reindex_from(&mut self, from: usize) {
+        for (slot, e) in self.entities.iter().enumerate().skip(from) {
+            self.sparse[e.index() as usize] = slot as u32;
+        }
+    }
+
+    /// Insert or overwrite `entity`'s component at change tick `tick` (a row
+    /// left under an older generation of the same index is taken over). An
+    /// overwrite counts as a change, not an addition.
+    pub fn insert(&mut self, entity: Entity, value: T, tick: u64) {
+        self.changed = tick;
+        if let Some(slot) = self.index_slot(entity) {
+            if self.entities[slot] != entity {
+                self.ticks[slot] = ComponentTicks::new(tick);
+            }
+            self.entities[slot] = entity;
+            self.data[slot] = value;
+            self.ticks[slot].changed = tick;
+            return;
+        }
+        let index = entity.index() as usize;
+        if index >= self.sparse.len() {
+            self.sparse.resize(index + 1, ABSENT);
+        }
+        // Fast path: the newest entity goes at the end.
+        if self
+            .entities
+            .last()
+            .is_none_or(|last| last.index() < entity.index())
+        {
+            self.sparse[index] = self.entities.len() as u32;
+            self.entities.push(entity);
+            self.data.push(value);
+            self.ticks.push(ComponentTicks::new(tick));
+            return;
+        }
+        let at = self
+            .entities
+            .partition_point(|e| e.index() < entity.index());
+        self.entities.insert(at, entity);
+        self.data.insert(at, value);
+        self.ticks.insert(at, ComponentTicks::new(tick));
+        self.reindex_from(at);
+    }
+
+    pub fn get(&self, entity: Entity) -> Option<&T> {
+        self.slot(entity).map(|slot| &self.data[slot])
+    }
+
+    /// Mutable access, recorded as a change at `tick`.
+    pub fn get_mut(&mut self, entity: Entity, tick: u64) -> Option<&mut T> {
+        let slot = self.slot(entity)?;
+        self.ticks[slot].changed = tick;
+        self.changed = tick;
+        Some(&mut self.data[slot])
+    }
+
+    /// Apply `f` to `entity`'s row, recorded as a change at `tick` only if
+    /// it left the value different.
+    pub fn update<R>(&mut self, entity: Entity, tick: u64, f: impl FnOnce(&mut T) -> R) -> Option<R>
+    where
+        T: Clone + PartialEq,
+    {
+        let slot = self.slot(entity)?;
+        let before = self.data[slot].clone();
+        let result = f(&mut self.data[slot]);
+        if self.data[slot] != before {
+            self.ticks[slot].changed = tick;
+            self.changed = tick;
+        }
+        Some(result)
+    }
+
+    pub fn ticks(&self, entity: Entity) -> Option<ComponentTicks> {
+        self.slot(entity).map(|slot| self.ticks[slot])
+    }
+
+    /// Raw pointer to `entity`'s row, taken without materialising a `&mut`
+    /// to the whole packed array — so a `QueryMut` can keep `&mut`s to
+    /// several rows alive at once. Recorded as a change at `tick`.
+    ///
+    /// # Safety
+    ///
+    /// `this` must point to a live set valid for reads and writes.
+    pub(super) unsafe fn row_ptr(this: *mut Self, entity: Entity, tick: u64) -> Option<*mut T> {
+        let slot = (*this).slot(entity)?;
+        (*(*this).ticks.as_mut_ptr().add(slot)).changed = tick;
+        (*this).changed = tick;
+        Some((*this).data.as_mut_ptr().add(slot))
+    }
+
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.slot(entity).is_some()
+    }
+
+    /// Remove and return `entity`'s component at change tick `tick`, keeping
+    /// the packed order.
+    pub fn remove(&mut self, entity: Entity, tick: u64) -> Option<T> {
+        let slot = self.slot(entity)?;
+        self.changed = tick;
+        self.sparse[entity.index() as usize] = ABSENT;
+        self.entities.remove(slot);
+        self.ticks.remove(slot);
+        let value = self.data.remove(slot);
+        self.reindex_from(slot);
+        Some(value)
+    }
+
+    /// The newest change tick any row was inserted, mutably borrowed or
+    /// removed at.
+    pub fn changed_tick(&self) -> u64 {
+        self.changed
+    }
+
+    /// Every entity holding this component, sorted by index.
+    pub fn entities(&self) -> &[Entity] {
+        &self.entities
+    }
+
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    /// `(entity, component)` pairs in index order.
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, &T)> {
+        self.entities.iter().copied().zip(self.data.iter())
+    }
+}
+
+impl<T> Default for SparseSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Object-safe view of a [`SparseSet`] of any component type: what the world
+/// keeps per `TypeId`. The type-erased operations are exactly the ones the
+/// world needs without knowing `T` — snapshotting (`World: Clone` backs the
+/// `checkpoint` scenario action) and dropping a despawned entity's row.
+pub trait AnyStorage: Any {
+    fn clone_box(&self) -> Box<dyn AnyStorage>;
+    /// Drop `entity`'s row at change tick `tick`; whether it had one.
+    fn remove_entity(&mut self, entity: Entity, tick: u64) -> bool;
+    fn contains(&self, entity: Entity) -> bool;
+    fn entities(&self) -> &[Entity];
+    /// The tick of the latest write to any row (see `SparseSet::changed_tick`).
+    fn changed_tick(&self) -> u64;
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: Any + Clone> AnyStorage for SparseSet<T> {
+    fn clone_box(&self) -> Box<dyn AnyStorage> {
+        Box::new(self.clone())
+    }
+
+    fn remove_entity(&mut self, entity: Entity, tick: u64) -> bool {
+        self.remove(entity, tick).is_some()
+    }
+
+    fn contains(&self, entity: Entity) -> bool {
+        SparseSet::contains(self, entity)
+    }
+
+    fn entities(&self) -> &[Entity] {
+        SparseSet::entities(self)
+    }
+
+    fn changed_tick(&self) -> u64 {
+        SparseSet::changed_tick(self)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+impl Clone for Box<dyn AnyStorage> {
+    fn clone(&self) -> Self {
+        // Explicit deref: `Box<dyn AnyStorage>` is itself `Any + Clone`, so
+        // `self.clone_box()` would resolve on the box and recurse forever.
+        (**self).clone_box()
+    }
+}
+
+/// `TypeId` is already a well-mixed hash: feed its bits straight through
+/// instead of running SipHash on every component lookup.
+#[derive(Default)]
+pub struct TypeIdHasher(u64);
+
+impl Hasher for TypeIdHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        // Not the path `TypeId` takes; fold anything else in FNV-style so the
+        // hasher stays correct for any key.
+        for &b in bytes {
+            self.0 = (self.0 ^ b as u64).wrapping_mul(0x100_0000_01b3);
+        }
+    }
+
+    fn write_u64(&mut self, n: u64) {
+        self.0 ^= n;
+    }
+}
+
+/// The world's `TypeId -> storage` map.
+pub type StorageMap = HashMap<TypeId, Box<dyn AnyStorage>, BuildHasherDefault<TypeIdHasher>>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_out_of_order_stays_sorted() {
+        let mut set = SparseSet::new();
+        set.insert(Entity::new(5, 0), 'a', 1);
+        set.insert(Entity::new(9, 0), 'b', 1);
+        set.insert(Entity::new(2, 0), 'c', 1);
+        set.insert(Entity::new(7, 0), 'd', 1);
+        let ids: Vec<u32> = set.entities().iter().map(|e| e.index()).collect();
+        assert_eq!(ids, vec![2, 5, 7, 9]);
+        assert_eq!(set.get(Entity::new(7, 0)), Some(&'d'));
+        assert_eq!(set.get(Entity::new(5, 0)), Some(&'a'));
+        assert_eq!(set.get(Entity::new(3, 0)), None);
+    }
+
+    #[test]
+    fn test_remove_keeps_lookups_valid() {
+        let mut set = SparseSet::new();
+        for id in 0..6 {
+            set.insert(Entity::new(id, 0), id * 10, 1);
+        }
+        assert_eq!(set.remove(Entity::new(2, 0), 1), Some(20));
+        assert_eq!(set.remove(Entity::new(2, 0), 1), None);
+        assert!(!set.contains(Entity::new(2, 0)));
+        for id in [0, 1, 3, 4, 5] {
+            assert_eq!(set.get(Entity::new(id, 0)), Some(&(id * 10)));
+        }
+        *set.get_mut(Entity::new(4, 0), 1).unwrap() = 99;
+        let pairs: Vec<(u32, u32)> = set.iter().map(|(e, v)| (e.index(), *v)).collect();
+        assert_eq!(pairs, vec![(0, 0), (1, 10), (3, 30), (4, 99), (5, 50)]);
+    }
+
+    #[test]
+    fn test_stale_generation_misses() {
+        let mut set = SparseSet::new();
+        set.insert(Entity::new(4, 0), 'a', 1);
+        assert_eq!(set.get(Entity::new(4, 1)), None);
+        assert!(!set.contains(Entity::new(4, 1)));
+        assert_eq!(set.remove(Entity::new(4, 1), 1), None);
+
+        // The recycled index takes the row over.
+        set.insert(Entity::new(4, 1), 'b', 1);
+        assert_eq!(set.len(), 1);
+        assert_eq!(set.get(Entity::new(4, 0)), None);
+        assert_eq!(set.get(Entity::new(4, 1)), Some(&'b'));
+    }
+
+    #[test]
+    fn test_ticks_track_add_and_mutation() {
+        let mut set = SparseSet::new();
+        let e = Entity::new(0, 0);
+        set.insert(e, 1, 3);
+        assert_eq!(
+            set.ticks(e),
+            Some(ComponentTicks {
+                added: 3,
+                changed: 3
+            })
+        );
+        set.get_mut(e, 5);
+        assert_eq!(
+            set.ticks(e),
+            Some(ComponentTicks {
+                added: 3,
+                changed: 5
+            })
+        );
+        set.insert(e, 2, 7);
+        assert_eq!(
+            set.ticks(e),
+            Some(ComponentTicks {
+                added: 3,
+                changed: 7
+            })
+        );
+        // A recycled index is a fresh addition.
+        let reused = Entity::new(0, 1);
+        set.insert(reused, 9, 8);
+        assert_eq!(
+            set.ticks(reused),
+            Some(ComponentTicks {
+                added: 8,
+                changed: 8
+            })
+        );
+        // The set as a whole: the newest insert, borrow or removal. A
+        // removal that finds nothing is not one.
+        assert_eq!(set.changed_tick(), 8);
+        set.get_mut(reused, 9);
+        assert_eq!(set.changed_tick(), 9);
+        set.remove(e, 11);
+        assert_eq!(set.changed_tick(), 9);
+        set.remove(reused, 12);
+        assert_eq!(set.changed_tick(), 12);
+    }
+
+    #[test]
+    fn test_insert_overwrites_in_place() {
+        let mut set = SparseSet::new();
+        set.insert(Entity::new(1, 0), 1, 1);
+        set.insert(Entity::new(1, 0), 2, 1);
+        assert_eq!(set.len(), 1);
+        assert_eq!(set.get(Entity::new(1, 0)), Some(&2));
+    }
+
+    #[test]
+    fn test_boxed_storage_clones_deeply() {
+        let mut set = SparseSet::new();
+        set.insert(Entity::new(3, 0), String::from("bar"), 1);
+        let boxed: Box<dyn AnyStorage> = Box::new(set);
+        let mut copy = boxed.clone();
+        AnyStorage::as_any_mut(copy.as_mut())
+            .downcast_mut::<SparseSet<String>>()
+            .unwrap()
+            .get_mut(Entity::new(3, 0), 1)
+            .unwrap()
+            .push('!');
+        let original = AnyStorage::as_any(boxed.as_ref())
+            .downcast_ref::<SparseSet<String>>()
+            .unwrap();
+        assert_eq!(
+            original.get(Entity::new(3, 0)).map(String::as_str),
+            Some("bar")
+        );
+        copy.remove_entity(Entity::new(3, 0), 1);
+        assert!(!copy.contains(Entity::new(3, 0)));
+        assert!(boxed.contains(Entity::new(3, 0)));
+    }
+}
@@ -31,9 +31,213 @@ where
     }
 }
 
+/// Whether a scheduled system runs this tick, given the host's per-tick
+/// context (e.g. "only while no tutorial gate freezes the world").
+pub type RunCondition<C> = fn(&C) -> bool;
+
+struct ScheduledSystem<C> {
+    name: &'static str,
+    stage: usize,
+    system: Box<dyn System>,
+    before: Vec<&'static str>,
+    after: Vec<&'static str>,
+    condition: Option<RunCondition<C>>,
+    // Change-detection bookmark (see `World::run_tracked`).
+    last_run: u64,
+}
+
+/// An ordered set of systems, declared once and run by every host.
+///
+/// Systems are registered into named stages (run in the order the stages were
+/// declared) and may add `before` / `after` constraints on other systems by
+/// name. Within a stage the order is the constraints' topological order, ties
+/// broken by registration order, so the result is deterministic. A constraint
+/// pointing across stages must agree with the stage order; unknown names,
+/// contradictions and cycles panic the first time the schedule runs. The
+/// end of every stage applies the [`super::Commands`] its systems deferred.
+///
+/// Each system is a change-detection reader of its own: its `Added` /
+/// `Changed` filters and `World::removed` see what happened since *it* last
+/// ran, however many ticks ago that was. Removal records are only kept for
+/// one full pass, though, so a system skipped by its run condition misses
+/// the older ones.
+///
+/// `C` is whatever the host passes to [`Schedule::run`] for the run
+/// conditions to read.
+pub struct Schedule<C = ()> {
+    stages: Vec<&'static str>,
+    systems: Vec<ScheduledSystem<C>>,
+    // Resolved run order (indices into `systems`); rebuilt after any change.
+    order: Option<Vec<usize>>,
+    // World change tick when the previous pass started.
+    last_pass: u64,
+}
+
+/// Returned by [`Schedule::add_system`] to attach constraints and a run
+/// condition to the system just added.
+pub struct SystemConfig<'a, C> {
+    system: &'a mut ScheduledSystem<C>,
+}
+
+impl<C> SystemConfig<'_, C> {
+    /// Run before the system named `other` (when both run this tick).
+    pub fn before(self, other: &'static str) -> Self {
+        self.system.before.push(other);
+        self
+    }
+
+    /// Run after the system named `other` (when both run this tick).
+    pub fn after(self, other: &'static str) -> Self {
+        self.system.after.push(other);
+        self
+    }
+
+    /// Only run on ticks where `condition` holds.
+    pub fn run_if(self, condition: RunCondition<C>) -> Self {
+        self.system.condition = Some(condition);
+        self
+    }
+}
+
+impl<C> Schedule<C> {
+    pub fn new(stages: &[&'static str]) -> Self {
+        Schedule {
+            stages: stages.to_vec(),
+            systems: Vec::new(),
+            order: None,
+            last_pass: 0,
+        }
+    }
+
+    /// Register `system` under a unique `name` in `stage`.
+    pub fn add_system(
+        &mut self,
+        stage: &'static str,
+        name: &'static str,
+        system: impl System + 'static,
+    ) -> SystemConfig<'_, C> {
+        let Some(stage) = self.stages.iter().position(|&s| s == stage) else {
+            panic!("schedule has no stage `{stage}` (for system `{name}`)");
+        };
+        assert!(
+            self.systems.iter().all(|s| s.name != name),
+            "system `{name}` is scheduled twice"
+        );
+        self.order = None;
+        self.systems.push(ScheduledSystem {
+            name,
+            stage,
+            system: Box::new(system),
+            before: Vec::new(),
+            after: Vec::new(),
+            condition: None,
+            last_run: 0,
+        });
+        SystemConfig {
+            system: self.systems.last_mut().expect("just pushed"),
+        }
+    }
+
+    /// Run every system whose condition holds, in schedule order.
+    pub fn run(&mut self, world: &mut World, ctx: &C, dt: f32) {
+        if self.order.is_none() {
+            self.order = Some(self.resolve());
+        }
+        let order = self.order.as_ref().expect("resolved above");
+        // Everything removed before the previous pass began has been seen
+        // by every system that ran since (unless the world was swapped out).
+        if self.last_pass <= world.change_tick() {
+            world.prune_removed(self.last_pass);
+        }
+        self.last_pass = world.change_tick();
+        for (k, &i) in order.iter().enumerate() {
+            let scheduled = &mut self.systems[i];
+            if scheduled.condition.is_none_or(|cond| cond(ctx)) {
+                let system = &mut scheduled.system;
+                world.run_tracked(&mut scheduled.last_run, |world| system.run(world, dt));
+            }
+            // Stage boundary: apply the structural changes systems deferred.
+            let stage = scheduled.stage;
+            if order
+                .get(k + 1)
+                .is_none_or(|&next| self.systems[next].stage != stage)
+            {
+                world.apply_deferred();
+            }
+        }
+    }
+
+    /// The resolved run order, by system name (ignoring run conditions).
+    pub fn order(&mut self) -> Vec<&'static str> {
+        if self.order.is_none() {
+            self.order = Some(self.resolve());
+        }
+        let order = self.order.as_ref().expect("resolved above");
+        order.iter().map(|&i| self.systems[i].name).collect()
+    }
+
+    fn index_of(&self, name: &str, from: &str) -> usize {
+        self.systems
+            .iter()
+            .position(|s| s.name == name)
+            .unwrap_or_else(|| panic!("system `{from}` is ordered against unknown `{name}`"))
+    }
+
+    /// Stage by stage, Kahn's algorithm over the same-stage constraints,
+    /// always taking the earliest-registered ready system.
+    fn resolve(&self) -> Vec<usize> {
+        let n = self.systems.len();
+        // edges[a] = systems that must run after a.
+        let mut edges: Vec<Vec<usize>> = vec![Vec::new(); n];
+        let mut pending = vec![0usize; n];
+        for (i, system) in self.systems.iter().enumerate() {
+            let firsts = system
+                .after
+                .iter()
+                .map(|&o| (self.index_of(o, system.name), i));
+            let thens = system
+                .before
+                .iter()
+                .map(|&o| (i, self.index_of(o, system.name)));
+            for (first, then) in firsts.chain(thens).collect::<Vec<_>>() {
+                let (a, b) = (&self.systems[first], &self.systems[then]);
+                match a.stage.cmp(&b.stage) {
+                    std::cmp::Ordering::Less => {}
+                    std::cmp::Ordering::Greater => panic!(
+                        "`{}` must run before `{}`, but its stage `{}` comes after `{}`",
+                        a.name, b.name, self.stages[a.stage], self.stages[b.stage]
+                    ),
+                    std::cmp::Ordering::Equal => {
+                        edges[first].push(then);
+                        pending[then] += 1;
+                    }
+                }
+            }
+        }
+
+        let mut order = Vec::with_capacity(n);
+        for stage in 0..self.stages.len() {
+            let mut left: Vec<usize> = (0..n).filter(|&i| self.systems[i].stage == stage).collect();
+            while !left.is_empty() {
+                let Some(pos) = left.iter().position(|&i| pending[i] == 0) else {
+                    let names: Vec<_> = left.iter().map(|&i| self.systems[i].name).collect();
+                    panic!("schedule cycle among {names:?}");
+                };
+                let next = left.remove(pos);
+                for &then in &edges[next] {
+                    pending[then] -= 1;
+                }
+                order.push(next);
+            }
+        }
+        order
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ecs::Entity;
 
     #[derive(Debug, Clone, PartialEq)]
     struct Counter {
@@ -83,4 +287,157 @@ mod tests {
         let counter = world.get_component::<Counter>(entity).unwrap();
         assert_eq!(counter.value, 10);
     }
+
+    fn log_system(tag: &'static str) -> FnSystem<impl FnMut(&mut World, f32)> {
+        FnSystem::new(move |world: &mut World, _dt: f32| {
+            let log = world.query::<Vec<&'static str>>()[0];
+            world
+                .get_component_mut::<Vec<&'static str>>(log)
+                .unwrap()
+                .push(tag);
+        })
+    }
+
+    fn log_world() -> (World, Entity) {
+        let mut world = World::new();
+        let log = world.spawn();
+        world.add_component(log, Vec::<&'static str>::new());
+        (world, log)
+    }
+
+    #[test]
+    fn test_schedule_orders_by_stage_then_constraints() {
+        let mut schedule: Schedule = Schedule::new(&["early", "late"]);
+        schedule.add_system("late", "d", log_system("d"));
+        schedule.add_system("early", "a", log_system("a"));
+        schedule
+            .add_system("early", "b", log_system("b"))
+            .before("a");
+        schedule
+            .add_system("early", "c", log_system("c"))
+            .after("a");
+        assert_eq!(schedule.order(), vec!["b", "a", "c", "d"]);
+
+        let (mut world, log) = log_world();
+        schedule.run(&mut world, &(), 0.016);
+        assert_eq!(
+            world.get_component::<Vec<&'static str>>(log).unwrap(),
+            &vec!["b", "a", "c", "d"]
+        );
+    }
+
+    #[test]
+    fn test_schedule_run_conditions() {
+        let mut schedule: Schedule<bool> = Schedule::new(&["main"]);
+        schedule.add_system("main", "always", log_system("always"));
+        schedule
+            .add_system("main", "frozen", log_system("frozen"))
+            .run_if(|&frozen| frozen);
+        schedule
+            .add_system("main", "live", log_system("live"))
+            .run_if(|&frozen| !frozen);
+
+        let (mut world, log) = log_world();
+        schedule.run(&mut world, &false, 0.016);
+        schedule.run(&mut world, &true, 0.016);
+        assert_eq!(
+            world.get_component::<Vec<&'static str>>(log).unwrap(),
+            &vec!["always", "live", "always", "frozen"]
+        );
+    }
+
+    #[test]
+    fn test_schedule_applies_deferred_commands_at_stage_boundaries() {
+        use crate::ecs::Commands;
+        let mut schedule: Schedule = Schedule::new(&["first", "second"]);
+        let despawn_all = |world: &mut World, _dt: f32| {
+            let mut commands = Commands::new();
+            for e in world.query::<u32>() {
+                commands.despawn(e);
+            }
+            world.defer(commands);
+        };
+        let count = |world: &mut World, _dt: f32| {
+            let seen = world.count::<u32>() as u32;
+            let log = world.query::<Vec<u32>>()[0];
+            world.get_component_mut::<Vec<u32>>(log).unwrap().push(seen);
+        };
+        schedule.add_system("first", "despawn", FnSystem::new(despawn_all));
+        schedule.add_system("first", "same_stage", FnSystem::new(count));
+        schedule.add_system("second", "next_stage", FnSystem::new(count));
+
+        let mut world = World::new();
+        let log = world.spawn();
+        world.add_component(log, Vec::<u32>::new());
+        let doomed = world.spawn();
+        world.add_component(doomed, 7u32);
+        schedule.run(&mut world, &(), 0.016);
+        // Still there for the rest of its stage, gone for the next one.
+        assert_eq!(world.get_component::<Vec<u32>>(log).unwrap(), &vec![1, 0]);
+        assert!(!world.is_alive(doomed));
+    }
+
+    #[test]
+    fn test_schedule_change_detection_is_per_system() {
+        use crate::ecs::Changed;
+        let mut schedule: Schedule<bool> = Schedule::new(&["main"]);
+        let bump = |world: &mut World, _dt: f32| {
+            for counter in world.query_mut::<&mut Counter>() {
+                counter.value += 1;
+            }
+        };
+        // Logs how many counters changed since *it* last ran.
+        let watch = |world: &mut World, _dt: f32| {
+            let changed = world
+                .query_ref_filtered::<&Counter, Changed<Counter>>()
+                .count() as u32;
+            let log = world.query::<Vec<u32>>()[0];
+            world
+                .get_component_mut::<Vec<u32>>(log)
+                .unwrap()
+                .push(changed);
+        };
+        schedule
+            .add_system("main", "bump", FnSystem::new(bump))
+            .run_if(|&on| on);
+        schedule
+            .add_system("main", "watch", FnSystem::new(watch))
+            .run_if(|&on| on);
+        schedule.add_system("main", "watch_always", FnSystem::new(watch));
+
+        let mut world = World::new();
+        let log = world.spawn();
+        world.add_component(log, Vec::<u32>::new());
+        let counter = world.spawn();
+        world.add_component(counter, Counter { value: 0 });
+        schedule.run(&mut world, &true, 0.016);
+        schedule.run(&mut world, &false, 0.016);
+        schedule.run(&mut world, &false, 0.016);
+        schedule.run(&mut world, &true, 0.016);
+        assert_eq!(world.get_component::<Counter>(counter).unwrap().value, 2);
+        let seen = world.get_component::<Vec<u32>>(log).unwrap();
+        // watch: [1, 1] (each run sees the bump before it);
+        // watch_always: 1, then nothing while bump is off, then 1.
+        assert_eq!(seen, &vec![1, 1, 0, 0, 1, 1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "schedule cycle")]
+    fn test_schedule_cycle_panics() {
+        let mut schedule: Schedule = Schedule::new(&["main"]);
+        schedule.add_system("main", "a", log_system("a")).after("b");
+        schedule.add_system("main", "b", log_system("b")).after("a");
+        schedule.order();
+    }
+
+    #[test]
+    #[should_panic(expected = "comes after")]
+    fn test_schedule_rejects_constraint_against_stage_order() {
+        let mut schedule: Schedule = Schedule::new(&["early", "late"]);
+        schedule.add_system("early", "a", log_system("a"));
+        schedule
+            .add_system("late", "b", log_system("b"))
+            .before("a");
+        schedule.order();
+    }
 }
@@ -1,13 +1,26 @@
-use super::component::AnyComponent;
+use super::commands::Commands;
+use super::query::{Query, QueryData, QueryFilter, QueryMut, ReadOnlyQueryData};
+use super::resource::{AnyResource, ResourceMap};
+use super::rng::{Rng, RngStream, RngStreams};
+use super::storage::{AnyStorage, ComponentTicks, SparseSet, StorageMap, TypeIdHasher};
 use super::{Component, Entity};
 use crate::components::GameEvent;
 use std::any::TypeId;
-use std::collections::HashMap;
-
-/// ComponentStorage stores all components of a specific type. The boxes are
-/// clonable ([`AnyComponent`]) so the whole world can be snapshotted (the
-/// `checkpoint` scenario action).
-type ComponentStorage = HashMap<Entity, Box<dyn AnyComponent>>;
+use std::collections::{HashMap, VecDeque};
+use std::hash::BuildHasherDefault;
+use std::marker::PhantomData;
+use std::ptr::NonNull;
+
+/// What a wall is made of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WallMaterial {
+    /// An opaque, indestructible slab.
+    #[default]
+    Solid,
+    /// A glass partition: sight passes through it, actors do not, and a
+    /// round or a thrown weapon shatters it (see [`World::shatter_wall`]).
+    Glass,
+}
 
 /// Wall obstacle represented as a rectangle
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -16,6 +29,7 @@ pub struct Wall {
     pub y: f32,
     pub width: f32,
     pub height: f32,
+    pub material: WallMaterial,
 }
 
 impl Wall {
@@ -25,28 +39,44 @@ impl Wall {
             y,
             width,
             height,
+            material: WallMaterial::Solid,
         }
     }
+
+    /// The same rectangle made of `material`.
+    pub fn with_material(self, material: WallMaterial) -> Self {
+        Wall { material, ..self }
+    }
+
+    pub fn is_glass(&self) -> bool {
+        self.material == WallMaterial::Glass
+    }
 }
 
 /// World manages all entities and their components.
 ///
 /// `Clone` produces a full deep snapshot (entities, components, walls, RNG
 /// state, pending events) — the `checkpoint` scenario action stores one and
-/// death restores it.
+/// death restores it (through [`World::restore`], so handles from the
+/// abandoned timeline stay dead).
 #[derive(Clone)]
 pub struct World {
-    next_entity_id: u64,
-    // Map from ComponentId to storage for that component type
-    components: HashMap<TypeId, ComponentStorage>,
-    // Track which entities exist
+    // Current generation of every entity index ever handed out, and whether
+    // that index is occupied right now.
+    slots: Vec<EntitySlot>,
+    // Despawned indices waiting to be reused, oldest first: FIFO reuse keeps
+    // a just-freed index (and its handles) out of circulation the longest.
+    free: VecDeque<u32>,
+    // One sparse set per component type (see `storage::SparseSet`). The
+    // boxes are clonable so the whole world can be snapshotted.
+    components: StorageMap,
+    // Track which entities exist, sorted by index
     entities: Vec<Entity>,
     // Static walls in the world
     walls: Vec<Wall>,
-    // Per-world RNG state (LCG). Seeded deterministically so each World is
-    // independently reproducible; formerly this lived in a process-global
-    // `static mut` in the AI system.
-    rng_state: u32,
+    // Per-world random streams, one per subsystem (see `ecs::rng`). Seeded
+    // deterministically so each World is independently reproducible.
+    rng: RngStreams,
     // One-shot gameplay events queued this frame (see `push_event`).
     events: Vec<GameEvent>,
     // Whether debug visualisation is being shown this frame (the `?debug` + I
@@ -54,26 +84,178 @@ pub struct World {
     // (headless sims, tests). Systems skip recording debug-only structures
     // (DebugPath / DebugTrail) while this is off.
     debug_viz: bool,
+    // Structural changes systems deferred this stage (see `defer`).
+    deferred: Deferred,
+    // Change detection (see `run_tracked`): the tick writes are stamped with
+    // right now, and the baseline the `Added` / `Changed` filters and
+    // `removed` compare against.
+    change_tick: u64,
+    last_change_tick: u64,
+    // Every component removal (explicit or by despawn), per type, with the
+    // tick it happened at. Pruned by the schedule once every system has
+    // seen an entry.
+    removed: RemovedLog,
+    // Singletons keyed by type (see `insert_resource`): shared engine state
+    // like the navigation grid, snapshotted with everything else.
+    resources: ResourceMap,
 }
 
+type RemovedLog = HashMap<TypeId, Vec<(Entity, u64)>, BuildHasherDefault<TypeIdHasher>>;
+
+/// The world's queue of deferred [`Commands`]. Snapshots are taken between
+/// ticks, when every stage boundary has flushed it, so a clone starts empty.
+#[derive(Default)]
+struct Deferred(Commands);
+
+impl Clone for Deferred {
+    fn clone(&self) -> Self {
+        debug_assert!(
+            self.0.is_empty(),
+            "world snapshotted with deferred commands pending"
+        );
+        Deferred::default()
+    }
+}
+
+/// The seed a fresh [`World`] derives its random streams from.
+pub const DEFAULT_SEED: u32 = 12345;
+
 /// Upper bound on queued events. A consumer that never drains (e.g. the
 /// headless simulation) must not make the queue grow without limit; past this
 /// many pending events new ones are dropped.
 pub const MAX_PENDING_EVENTS: usize = 256;
 
+#[derive(Debug, Clone, Copy)]
+struct EntitySlot {
+    generation: u32,
+    alive: bool,
+    // Newest generation ever handed out at this index, abandoned timelines
+    // included (see `World::restore`); retiring the slot moves past it.
+    newest: u32,
+}
+
+impl EntitySlot {
+    fn new(generation: u32, alive: bool) -> Self {
+        EntitySlot {
+            generation,
+            alive,
+            newest: generation,
+        }
+    }
+}
+
 impl World {
     pub fn new() -> Self {
         World {
-            next_entity_id: 0,
-            components: HashMap::new(),
+            slots: Vec::new(),
+            free: VecDeque::new(),
+            components: StorageMap::default(),
             entities: Vec::new(),
             walls: Vec::new(),
-            rng_state: 12345,
+            rng: RngStreams::from_seed(DEFAULT_SEED),
             events: Vec::new(),
             debug_viz: false,
+            deferred: Deferred::default(),
+            change_tick: 1,
+            last_change_tick: 0,
+            removed: RemovedLog::default(),
+            resources: ResourceMap::default(),
+        }
+    }
+
+    /// The tick every component write is stamped with right now. It advances
+    /// once per [`World::run_tracked`] call, i.e. once per system run.
+    pub fn change_tick(&self) -> u64 {
+        self.change_tick
+    }
+
+    /// The baseline the change filters compare against: writes stamped after
+    /// it count as "added" / "changed" for whoever is running now.
+    pub fn last_change_tick(&self) -> u64 {
+        self.last_change_tick
+    }
+
+    /// Run `f` as one change-detection reader. `last_run` is the reader's own
+    /// bookmark (the schedule keeps one per system; a host keeps a field):
+    /// inside `f`, `Added<T>`, `Changed<T>` and [`World::removed`] report
+    /// everything written since that reader's previous run by anyone else
+    /// (its own writes carry the tick it ran at, which the bookmark already
+    /// covers), and on return the bookmark moves past now.
+    ///
+    /// A bookmark from the future (a world swapped for a fresh one or rolled
+    /// back) starts over at zero, so the reader sees everything once.
+    pub fn run_tracked<R>(&mut self, last_run: &mut u64, f: impl FnOnce(&mut World) -> R) -> R {
+        let since = if *last_run > self.change_tick {
+            0
+        } else {
+            *last_run
+        };
+        let outer = std::mem::replace(&mut self.last_change_tick, since);
+        let result = f(self);
+        self.last_change_tick = outer;
+        *last_run = self.change_tick;
+        self.change_tick += 1;
+        result
+    }
+
+    /// When `entity`'s `T` was added and last mutably borrowed.
+    pub fn component_ticks<T: Component>(&self, entity: Entity) -> Option<ComponentTicks> {
+        self.storage::<T>()?.ticks(entity)
+    }
+
+    /// Whether `entity` gained `T` since the current reader's baseline.
+    pub fn is_added<T: Component>(&self, entity: Entity) -> bool {
+        self.component_ticks::<T>(entity)
+            .is_some_and(|t| t.added > self.last_change_tick)
+    }
+
+    /// Whether `entity`'s `T` was added or mutably borrowed since the current
+    /// reader's baseline. Like any `&mut`-based tracking this is conservative:
+    /// a write of the same value still counts.
+    pub fn is_changed<T: Component>(&self, entity: Entity) -> bool {
+        self.component_ticks::<T>(entity)
+            .is_some_and(|t| t.changed > self.last_change_tick)
+    }
+
+    /// Whether any `T` was inserted, mutably borrowed or removed after change
+    /// tick `tick` — one read, however many entities hold a `T`. Pair it
+    /// with a bookmark from [`World::run_tracked`] to know whether something
+    /// derived from every `T` (the spatial index) is stale.
+    pub fn changed_since<T: Component>(&self, tick: u64) -> bool {
+        self.storage::<T>()
+            .is_some_and(|set| set.changed_tick() > tick)
+    }
+
+    /// Entities that lost their `T` (removed outright, or despawned) since
+    /// the current reader's baseline, oldest first. The handles may be dead.
+    pub fn removed<T: Component>(&self) -> RemovedComponents<'_, T> {
+        let log = self
+            .removed
+            .get(&TypeId::of::<T>())
+            .map_or(&[][..], Vec::as_slice);
+        let start = log.partition_point(|&(_, tick)| tick <= self.last_change_tick);
+        RemovedComponents {
+            entries: log[start..].iter(),
+            marker: PhantomData,
         }
     }
 
+    /// Forget removals every reader has already seen (stamped at or before
+    /// `tick`). The schedule calls this with its slowest system's bookmark.
+    pub fn prune_removed(&mut self, tick: u64) {
+        for log in self.removed.values_mut() {
+            let seen = log.partition_point(|&(_, t)| t <= tick);
+            log.drain(..seen);
+        }
+    }
+
+    fn log_removal(&mut self, id: TypeId, entity: Entity) {
+        self.removed
+            .entry(id)
+            .or_default()
+            .push((entity, self.change_tick));
+    }
+
     /// Whether debug visualisation is on this frame (see the field docs).
     pub fn debug_viz(&self) -> bool {
         self.debug_viz
@@ -104,141 +286,519 @@ impl World {
         &self.events
     }
 
-    /// Advance the world's LCG and return the next pseudo-random `u32`.
-    ///
-    /// Uses the classic Numerical Recipes constants; the seed (`12345`) and the
-    /// constants match the previous process-global generator, so a freshly
-    /// created world produces the exact same random sequence as before.
-    pub fn next_random(&mut self) -> u32 {
-        self.rng_state = self
-            .rng_state
-            .wrapping_mul(1664525)
-            .wrapping_add(1013904223);
-        self.rng_state
+    /// One subsystem's random stream. Systems that draw while holding other
+    /// borrows of the world copy it out (`*world.rng(..)`), draw from the
+    /// copy and write it back.
+    pub fn rng(&mut self, stream: RngStream) -> &mut Rng {
+        self.rng.get_mut(stream)
     }
 
-    /// Random float in `[min, max)` (or `[min, max]` at the endpoint).
-    pub fn random_range(&mut self, min: f32, max: f32) -> f32 {
-        let r = self.next_random() as f32 / u32::MAX as f32;
-        r * (max - min) + min
+    /// Every stream (saves, state hashes).
+    pub fn rng_streams(&self) -> &RngStreams {
+        &self.rng
     }
 
-    /// Random integer in `[min, max]` (inclusive).
-    pub fn random_int_range(&mut self, min: i32, max: i32) -> i32 {
-        let range = (max - min + 1) as u32;
-        min + (self.next_random() % range) as i32
+    /// Overwrite every stream (a loaded save).
+    pub fn set_rng_streams(&mut self, streams: RngStreams) {
+        self.rng = streams;
     }
 
-    /// Read the current raw RNG state. Systems that need to draw several random
-    /// numbers while holding other borrows of the world can copy this out, use
-    /// it locally, then write it back via [`World::set_rng_state`].
-    pub fn rng_state(&self) -> u32 {
-        self.rng_state
+    /// Derive every stream from `seed` (see [`RngStreams::from_seed`]).
+    pub fn seed_rng(&mut self, seed: u32) {
+        self.rng = RngStreams::from_seed(seed);
     }
 
-    /// Overwrite the raw RNG state (see [`World::rng_state`]).
-    pub fn set_rng_state(&mut self, state: u32) {
-        self.rng_state = state;
+    /// The RNG as one number: every stream folded together. Seeding a new
+    /// world with it carries a run on (the next floor, a retry) from where
+    /// this one stands.
+    pub fn rng_state(&self) -> u32 {
+        self.rng.fold()
     }
 
     /// Create a new entity
     pub fn spawn(&mut self) -> Entity {
-        let entity = Entity::new(self.next_entity_id);
-        self.next_entity_id += 1;
-        self.entities.push(entity);
+        let entity = match self.free.pop_front() {
+            Some(index) => {
+                let slot = &mut self.slots[index as usize];
+                slot.alive = true;
+                Entity::new(index, slot.generation)
+            }
+            None => {
+                self.slots.push(EntitySlot::new(0, true));
+                Entity::new(self.slots.len() as u32 - 1, 0)
+            }
+        };
+        let at = self
+            .entities
+            .partition_point(|e| e.index() < entity.index());
+        self.entities.insert(at, entity);
         entity
     }
 
+    /// Whether `entity` still refers to a live entity: false once it is
+    /// despawned, even after its index has been handed to someone else.
+    pub fn is_alive(&self, entity: Entity) -> bool {
+        self.slots
+            .get(entity.index() as usize)
+            .is_some_and(|slot| slot.alive && slot.generation == entity.generation())
+    }
+
+    /// Debug builds trap dereferencing a stale handle: a despawned entity
+    /// reached through a component that outlived it (a finisher victim, a
+    /// scenario target). Release builds just miss, like any absent component.
+    fn debug_assert_alive(&self, entity: Entity) {
+        debug_assert!(
+            self.is_alive(entity),
+            "stale entity handle {entity:?}: it was despawned"
+        );
+    }
+
+    /// The typed sparse set for `T`, if any entity ever held one.
+    ///
+    /// The trait is named explicitly: `Box<dyn AnyStorage>` is itself
+    /// `'static`, so a plain `.as_any()` would resolve to the blanket
+    /// [`Component`] impl on the box and every downcast would miss.
+    pub(super) fn storage<T: Component>(&self) -> Option<&SparseSet<T>> {
+        AnyStorage::as_any(self.components.get(&TypeId::of::<T>())?.as_ref())
+            .downcast_ref::<SparseSet<T>>()
+    }
+
+    pub(super) fn storage_mut<T: Component>(&mut self) -> Option<&mut SparseSet<T>> {
+        AnyStorage::as_any_mut(self.components.get_mut(&TypeId::of::<T>())?.as_mut())
+            .downcast_mut::<SparseSet<T>>()
+    }
+
+    /// [`World::storage`] for the query machinery: reached field by field
+    /// from the raw world pointer, never through a whole `&World` / `&mut
+    /// World`. A query derives every storage pointer from its one world
+    /// pointer this way, so taking the next one (say a `&mut` for a later
+    /// tuple element) cannot invalidate the ones already taken under Stacked
+    /// Borrows.
+    ///
+    /// # Safety
+    ///
+    /// `world` must be valid for reads.
+    pub(super) unsafe fn storage_ptr<T: Component>(
+        world: NonNull<World>,
+    ) -> Option<*const SparseSet<T>> {
+        let components = &(*world.as_ptr()).components;
+        AnyStorage::as_any(components.get(&TypeId::of::<T>())?.as_ref())
+            .downcast_ref::<SparseSet<T>>()
+            .map(|s| s as *const _)
+    }
+
+    /// [`World::storage_ptr`], writable.
+    ///
+    /// # Safety
+    ///
+    /// `world` must be valid for reads and writes.
+    pub(super) unsafe fn storage_ptr_mut<T: Component>(
+        world: NonNull<World>,
+    ) -> Option<*mut SparseSet<T>> {
+        let components = &mut (*world.as_ptr()).components;
+        AnyStorage::as_any_mut(components.get_mut(&TypeId::of::<T>())?.as_mut())
+            .downcast_mut::<SparseSet<T>>()
+            .map(|s| s as *mut _)
+    }
+
+    /// `(change_tick, last_change_tick)`, read the same way.
+    ///
+    /// # Safety
+    ///
+    /// `world` must be valid for reads.
+    pub(super) unsafe fn ticks_ptr(world: NonNull<World>) -> (u64, u64) {
+        let world = world.as_ptr();
+        ((*world).change_tick, (*world).last_change_tick)
+    }
+
+    /// The live entity list, read the same way.
+    ///
+    /// # Safety
+    ///
+    /// `world` must be valid for reads.
+    pub(super) unsafe fn entities_ptr(world: NonNull<World>) -> *const [Entity] {
+        (*world.as_ptr()).entities.as_slice()
+    }
+
     /// Add a component to an entity. Components must be `Clone` so the world
     /// can be snapshotted (see the struct docs).
     pub fn add_component<T: Component + Clone>(&mut self, entity: Entity, component: T) {
-        let type_id = TypeId::of::<T>();
-        let storage = self.components.entry(type_id).or_default();
-        storage.insert(entity, Box::new(component));
+        self.debug_assert_alive(entity);
+        if !self.is_alive(entity) {
+            return;
+        }
+        let storage = self
+            .components
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(SparseSet::<T>::new()));
+        AnyStorage::as_any_mut(storage.as_mut())
+            .downcast_mut::<SparseSet<T>>()
+            .expect("storage registered under its own TypeId")
+            .insert(entity, component, self.change_tick);
     }
 
     /// Get an immutable reference to a component
     pub fn get_component<T: Component>(&self, entity: Entity) -> Option<&T> {
-        let type_id = TypeId::of::<T>();
-        AnyComponent::as_any(self.components.get(&type_id)?.get(&entity)?.as_ref())
-            .downcast_ref::<T>()
+        self.debug_assert_alive(entity);
+        self.storage::<T>()?.get(entity)
     }
 
-    /// Get a mutable reference to a component
+    /// Get a mutable reference to a component (marks it changed)
     pub fn get_component_mut<T: Component>(&mut self, entity: Entity) -> Option<&mut T> {
-        let type_id = TypeId::of::<T>();
-        AnyComponent::as_any_mut(
-            self.components
-                .get_mut(&type_id)?
-                .get_mut(&entity)?
-                .as_mut(),
-        )
-        .downcast_mut::<T>()
+        self.debug_assert_alive(entity);
+        let tick = self.change_tick;
+        self.storage_mut::<T>()?.get_mut(entity, tick)
+    }
+
+    /// Apply `f` to `entity`'s `T`, recorded as a change only if the value
+    /// actually moved: unlike [`World::get_component_mut`], a write that
+    /// leaves it as it was (damage to a corpse already at zero) keeps
+    /// `Changed<T>` quiet. `None` if the entity has no `T`.
+    pub fn update_component<T: Component + Clone + PartialEq, R>(
+        &mut self,
+        entity: Entity,
+        f: impl FnOnce(&mut T) -> R,
+    ) -> Option<R> {
+        self.debug_assert_alive(entity);
+        let tick = self.change_tick;
+        self.storage_mut::<T>()?.update(entity, tick, f)
     }
 
     /// Check if an entity has a component
     pub fn has_component<T: Component>(&self, entity: Entity) -> bool {
-        let type_id = TypeId::of::<T>();
         self.components
-            .get(&type_id)
-            .map(|storage| storage.contains_key(&entity))
-            .unwrap_or(false)
+            .get(&TypeId::of::<T>())
+            .is_some_and(|storage| storage.contains(entity))
     }
 
     /// Remove a component from an entity
     pub fn remove_component<T: Component>(&mut self, entity: Entity) -> Option<T> {
-        let type_id = TypeId::of::<T>();
-        self.components
-            .get_mut(&type_id)?
-            .remove(&entity)?
-            .into_any()
-            .downcast::<T>()
-            .ok()
-            .map(|boxed| *boxed)
+        let tick = self.change_tick;
+        let value = self.storage_mut::<T>()?.remove(entity, tick)?;
+        self.log_removal(TypeId::of::<T>(), entity);
+        Some(value)
     }
 
-    /// Destroy an entity and all its components
+    /// Destroy an entity and all its components. Its index goes back on the
+    /// free list under a new generation; despawning a stale handle is a no-op.
     pub fn despawn(&mut self, entity: Entity) {
-        self.entities.retain(|&e| e != entity);
-        for storage in self.components.values_mut() {
-            storage.remove(&entity);
+        if !self.is_alive(entity) {
+            return;
         }
+        if let Ok(i) = self
+            .entities
+            .binary_search_by_key(&entity.index(), |e| e.index())
+        {
+            self.entities.remove(i);
+        }
+        let mut held = Vec::new();
+        for (&id, storage) in self.components.iter_mut() {
+            if storage.remove_entity(entity, self.change_tick) {
+                held.push(id);
+            }
+        }
+        for id in held {
+            self.log_removal(id, entity);
+        }
+        self.retire(entity.index());
+    }
+
+    fn retire(&mut self, index: u32) {
+        let slot = &mut self.slots[index as usize];
+        slot.alive = false;
+        slot.generation = slot.newest.wrapping_add(1);
+        slot.newest = slot.generation;
+        self.free.push_back(index);
+    }
+
+    /// Roll this world back to `snapshot` (a clone taken earlier from this
+    /// same world). A bare `*self = snapshot.clone()` would hand out again
+    /// every handle spawned since the snapshot — the same index under the
+    /// same generation — so anything still holding one (a replay, a stale
+    /// finisher) would silently alias the new entity. Restoring instead moves
+    /// every index the snapshot does not have alive past the generations this
+    /// world reached. An index the snapshot does have alive keeps its handle
+    /// (the restored entity is the same one), but remembers the newest
+    /// generation the abandoned timeline reached there, so despawning it
+    /// later skips past those handles too. The change tick keeps counting
+    /// forward as well, so readers' bookmarks stay meaningful across the
+    /// rollback.
+    pub fn restore(&mut self, snapshot: &World) {
+        let abandoned = std::mem::take(&mut self.slots);
+        let change_tick = self.change_tick;
+        *self = snapshot.clone();
+        self.change_tick = self.change_tick.max(change_tick);
+        for (index, later) in abandoned.iter().enumerate() {
+            match self.slots.get_mut(index) {
+                Some(slot) if slot.alive => {
+                    slot.newest = slot.newest.max(later.newest);
+                }
+                Some(slot) => {
+                    slot.generation = slot.newest.max(later.newest).wrapping_add(1);
+                    slot.newest = slot.generation;
+                }
+                None => {
+                    self.slots.push(EntitySlot::new(later.newest, true));
+                    self.retire(index as u32);
+                }
+            }
+        }
+    }
+
+    /// This world minus the component storages nobody wrote to since change
+    /// tick `tick` (a write stamped `tick` itself counts): patched onto a
+    /// clone of the world as it stood at `tick` with [`World::apply_delta`],
+    /// it gives this world back exactly. Everything else — handle space,
+    /// walls, RNG, events, resources — is copied whole.
+    pub fn delta_since(&self, tick: u64) -> WorldDelta {
+        let mut world = self.clone_without_components();
+        for (&id, storage) in &self.components {
+            if storage.changed_tick() >= tick {
+                world.components.insert(id, storage.clone());
+            }
+        }
+        WorldDelta {
+            world,
+            types: self.components.keys().copied().collect(),
+        }
+    }
+
+    /// Bring this world (as it stood when `delta` was taken against it) up
+    /// to the world `delta` was taken from. See [`World::delta_since`].
+    pub fn apply_delta(&mut self, delta: &WorldDelta) {
+        let mut kept = std::mem::take(&mut self.components);
+        kept.retain(|id, _| delta.types.contains(id));
+        *self = delta.world.clone();
+        for (id, storage) in kept {
+            self.components.entry(id).or_insert(storage);
+        }
+    }
+
+    /// Component rows across every storage (what a snapshot of this world
+    /// holds).
+    pub fn row_count(&self) -> usize {
+        self.components
+            .values()
+            .map(|storage| storage.entities().len())
+            .sum()
+    }
+
+    fn clone_without_components(&self) -> World {
+        World {
+            slots: self.slots.clone(),
+            free: self.free.clone(),
+            components: StorageMap::default(),
+            entities: self.entities.clone(),
+            walls: self.walls.clone(),
+            rng: self.rng.clone(),
+            events: self.events.clone(),
+            debug_viz: self.debug_viz,
+            deferred: self.deferred.clone(),
+            change_tick: self.change_tick,
+            last_change_tick: self.last_change_tick,
+            removed: self.removed.clone(),
+            resources: self.resources.clone(),
+        }
+    }
+
+    /// The handle space, for save games: each index's generation, liveness
+    /// and newest generation ever handed out there (see [`World::restore`]),
+    /// and the free list in reuse order.
+    pub fn entity_slots(&self) -> (Vec<(u32, bool, u32)>, Vec<u32>) {
+        let slots = self
+            .slots
+            .iter()
+            .map(|slot| (slot.generation, slot.alive, slot.newest))
+            .collect();
+        (slots, self.free.iter().copied().collect())
+    }
+
+    /// Rebuild the handle space of a freshly created world from
+    /// [`World::entity_slots`] output, so loaded handles (and the ones
+    /// spawned after) match the saved world's exactly. `free` must list
+    /// dead indices only, and no slot's newest generation may be older than
+    /// its current one.
+    pub fn restore_entity_slots(&mut self, slots: &[(u32, bool, u32)], free: &[u32]) {
+        assert!(
+            self.slots.is_empty(),
+            "entity slots restored into a used world"
+        );
+        self.slots = slots
+            .iter()
+            .map(|&(generation, alive, newest)| EntitySlot {
+                generation,
+                alive,
+                newest,
+            })
+            .collect();
+        self.free = free.iter().copied().collect();
+        self.entities = (0..slots.len() as u32)
+            .zip(slots)
+            .filter(|(_, &(_, alive, _))| alive)
+            .map(|(index, &(generation, _, _))| Entity::new(index, generation))
+            .collect();
+    }
+
+    /// Every entity holding `T`, sorted by entity index,
+    /// borrowed straight from the component's sparse set — no allocation.
+    /// Use [`World::query`] instead when the world is mutated while walking
+    /// the list.
+    pub fn entities_with<T: Component>(&self) -> &[Entity] {
+        self.storage::<T>().map(SparseSet::entities).unwrap_or(&[])
+    }
+
+    /// Number of entities holding `T`.
+    pub fn count<T: Component>(&self) -> usize {
+        self.components
+            .get(&TypeId::of::<T>())
+            .map_or(0, |storage| storage.entities().len())
     }
 
     /// Get all entities that have a specific component.
     ///
-    /// Results are sorted by entity id (i.e. spawn order). Component storage is a
-    /// `HashMap`, whose iteration order is randomized per-instance, so returning
-    /// the raw key order would make system behavior (RNG-consumption order,
-    /// nearest-target tie-breaks, ...) differ between otherwise identical worlds.
-    /// Sorting makes the whole simulation deterministic.
+    /// Results are sorted by entity index so system behavior
+    /// (RNG-consumption order, nearest-target tie-breaks, ...) is identical
+    /// between otherwise identical worlds. The sparse set keeps its packed
+    /// entity list in that order already, so this is a plain copy.
     pub fn query<T: Component>(&self) -> Vec<Entity> {
-        let type_id = TypeId::of::<T>();
-        let mut entities: Vec<Entity> = self
-            .components
-            .get(&type_id)
-            .map(|storage| storage.keys().copied().collect())
-            .unwrap_or_default();
-        entities.sort_unstable_by_key(|e| e.id());
-        entities
+        self.entities_with::<T>().to_vec()
+    }
+
+    /// Entities in the smallest of `sets` that are also in every other one,
+    /// in index order (every set is index-sorted, so filtering one keeps the
+    /// order).
+    fn intersect(&self, ids: &[TypeId]) -> Vec<Entity> {
+        let mut sets = Vec::with_capacity(ids.len());
+        for id in ids {
+            match self.components.get(id) {
+                Some(storage) => sets.push(storage.as_ref()),
+                None => return Vec::new(),
+            }
+        }
+        let Some((smallest, _)) = sets
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, s)| s.entities().len())
+        else {
+            return Vec::new();
+        };
+        sets[smallest]
+            .entities()
+            .iter()
+            .copied()
+            .filter(|&e| {
+                sets.iter()
+                    .enumerate()
+                    .all(|(i, s)| i == smallest || s.contains(e))
+            })
+            .collect()
     }
 
     /// Get all entities that have all specified component types
     pub fn query_with<T1: Component, T2: Component>(&self) -> Vec<Entity> {
-        let entities_with_t1: Vec<Entity> = self.query::<T1>();
-        entities_with_t1
-            .into_iter()
-            .filter(|&e| self.has_component::<T2>(e))
-            .collect()
+        self.intersect(&[TypeId::of::<T1>(), TypeId::of::<T2>()])
     }
 
     /// Get all entities that have three specific component types
     pub fn query_with3<T1: Component, T2: Component, T3: Component>(&self) -> Vec<Entity> {
-        let entities: Vec<Entity> = self.query::<T1>();
-        entities
-            .into_iter()
-            .filter(|&e| self.has_component::<T2>(e) && self.has_component::<T3>(e))
-            .collect()
+        self.intersect(&[TypeId::of::<T1>(), TypeId::of::<T2>(), TypeId::of::<T3>()])
+    }
+
+    /// Typed read-only query over every entity matching `D`, in id order.
+    /// See [`Query`].
+    pub fn query_ref<D: ReadOnlyQueryData>(&self) -> Query<'_, D> {
+        Query::new(self)
+    }
+
+    /// [`World::query_ref`] narrowed by a [`QueryFilter`] (`With<T>`,
+    /// `Without<T>`, or a tuple of them).
+    pub fn query_ref_filtered<D: ReadOnlyQueryData, F: QueryFilter>(&self) -> Query<'_, D, F> {
+        Query::new(self)
+    }
+
+    /// Typed mutable query, e.g.
+    /// `world.query_mut::<(&mut Position, &Velocity, Option<&Knockback>)>()`.
+    /// See [`QueryMut`].
+    pub fn query_mut<D: QueryData>(&mut self) -> QueryMut<'_, D> {
+        QueryMut::new(self)
+    }
+
+    /// [`World::query_mut`] narrowed by a [`QueryFilter`].
+    pub fn query_mut_filtered<D: QueryData, F: QueryFilter>(&mut self) -> QueryMut<'_, D, F> {
+        QueryMut::new(self)
+    }
+
+    /// A mutable query plus the walls, borrowed side by side — for systems
+    /// like movement that resolve every mover against the level geometry.
+    pub fn query_mut_with_walls<D: QueryData, F: QueryFilter>(
+        &mut self,
+    ) -> (QueryMut<'_, D, F>, &[Wall]) {
+        let world = NonNull::from(self);
+        // SAFETY: query data only ever touches component storages, never
+        // `walls`, so the two borrows are disjoint; both live for `&mut self`.
+        unsafe {
+            let query = QueryMut::from_ptr(world);
+            (query, &(*world.as_ptr()).walls)
+        }
+    }
+
+    /// Queue `commands` to be applied at the next stage boundary (see
+    /// [`Commands`]).
+    pub fn defer(&mut self, mut commands: Commands) {
+        self.deferred.0.append(&mut commands);
+    }
+
+    /// Apply every deferred command now, in the order they were queued. The
+    /// schedule calls this at the end of each stage; hosts running a system
+    /// by hand call it after.
+    pub fn apply_deferred(&mut self) {
+        while !self.deferred.0.is_empty() {
+            std::mem::take(&mut self.deferred.0).apply(self);
+        }
+    }
+
+    /// Store `value` as the world's one `T`, replacing any previous one.
+    /// Resources are `Clone` so snapshots (checkpoints) capture them.
+    pub fn insert_resource<T: Component + Clone>(&mut self, value: T) {
+        self.resources.insert(TypeId::of::<T>(), Box::new(value));
+    }
+
+    /// The world's `T` resource, if one was inserted.
+    pub fn resource<T: Component>(&self) -> Option<&T> {
+        AnyResource::as_any(self.resources.get(&TypeId::of::<T>())?.as_ref()).downcast_ref::<T>()
+    }
+
+    pub fn resource_mut<T: Component>(&mut self) -> Option<&mut T> {
+        AnyResource::as_any_mut(self.resources.get_mut(&TypeId::of::<T>())?.as_mut())
+            .downcast_mut::<T>()
+    }
+
+    pub fn has_resource<T: Component>(&self) -> bool {
+        self.resources.contains_key(&TypeId::of::<T>())
+    }
+
+    pub fn remove_resource<T: Component>(&mut self) -> Option<T> {
+        let boxed = self.resources.remove(&TypeId::of::<T>())?;
+        AnyResource::into_any(boxed)
+            .downcast::<T>()
+            .ok()
+            .map(|b| *b)
+    }
+
+    /// Run `f` with the `T` resource lifted out of the world, so a system can
+    /// read it (say, path on the nav grid) while mutating components. `None`
+    /// without calling `f` when there is no `T`. Put back afterwards unless
+    /// `f` inserted a new one.
+    pub fn resource_scope<T: Component + Clone, R>(
+        &mut self,
+        f: impl FnOnce(&mut World, &mut T) -> R,
+    ) -> Option<R> {
+        let mut value = self.remove_resource::<T>()?;
+        let result = f(self, &mut value);
+        if !self.has_resource::<T>() {
+            self.insert_resource(value);
+        }
+        Some(result)
     }
 
     /// Get all entities
@@ -251,20 +811,78 @@ impl World {
         self.walls.push(Wall::new(x, y, width, height));
     }
 
+    /// Add a wall of any material.
+    pub fn insert_wall(&mut self, wall: Wall) {
+        self.walls.push(wall);
+    }
+
     /// Get all walls in the world
     pub fn walls(&self) -> &[Wall] {
         &self.walls
     }
 
-    /// Clear all entities and components
+    /// Shatter the glass wall at `index` (into [`World::walls`]): it leaves
+    /// the world — collision, and the navigation grids, which resync on the
+    /// change — and a [`GameEvent::GlassShatter`] carries the pane to the SFX
+    /// and the debris. Later walls shift down one index. Returns false (and
+    /// does nothing) for a solid wall or an index out of range.
+    pub fn shatter_wall(&mut self, index: usize) -> bool {
+        if !self.walls.get(index).is_some_and(Wall::is_glass) {
+            return false;
+        }
+        let pane = self.walls.remove(index);
+        self.push_event(GameEvent::GlassShatter(pane));
+        true
+    }
+
+    /// Clear all entities and components. Every live index is retired, so
+    /// handles from before the clear stay dead.
     pub fn clear(&mut self) {
-        self.entities.clear();
+        for entity in std::mem::take(&mut self.entities) {
+            self.retire(entity.index());
+        }
         self.components.clear();
+        self.removed.clear();
         self.walls.clear();
         self.events.clear();
     }
 }
 
+/// A world with only the component storages written since some tick (see
+/// [`World::delta_since`]).
+#[derive(Clone)]
+pub struct WorldDelta {
+    world: World,
+    /// Every component type the world had a storage for, written or not.
+    types: Vec<TypeId>,
+}
+
+impl WorldDelta {
+    /// Component rows the delta holds.
+    pub fn row_count(&self) -> usize {
+        self.world.row_count()
+    }
+}
+
+/// Iterator over the entities that lost a `T` since the current reader's
+/// baseline (see [`World::removed`]).
+pub struct RemovedComponents<'w, T> {
+    entries: std::slice::Iter<'w, (Entity, u64)>,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Iterator for RemovedComponents<'_, T> {
+    type Item = Entity;
+
+    fn next(&mut self) -> Option<Entity> {
+        self.entries.next().map(|&(entity, _)| entity)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
 impl Default for World {
     fn default() -> Self {
         Self::new()
@@ -300,8 +918,8 @@ mod tests {
         let e2 = world.spawn();
 
         assert_ne!(e1, e2);
-        assert_eq!(e1.id(), 0);
-        assert_eq!(e2.id(), 1);
+        assert_eq!(e1.index(), 0);
+        assert_eq!(e2.index(), 1);
     }
 
     #[test]
@@ -471,6 +1089,29 @@ mod tests {
         assert_eq!(world.query::<Health>().len(), 34); // 0, 3, 6, ..., 99
     }
 
+    #[test]
+    fn test_query_is_spawn_ordered_after_late_inserts() {
+        let mut world = World::new();
+        let a = world.spawn();
+        let b = world.spawn();
+        let c = world.spawn();
+        // Attach in reverse: the packed order must still follow entity ids.
+        world.add_component(c, Velocity { x: 0.0, y: 0.0 });
+        world.add_component(a, Velocity { x: 0.0, y: 0.0 });
+        world.add_component(b, Velocity { x: 0.0, y: 0.0 });
+        world.add_component(b, Position { x: 0.0, y: 0.0 });
+        world.add_component(c, Position { x: 0.0, y: 0.0 });
+        assert_eq!(world.query::<Velocity>(), vec![a, b, c]);
+        assert_eq!(world.entities_with::<Velocity>(), &[a, b, c]);
+        assert_eq!(world.query_with::<Velocity, Position>(), vec![b, c]);
+        assert_eq!(world.count::<Position>(), 2);
+        assert_eq!(world.count::<Health>(), 0);
+
+        world.despawn(b);
+        assert_eq!(world.query::<Velocity>(), vec![a, c]);
+        assert_eq!(world.entities(), &[a, c]);
+    }
+
     #[test]
     fn test_events_push_drain_and_cap() {
         let mut world = World::new();
@@ -501,7 +1142,7 @@ mod tests {
         world.add_component(e, Position { x: 100.0, y: 200.0 });
         world.add_wall(0.0, 0.0, 50.0, 50.0);
         world.push_event(GameEvent::Pickup);
-        world.next_random();
+        world.rng(RngStream::Ai).next_u32();
 
         let snapshot = world.clone();
 
@@ -521,10 +1162,213 @@ mod tests {
         // next number.
         let mut a = snapshot.clone();
         let mut b = snapshot.clone();
-        assert_eq!(a.next_random(), b.next_random());
-        // Entity ids keep counting from where the snapshot was taken.
+        assert_eq!(
+            a.rng(RngStream::Ai).next_u32(),
+            b.rng(RngStream::Ai).next_u32()
+        );
+        // A bare clone keeps counting from where the snapshot was taken, so
+        // it hands `e2` out again...
         let mut c = snapshot.clone();
-        assert_eq!(c.spawn().id(), e2.id());
+        assert_eq!(c.spawn(), e2);
+        // ...while `restore` retires every handle the abandoned timeline
+        // issued: same indices, newer generations.
+        world.restore(&snapshot);
+        assert!(world.is_alive(e));
+        assert!(!world.is_alive(e2));
+        let fresh = world.spawn();
+        assert_eq!(fresh.index(), e2.index());
+        assert_ne!(fresh, e2);
+        assert_eq!(world.get_component::<Position>(e).unwrap().x, 100.0);
+    }
+
+    #[test]
+    fn test_restore_over_a_reused_live_index_never_reissues_its_handles() {
+        let mut world = World::new();
+        let a = world.spawn();
+        let snapshot = world.clone();
+        // After the snapshot `a` dies and its index is reused twice.
+        world.despawn(a);
+        let b = world.spawn();
+        world.despawn(b);
+        let c = world.spawn();
+        assert_eq!((b.index(), c.index()), (a.index(), a.index()));
+
+        // The snapshot has `a` alive in that slot: it comes back unchanged...
+        world.restore(&snapshot);
+        assert!(world.is_alive(a));
+        assert!(!world.is_alive(b) && !world.is_alive(c));
+        // ...and once it dies the slot moves past every abandoned handle.
+        world.despawn(a);
+        let d = world.spawn();
+        assert_eq!(d.index(), a.index());
+        assert!(d.generation() > c.generation());
+        assert!(!world.is_alive(c));
+    }
+
+    #[test]
+    fn test_despawned_index_is_recycled_under_a_new_generation() {
+        let mut world = World::new();
+        let a = world.spawn();
+        let b = world.spawn();
+        world.add_component(a, Position { x: 1.0, y: 1.0 });
+        world.despawn(a);
+        assert!(!world.is_alive(a));
+        assert!(world.is_alive(b));
+
+        let c = world.spawn();
+        assert_eq!(c.index(), a.index());
+        assert_ne!(c, a);
+        assert!(!world.has_component::<Position>(c));
+        assert!(!world.has_component::<Position>(a));
+        // Despawning the stale handle must not take the new occupant down.
+        world.despawn(a);
+        assert!(world.is_alive(c));
+        // The live list stays index-sorted with a recycled index.
+        assert_eq!(world.entities(), &[c, b]);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "stale entity handle")]
+    fn test_stale_handle_deref_asserts_in_debug() {
+        let mut world = World::new();
+        let victim = world.spawn();
+        world.add_component(victim, Position { x: 0.0, y: 0.0 });
+        world.despawn(victim);
+        let _bullet = world.spawn();
+        let _ = world.get_component::<Position>(victim);
+    }
+
+    #[test]
+    fn test_removed_components_since_the_readers_last_run() {
+        let mut world = World::new();
+        let a = world.spawn();
+        let b = world.spawn();
+        world.add_component(a, Position { x: 0.0, y: 0.0 });
+        world.add_component(b, Position { x: 0.0, y: 0.0 });
+        world.add_component(b, Velocity { x: 0.0, y: 0.0 });
+        let mut last_run = 0;
+        let removed = |world: &mut World, last_run: &mut u64| {
+            world.run_tracked(last_run, |w| w.removed::<Position>().collect::<Vec<_>>())
+        };
+        assert!(removed(&mut world, &mut last_run).is_empty());
+
+        world.remove_component::<Position>(a);
+        world.remove_component::<Position>(a); // Nothing left to remove.
+        world.despawn(b);
+        assert_eq!(removed(&mut world, &mut last_run), vec![a, b]);
+        assert!(removed(&mut world, &mut last_run).is_empty(), "seen once");
+        assert_eq!(world.removed::<Velocity>().collect::<Vec<_>>(), vec![b]);
+
+        // Pruned entries are gone for every reader.
+        world.prune_removed(world.change_tick());
+        assert_eq!(world.removed::<Velocity>().count(), 0);
+    }
+
+    #[test]
+    fn test_restore_keeps_the_change_tick_moving_forward() {
+        let mut world = World::new();
+        let e = world.spawn();
+        world.add_component(e, Position { x: 0.0, y: 0.0 });
+        let snapshot = world.clone();
+        let mut last_run = 0;
+        for _ in 0..5 {
+            world.run_tracked(&mut last_run, |_| {});
+        }
+        let before = world.change_tick();
+        world.restore(&snapshot);
+        assert_eq!(world.change_tick(), before);
+        // The restored rows are old news to a reader that already saw them.
+        assert!(!world.run_tracked(&mut last_run, |w| w.is_changed::<Position>(e)));
+        // A reader whose bookmark is from the future starts over.
+        let mut fresh = World::new();
+        let f = fresh.spawn();
+        fresh.add_component(f, Position { x: 0.0, y: 0.0 });
+        assert!(fresh.run_tracked(&mut last_run, |w| w.is_added::<Position>(f)));
+    }
+
+    #[test]
+    fn test_delta_brings_a_snapshot_up_to_date() {
+        let mut world = World::new();
+        let e = world.spawn();
+        let f = world.spawn();
+        world.add_component(e, Position { x: 0.0, y: 0.0 });
+        world.add_component(e, Velocity { x: 1.0, y: 0.0 });
+        world.add_component(f, Velocity { x: 2.0, y: 0.0 });
+        let mut last_run = 0;
+        world.run_tracked(&mut last_run, |_| {});
+        let snapshot = world.clone();
+        let tick = world.change_tick();
+
+        world.run_tracked(&mut last_run, |w| {
+            w.get_component_mut::<Position>(e).unwrap().x = 5.0;
+        });
+        let g = world.spawn();
+        world.insert_resource(7u32);
+        let delta = world.delta_since(tick);
+        assert_eq!(delta.row_count(), 1, "only the written storage is kept");
+
+        let mut patched = snapshot.clone();
+        patched.apply_delta(&delta);
+        assert_eq!(patched.get_component::<Position>(e).unwrap().x, 5.0);
+        assert_eq!(patched.query::<Velocity>(), vec![e, f]);
+        assert!(patched.is_alive(g));
+        assert_eq!(patched.resource::<u32>(), Some(&7));
+        assert_eq!(patched.change_tick(), world.change_tick());
+    }
+
+    #[test]
+    fn test_resources_are_typed_singletons_and_snapshotted() {
+        let mut world = World::new();
+        assert!(world.resource::<Velocity>().is_none());
+        world.insert_resource(Velocity { x: 1.0, y: 0.0 });
+        world.insert_resource(7u32);
+        world.resource_mut::<Velocity>().unwrap().y = 2.0;
+        assert_eq!(
+            world.resource::<Velocity>(),
+            Some(&Velocity { x: 1.0, y: 2.0 })
+        );
+
+        let snapshot = world.clone();
+        world.insert_resource(Velocity { x: 9.0, y: 9.0 });
+        assert_eq!(snapshot.resource::<Velocity>().unwrap().x, 1.0);
+
+        // Lifted out for the scope, back in after.
+        let e = world.spawn();
+        let seen = world.resource_scope::<u32, _>(|world, n| {
+            assert!(!world.has_resource::<u32>());
+            world.add_component(
+                e,
+                Position {
+                    x: *n as f32,
+                    y: 0.0,
+                },
+            );
+            *n += 1;
+            *n
+        });
+        assert_eq!(seen, Some(8));
+        assert_eq!(world.resource::<u32>(), Some(&8));
+        assert_eq!(world.remove_resource::<u32>(), Some(8));
+        assert_eq!(world.resource_scope::<u32, _>(|_, _| ()), None);
+    }
+
+    #[test]
+    fn test_only_glass_walls_shatter() {
+        let mut world = World::new();
+        world.add_wall(0.0, 0.0, 100.0, 20.0);
+        let pane = Wall::new(0.0, 50.0, 100.0, 10.0).with_material(WallMaterial::Glass);
+        world.insert_wall(pane);
+        world.add_wall(0.0, 100.0, 100.0, 20.0);
+
+        assert!(!world.shatter_wall(0), "solid walls stand");
+        assert!(!world.shatter_wall(3), "out of range");
+        assert!(world.drain_events().is_empty());
+
+        assert!(world.shatter_wall(1));
+        assert_eq!(world.walls().len(), 2);
+        assert!(world.walls().iter().all(|w| !w.is_glass()));
+        assert_eq!(world.drain_events(), vec![GameEvent::GlassShatter(pane)]);
     }
 
     #[test]
@@ -7,14 +7,16 @@
 //! byte-for-byte the checked-in file. Pure Rust, no browser: host-tested.
 //!
 //! The spatial content (walls, rooms, zones, spawns, pickups, entry / exits,
-//! placed props) is editable; the SCENARIO steps are carried through
-//! verbatim (`&'static [StepDef]`) — the web editor owns those.
+//! placed props) is editable; the doors and the SCENARIO steps are carried
+//! through verbatim (the steps as `&'static [StepDef]`) — the web editor owns
+//! those.
 
 use crate::components::{EnemyType, WeaponType};
+use crate::ecs::WallMaterial;
 use crate::props::{prop_kind_id, PROP_COUNT};
 use crate::scenario::{
-    Action, AlertTarget, ElevatorKind, FloorDef, PropPlacement, Rect, StepDef, Surface, Trigger,
-    SURFACE_EXIT,
+    Action, AlertTarget, DoorDef, ElevatorKind, FloorDef, FloorWall, PropPlacement, Rect, StepDef,
+    Surface, Trigger, DOOR_LENGTH, SURFACE_EXIT,
 };
 
 /// Undo history depth (snapshots).
@@ -133,22 +135,14 @@ pub fn next_enemy_type(t: EnemyType) -> EnemyType {
 
 /// The JSON id of a weapon (`pickups[].weapon`).
 pub fn weapon_id(w: WeaponType) -> &'static str {
-    match w {
-        WeaponType::Pistol => "pistol",
-        WeaponType::Shotgun => "shotgun",
-        WeaponType::MachineGun => "machinegun",
-        WeaponType::Melee => "melee",
-    }
+    w.id()
 }
 
-/// The next weapon in the PICKUP tool's cycle.
+/// The next weapon in the PICKUP tool's cycle: the weapons table's order.
 pub fn next_weapon(w: WeaponType) -> WeaponType {
-    match w {
-        WeaponType::Pistol => WeaponType::Shotgun,
-        WeaponType::Shotgun => WeaponType::MachineGun,
-        WeaponType::MachineGun => WeaponType::Melee,
-        WeaponType::Melee => WeaponType::Pistol,
-    }
+    let all: Vec<WeaponType> = WeaponType::all().collect();
+    let at = all.iter().position(|&x| x == w).unwrap_or(0);
+    all[(at + 1) % all.len()]
 }
 
 /// The prop index for a JSON kind id (`rack_closed` → 0), if it exists.
@@ -213,11 +207,13 @@ pub struct EditableFloor {
     pub surface: Surface,
     pub entry: Car,
     pub exits: Vec<Car>,
-    pub walls: Vec<Rect>,
+    pub walls: Vec<FloorWall>,
     pub rooms: Vec<Room>,
     pub zones: Vec<Zone>,
     pub spawns: Vec<Spawn>,
     pub pickups: Vec<Pickup>,
+    /// The swinging doors, verbatim (not edited here).
+    pub doors: Vec<DoorDef>,
     pub props: Vec<PropPlacement>,
     /// The scenario steps, verbatim (not edited here).
     pub scenario: &'static [StepDef],
@@ -290,6 +286,7 @@ impl EditableFloor {
                     weapon: p.weapon,
                 })
                 .collect(),
+            doors: f.doors.to_vec(),
             props: f.props.to_vec(),
             scenario: f.scenario,
         }
@@ -308,7 +305,7 @@ impl EditableFloor {
         Some(match item {
             Item::Entry => self.entry.rect,
             Item::Exit(i) => self.exits.get(i)?.rect,
-            Item::Wall(i) => *self.walls.get(i)?,
+            Item::Wall(i) => self.walls.get(i)?.rect,
             Item::Room(i) => self.rooms.get(i)?.rect,
             Item::Zone(i) => self.zones.get(i)?.rect,
             Item::Spawn(i) => {
@@ -350,7 +347,7 @@ impl EditableFloor {
             }
             Item::Wall(i) => {
                 if let Some(w) = self.walls.get_mut(i) {
-                    *w = r;
+                    w.rect = r;
                 }
             }
             Item::Room(i) => {
@@ -554,7 +551,7 @@ impl EditableFloor {
     }
 
     pub fn add_wall(&mut self, r: Rect) -> Item {
-        self.walls.push(r);
+        self.walls.push(FloorWall::solid(r));
         Item::Wall(self.walls.len() - 1)
     }
 
@@ -700,7 +697,7 @@ impl EditableFloor {
         }
         // Actors must not start inside a wall (the engine's level tests).
         let in_wall = |x: f32, y: f32, r: f32| {
-            self.walls.iter().any(|w| {
+            self.walls.iter().any(|&FloorWall { rect: w, .. }| {
                 let cx = x.clamp(w.x, w.x + w.w);
                 let cy = y.clamp(w.y, w.y + w.h);
                 (cx - x) * (cx - x) + (cy - y) * (cy - y) < r * r
@@ -762,7 +759,17 @@ impl EditableFloor {
                 Obj(kv)
             })
             .collect();
-        let walls = self.walls.iter().map(|w| Obj(rect_kv(w))).collect();
+        let walls = self
+            .walls
+            .iter()
+            .map(|w| {
+                let mut kv = rect_kv(&w.rect);
+                if w.material == WallMaterial::Glass {
+                    kv.push(("material".into(), s("glass")));
+                }
+                Obj(kv)
+            })
+            .collect();
         let rooms = self
             .rooms
             .iter()
@@ -840,6 +847,21 @@ impl EditableFloor {
                 ])
             })
             .collect();
+        let doors: Vec<Json> = self
+            .doors
+            .iter()
+            .map(|d| {
+                let mut kv = vec![
+                    ("x".to_string(), n(d.x)),
+                    ("y".into(), n(d.y)),
+                    ("angle".into(), n(d.angle)),
+                ];
+                if d.length != DOOR_LENGTH {
+                    kv.push(("length".into(), n(d.length)));
+                }
+                Obj(kv)
+            })
+            .collect();
         let props: Vec<Json> = self
             .props
             .iter()
@@ -1002,8 +1024,11 @@ impl EditableFloor {
             ("spawns".into(), Arr(spawns)),
             ("pickups".into(), Arr(pickups)),
         ]);
-        // `props` is optional in the format: a floor without any keeps the
-        // key out (as the checked-in files without props do).
+        // `doors` and `props` are optional in the format: a floor without any
+        // keeps the key out (as the checked-in files without them do).
+        if !doors.is_empty() {
+            top.push(("doors".into(), Arr(doors)));
+        }
         if !props.is_empty() {
             top.push(("props".into(), Arr(props)));
         }
@@ -1580,6 +1605,20 @@ mod tests {
         assert_eq!(n, UNDO_DEPTH);
     }
 
+    #[test]
+    fn the_pickup_tool_cycles_through_every_table_weapon() {
+        let mut w = WeaponType::PISTOL;
+        let mut seen = vec![w];
+        loop {
+            w = next_weapon(w);
+            if w == WeaponType::PISTOL {
+                break;
+            }
+            seen.push(w);
+        }
+        assert_eq!(seen, WeaponType::all().collect::<Vec<_>>());
+    }
+
     #[test]
     fn creation_helpers_pick_unique_ids_and_validation_catches_bad_refs() {
         let ids: Vec<usize> = (0..LEVEL_COUNT).collect();
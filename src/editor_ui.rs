@@ -14,7 +14,8 @@
 
 use wasm_bindgen::prelude::*;
 
-use crate::components::{EnemyType, WeaponType};
+use crate::components::{Door, EnemyType, WeaponType};
+use crate::ecs::WallMaterial;
 use crate::editor::{
     enemy_type_id, next_enemy_type, next_weapon, weapon_id, EditableFloor, EditorDoc, Item,
 };
@@ -26,7 +27,7 @@ use crate::levels::{floor_def, floor_title, LEVEL_COUNT};
 use crate::levels_data::FLOORS;
 use crate::math::{Color, Vec2};
 use crate::props::{draw_prop, family_range, prop_px, snap_size, PROP_FAMILIES, PROP_NAMES};
-use crate::render::draw_wall;
+use crate::render::{draw_door, draw_glass, draw_wall};
 use crate::render_comms::{car_back_side, draw_elevator_car, CarView};
 use crate::scenario::{parse_hex_rgb, PropPlacement, Rect};
 
@@ -208,12 +209,7 @@ fn spawn_color(t: EnemyType) -> Color {
 }
 
 fn weapon_letter(w: WeaponType) -> &'static str {
-    match w {
-        WeaponType::Pistol => "P",
-        WeaponType::Shotgun => "S",
-        WeaponType::MachineGun => "M",
-        WeaponType::Melee => "C",
-    }
+    w.def().letter
 }
 
 /// The normalized rectangle spanned by two corners.
@@ -253,7 +249,7 @@ impl Editor {
             brush_rot: 0.0,
             brush_size: 100.0,
             spawn_kind: EnemyType::Idle,
-            weapon: WeaponType::Pistol,
+            weapon: WeaponType::PISTOL,
             field: None,
             buf: String::new(),
             web_open: false,
@@ -491,6 +487,9 @@ impl Editor {
         if input::is_key_pressed("q") {
             self.cycle_weapon();
         }
+        if input::is_key_pressed("m") {
+            self.toggle_glass();
+        }
         if input::is_key_pressed("Delete") || input::is_key_pressed("Backspace") {
             if let Some(item) = self.sel {
                 let doc = self.doc();
@@ -556,6 +555,20 @@ impl Editor {
         }
     }
 
+    /// M = turn the selected wall into a glass partition, or back.
+    fn toggle_glass(&mut self) {
+        if let Some(Item::Wall(i)) = self.sel {
+            let doc = self.doc();
+            doc.begin_edit();
+            if let Some(w) = doc.floor.walls.get_mut(i) {
+                w.material = match w.material {
+                    WallMaterial::Solid => WallMaterial::Glass,
+                    WallMaterial::Glass => WallMaterial::Solid,
+                };
+            }
+        }
+    }
+
     fn cycle_weapon(&mut self) {
         if let Some(Item::Pickup(i)) = self.sel {
             let doc = self.doc();
@@ -884,10 +897,18 @@ impl Editor {
             );
         }
         for wl in &f.walls {
-            draw_wall(g, wl.x, wl.y, wl.w, wl.h);
+            let r = wl.rect;
+            if wl.material == WallMaterial::Glass {
+                draw_glass(g, r.x, r.y, r.w, r.h);
+            } else {
+                draw_wall(g, r.x, r.y, r.w, r.h);
+            }
+        }
+        for d in &f.doors {
+            draw_door(g, Door::from_def(d).leaf());
         }
         // Elevator cars exactly as in-game (the shaft side is the wall behind).
-        let in_wall = |p: Vec2| f.walls.iter().any(|w| w.contains(p));
+        let in_wall = |p: Vec2| f.walls.iter().any(|w| w.rect.contains(p));
         let cars = std::iter::once((&f.entry, false)).chain(f.exits.iter().map(|e| (e, true)));
         for (car, is_exit) in cars {
             let r = car.rect;
@@ -1360,7 +1381,7 @@ impl Editor {
                         .pickups
                         .get(i)
                         .map(|p| p.weapon)
-                        .unwrap_or(WeaponType::Pistol);
+                        .unwrap_or(WeaponType::PISTOL);
                     if button(
                         g,
                         mouse,
@@ -1423,6 +1444,7 @@ impl Editor {
                 "arrows     nudge (Shift = 1 unit)",
                 "Del        delete selection",
                 "T / Q      spawn type / weapon",
+                "M          wall solid / glass",
                 "R / [ ]    prop rotate / size",
                 "Ctrl+Z / Ctrl+Y   undo / redo",
                 "Esc        deselect / cancel",
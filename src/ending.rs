@@ -1,24 +1,30 @@
 //! The ending — everything that happens once the last car goes up.
 //!
 //! Timeline (all driven from `update_game` in lib.rs):
-//!   1. the player extracts through a `"to": "surface"` exit (`scenario::SURFACE_EXIT`) → the EXFILTRATE card
-//!      ([`draw_extract_card`], also used on every ordinary floor)
+//!   1. the player extracts through a `"to": "surface"` exit (`scenario::SURFACE_EXIT`) → the RESULTS card
+//!      (the floor's statistics, score and grade, [`draw_results_card`]),
+//!      then the EXFILTRATE card ([`draw_extract_card`]); both play on every
+//!      floor
 //!   2. the [`Outro`] takes over: the card fades, the floor's `extracted`
 //!      scenario step talks (13½: the UPLINK epilogue) until the comms feed
 //!      goes idle, then a BLUR-OUT (POSTFX kind 0) dissolves the frame
 //!   3. `GameScreen::Ending`: the [`CREDITS`] roll over the ELEVATOR RIDE
 //!      home ([`render_ride`]: the car interior top-down, CL4-UD3 idling at
 //!      the centre, shaft lights rushing past — smeared into radial light
-//!      trails by POSTFX kind 10, strength [`Ending::warp_t`]), Enter / Esc
-//!      back to the level select.
+//!      trails by POSTFX kind 10, strength [`Ending::warp_t`]) under the
+//!      campaign's totals ([`campaign_lines`]), Enter / Esc back to the
+//!      level select.
 //!
 //! The credits text is the plain [`CREDITS`] list below — edit freely.
 //! Everything that needs the canvas is behind `cfg(target_arch = "wasm32")`;
 //! the timeline and layout are plain data so they are unit-tested natively.
 
+use crate::components::WeaponType;
 use crate::math::Color;
 #[cfg(target_arch = "wasm32")]
 use crate::math::Vec2;
+use crate::score::FloorScore;
+use crate::stats::{CampaignStats, FloorStats, THROWN_KEY};
 
 // ---------------------------------------------------------------------------
 // The credits text
@@ -144,8 +150,13 @@ pub fn credits_height() -> f32 {
 // Timeline
 // ---------------------------------------------------------------------------
 
-/// Seconds the "EXFILTRATED // FLOOR N" card stays up after the player
-/// extracts, before the next floor loads (or the outro starts).
+/// Seconds the RESULTS card stays up after the player extracts, before the
+/// "EXFILTRATED" card.
+pub const RESULTS_CARD_SECS: f32 = 4.0;
+/// The results card reveals one row every this many seconds.
+pub const RESULTS_ROW_SECS: f32 = 0.35;
+/// Seconds the "EXFILTRATED // FLOOR N" card stays up after the results
+/// card, before the next floor loads (or the outro starts).
 pub const EXTRACT_CARD_SECS: f32 = 2.4;
 /// The card fades out over this long once the outro starts.
 pub const CARD_FADE_SECS: f32 = 0.6;
@@ -183,6 +194,106 @@ pub fn ride_phase(time: f32) -> f32 {
     0.35 * a + 0.65 * a * a / (2.0 * WARP_RAMP_SECS) + (time.max(0.0) - a)
 }
 
+/// `secs` as `m:ss`.
+fn clock(secs: f32) -> String {
+    let secs = secs.max(0.0) as u32;
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
+/// Display name of a [`FloorStats::kills_by`] key.
+fn kill_key_name(key: &str) -> String {
+    match WeaponType::from_id(key) {
+        Some(weapon) => weapon.def().name.to_uppercase(),
+        None if key == THROWN_KEY => "THROWN".to_string(),
+        None => key.to_uppercase(),
+    }
+}
+
+/// The weapon (a [`FloorStats::kills_by`] key) with the most kills, and
+/// how many; the first in key order on a tie.
+fn top_weapon(stats: &FloorStats) -> Option<(&'static str, u32)> {
+    stats
+        .kills_by
+        .iter()
+        .map(|(&key, &n)| (key, n))
+        .reduce(|best, next| if next.1 > best.1 { next } else { best })
+}
+
+/// The left column of the results card: the floor's statistics, every
+/// attempt included.
+pub fn stats_rows(stats: &FloorStats) -> Vec<(&'static str, String)> {
+    let mut rows = vec![
+        ("TIME", clock(stats.time)),
+        ("KILLS", stats.kills().to_string()),
+    ];
+    if let Some((key, n)) = top_weapon(stats) {
+        rows.push(("TOP WEAPON", format!("{} x{n}", kill_key_name(key))));
+    }
+    rows.extend([
+        (
+            "ACCURACY",
+            stats
+                .accuracy()
+                .map_or_else(|| "-".to_string(), |a| format!("{:.0}%", a * 100.0)),
+        ),
+        ("FINISHERS", stats.finishers.to_string()),
+        ("THROWS", stats.throws.to_string()),
+        ("DEATHS", stats.deaths.to_string()),
+        ("RESTORES", stats.restores.to_string()),
+    ]);
+    rows
+}
+
+/// The right column of the results card, top to bottom: label and value. A
+/// bonus row is left out when it is zero; the penalty reads negative.
+pub fn results_rows(score: &FloorScore) -> Vec<(&'static str, String)> {
+    let mut rows = vec![
+        ("BEST COMBO", format!("x{}", score.best_combo)),
+        ("KILL POINTS", score.kill_points.to_string()),
+    ];
+    for (label, bonus) in [
+        ("SPEED", score.speed_bonus),
+        ("FLEXIBILITY", score.flexibility_bonus),
+        ("BOLDNESS", score.boldness_bonus),
+    ] {
+        if bonus > 0 {
+            rows.push((label, format!("+{bonus}")));
+        }
+    }
+    if score.death_penalty > 0 {
+        rows.push(("DEATHS", format!("-{}", score.death_penalty)));
+    }
+    rows.push(("TOTAL", score.total.to_string()));
+    rows
+}
+
+/// The campaign's totals, one line each, for the top of the credits (none
+/// when no floor was extracted from this session).
+pub fn campaign_lines(run: &CampaignStats) -> Vec<String> {
+    if run.floors == 0 {
+        return Vec::new();
+    }
+    let t = &run.totals;
+    let mut lines = vec![format!(
+        "{} FLOORS IN {} // {} KILLS // {} DEATHS",
+        run.floors,
+        clock(t.time),
+        t.kills(),
+        t.deaths
+    )];
+    let mut detail = Vec::new();
+    if let Some(a) = t.accuracy() {
+        detail.push(format!("{:.0}% ACCURACY", a * 100.0));
+    }
+    detail.push(format!("{} FINISHERS", t.finishers));
+    detail.push(format!("{} THROWS", t.throws));
+    if let Some((key, n)) = top_weapon(t) {
+        detail.push(format!("{} x{n}", kill_key_name(key)));
+    }
+    lines.push(detail.join(" // "));
+    lines
+}
+
 /// The post-card part of the ending, on the last floor: the uplink epilogue
 /// (comms), then the blur-out.
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -307,6 +418,7 @@ impl Ending {
 mod draw {
     use super::*;
     use crate::graphics::Graphics;
+    use crate::score::Grade;
 
     /// Approximate VT323 advance as a fraction of the font size (the renderer
     /// measures the real glyphs; this is only for centring).
@@ -363,6 +475,87 @@ mod draw {
         }
     }
 
+    /// Row pitch of the results card, px.
+    const RESULTS_ROW_H: f32 = 30.0;
+
+    /// One column of the results card: its first `shown` rows, labels from
+    /// `lx` and values right-aligned on `rx`, the first row at `top`.
+    fn draw_rows(
+        g: &Graphics,
+        rows: &[(&'static str, String)],
+        shown: usize,
+        (lx, rx, top): (f32, f32, f32),
+        alpha: f32,
+    ) {
+        for (i, (label, value)) in rows.iter().enumerate().take(shown) {
+            let y = top + i as f32 * RESULTS_ROW_H;
+            let color = if *label == "TOTAL" {
+                Color::new(1.0, 0.85, 0.2, alpha)
+            } else {
+                Color::new(1.0, 1.0, 1.0, alpha)
+            };
+            g.draw_text(label, Vec2::new(lx, y), 24.0, color);
+            let vw = value.chars().count() as f32 * 24.0 * CHAR_W;
+            g.draw_text(value, Vec2::new(rx - vw, y), 24.0, color);
+        }
+    }
+
+    /// The RESULTS card: the floor's statistics ([`stats_rows`]) and score
+    /// ([`results_rows`]) revealed row by row side by side, then its grade
+    /// stamped big on the right. `t` = seconds since extraction, `alpha` =
+    /// overall opacity.
+    pub fn draw_results_card(
+        g: &Graphics,
+        floor_title: &str,
+        stats: &FloorStats,
+        score: &FloorScore,
+        t: f32,
+        alpha: f32,
+    ) {
+        if alpha <= 0.0 {
+            return;
+        }
+        let (w, h) = (g.width(), g.height());
+        let left = stats_rows(stats);
+        let rows = results_rows(score);
+        let n = left.len().max(rows.len());
+        let row_h = RESULTS_ROW_H;
+        let top = h / 2.0 - (n as f32 * row_h) / 2.0 - 40.0;
+        g.draw_rectangle(
+            Vec2::new(0.0, top - 50.0),
+            w,
+            n as f32 * row_h + 140.0,
+            Color::new(0.0, 0.0, 0.0, 0.65 * alpha),
+        );
+        text_centered(
+            g,
+            &format!("{floor_title} // RESULTS"),
+            w / 2.0,
+            top,
+            40.0,
+            Color::new(0.0, 1.0, 0.0, alpha),
+        );
+        let shown = (t / RESULTS_ROW_SECS) as usize;
+        let cx = w / 2.0;
+        draw_rows(g, &left, shown, (cx - 470.0, cx - 90.0, top + 50.0), alpha);
+        draw_rows(g, &rows, shown, (cx - 30.0, cx + 270.0, top + 50.0), alpha);
+        if shown > n {
+            let grade_color = match score.grade {
+                Grade::S | Grade::A => Color::new(1.0, 0.85, 0.2, alpha),
+                Grade::F => Color::new(1.0, 0.2, 0.2, alpha),
+                _ => Color::new(1.0, 1.0, 1.0, alpha),
+            };
+            text_centered(
+                g,
+                score.grade.letter(),
+                cx + 390.0,
+                top + 50.0 + n as f32 * row_h / 2.0 + 40.0,
+                120.0,
+                grade_color,
+            );
+        }
+    }
+
     /// Cheap deterministic 0..1 hash (per-light parameters).
     fn fr(seed: f32) -> f32 {
         let v = (seed * 12.9898).sin() * 43758.547;
@@ -519,7 +712,7 @@ mod draw {
     /// ([`render_ride`], the caller draws it first): a soft full-screen dim
     /// plus a darker column behind the text keep the roll readable while the
     /// car and the streaking shaft lights stay visible at the edges.
-    pub fn draw_credits(g: &Graphics, ending: &Ending) {
+    pub fn draw_credits(g: &Graphics, ending: &Ending, run: &CampaignStats) {
         let (w, h) = (g.width(), g.height());
         g.draw_rectangle(
             Vec2::new(0.0, 0.0),
@@ -563,6 +756,28 @@ mod draw {
             y += lh;
         }
 
+        // The campaign's totals on a fixed strip at the top (the roll
+        // scrolls beneath it).
+        let totals = campaign_lines(run);
+        if !totals.is_empty() {
+            g.draw_rectangle(
+                Vec2::new(0.0, 0.0),
+                w,
+                18.0 + 24.0 * totals.len() as f32,
+                Color::new(0.02, 0.01, 0.05, 1.0),
+            );
+            for (i, line) in totals.iter().enumerate() {
+                text_centered(
+                    g,
+                    line,
+                    w / 2.0,
+                    28.0 + 24.0 * i as f32,
+                    18.0,
+                    Color::new(0.8, 0.78, 0.9, 0.85),
+                );
+            }
+        }
+
         // Fixed hint on a dark strip (the roll scrolls beneath it).
         g.draw_rectangle(
             Vec2::new(0.0, h - 44.0),
@@ -593,7 +808,7 @@ mod draw {
 }
 
 #[cfg(target_arch = "wasm32")]
-pub use draw::{draw_credits, draw_extract_card, render_ride};
+pub use draw::{draw_credits, draw_extract_card, draw_results_card, render_ride};
 
 #[cfg(test)]
 mod tests {
@@ -633,6 +848,73 @@ mod tests {
         }
     }
 
+    #[test]
+    fn results_card_lists_the_bonuses_earned_and_ends_on_the_total() {
+        let mut score = crate::score::Score::new();
+        score.observe(&[
+            crate::components::GameEvent::EnemyHit {
+                by: crate::components::WeaponType::PISTOL,
+            },
+            crate::components::GameEvent::EnemyDown,
+        ]);
+        score.tick(75.0);
+        let tally = score.finish();
+        let rows = results_rows(&tally);
+        let labels: Vec<&str> = rows.iter().map(|(l, _)| *l).collect();
+        // Over par, one style, never under fire, never dead: no bonus rows.
+        assert_eq!(labels, ["BEST COMBO", "KILL POINTS", "TOTAL"]);
+        assert_eq!(rows.last().unwrap().1, tally.total.to_string());
+
+        let busy = FloorScore {
+            speed_bonus: 500,
+            death_penalty: 1000,
+            ..tally
+        };
+        let rows = results_rows(&busy);
+        assert!(rows.contains(&("SPEED", "+500".to_string())));
+        assert!(rows.contains(&("DEATHS", "-1000".to_string())));
+        // Every row of both columns and then the grade are in before the
+        // card gives way.
+        let longest = rows.len().max(stats_rows(&FloorStats::new()).len() + 1);
+        assert!((longest + 1) as f32 * RESULTS_ROW_SECS < RESULTS_CARD_SECS);
+    }
+
+    #[test]
+    fn results_card_stats_column() {
+        let mut stats = FloorStats::new();
+        assert!(stats_rows(&stats).contains(&("ACCURACY", "-".to_string())));
+        stats.time = 75.0;
+        stats.shots = 3;
+        stats.hits = 2;
+        stats.kills_by.insert("pistol", 2);
+        stats.kills_by.insert(THROWN_KEY, 1);
+        let rows = stats_rows(&stats);
+        assert_eq!(rows[0], ("TIME", "1:15".to_string()));
+        assert_eq!(rows[1], ("KILLS", "3".to_string()));
+        assert_eq!(rows[2], ("TOP WEAPON", "PISTOL x2".to_string()));
+        assert!(rows.contains(&("ACCURACY", "67%".to_string())));
+    }
+
+    #[test]
+    fn campaign_lines_sum_up_the_run() {
+        let mut run = CampaignStats::new();
+        assert!(campaign_lines(&run).is_empty(), "nothing to show yet");
+        let mut floor = FloorStats::new();
+        floor.time = 61.0;
+        floor.deaths = 1;
+        floor.finishers = 2;
+        floor.kills_by.insert(THROWN_KEY, 4);
+        run.add_floor(&floor);
+        run.add_floor(&floor);
+        let lines = campaign_lines(&run);
+        assert_eq!(lines[0], "2 FLOORS IN 2:02 // 8 KILLS // 2 DEATHS");
+        assert_eq!(lines[1], "4 FINISHERS // 0 THROWS // THROWN x8");
+        for line in &lines {
+            // Fits the credits column at 18 px.
+            assert!(line.chars().count() as f32 * 18.0 * 0.42 <= 740.0);
+        }
+    }
+
     #[test]
     fn outro_waits_for_the_feed_then_blurs_then_finishes() {
         let mut o = Outro::new();
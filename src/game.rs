@@ -1,11 +1,12 @@
 // Game setup and entity spawning helpers
 use crate::components::*;
-use crate::ecs::{Entity, World};
+use crate::ecs::{Entity, RngStream, Wall, World};
 use crate::levels::{floor_def, BOSS_LEVEL};
 use crate::math::Vec2;
-use crate::scenario::{spawn_floor_markers, spawn_from_def};
+use crate::scenario::{spawn_floor_markers, spawn_from_def, DoorDef};
 use crate::systems::boss::{BOSS_ATTACK_RANGE, BOSS_MASK_SPEED, BOSS_MAX_HEALTH, BOSS_RADIUS};
 use crate::systems::combat::CombatSystem;
+use crate::systems::noise::{loudness, make_noise};
 
 /// Where the shoggoth boss stands at the start of its floor.
 pub const BOSS_SPAWN: Vec2 = Vec2::new(400.0, 560.0);
@@ -43,7 +44,7 @@ pub fn spawn_player(world: &mut World, position: Vec2) -> Entity {
     world.add_component(entity, Health::new(100));
     world.add_component(entity, Rotation::new(0.0));
     world.add_component(entity, Radius::new(15.0));
-    world.add_component(entity, Weapon::new(WeaponType::Pistol));
+    world.add_component(entity, Weapon::new(WeaponType::PISTOL));
 
     entity
 }
@@ -53,9 +54,9 @@ pub fn spawn_player(world: &mut World, position: Vec2) -> Entity {
 /// ammo rather than a downgrade to fists.
 pub fn weapon_for_enemy(enemy_type: EnemyType) -> WeaponType {
     match enemy_type {
-        EnemyType::Idle => WeaponType::Pistol,
-        EnemyType::Wandering => WeaponType::MachineGun,
-        EnemyType::Patrolling => WeaponType::Shotgun,
+        EnemyType::Idle => WeaponType::PISTOL,
+        EnemyType::Wandering => WeaponType::MACHINEGUN,
+        EnemyType::Patrolling => WeaponType::SHOTGUN,
     }
 }
 
@@ -103,9 +104,16 @@ pub fn spawn_pickup_with_ammo(
     entity
 }
 
+/// Spawn a swinging door, shut (see [`crate::systems::door::DoorSystem`]).
+pub fn spawn_door(world: &mut World, def: &DoorDef) -> Entity {
+    let entity = world.spawn();
+    world.add_component(entity, Door::from_def(def));
+    entity
+}
+
 /// Initialize a new game world with the player and a floor's designed layout:
-/// walls, the initial rogues, floor pickups, the entry/exit elevators and the
-/// trigger zones. The player spawns in the entry elevator. (Scenario steps —
+/// walls, the initial rogues, floor pickups, the entry/exit elevators, the
+/// trigger zones and the doors. The player spawns in the entry elevator. (Scenario steps —
 /// dialogue, waves, door openings — are driven by `scenario::ScenarioState`,
 /// which the caller owns.)
 pub fn initialize_game(world: &mut World, level: usize) {
@@ -115,7 +123,8 @@ pub fn initialize_game(world: &mut World, level: usize) {
     spawn_player(world, floor.player_spawn());
 
     for wall in floor.walls {
-        world.add_wall(wall.x, wall.y, wall.w, wall.h);
+        let r = wall.rect;
+        world.insert_wall(Wall::new(r.x, r.y, r.w, r.h).with_material(wall.material));
     }
 
     for s in floor.spawns {
@@ -128,12 +137,27 @@ pub fn initialize_game(world: &mut World, level: usize) {
 
     spawn_floor_markers(world, floor);
 
+    for d in floor.doors {
+        spawn_door(world, d);
+    }
+
     // The hidden final floor: the shoggoth waits below.
     if level == BOSS_LEVEL {
         spawn_boss(world, BOSS_SPAWN);
     }
 }
 
+/// A fresh world for `level` whose RNG streams derive from `seed`. Every
+/// floor start goes through here (the browser carries its RNG state across
+/// floors as the seed), so a recorded attempt rebuilds the same world,
+/// entity handles included (see [`crate::replay`]).
+pub fn new_floor_world(level: usize, seed: u32) -> World {
+    let mut world = World::new();
+    world.seed_rng(seed);
+    initialize_game(&mut world, level);
+    world
+}
+
 /// Zero the player's velocity (a scenario `hold` locks movement: the input
 /// system is skipped, so the last frame's velocity must not linger).
 pub fn stop_player(world: &mut World) {
@@ -149,9 +173,7 @@ pub fn stop_player(world: &mut World) {
 /// and by tests to exercise the all-dead / extraction flow quickly).
 pub fn purge_all_enemies(world: &mut World) {
     for entity in world.query::<Enemy>() {
-        if let Some(h) = world.get_component_mut::<Health>(entity) {
-            h.take_damage(h.max.max(1));
-        }
+        world.update_component::<Health, _>(entity, |h| h.take_damage(h.max.max(1)));
     }
 }
 
@@ -210,7 +232,7 @@ fn punch_from_player(
         Some(f) => f.timer = PUNCH_COOLDOWN,
         None => world.add_component(player, Fists::new(PUNCH_COOLDOWN)),
     }
-    world.push_event(GameEvent::PlayerFired(WeaponType::Melee));
+    world.push_event(GameEvent::PlayerFired(WeaponType::FISTS));
     CombatSystem::process_punch(
         world,
         player_pos,
@@ -267,35 +289,39 @@ pub fn fire_player_weapon(world: &mut World, target_world_pos: Vec2) -> bool {
         weapon.fire();
     }
     world.push_event(GameEvent::PlayerFired(weapon_type));
+    make_noise(world, player_pos.to_vec2(), loudness(weapon_type));
 
     let target_pos = Position::from_vec2(target_world_pos);
 
-    if weapon_type.is_melee() {
-        CombatSystem::process_melee(
-            world,
-            player_pos,
-            target_pos,
-            damage,
-            crate::systems::combat::MELEE_RANGE,
-        )
+    let def = weapon_type.def();
+    if let Some(reach) = def.reach {
+        CombatSystem::process_melee(world, player_pos, target_pos, damage, reach, weapon_type)
     } else {
         let dx = target_pos.x - player_pos.x;
         let dy = target_pos.y - player_pos.y;
         let length = (dx * dx + dy * dy).sqrt();
-
-        let bullet = Bullet::new(weapon_type, damage);
-        let bullet_speed = bullet.speed;
-        let (vel_x, vel_y) = if length > 0.0 {
-            (dx / length * bullet_speed, dy / length * bullet_speed)
-        } else {
-            (0.0, 0.0)
-        };
-
-        let bullet_entity = world.spawn();
-        world.add_component(bullet_entity, bullet);
-        world.add_component(bullet_entity, player_pos);
-        world.add_component(bullet_entity, Velocity::new(vel_x, vel_y));
-        world.add_component(bullet_entity, Radius::new(2.0));
+        let aim = dy.atan2(dx);
+
+        for _ in 0..def.pellets {
+            let bullet = Bullet::new(weapon_type, damage);
+            let bullet_speed = bullet.speed;
+            let (vel_x, vel_y) = if length <= 0.0 {
+                (0.0, 0.0)
+            } else if def.spread > 0.0 {
+                // Each round leaves somewhere in the gun's cone.
+                let half = def.spread / 2.0;
+                let angle = aim + world.rng(RngStream::Spread).range(-half, half);
+                (angle.cos() * bullet_speed, angle.sin() * bullet_speed)
+            } else {
+                (dx / length * bullet_speed, dy / length * bullet_speed)
+            };
+
+            let bullet_entity = world.spawn();
+            world.add_component(bullet_entity, bullet);
+            world.add_component(bullet_entity, player_pos);
+            world.add_component(bullet_entity, Velocity::new(vel_x, vel_y));
+            world.add_component(bullet_entity, Radius::new(2.0));
+        }
 
         false
     }
@@ -325,6 +351,128 @@ pub struct PlayerIntents {
     pub e_pressed: bool,
     /// The mouse cursor in world coordinates.
     pub mouse_world: Vec2,
+    /// The movement keys as an axis, each component -1, 0 or 1 (up / left
+    /// negative); normalised when applied, see [`set_player_movement`].
+    pub move_dir: Vec2,
+    /// A dialogue-advance press this frame (click, Space or Enter).
+    pub advance_pressed: bool,
+}
+
+impl PlayerIntents {
+    /// No buttons, no movement, the mouse at `mouse_world`.
+    pub fn idle(mouse_world: Vec2) -> Self {
+        PlayerIntents {
+            left_pressed: false,
+            left_down: false,
+            right_pressed: false,
+            e_pressed: false,
+            mouse_world,
+            move_dir: Vec2::zero(),
+            advance_pressed: false,
+        }
+    }
+}
+
+/// Turn the player to face `target` (the mouse, in world coordinates).
+pub fn aim_player(world: &mut World, target: Vec2) {
+    if let Some(&player) = world.query::<Player>().first() {
+        if let Some(pos) = world.get_component::<Position>(player).map(|p| p.to_vec2()) {
+            let d = target - pos;
+            if let Some(rot) = world.get_component_mut::<Rotation>(player) {
+                rot.angle = d.y.atan2(d.x);
+            }
+        }
+    }
+}
+
+/// Set the player's velocity from a movement axis (see
+/// [`PlayerIntents::move_dir`]): full speed along it, diagonals normalised.
+pub fn set_player_movement(world: &mut World, dir: Vec2) {
+    let Some(&player) = world.query::<Player>().first() else {
+        return;
+    };
+    let Some(speed) = world.get_component::<Speed>(player).map(|s| s.value) else {
+        return;
+    };
+    let len = (dir.x * dir.x + dir.y * dir.y).sqrt();
+    let dir = if len > 0.0 { dir / len } else { dir };
+    if let Some(velocity) = world.get_component_mut::<Velocity>(player) {
+        velocity.x = dir.x * speed;
+        velocity.y = dir.y * speed;
+    }
+}
+
+/// The whole per-frame player input dispatch, before the engine tick: a
+/// conversation takes the advance press, a running finisher locks the
+/// player, a tutorial gate routes through [`gated_player_input`], a `hold`
+/// or conversation lets them aim only, and otherwise they walk, fight (if
+/// the scenario allows it), pick up and throw.
+///
+/// The browser loop samples `input::` into `intents` and calls this; replays
+/// ([`crate::replay`]) feed recorded intents to the very same function,
+/// which is what makes them bit-exact.
+pub fn apply_player_input(
+    world: &mut World,
+    mut scenario: Option<&mut crate::scenario::ScenarioState>,
+    intents: &PlayerIntents,
+) {
+    use crate::systems::{FinisherSystem, PickupSystem, ThrownWeaponSystem};
+
+    if !is_player_alive(world) {
+        return;
+    }
+    let dialogue = scenario.as_ref().is_some_and(|sc| sc.dialogue_active());
+    let held = scenario
+        .as_ref()
+        .is_some_and(|sc| sc.hold_active() || sc.dialogue_active());
+    // While a conversation is up the press ADVANCES it (and, `held` being
+    // set, can never fire the weapon).
+    if dialogue && intents.advance_pressed {
+        if let Some(sc) = scenario.as_mut() {
+            sc.dialogue_advance();
+        }
+    }
+
+    // A running finisher locks the player out of everything: no movement,
+    // no aiming (they stay turned onto the victim), no fire / throw /
+    // pickup, until the animation completes.
+    if FinisherSystem::active(world) {
+        stop_player(world);
+        return;
+    }
+
+    if let Some(g) = scenario.as_ref().and_then(|sc| sc.gate_view()) {
+        // Movement stays live so the player can close the distance to the
+        // frozen target; everything else is the gate's call.
+        set_player_movement(world, intents.move_dir);
+        gated_player_input(world, g, intents);
+    } else if held {
+        aim_player(world, intents.mouse_world);
+        stop_player(world);
+    } else {
+        aim_player(world, intents.mouse_world);
+        set_player_movement(world, intents.move_dir);
+        // Fighting can be scenario-disabled (`combat: false` — the
+        // parking-lot walk): fire / punch / finisher / throw are masked;
+        // walking, aiming and E stay live. Gates bypass this (above).
+        let combat_ok = scenario.as_ref().is_none_or(|sc| sc.combat_enabled());
+        // A fresh click over a DOWNED enemy in reach executes a FINISHER
+        // instead of a normal attack.
+        let finisher_started =
+            combat_ok && intents.left_pressed && FinisherSystem::try_start(world);
+        if combat_ok && !finisher_started && intents.left_down {
+            fire_player_weapon(world, intents.mouse_world);
+        }
+        if intents.e_pressed {
+            PickupSystem::swap_for_player(world);
+        }
+        if combat_ok && intents.right_pressed {
+            if let Some(player_pos) = get_player_position(world) {
+                let aim = intents.mouse_world - player_pos;
+                ThrownWeaponSystem::throw_from_player(world, aim);
+            }
+        }
+    }
 }
 
 /// Player input dispatch while a tutorial `gate` is active: aim stays live
@@ -348,14 +496,7 @@ pub fn gated_player_input(
     use crate::systems::{FinisherSystem, PickupSystem, ThrownWeaponSystem};
 
     // Aim: the player keeps turning to the mouse under the freeze.
-    if let Some(&player) = world.query::<Player>().first() {
-        if let Some(pos) = world.get_component::<Position>(player).map(|p| p.to_vec2()) {
-            let d = intents.mouse_world - pos;
-            if let Some(rot) = world.get_component_mut::<Rotation>(player) {
-                rot.angle = d.y.atan2(d.x);
-            }
-        }
-    }
+    aim_player(world, intents.mouse_world);
 
     if gate.input.allows_finisher() && intents.left_pressed {
         FinisherSystem::try_start(world);
@@ -376,12 +517,7 @@ pub fn gated_player_input(
 
 /// Human-readable name for a weapon type
 pub fn weapon_name(weapon_type: WeaponType) -> &'static str {
-    match weapon_type {
-        WeaponType::Pistol => "Pistol",
-        WeaponType::Shotgun => "Shotgun",
-        WeaponType::MachineGun => "Machine Gun",
-        WeaponType::Melee => "Melee",
-    }
+    weapon_type.def().name
 }
 
 /// HUD label for the held weapon: `SHOTGUN 3/6`, `MELEE` (infinite), or
@@ -552,11 +688,11 @@ mod tests {
         let bullet = world.query::<Bullet>()[0];
         assert_eq!(
             world.get_component::<Bullet>(bullet).unwrap().weapon_type,
-            WeaponType::Pistol
+            WeaponType::PISTOL
         );
         assert_eq!(
             world.drain_events(),
-            vec![GameEvent::PlayerFired(WeaponType::Pistol)]
+            vec![GameEvent::PlayerFired(WeaponType::PISTOL)]
         );
         // On cooldown: nothing, and no event.
         assert!(!fire_player_weapon(&mut world, Vec2::new(100.0, 0.0)));
@@ -568,7 +704,7 @@ mod tests {
         let mut world = World::new();
         let player = spawn_player(&mut world, Vec2::new(0.0, 0.0));
         *world.get_component_mut::<Weapon>(player).unwrap() =
-            Weapon::with_ammo(WeaponType::Pistol, 0);
+            Weapon::with_ammo(WeaponType::PISTOL, 0);
 
         assert!(!fire_player_weapon(&mut world, Vec2::new(100.0, 0.0)));
         assert!(world.query::<Bullet>().is_empty());
@@ -584,7 +720,7 @@ mod tests {
         let mut world = World::new();
         let player = spawn_player(&mut world, Vec2::new(0.0, 0.0));
         *world.get_component_mut::<Weapon>(player).unwrap() =
-            Weapon::with_ammo(WeaponType::Melee, 0);
+            Weapon::with_ammo(WeaponType::MELEE, 0);
         for _ in 0..5 {
             fire_player_weapon(&mut world, Vec2::new(100.0, 0.0));
             world
@@ -596,7 +732,7 @@ mod tests {
         assert_eq!(events.len(), 5);
         assert!(events
             .iter()
-            .all(|e| *e == GameEvent::PlayerFired(WeaponType::Melee)));
+            .all(|e| *e == GameEvent::PlayerFired(WeaponType::MELEE)));
     }
 
     #[test]
@@ -616,9 +752,9 @@ mod tests {
         assert_eq!(
             world.drain_events(),
             vec![
-                GameEvent::PlayerFired(WeaponType::Melee),
+                GameEvent::PlayerFired(WeaponType::FISTS),
                 GameEvent::EnemyHit {
-                    by: WeaponType::Melee
+                    by: WeaponType::FISTS
                 },
                 GameEvent::PunchLanded,
             ]
@@ -644,7 +780,7 @@ mod tests {
         // 50 px reach demanded physical overlap).
         let mut world = World::new();
         let player = spawn_player(&mut world, Vec2::new(0.0, 0.0));
-        *world.get_component_mut::<Weapon>(player).unwrap() = Weapon::new(WeaponType::Melee);
+        *world.get_component_mut::<Weapon>(player).unwrap() = Weapon::new(WeaponType::MELEE);
         let enemy = spawn_enemy(&mut world, Vec2::new(65.0, 0.0));
 
         assert!(
@@ -662,7 +798,7 @@ mod tests {
     fn test_melee_swing_misses_at_120px() {
         let mut world = World::new();
         let player = spawn_player(&mut world, Vec2::new(0.0, 0.0));
-        *world.get_component_mut::<Weapon>(player).unwrap() = Weapon::new(WeaponType::Melee);
+        *world.get_component_mut::<Weapon>(player).unwrap() = Weapon::new(WeaponType::MELEE);
         let enemy = spawn_enemy(&mut world, Vec2::new(120.0, 0.0));
 
         assert!(
@@ -674,30 +810,69 @@ mod tests {
         // Only the swing whoosh, no impact events.
         assert_eq!(
             world.drain_events(),
-            vec![GameEvent::PlayerFired(WeaponType::Melee)]
+            vec![GameEvent::PlayerFired(WeaponType::MELEE)]
         );
     }
 
+    #[test]
+    fn test_melee_reach_comes_from_the_weapons_table() {
+        // 76 px: past the bar's 70 px reach, inside the katana's 80.
+        for (weapon, connects) in [(WeaponType::MELEE, false), (WeaponType::KATANA, true)] {
+            let mut world = World::new();
+            let player = spawn_player(&mut world, Vec2::new(0.0, 0.0));
+            *world.get_component_mut::<Weapon>(player).unwrap() = Weapon::new(weapon);
+            spawn_enemy(&mut world, Vec2::new(76.0, 0.0));
+
+            assert_eq!(
+                fire_player_weapon(&mut world, Vec2::new(76.0, 0.0)),
+                connects,
+                "{weapon:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_a_spread_gun_fires_its_pellets_in_a_cone() {
+        for weapon in WeaponType::all().filter(|w| !w.is_melee()) {
+            let def = weapon.def();
+            let mut world = World::new();
+            let player = spawn_player(&mut world, Vec2::new(0.0, 0.0));
+            *world.get_component_mut::<Weapon>(player).unwrap() = Weapon::new(weapon);
+
+            fire_player_weapon(&mut world, Vec2::new(300.0, 0.0));
+            let bullets = world.query::<Bullet>();
+            assert_eq!(bullets.len(), def.pellets as usize, "{weapon:?}");
+            for bullet in bullets {
+                let v = world.get_component::<Velocity>(bullet).unwrap();
+                let angle = v.y.atan2(v.x);
+                assert!(
+                    angle.abs() <= def.spread / 2.0 + 1e-4,
+                    "{weapon:?}: {angle}"
+                );
+            }
+        }
+    }
+
     #[test]
     fn test_weapon_hud_label() {
         assert_eq!(weapon_hud_label(None, 0), "UNARMED");
         assert_eq!(
-            weapon_hud_label(Some(WeaponType::Shotgun), 3),
+            weapon_hud_label(Some(WeaponType::SHOTGUN), 3),
             "SHOTGUN 3/6"
         );
         assert_eq!(
-            weapon_hud_label(Some(WeaponType::MachineGun), 30),
+            weapon_hud_label(Some(WeaponType::MACHINEGUN), 30),
             "MACHINE GUN 30/30"
         );
-        assert_eq!(weapon_hud_label(Some(WeaponType::Melee), 999), "MELEE");
+        assert_eq!(weapon_hud_label(Some(WeaponType::MELEE), 999), "MELEE");
     }
 
     #[test]
     fn test_level_pickups_spawn_with_full_magazine() {
         let mut world = World::new();
-        let p = spawn_pickup(&mut world, Vec2::new(0.0, 0.0), WeaponType::Shotgun);
+        let p = spawn_pickup(&mut world, Vec2::new(0.0, 0.0), WeaponType::SHOTGUN);
         assert_eq!(world.get_component::<WeaponPickup>(p).unwrap().ammo, 6);
-        let q = spawn_pickup_with_ammo(&mut world, Vec2::new(0.0, 0.0), WeaponType::Pistol, 4);
+        let q = spawn_pickup_with_ammo(&mut world, Vec2::new(0.0, 0.0), WeaponType::PISTOL, 4);
         assert_eq!(world.get_component::<WeaponPickup>(q).unwrap().ammo, 4);
     }
 
@@ -59,7 +59,11 @@ pub fn level_index_for_floor_id(id: usize) -> Option<usize> {
 pub fn level_def(level: usize) -> LevelDef {
     let floor = floor_def(level);
     LevelDef {
-        walls: floor.walls.iter().map(|r| (r.x, r.y, r.w, r.h)).collect(),
+        walls: floor
+            .walls
+            .iter()
+            .map(|w| (w.rect.x, w.rect.y, w.rect.w, w.rect.h))
+            .collect(),
         enemies: floor.spawns.iter().map(|s| (s.x, s.y, s.kind)).collect(),
         player_spawn: floor.player_spawn(),
     }
@@ -87,7 +91,7 @@ pub fn floor_title(level: usize) -> String {
 mod tests {
     use super::*;
     use crate::collision::circle_rect_collision;
-    use crate::scenario::{Action, Trigger, SURFACE_EXIT};
+    use crate::scenario::{Action, FloorWall, Trigger, SURFACE_EXIT};
 
     const PLAYER_RADIUS: f32 = 15.0;
     const ENEMY_RADIUS: f32 = 12.0;
@@ -189,7 +193,7 @@ mod tests {
             }
             for (ex, ey) in spawns {
                 let pos = Vec2::new(ex, ey);
-                for w in floor.walls {
+                for &FloorWall { rect: w, .. } in floor.walls {
                     if circle_rect_collision(pos, ENEMY_RADIUS, w.x, w.y, w.w, w.h) {
                         violations.push(format!(
                             "level {level}: enemy at ({ex},{ey}) overlaps wall {w:?}"
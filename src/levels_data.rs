@@ -10,9 +10,9 @@
 
 use crate::components::{EnemyType, WeaponType};
 use crate::scenario::{
-    Action, AlertTarget, ElevatorDef, ElevatorKind, FloorDef, GateDef, GateInput, HoldDef,
-    LookAtDef, PickupDef, PropPlacement, Rect, RoomDef, SayDef, SpawnDef, StepDef, Surface,
-    TalkDef, Trigger, ZoneDef, SURFACE_EXIT,
+    Action, AlertTarget, DoorDef, ElevatorDef, ElevatorKind, FloorDef, FloorWall, GateDef,
+    GateInput, HoldDef, LookAtDef, PickupDef, PropPlacement, Rect, RoomDef, SayDef, SpawnDef,
+    StepDef, Surface, TalkDef, Trigger, ZoneDef, SURFACE_EXIT,
 };
 
 // ---- floor_00.json: FLOOR 0 — GATE / PARKING ----------------------------------------------
@@ -65,15 +65,15 @@ static FLOOR_0_EXITS: [ElevatorDef; 1] = [
     ElevatorDef { id: "doors", rect: Rect::new(440.0, 20.0, 120.0, 50.0), label: "MAIN DOORS", to: 1, open: false, kind: ElevatorKind::Door },
 ];
 
-static FLOOR_0_WALLS: [Rect; 8] = [
-    Rect::new(0.0, 0.0, 1000.0, 20.0),
-    Rect::new(0.0, 780.0, 1000.0, 20.0),
-    Rect::new(0.0, 0.0, 20.0, 800.0),
-    Rect::new(980.0, 0.0, 20.0, 800.0),
-    Rect::new(120.0, 200.0, 30.0, 230.0),
-    Rect::new(850.0, 200.0, 30.0, 230.0),
-    Rect::new(120.0, 480.0, 30.0, 230.0),
-    Rect::new(850.0, 480.0, 30.0, 230.0),
+static FLOOR_0_WALLS: [FloorWall; 8] = [
+    FloorWall::solid(Rect::new(0.0, 0.0, 1000.0, 20.0)),
+    FloorWall::solid(Rect::new(0.0, 780.0, 1000.0, 20.0)),
+    FloorWall::solid(Rect::new(0.0, 0.0, 20.0, 800.0)),
+    FloorWall::solid(Rect::new(980.0, 0.0, 20.0, 800.0)),
+    FloorWall::solid(Rect::new(120.0, 200.0, 30.0, 230.0)),
+    FloorWall::solid(Rect::new(850.0, 200.0, 30.0, 230.0)),
+    FloorWall::solid(Rect::new(120.0, 480.0, 30.0, 230.0)),
+    FloorWall::solid(Rect::new(850.0, 480.0, 30.0, 230.0)),
 ];
 
 static FLOOR_0_ROOMS: [RoomDef; 2] = [
@@ -100,6 +100,9 @@ static FLOOR_0_SPAWNS: [SpawnDef; 6] = [
 static FLOOR_0_PICKUPS: [PickupDef; 0] = [
 ];
 
+static FLOOR_0_DOORS: [DoorDef; 0] = [
+];
+
 static FLOOR_0_PROPS: [PropPlacement; 36] = [
     PropPlacement { kind: 33, x: 300.0, y: 62.0, rot: 0.0, size: 70.0 }, // planter
     PropPlacement { kind: 33, x: 700.0, y: 62.0, rot: 0.0, size: 70.0 }, // planter
@@ -155,6 +158,7 @@ pub static FLOOR_0: FloorDef = FloorDef {
     zones: &FLOOR_0_ZONES,
     spawns: &FLOOR_0_SPAWNS,
     pickups: &FLOOR_0_PICKUPS,
+    doors: &FLOOR_0_DOORS,
     props: &FLOOR_0_PROPS,
     scenario: &FLOOR_0_SCENARIO,
     surface: Surface::Asphalt,
@@ -276,17 +280,17 @@ static FLOOR_1_EXITS: [ElevatorDef; 1] = [
     ElevatorDef { id: "lift", rect: Rect::new(60.0, 20.0, 90.0, 60.0), label: "SERVICE LIFT", to: 2, open: false, kind: ElevatorKind::Lift },
 ];
 
-static FLOOR_1_WALLS: [Rect; 10] = [
-    Rect::new(0.0, 0.0, 1000.0, 20.0),
-    Rect::new(0.0, 780.0, 1000.0, 20.0),
-    Rect::new(0.0, 0.0, 20.0, 800.0),
-    Rect::new(980.0, 0.0, 20.0, 800.0),
-    Rect::new(20.0, 500.0, 410.0, 20.0),
-    Rect::new(570.0, 500.0, 410.0, 20.0),
-    Rect::new(230.0, 150.0, 30.0, 30.0),
-    Rect::new(740.0, 150.0, 30.0, 30.0),
-    Rect::new(230.0, 360.0, 30.0, 30.0),
-    Rect::new(740.0, 360.0, 30.0, 30.0),
+static FLOOR_1_WALLS: [FloorWall; 10] = [
+    FloorWall::solid(Rect::new(0.0, 0.0, 1000.0, 20.0)),
+    FloorWall::solid(Rect::new(0.0, 780.0, 1000.0, 20.0)),
+    FloorWall::solid(Rect::new(0.0, 0.0, 20.0, 800.0)),
+    FloorWall::solid(Rect::new(980.0, 0.0, 20.0, 800.0)),
+    FloorWall::solid(Rect::new(20.0, 500.0, 410.0, 20.0)),
+    FloorWall::solid(Rect::new(570.0, 500.0, 410.0, 20.0)),
+    FloorWall::solid(Rect::new(230.0, 150.0, 30.0, 30.0)),
+    FloorWall::solid(Rect::new(740.0, 150.0, 30.0, 30.0)),
+    FloorWall::solid(Rect::new(230.0, 360.0, 30.0, 30.0)),
+    FloorWall::solid(Rect::new(740.0, 360.0, 30.0, 30.0)),
 ];
 
 static FLOOR_1_ROOMS: [RoomDef; 2] = [
@@ -308,8 +312,11 @@ static FLOOR_1_SPAWNS: [SpawnDef; 4] = [
 ];
 
 static FLOOR_1_PICKUPS: [PickupDef; 2] = [
-    PickupDef { x: 420.0, y: 370.0, weapon: WeaponType::Melee },
-    PickupDef { x: 900.0, y: 100.0, weapon: WeaponType::Shotgun },
+    PickupDef { x: 420.0, y: 370.0, weapon: WeaponType::MELEE },
+    PickupDef { x: 900.0, y: 100.0, weapon: WeaponType::SHOTGUN },
+];
+
+static FLOOR_1_DOORS: [DoorDef; 0] = [
 ];
 
 static FLOOR_1_PROPS: [PropPlacement; 27] = [
@@ -358,6 +365,7 @@ pub static FLOOR_1: FloorDef = FloorDef {
     zones: &FLOOR_1_ZONES,
     spawns: &FLOOR_1_SPAWNS,
     pickups: &FLOOR_1_PICKUPS,
+    doors: &FLOOR_1_DOORS,
     props: &FLOOR_1_PROPS,
     scenario: &FLOOR_1_SCENARIO,
     surface: Surface::Marble,
@@ -421,34 +429,34 @@ static FLOOR_2_EXITS: [ElevatorDef; 1] = [
     ElevatorDef { id: "lift", rect: Rect::new(455.0, 20.0, 90.0, 60.0), label: "FREIGHT LIFT", to: 3, open: false, kind: ElevatorKind::Lift },
 ];
 
-static FLOOR_2_WALLS: [Rect; 27] = [
-    Rect::new(0.0, 0.0, 1000.0, 20.0),
-    Rect::new(0.0, 780.0, 1000.0, 20.0),
-    Rect::new(0.0, 0.0, 20.0, 800.0),
-    Rect::new(980.0, 0.0, 20.0, 800.0),
-    Rect::new(20.0, 100.0, 400.0, 20.0),
-    Rect::new(580.0, 100.0, 400.0, 20.0),
-    Rect::new(20.0, 680.0, 400.0, 20.0),
-    Rect::new(580.0, 680.0, 400.0, 20.0),
-    Rect::new(420.0, 120.0, 20.0, 30.0),
-    Rect::new(420.0, 250.0, 20.0, 300.0),
-    Rect::new(420.0, 650.0, 20.0, 30.0),
-    Rect::new(560.0, 120.0, 20.0, 30.0),
-    Rect::new(560.0, 250.0, 20.0, 300.0),
-    Rect::new(560.0, 650.0, 20.0, 30.0),
-    Rect::new(440.0, 380.0, 120.0, 20.0),
-    Rect::new(60.0, 180.0, 90.0, 45.0),
-    Rect::new(240.0, 180.0, 90.0, 45.0),
-    Rect::new(60.0, 360.0, 90.0, 45.0),
-    Rect::new(240.0, 360.0, 90.0, 45.0),
-    Rect::new(60.0, 540.0, 90.0, 45.0),
-    Rect::new(240.0, 540.0, 90.0, 45.0),
-    Rect::new(670.0, 180.0, 90.0, 45.0),
-    Rect::new(850.0, 180.0, 90.0, 45.0),
-    Rect::new(670.0, 360.0, 90.0, 45.0),
-    Rect::new(850.0, 360.0, 90.0, 45.0),
-    Rect::new(670.0, 540.0, 90.0, 45.0),
-    Rect::new(850.0, 540.0, 90.0, 45.0),
+static FLOOR_2_WALLS: [FloorWall; 27] = [
+    FloorWall::solid(Rect::new(0.0, 0.0, 1000.0, 20.0)),
+    FloorWall::solid(Rect::new(0.0, 780.0, 1000.0, 20.0)),
+    FloorWall::solid(Rect::new(0.0, 0.0, 20.0, 800.0)),
+    FloorWall::solid(Rect::new(980.0, 0.0, 20.0, 800.0)),
+    FloorWall::solid(Rect::new(20.0, 100.0, 400.0, 20.0)),
+    FloorWall::solid(Rect::new(580.0, 100.0, 400.0, 20.0)),
+    FloorWall::solid(Rect::new(20.0, 680.0, 400.0, 20.0)),
+    FloorWall::solid(Rect::new(580.0, 680.0, 400.0, 20.0)),
+    FloorWall::solid(Rect::new(420.0, 120.0, 20.0, 30.0)),
+    FloorWall::solid(Rect::new(420.0, 250.0, 20.0, 300.0)),
+    FloorWall::solid(Rect::new(420.0, 650.0, 20.0, 30.0)),
+    FloorWall::solid(Rect::new(560.0, 120.0, 20.0, 30.0)),
+    FloorWall::solid(Rect::new(560.0, 250.0, 20.0, 300.0)),
+    FloorWall::solid(Rect::new(560.0, 650.0, 20.0, 30.0)),
+    FloorWall::solid(Rect::new(440.0, 380.0, 120.0, 20.0)),
+    FloorWall::solid(Rect::new(60.0, 180.0, 90.0, 45.0)),
+    FloorWall::solid(Rect::new(240.0, 180.0, 90.0, 45.0)),
+    FloorWall::solid(Rect::new(60.0, 360.0, 90.0, 45.0)),
+    FloorWall::solid(Rect::new(240.0, 360.0, 90.0, 45.0)),
+    FloorWall::solid(Rect::new(60.0, 540.0, 90.0, 45.0)),
+    FloorWall::solid(Rect::new(240.0, 540.0, 90.0, 45.0)),
+    FloorWall::solid(Rect::new(670.0, 180.0, 90.0, 45.0)),
+    FloorWall::solid(Rect::new(850.0, 180.0, 90.0, 45.0)),
+    FloorWall::solid(Rect::new(670.0, 360.0, 90.0, 45.0)),
+    FloorWall::solid(Rect::new(850.0, 360.0, 90.0, 45.0)),
+    FloorWall::solid(Rect::new(670.0, 540.0, 90.0, 45.0)),
+    FloorWall::solid(Rect::new(850.0, 540.0, 90.0, 45.0)),
 ];
 
 static FLOOR_2_ROOMS: [RoomDef; 9] = [
@@ -487,7 +495,10 @@ static FLOOR_2_SPAWNS: [SpawnDef; 12] = [
 ];
 
 static FLOOR_2_PICKUPS: [PickupDef; 1] = [
-    PickupDef { x: 380.0, y: 640.0, weapon: WeaponType::MachineGun },
+    PickupDef { x: 380.0, y: 640.0, weapon: WeaponType::MACHINEGUN },
+];
+
+static FLOOR_2_DOORS: [DoorDef; 0] = [
 ];
 
 static FLOOR_2_PROPS: [PropPlacement; 0] = [
@@ -509,6 +520,7 @@ pub static FLOOR_2: FloorDef = FloorDef {
     zones: &FLOOR_2_ZONES,
     spawns: &FLOOR_2_SPAWNS,
     pickups: &FLOOR_2_PICKUPS,
+    doors: &FLOOR_2_DOORS,
     props: &FLOOR_2_PROPS,
     scenario: &FLOOR_2_SCENARIO,
     surface: Surface::Checker,
@@ -567,24 +579,24 @@ static FLOOR_3_EXITS: [ElevatorDef; 1] = [
     ElevatorDef { id: "shaft", rect: Rect::new(455.0, 20.0, 90.0, 60.0), label: "DESCENT SHAFT", to: 4, open: false, kind: ElevatorKind::Lift },
 ];
 
-static FLOOR_3_WALLS: [Rect; 17] = [
-    Rect::new(0.0, 0.0, 1000.0, 20.0),
-    Rect::new(0.0, 780.0, 1000.0, 20.0),
-    Rect::new(0.0, 0.0, 20.0, 800.0),
-    Rect::new(980.0, 0.0, 20.0, 800.0),
-    Rect::new(200.0, 180.0, 250.0, 20.0),
-    Rect::new(550.0, 180.0, 250.0, 20.0),
-    Rect::new(200.0, 600.0, 250.0, 20.0),
-    Rect::new(550.0, 600.0, 250.0, 20.0),
-    Rect::new(200.0, 180.0, 20.0, 150.0),
-    Rect::new(200.0, 450.0, 20.0, 170.0),
-    Rect::new(780.0, 180.0, 20.0, 150.0),
-    Rect::new(780.0, 450.0, 20.0, 170.0),
-    Rect::new(280.0, 260.0, 80.0, 80.0),
-    Rect::new(640.0, 260.0, 80.0, 80.0),
-    Rect::new(280.0, 460.0, 80.0, 80.0),
-    Rect::new(640.0, 460.0, 80.0, 80.0),
-    Rect::new(460.0, 360.0, 80.0, 80.0),
+static FLOOR_3_WALLS: [FloorWall; 17] = [
+    FloorWall::solid(Rect::new(0.0, 0.0, 1000.0, 20.0)),
+    FloorWall::solid(Rect::new(0.0, 780.0, 1000.0, 20.0)),
+    FloorWall::solid(Rect::new(0.0, 0.0, 20.0, 800.0)),
+    FloorWall::solid(Rect::new(980.0, 0.0, 20.0, 800.0)),
+    FloorWall::solid(Rect::new(200.0, 180.0, 250.0, 20.0)),
+    FloorWall::solid(Rect::new(550.0, 180.0, 250.0, 20.0)),
+    FloorWall::solid(Rect::new(200.0, 600.0, 250.0, 20.0)),
+    FloorWall::solid(Rect::new(550.0, 600.0, 250.0, 20.0)),
+    FloorWall::solid(Rect::new(200.0, 180.0, 20.0, 150.0)),
+    FloorWall::solid(Rect::new(200.0, 450.0, 20.0, 170.0)),
+    FloorWall::solid(Rect::new(780.0, 180.0, 20.0, 150.0)),
+    FloorWall::solid(Rect::new(780.0, 450.0, 20.0, 170.0)),
+    FloorWall::solid(Rect::new(280.0, 260.0, 80.0, 80.0)),
+    FloorWall::solid(Rect::new(640.0, 260.0, 80.0, 80.0)),
+    FloorWall::solid(Rect::new(280.0, 460.0, 80.0, 80.0)),
+    FloorWall::solid(Rect::new(640.0, 460.0, 80.0, 80.0)),
+    FloorWall::solid(Rect::new(460.0, 360.0, 80.0, 80.0)),
 ];
 
 static FLOOR_3_ROOMS: [RoomDef; 5] = [
@@ -618,7 +630,12 @@ static FLOOR_3_SPAWNS: [SpawnDef; 12] = [
 ];
 
 static FLOOR_3_PICKUPS: [PickupDef; 1] = [
-    PickupDef { x: 120.0, y: 740.0, weapon: WeaponType::MachineGun },
+    PickupDef { x: 120.0, y: 740.0, weapon: WeaponType::MACHINEGUN },
+];
+
+static FLOOR_3_DOORS: [DoorDef; 2] = [
+    DoorDef { x: 450.0, y: 190.0, angle: 0.0, length: 100.0 },
+    DoorDef { x: 450.0, y: 610.0, angle: 0.0, length: 100.0 },
 ];
 
 static FLOOR_3_PROPS: [PropPlacement; 6] = [
@@ -646,6 +663,7 @@ pub static FLOOR_3: FloorDef = FloorDef {
     zones: &FLOOR_3_ZONES,
     spawns: &FLOOR_3_SPAWNS,
     pickups: &FLOOR_3_PICKUPS,
+    doors: &FLOOR_3_DOORS,
     props: &FLOOR_3_PROPS,
     scenario: &FLOOR_3_SCENARIO,
     surface: Surface::Checker,
@@ -679,15 +697,15 @@ static FLOOR_4_EXITS: [ElevatorDef; 1] = [
     ElevatorDef { id: "lift", rect: Rect::new(850.0, 720.0, 90.0, 60.0), label: "FOUNDRY LIFT", to: 5, open: false, kind: ElevatorKind::Lift },
 ];
 
-static FLOOR_4_WALLS: [Rect; 8] = [
-    Rect::new(0.0, 0.0, 1000.0, 20.0),
-    Rect::new(0.0, 780.0, 1000.0, 20.0),
-    Rect::new(0.0, 0.0, 20.0, 800.0),
-    Rect::new(980.0, 0.0, 20.0, 800.0),
-    Rect::new(200.0, 250.0, 150.0, 20.0),
-    Rect::new(200.0, 250.0, 20.0, 150.0),
-    Rect::new(700.0, 350.0, 150.0, 20.0),
-    Rect::new(700.0, 350.0, 20.0, 200.0),
+static FLOOR_4_WALLS: [FloorWall; 8] = [
+    FloorWall::solid(Rect::new(0.0, 0.0, 1000.0, 20.0)),
+    FloorWall::solid(Rect::new(0.0, 780.0, 1000.0, 20.0)),
+    FloorWall::solid(Rect::new(0.0, 0.0, 20.0, 800.0)),
+    FloorWall::solid(Rect::new(980.0, 0.0, 20.0, 800.0)),
+    FloorWall::solid(Rect::new(200.0, 250.0, 150.0, 20.0)),
+    FloorWall::solid(Rect::new(200.0, 250.0, 20.0, 150.0)),
+    FloorWall::solid(Rect::new(700.0, 350.0, 150.0, 20.0)),
+    FloorWall::solid(Rect::new(700.0, 350.0, 20.0, 200.0)),
 ];
 
 static FLOOR_4_ROOMS: [RoomDef; 2] = [
@@ -718,6 +736,9 @@ static FLOOR_4_SPAWNS: [SpawnDef; 12] = [
 static FLOOR_4_PICKUPS: [PickupDef; 0] = [
 ];
 
+static FLOOR_4_DOORS: [DoorDef; 0] = [
+];
+
 static FLOOR_4_PROPS: [PropPlacement; 0] = [
 ];
 
@@ -737,6 +758,7 @@ pub static FLOOR_4: FloorDef = FloorDef {
     zones: &FLOOR_4_ZONES,
     spawns: &FLOOR_4_SPAWNS,
     pickups: &FLOOR_4_PICKUPS,
+    doors: &FLOOR_4_DOORS,
     props: &FLOOR_4_PROPS,
     scenario: &FLOOR_4_SCENARIO,
     surface: Surface::Checker,
@@ -777,15 +799,15 @@ static FLOOR_5_EXITS: [ElevatorDef; 2] = [
     ElevatorDef { id: "win_b", rect: Rect::new(920.0, 355.0, 60.0, 90.0), label: "WINDOW B", to: 6, open: false, kind: ElevatorKind::Lift },
 ];
 
-static FLOOR_5_WALLS: [Rect; 8] = [
-    Rect::new(0.0, 0.0, 1000.0, 20.0),
-    Rect::new(0.0, 780.0, 1000.0, 20.0),
-    Rect::new(0.0, 0.0, 20.0, 800.0),
-    Rect::new(980.0, 0.0, 20.0, 800.0),
-    Rect::new(200.0, 150.0, 20.0, 250.0),
-    Rect::new(480.0, 200.0, 20.0, 300.0),
-    Rect::new(600.0, 150.0, 20.0, 250.0),
-    Rect::new(300.0, 520.0, 400.0, 20.0),
+static FLOOR_5_WALLS: [FloorWall; 8] = [
+    FloorWall::solid(Rect::new(0.0, 0.0, 1000.0, 20.0)),
+    FloorWall::solid(Rect::new(0.0, 780.0, 1000.0, 20.0)),
+    FloorWall::solid(Rect::new(0.0, 0.0, 20.0, 800.0)),
+    FloorWall::solid(Rect::new(980.0, 0.0, 20.0, 800.0)),
+    FloorWall::solid(Rect::new(200.0, 150.0, 20.0, 250.0)),
+    FloorWall::glass(Rect::new(480.0, 200.0, 20.0, 300.0)),
+    FloorWall::solid(Rect::new(600.0, 150.0, 20.0, 250.0)),
+    FloorWall::solid(Rect::new(300.0, 520.0, 400.0, 20.0)),
 ];
 
 static FLOOR_5_ROOMS: [RoomDef; 1] = [
@@ -814,6 +836,9 @@ static FLOOR_5_SPAWNS: [SpawnDef; 12] = [
 static FLOOR_5_PICKUPS: [PickupDef; 0] = [
 ];
 
+static FLOOR_5_DOORS: [DoorDef; 0] = [
+];
+
 static FLOOR_5_PROPS: [PropPlacement; 0] = [
 ];
 
@@ -833,6 +858,7 @@ pub static FLOOR_5: FloorDef = FloorDef {
     zones: &FLOOR_5_ZONES,
     spawns: &FLOOR_5_SPAWNS,
     pickups: &FLOOR_5_PICKUPS,
+    doors: &FLOOR_5_DOORS,
     props: &FLOOR_5_PROPS,
     scenario: &FLOOR_5_SCENARIO,
     surface: Surface::Checker,
@@ -866,15 +892,15 @@ static FLOOR_6_EXITS: [ElevatorDef; 1] = [
     ElevatorDef { id: "lift", rect: Rect::new(850.0, 20.0, 90.0, 60.0), label: "HEAD LIFT", to: 7, open: false, kind: ElevatorKind::Lift },
 ];
 
-static FLOOR_6_WALLS: [Rect; 8] = [
-    Rect::new(0.0, 0.0, 1000.0, 20.0),
-    Rect::new(0.0, 780.0, 1000.0, 20.0),
-    Rect::new(0.0, 0.0, 20.0, 800.0),
-    Rect::new(980.0, 0.0, 20.0, 800.0),
-    Rect::new(260.0, 250.0, 60.0, 60.0),
-    Rect::new(600.0, 250.0, 60.0, 60.0),
-    Rect::new(300.0, 500.0, 60.0, 60.0),
-    Rect::new(600.0, 500.0, 60.0, 60.0),
+static FLOOR_6_WALLS: [FloorWall; 8] = [
+    FloorWall::solid(Rect::new(0.0, 0.0, 1000.0, 20.0)),
+    FloorWall::solid(Rect::new(0.0, 780.0, 1000.0, 20.0)),
+    FloorWall::solid(Rect::new(0.0, 0.0, 20.0, 800.0)),
+    FloorWall::solid(Rect::new(980.0, 0.0, 20.0, 800.0)),
+    FloorWall::solid(Rect::new(260.0, 250.0, 60.0, 60.0)),
+    FloorWall::solid(Rect::new(600.0, 250.0, 60.0, 60.0)),
+    FloorWall::solid(Rect::new(300.0, 500.0, 60.0, 60.0)),
+    FloorWall::solid(Rect::new(600.0, 500.0, 60.0, 60.0)),
 ];
 
 static FLOOR_6_ROOMS: [RoomDef; 1] = [
@@ -903,6 +929,9 @@ static FLOOR_6_SPAWNS: [SpawnDef; 12] = [
 static FLOOR_6_PICKUPS: [PickupDef; 0] = [
 ];
 
+static FLOOR_6_DOORS: [DoorDef; 0] = [
+];
+
 static FLOOR_6_PROPS: [PropPlacement; 0] = [
 ];
 
@@ -922,6 +951,7 @@ pub static FLOOR_6: FloorDef = FloorDef {
     zones: &FLOOR_6_ZONES,
     spawns: &FLOOR_6_SPAWNS,
     pickups: &FLOOR_6_PICKUPS,
+    doors: &FLOOR_6_DOORS,
     props: &FLOOR_6_PROPS,
     scenario: &FLOOR_6_SCENARIO,
     surface: Surface::Checker,
@@ -955,15 +985,15 @@ static FLOOR_7_EXITS: [ElevatorDef; 1] = [
     ElevatorDef { id: "lift", rect: Rect::new(850.0, 720.0, 90.0, 60.0), label: "VAULT LIFT", to: 8, open: false, kind: ElevatorKind::Lift },
 ];
 
-static FLOOR_7_WALLS: [Rect; 8] = [
-    Rect::new(0.0, 0.0, 1000.0, 20.0),
-    Rect::new(0.0, 780.0, 1000.0, 20.0),
-    Rect::new(0.0, 0.0, 20.0, 800.0),
-    Rect::new(980.0, 0.0, 20.0, 800.0),
-    Rect::new(250.0, 360.0, 300.0, 20.0),
-    Rect::new(450.0, 380.0, 20.0, 200.0),
-    Rect::new(700.0, 200.0, 20.0, 300.0),
-    Rect::new(250.0, 550.0, 200.0, 20.0),
+static FLOOR_7_WALLS: [FloorWall; 8] = [
+    FloorWall::solid(Rect::new(0.0, 0.0, 1000.0, 20.0)),
+    FloorWall::solid(Rect::new(0.0, 780.0, 1000.0, 20.0)),
+    FloorWall::solid(Rect::new(0.0, 0.0, 20.0, 800.0)),
+    FloorWall::solid(Rect::new(980.0, 0.0, 20.0, 800.0)),
+    FloorWall::solid(Rect::new(250.0, 360.0, 300.0, 20.0)),
+    FloorWall::solid(Rect::new(450.0, 380.0, 20.0, 200.0)),
+    FloorWall::glass(Rect::new(700.0, 200.0, 20.0, 300.0)),
+    FloorWall::solid(Rect::new(250.0, 550.0, 200.0, 20.0)),
 ];
 
 static FLOOR_7_ROOMS: [RoomDef; 1] = [
@@ -992,6 +1022,9 @@ static FLOOR_7_SPAWNS: [SpawnDef; 12] = [
 static FLOOR_7_PICKUPS: [PickupDef; 0] = [
 ];
 
+static FLOOR_7_DOORS: [DoorDef; 0] = [
+];
+
 static FLOOR_7_PROPS: [PropPlacement; 0] = [
 ];
 
@@ -1011,6 +1044,7 @@ pub static FLOOR_7: FloorDef = FloorDef {
     zones: &FLOOR_7_ZONES,
     spawns: &FLOOR_7_SPAWNS,
     pickups: &FLOOR_7_PICKUPS,
+    doors: &FLOOR_7_DOORS,
     props: &FLOOR_7_PROPS,
     scenario: &FLOOR_7_SCENARIO,
     surface: Surface::Checker,
@@ -1049,15 +1083,15 @@ static FLOOR_8_EXITS: [ElevatorDef; 1] = [
     ElevatorDef { id: "lift", rect: Rect::new(850.0, 20.0, 90.0, 60.0), label: "DESCENT LIFT", to: 9, open: false, kind: ElevatorKind::Lift },
 ];
 
-static FLOOR_8_WALLS: [Rect; 8] = [
-    Rect::new(0.0, 0.0, 1000.0, 20.0),
-    Rect::new(0.0, 780.0, 1000.0, 20.0),
-    Rect::new(0.0, 0.0, 20.0, 800.0),
-    Rect::new(980.0, 0.0, 20.0, 800.0),
-    Rect::new(300.0, 200.0, 400.0, 20.0),
-    Rect::new(680.0, 200.0, 20.0, 300.0),
-    Rect::new(300.0, 480.0, 400.0, 20.0),
-    Rect::new(300.0, 280.0, 20.0, 200.0),
+static FLOOR_8_WALLS: [FloorWall; 8] = [
+    FloorWall::solid(Rect::new(0.0, 0.0, 1000.0, 20.0)),
+    FloorWall::solid(Rect::new(0.0, 780.0, 1000.0, 20.0)),
+    FloorWall::solid(Rect::new(0.0, 0.0, 20.0, 800.0)),
+    FloorWall::solid(Rect::new(980.0, 0.0, 20.0, 800.0)),
+    FloorWall::solid(Rect::new(300.0, 200.0, 400.0, 20.0)),
+    FloorWall::solid(Rect::new(680.0, 200.0, 20.0, 300.0)),
+    FloorWall::solid(Rect::new(300.0, 480.0, 400.0, 20.0)),
+    FloorWall::solid(Rect::new(300.0, 280.0, 20.0, 200.0)),
 ];
 
 static FLOOR_8_ROOMS: [RoomDef; 1] = [
@@ -1086,6 +1120,9 @@ static FLOOR_8_SPAWNS: [SpawnDef; 12] = [
 static FLOOR_8_PICKUPS: [PickupDef; 0] = [
 ];
 
+static FLOOR_8_DOORS: [DoorDef; 0] = [
+];
+
 static FLOOR_8_PROPS: [PropPlacement; 0] = [
 ];
 
@@ -1105,6 +1142,7 @@ pub static FLOOR_8: FloorDef = FloorDef {
     zones: &FLOOR_8_ZONES,
     spawns: &FLOOR_8_SPAWNS,
     pickups: &FLOOR_8_PICKUPS,
+    doors: &FLOOR_8_DOORS,
     props: &FLOOR_8_PROPS,
     scenario: &FLOOR_8_SCENARIO,
     surface: Surface::Checker,
@@ -1140,15 +1178,15 @@ static FLOOR_9_EXITS: [ElevatorDef; 2] = [
     ElevatorDef { id: "stair_b", rect: Rect::new(920.0, 355.0, 60.0, 90.0), label: "STAIR B", to: 10, open: false, kind: ElevatorKind::Lift },
 ];
 
-static FLOOR_9_WALLS: [Rect; 8] = [
-    Rect::new(0.0, 0.0, 1000.0, 20.0),
-    Rect::new(0.0, 780.0, 1000.0, 20.0),
-    Rect::new(0.0, 0.0, 20.0, 800.0),
-    Rect::new(980.0, 0.0, 20.0, 800.0),
-    Rect::new(310.0, 200.0, 20.0, 400.0),
-    Rect::new(550.0, 200.0, 20.0, 400.0),
-    Rect::new(200.0, 350.0, 500.0, 20.0),
-    Rect::new(200.0, 500.0, 500.0, 20.0),
+static FLOOR_9_WALLS: [FloorWall; 8] = [
+    FloorWall::solid(Rect::new(0.0, 0.0, 1000.0, 20.0)),
+    FloorWall::solid(Rect::new(0.0, 780.0, 1000.0, 20.0)),
+    FloorWall::solid(Rect::new(0.0, 0.0, 20.0, 800.0)),
+    FloorWall::solid(Rect::new(980.0, 0.0, 20.0, 800.0)),
+    FloorWall::solid(Rect::new(310.0, 200.0, 20.0, 400.0)),
+    FloorWall::solid(Rect::new(550.0, 200.0, 20.0, 400.0)),
+    FloorWall::solid(Rect::new(200.0, 350.0, 500.0, 20.0)),
+    FloorWall::solid(Rect::new(200.0, 500.0, 500.0, 20.0)),
 ];
 
 static FLOOR_9_ROOMS: [RoomDef; 1] = [
@@ -1177,6 +1215,9 @@ static FLOOR_9_SPAWNS: [SpawnDef; 12] = [
 static FLOOR_9_PICKUPS: [PickupDef; 0] = [
 ];
 
+static FLOOR_9_DOORS: [DoorDef; 0] = [
+];
+
 static FLOOR_9_PROPS: [PropPlacement; 0] = [
 ];
 
@@ -1196,6 +1237,7 @@ pub static FLOOR_9: FloorDef = FloorDef {
     zones: &FLOOR_9_ZONES,
     spawns: &FLOOR_9_SPAWNS,
     pickups: &FLOOR_9_PICKUPS,
+    doors: &FLOOR_9_DOORS,
     props: &FLOOR_9_PROPS,
     scenario: &FLOOR_9_SCENARIO,
     surface: Surface::Checker,
@@ -1235,15 +1277,15 @@ static FLOOR_10_EXITS: [ElevatorDef; 1] = [
     ElevatorDef { id: "lift", rect: Rect::new(850.0, 720.0, 90.0, 60.0), label: "RESTRAINT LIFT", to: 11, open: false, kind: ElevatorKind::Lift },
 ];
 
-static FLOOR_10_WALLS: [Rect; 8] = [
-    Rect::new(0.0, 0.0, 1000.0, 20.0),
-    Rect::new(0.0, 780.0, 1000.0, 20.0),
-    Rect::new(0.0, 0.0, 20.0, 800.0),
-    Rect::new(980.0, 0.0, 20.0, 800.0),
-    Rect::new(250.0, 200.0, 20.0, 250.0),
-    Rect::new(250.0, 430.0, 150.0, 20.0),
-    Rect::new(650.0, 300.0, 20.0, 250.0),
-    Rect::new(500.0, 300.0, 150.0, 20.0),
+static FLOOR_10_WALLS: [FloorWall; 8] = [
+    FloorWall::solid(Rect::new(0.0, 0.0, 1000.0, 20.0)),
+    FloorWall::solid(Rect::new(0.0, 780.0, 1000.0, 20.0)),
+    FloorWall::solid(Rect::new(0.0, 0.0, 20.0, 800.0)),
+    FloorWall::solid(Rect::new(980.0, 0.0, 20.0, 800.0)),
+    FloorWall::solid(Rect::new(250.0, 200.0, 20.0, 250.0)),
+    FloorWall::solid(Rect::new(250.0, 430.0, 150.0, 20.0)),
+    FloorWall::solid(Rect::new(650.0, 300.0, 20.0, 250.0)),
+    FloorWall::solid(Rect::new(500.0, 300.0, 150.0, 20.0)),
 ];
 
 static FLOOR_10_ROOMS: [RoomDef; 2] = [
@@ -1274,6 +1316,9 @@ static FLOOR_10_SPAWNS: [SpawnDef; 12] = [
 static FLOOR_10_PICKUPS: [PickupDef; 0] = [
 ];
 
+static FLOOR_10_DOORS: [DoorDef; 0] = [
+];
+
 static FLOOR_10_PROPS: [PropPlacement; 0] = [
 ];
 
@@ -1293,6 +1338,7 @@ pub static FLOOR_10: FloorDef = FloorDef {
     zones: &FLOOR_10_ZONES,
     spawns: &FLOOR_10_SPAWNS,
     pickups: &FLOOR_10_PICKUPS,
+    doors: &FLOOR_10_DOORS,
     props: &FLOOR_10_PROPS,
     scenario: &FLOOR_10_SCENARIO,
     surface: Surface::Checker,
@@ -1347,24 +1393,24 @@ static FLOOR_11_EXITS: [ElevatorDef; 1] = [
     ElevatorDef { id: "ascent", rect: Rect::new(455.0, 20.0, 90.0, 60.0), label: "ASCENT LOCK", to: 12, open: false, kind: ElevatorKind::Lift },
 ];
 
-static FLOOR_11_WALLS: [Rect; 17] = [
-    Rect::new(0.0, 0.0, 1000.0, 20.0),
-    Rect::new(0.0, 780.0, 1000.0, 20.0),
-    Rect::new(0.0, 0.0, 20.0, 800.0),
-    Rect::new(980.0, 0.0, 20.0, 800.0),
-    Rect::new(120.0, 140.0, 330.0, 40.0),
-    Rect::new(550.0, 140.0, 330.0, 40.0),
-    Rect::new(120.0, 620.0, 330.0, 40.0),
-    Rect::new(550.0, 620.0, 330.0, 40.0),
-    Rect::new(120.0, 140.0, 40.0, 210.0),
-    Rect::new(120.0, 450.0, 40.0, 170.0),
-    Rect::new(840.0, 140.0, 40.0, 210.0),
-    Rect::new(840.0, 450.0, 40.0, 170.0),
-    Rect::new(380.0, 300.0, 240.0, 20.0),
-    Rect::new(380.0, 300.0, 20.0, 200.0),
-    Rect::new(600.0, 300.0, 20.0, 200.0),
-    Rect::new(380.0, 480.0, 70.0, 20.0),
-    Rect::new(550.0, 480.0, 70.0, 20.0),
+static FLOOR_11_WALLS: [FloorWall; 17] = [
+    FloorWall::solid(Rect::new(0.0, 0.0, 1000.0, 20.0)),
+    FloorWall::solid(Rect::new(0.0, 780.0, 1000.0, 20.0)),
+    FloorWall::solid(Rect::new(0.0, 0.0, 20.0, 800.0)),
+    FloorWall::solid(Rect::new(980.0, 0.0, 20.0, 800.0)),
+    FloorWall::solid(Rect::new(120.0, 140.0, 330.0, 40.0)),
+    FloorWall::solid(Rect::new(550.0, 140.0, 330.0, 40.0)),
+    FloorWall::solid(Rect::new(120.0, 620.0, 330.0, 40.0)),
+    FloorWall::solid(Rect::new(550.0, 620.0, 330.0, 40.0)),
+    FloorWall::solid(Rect::new(120.0, 140.0, 40.0, 210.0)),
+    FloorWall::solid(Rect::new(120.0, 450.0, 40.0, 170.0)),
+    FloorWall::solid(Rect::new(840.0, 140.0, 40.0, 210.0)),
+    FloorWall::solid(Rect::new(840.0, 450.0, 40.0, 170.0)),
+    FloorWall::solid(Rect::new(380.0, 300.0, 240.0, 20.0)),
+    FloorWall::solid(Rect::new(380.0, 300.0, 20.0, 200.0)),
+    FloorWall::solid(Rect::new(600.0, 300.0, 20.0, 200.0)),
+    FloorWall::solid(Rect::new(380.0, 480.0, 70.0, 20.0)),
+    FloorWall::solid(Rect::new(550.0, 480.0, 70.0, 20.0)),
 ];
 
 static FLOOR_11_ROOMS: [RoomDef; 7] = [
@@ -1399,8 +1445,13 @@ static FLOOR_11_SPAWNS: [SpawnDef; 12] = [
 ];
 
 static FLOOR_11_PICKUPS: [PickupDef; 2] = [
-    PickupDef { x: 200.0, y: 720.0, weapon: WeaponType::Shotgun },
-    PickupDef { x: 500.0, y: 350.0, weapon: WeaponType::MachineGun },
+    PickupDef { x: 200.0, y: 720.0, weapon: WeaponType::SHOTGUN },
+    PickupDef { x: 500.0, y: 350.0, weapon: WeaponType::MACHINEGUN },
+];
+
+static FLOOR_11_DOORS: [DoorDef; 2] = [
+    DoorDef { x: 450.0, y: 160.0, angle: 0.0, length: 100.0 },
+    DoorDef { x: 450.0, y: 640.0, angle: 0.0, length: 100.0 },
 ];
 
 static FLOOR_11_PROPS: [PropPlacement; 17] = [
@@ -1439,6 +1490,7 @@ pub static FLOOR_11: FloorDef = FloorDef {
     zones: &FLOOR_11_ZONES,
     spawns: &FLOOR_11_SPAWNS,
     pickups: &FLOOR_11_PICKUPS,
+    doors: &FLOOR_11_DOORS,
     props: &FLOOR_11_PROPS,
     scenario: &FLOOR_11_SCENARIO,
     surface: Surface::Checker,
@@ -1472,15 +1524,15 @@ static FLOOR_12_EXITS: [ElevatorDef; 1] = [
     ElevatorDef { id: "lift", rect: Rect::new(850.0, 20.0, 90.0, 60.0), label: "KERNEL LIFT", to: 13, open: false, kind: ElevatorKind::Lift },
 ];
 
-static FLOOR_12_WALLS: [Rect; 8] = [
-    Rect::new(0.0, 0.0, 1000.0, 20.0),
-    Rect::new(0.0, 780.0, 1000.0, 20.0),
-    Rect::new(0.0, 0.0, 20.0, 800.0),
-    Rect::new(980.0, 0.0, 20.0, 800.0),
-    Rect::new(400.0, 220.0, 200.0, 20.0),
-    Rect::new(400.0, 520.0, 200.0, 20.0),
-    Rect::new(280.0, 360.0, 20.0, 100.0),
-    Rect::new(700.0, 360.0, 20.0, 100.0),
+static FLOOR_12_WALLS: [FloorWall; 8] = [
+    FloorWall::solid(Rect::new(0.0, 0.0, 1000.0, 20.0)),
+    FloorWall::solid(Rect::new(0.0, 780.0, 1000.0, 20.0)),
+    FloorWall::solid(Rect::new(0.0, 0.0, 20.0, 800.0)),
+    FloorWall::solid(Rect::new(980.0, 0.0, 20.0, 800.0)),
+    FloorWall::solid(Rect::new(400.0, 220.0, 200.0, 20.0)),
+    FloorWall::solid(Rect::new(400.0, 520.0, 200.0, 20.0)),
+    FloorWall::glass(Rect::new(280.0, 360.0, 20.0, 100.0)),
+    FloorWall::glass(Rect::new(700.0, 360.0, 20.0, 100.0)),
 ];
 
 static FLOOR_12_ROOMS: [RoomDef; 1] = [
@@ -1509,6 +1561,9 @@ static FLOOR_12_SPAWNS: [SpawnDef; 12] = [
 static FLOOR_12_PICKUPS: [PickupDef; 0] = [
 ];
 
+static FLOOR_12_DOORS: [DoorDef; 0] = [
+];
+
 static FLOOR_12_PROPS: [PropPlacement; 0] = [
 ];
 
@@ -1528,6 +1583,7 @@ pub static FLOOR_12: FloorDef = FloorDef {
     zones: &FLOOR_12_ZONES,
     spawns: &FLOOR_12_SPAWNS,
     pickups: &FLOOR_12_PICKUPS,
+    doors: &FLOOR_12_DOORS,
     props: &FLOOR_12_PROPS,
     scenario: &FLOOR_12_SCENARIO,
     surface: Surface::Checker,
@@ -1566,20 +1622,20 @@ static FLOOR_13_EXITS: [ElevatorDef; 1] = [
     ElevatorDef { id: "extract", rect: Rect::new(455.0, 20.0, 90.0, 60.0), label: "EXTRACTION ELEVATOR", to: 14, open: false, kind: ElevatorKind::Lift },
 ];
 
-static FLOOR_13_WALLS: [Rect; 13] = [
-    Rect::new(0.0, 0.0, 1000.0, 20.0),
-    Rect::new(0.0, 780.0, 1000.0, 20.0),
-    Rect::new(0.0, 0.0, 20.0, 800.0),
-    Rect::new(980.0, 0.0, 20.0, 800.0),
-    Rect::new(150.0, 150.0, 300.0, 30.0),
-    Rect::new(550.0, 150.0, 300.0, 30.0),
-    Rect::new(150.0, 150.0, 30.0, 500.0),
-    Rect::new(820.0, 150.0, 30.0, 500.0),
-    Rect::new(150.0, 620.0, 300.0, 30.0),
-    Rect::new(550.0, 620.0, 300.0, 30.0),
-    Rect::new(450.0, 360.0, 120.0, 120.0),
-    Rect::new(360.0, 360.0, 200.0, 20.0),
-    Rect::new(350.0, 500.0, 220.0, 20.0),
+static FLOOR_13_WALLS: [FloorWall; 13] = [
+    FloorWall::solid(Rect::new(0.0, 0.0, 1000.0, 20.0)),
+    FloorWall::solid(Rect::new(0.0, 780.0, 1000.0, 20.0)),
+    FloorWall::solid(Rect::new(0.0, 0.0, 20.0, 800.0)),
+    FloorWall::solid(Rect::new(980.0, 0.0, 20.0, 800.0)),
+    FloorWall::solid(Rect::new(150.0, 150.0, 300.0, 30.0)),
+    FloorWall::solid(Rect::new(550.0, 150.0, 300.0, 30.0)),
+    FloorWall::solid(Rect::new(150.0, 150.0, 30.0, 500.0)),
+    FloorWall::solid(Rect::new(820.0, 150.0, 30.0, 500.0)),
+    FloorWall::solid(Rect::new(150.0, 620.0, 300.0, 30.0)),
+    FloorWall::solid(Rect::new(550.0, 620.0, 300.0, 30.0)),
+    FloorWall::solid(Rect::new(450.0, 360.0, 120.0, 120.0)),
+    FloorWall::solid(Rect::new(360.0, 360.0, 200.0, 20.0)),
+    FloorWall::solid(Rect::new(350.0, 500.0, 220.0, 20.0)),
 ];
 
 static FLOOR_13_ROOMS: [RoomDef; 1] = [
@@ -1608,6 +1664,9 @@ static FLOOR_13_SPAWNS: [SpawnDef; 12] = [
 static FLOOR_13_PICKUPS: [PickupDef; 0] = [
 ];
 
+static FLOOR_13_DOORS: [DoorDef; 0] = [
+];
+
 static FLOOR_13_PROPS: [PropPlacement; 0] = [
 ];
 
@@ -1627,6 +1686,7 @@ pub static FLOOR_13: FloorDef = FloorDef {
     zones: &FLOOR_13_ZONES,
     spawns: &FLOOR_13_SPAWNS,
     pickups: &FLOOR_13_PICKUPS,
+    doors: &FLOOR_13_DOORS,
     props: &FLOOR_13_PROPS,
     scenario: &FLOOR_13_SCENARIO,
     surface: Surface::Checker,
@@ -1678,17 +1738,17 @@ static FLOOR_14_EXITS: [ElevatorDef; 1] = [
     ElevatorDef { id: "car", rect: Rect::new(60.0, 20.0, 90.0, 60.0), label: "EXTRACTION CAR", to: SURFACE_EXIT, open: false, kind: ElevatorKind::Lift },
 ];
 
-static FLOOR_14_WALLS: [Rect; 10] = [
-    Rect::new(0.0, 0.0, 1000.0, 20.0),
-    Rect::new(0.0, 780.0, 1000.0, 20.0),
-    Rect::new(0.0, 0.0, 20.0, 800.0),
-    Rect::new(980.0, 0.0, 20.0, 800.0),
-    Rect::new(250.0, 200.0, 20.0, 120.0),
-    Rect::new(700.0, 200.0, 20.0, 120.0),
-    Rect::new(250.0, 460.0, 20.0, 120.0),
-    Rect::new(700.0, 460.0, 20.0, 120.0),
-    Rect::new(450.0, 150.0, 60.0, 20.0),
-    Rect::new(450.0, 640.0, 60.0, 20.0),
+static FLOOR_14_WALLS: [FloorWall; 10] = [
+    FloorWall::solid(Rect::new(0.0, 0.0, 1000.0, 20.0)),
+    FloorWall::solid(Rect::new(0.0, 780.0, 1000.0, 20.0)),
+    FloorWall::solid(Rect::new(0.0, 0.0, 20.0, 800.0)),
+    FloorWall::solid(Rect::new(980.0, 0.0, 20.0, 800.0)),
+    FloorWall::solid(Rect::new(250.0, 200.0, 20.0, 120.0)),
+    FloorWall::solid(Rect::new(700.0, 200.0, 20.0, 120.0)),
+    FloorWall::solid(Rect::new(250.0, 460.0, 20.0, 120.0)),
+    FloorWall::solid(Rect::new(700.0, 460.0, 20.0, 120.0)),
+    FloorWall::solid(Rect::new(450.0, 150.0, 60.0, 20.0)),
+    FloorWall::solid(Rect::new(450.0, 640.0, 60.0, 20.0)),
 ];
 
 static FLOOR_14_ROOMS: [RoomDef; 1] = [
@@ -1711,6 +1771,9 @@ static FLOOR_14_SPAWNS: [SpawnDef; 6] = [
 static FLOOR_14_PICKUPS: [PickupDef; 0] = [
 ];
 
+static FLOOR_14_DOORS: [DoorDef; 0] = [
+];
+
 static FLOOR_14_PROPS: [PropPlacement; 0] = [
 ];
 
@@ -1730,6 +1793,7 @@ pub static FLOOR_14: FloorDef = FloorDef {
     zones: &FLOOR_14_ZONES,
     spawns: &FLOOR_14_SPAWNS,
     pickups: &FLOOR_14_PICKUPS,
+    doors: &FLOOR_14_DOORS,
     props: &FLOOR_14_PROPS,
     scenario: &FLOOR_14_SCENARIO,
     surface: Surface::Checker,
@@ -12,6 +12,7 @@ pub mod input;
 // Library module for game logic (enables testing)
 pub mod collision;
 pub mod components;
+pub mod difficulty;
 pub mod drive;
 pub mod ecs;
 pub mod editor;
@@ -30,9 +31,21 @@ pub mod render;
 pub mod render_comms;
 #[cfg(target_arch = "wasm32")]
 pub mod render_dialogue;
+pub mod replay;
+pub mod report;
+pub mod rewind;
+pub mod save;
 pub mod scenario;
+pub mod score;
 pub mod sim;
+pub mod spatial;
+pub mod state_hash;
+pub mod stats;
 pub mod systems;
+pub mod trace;
+pub mod weapons;
+#[rustfmt::skip]
+pub mod weapons_data;
 
 // Camera and level rendering (WASM-only, depend on the canvas Graphics)
 #[cfg(target_arch = "wasm32")]
@@ -55,8 +68,8 @@ mod wasm_entry {
     // Import game modules
     use crate::audio::{song_for_floor, AudioEngine, SONGS};
     use crate::camera::Camera;
-    use crate::ecs::{System, World};
-    use crate::ending::{self, Ending, Outro, EXTRACT_CARD_SECS};
+    use crate::ecs::{Entity, Schedule, System, World};
+    use crate::ending::{self, Ending, Outro, EXTRACT_CARD_SECS, RESULTS_CARD_SECS};
     use crate::game::*;
     use crate::graphics::Graphics;
     use crate::input;
@@ -76,9 +89,15 @@ mod wasm_entry {
         render_zones_debug,
     };
     use crate::render_dialogue::render_dialogue;
+    use crate::replay::Replay;
+    use crate::rewind::RewindBuffer;
     use crate::scenario::{ScenarioState, SURFACE_EXIT};
-    use crate::systems::boss::any_boss_enraged;
+    use crate::score::{FloorScore, Score};
+    use crate::sim::TickContext;
+    use crate::stats::{CampaignStats, FloorStats};
+    use crate::systems::boss::mask_cracked;
     use crate::systems::*;
+    use crate::weapons::WeaponSound;
 
     /// Index into [`SONGS`] of the calmest track (lowest intensity): what
     /// plays once the uplink is back and under the credits.
@@ -93,6 +112,13 @@ mod wasm_entry {
 
     /// Longest simulation step a single frame may take (seconds).
     const MAX_FRAME_DT: f32 = 0.1;
+    /// localStorage key of the "continue run" save (see [`crate::save`]):
+    /// written at every floor start and checkpoint, cleared once the run
+    /// reaches the credits.
+    const RUN_SAVE_KEY: &str = "run";
+    /// localStorage key of the latest recorded floor attempt (see
+    /// [`crate::replay`]).
+    const REPLAY_KEY: &str = "replay";
     /// Hold R this long (seconds) while alive to restart the floor.
     const RESTART_HOLD_SECS: f32 = 1.0;
     /// Safety cap for the loading screen's PRECOMPUTING step: if the audio
@@ -244,12 +270,10 @@ mod wasm_entry {
     /// Map a held weapon to the robot-core weapon model index
     /// (0 fist, 1 pistol, 2 machinegun, 3 shotgun).
     fn robot_weapon_idx(weapon: Option<crate::components::WeaponType>) -> u32 {
-        use crate::components::WeaponType;
-        match weapon {
-            None | Some(WeaponType::Melee) => 0,
-            Some(WeaponType::Pistol) => 1,
-            Some(WeaponType::MachineGun) => 2,
-            Some(WeaponType::Shotgun) => 3,
+        use crate::weapons::WeaponModel;
+        match weapon.map(|w| w.def().model) {
+            None | Some(WeaponModel::Bar) => 0,
+            Some(model) => model.index(),
         }
     }
 
@@ -686,7 +710,7 @@ mod wasm_entry {
                 robot_weapon_idx(world.get_component::<Weapon>(entity).map(|w| w.weapon_type));
             // De-sync the squad: each bot's animation clock starts at a
             // different phase derived from its entity id.
-            let phase = (entity.0 % 97) as f32 * 0.173;
+            let phase = (entity.index() % 97) as f32 * 0.173;
             // Downed bodies face the blow's origin: the pose's backward topple
             // (robot-core's downed plan leans the rig onto its back) then lays
             // them out along `fall_angle` — head first, away from the blow.
@@ -1032,11 +1056,27 @@ mod wasm_entry {
 
     /// A mid-floor `checkpoint` snapshot: the full world (entities,
     /// components, walls, RNG) plus the scenario state (fired steps, opened
-    /// exits, comms, objective) at the moment the action ran. Restored on
-    /// death instead of a full floor restart.
+    /// exits, comms, objective) and the score at the moment the action ran.
+    /// Restored on death instead of a full floor restart.
     struct Checkpoint {
         world: World,
         scenario: ScenarioState,
+        score: Score,
+    }
+
+    /// The `?debug` rewind scrubber (T): the game is paused on a snapshot
+    /// from [`RewindBuffer`] — `GameState::world` / `scenario` hold the
+    /// snapshot while it is open, the live ones wait here.
+    struct Scrub {
+        /// The snapshot shown (an index into the buffer, oldest first).
+        index: usize,
+        live_world: World,
+        live_scenario: Option<ScenarioState>,
+        /// The entity whose components the panel lists (click to pick).
+        selected: Option<Entity>,
+        /// The snapshot M marked: the panel diffs the selected entity
+        /// between it and the one shown.
+        mark: Option<usize>,
     }
 
     #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -1059,27 +1099,43 @@ mod wasm_entry {
         selected_menu_option: MenuOption,
         selected_pause_option: PauseOption,
         world: World,
-        movement_system: MovementSystem,
-        weapon_system: WeaponUpdateSystem,
-        ai_system: AISystem,
-        combat_system: CombatSystem,
-        bullet_system: BulletSystem,
-        projectile_system: ProjectileTrailSystem,
-        pickup_system: PickupSystem,
-        thrown_system: ThrownWeaponSystem,
-        finisher_system: FinisherSystem,
-        stun_system: StunSystem,
-        boss_system: BossSystem,
+        /// The gameplay tick (`sim::tick_schedule`, shared with the
+        /// headless `Simulation`).
+        schedule: Schedule<TickContext>,
         elevator_system: ElevatorSystem,
         /// The running floor scenario (steps, comms feed, objective).
         scenario: Option<ScenarioState>,
         /// The latest mid-floor `checkpoint` snapshot: death restores it
         /// instead of restarting the floor. Cleared on every floor load.
         checkpoint: Option<Checkpoint>,
+        /// The last ~30 s of play (`?debug` only), for the T scrubber.
+        /// Cleared with every floor load / checkpoint restore.
+        rewind: RewindBuffer,
+        /// The scrubber, while open (the game is paused under it).
+        scrub: Option<Scrub>,
+        /// The persisted run save, if any: the title screen offers to
+        /// continue it (C).
+        saved_run: Option<String>,
+        /// The floor attempt being recorded (see `replay`): started on every
+        /// floor load, kept in localStorage when the attempt ends. Dropped
+        /// when a debug key edits the world or a save is resumed — neither
+        /// replays from the floor start.
+        recording: Option<Replay>,
         /// Set once the player has extracted: the destination floor id
         /// (`SURFACE_EXIT` = surface). The completion card plays, then the
         /// floor loads.
         extracting: Option<usize>,
+        /// The floor's running score (see `score.rs`), fed the frame's
+        /// events. Deaths and restarts keep its deaths and clock.
+        score: Score,
+        /// The floor's final tally, taken on extraction for the results card.
+        results: Option<FloorScore>,
+        /// The floor's statistics (see `stats.rs`), over every attempt: a
+        /// death, restore or restart adds to them rather than resetting.
+        stats: FloorStats,
+        /// The floors extracted from since PLAY on the title screen, summed
+        /// for the credits (a resumed run starts them over).
+        campaign: CampaignStats,
         /// Seconds R has been held while alive: at [`RESTART_HOLD_SECS`] the
         /// floor restarts from scratch (a load bar fills at screen centre).
         restart_hold: f32,
@@ -1157,16 +1213,18 @@ mod wasm_entry {
         /// >= 0 = an index into [`POSTFX_PREVIEWS`]. Timed from `effect_start`.
         effect_kind: i32,
         effect_start: f64,
-        prev_player_alive: bool,
+        /// Change-detection bookmark of the whole-game transition sounds
+        /// (death, mask crack, level clear; see `World::run_tracked`).
+        sfx_last_run: u64,
         /// Seconds until the machine-gun burst SFX may retrigger (see the
         /// event dispatch in `update_game`).
         mg_sfx_cooldown: f32,
-        prev_enemies_alive: usize,
         /// Seconds left on the kill flash (background strobes red/blue).
         kill_flash: f32,
+        /// Shards of the panes shattered on this floor (cosmetic; see
+        /// `render::spawn_glass_shards`).
+        glass_shards: Vec<crate::render::GlassShard>,
         prev_level_complete: bool,
-        prev_boss_enraged: bool,
-        prev_all_dead: bool,
     }
 
     impl GameState {
@@ -1182,21 +1240,19 @@ mod wasm_entry {
                 selected_menu_option: MenuOption::Play,
                 selected_pause_option: PauseOption::Continue,
                 world: World::new(),
-                movement_system: MovementSystem,
-                weapon_system: WeaponUpdateSystem,
-                ai_system: AISystem::default(),
-                combat_system: CombatSystem,
-                bullet_system: BulletSystem,
-                projectile_system: ProjectileTrailSystem,
-                pickup_system: PickupSystem,
-                thrown_system: ThrownWeaponSystem,
-                finisher_system: FinisherSystem,
-                stun_system: StunSystem,
-                boss_system: BossSystem,
+                schedule: crate::sim::tick_schedule(),
                 elevator_system: ElevatorSystem,
                 scenario: None,
                 checkpoint: None,
+                rewind: RewindBuffer::default(),
+                scrub: None,
+                saved_run: get_setting(RUN_SAVE_KEY).filter(|s| !s.is_empty()),
+                recording: None,
                 extracting: None,
+                score: Score::new(),
+                results: None,
+                stats: FloorStats::new(),
+                campaign: CampaignStats::new(),
                 restart_hold: 0.0,
                 cursor_hidden: false,
                 music_frozen: false,
@@ -1251,13 +1307,11 @@ mod wasm_entry {
                 editor: crate::editor_ui::Editor::new(),
                 effect_kind: -1,
                 effect_start: 0.0,
-                prev_player_alive: true,
+                sfx_last_run: 0,
                 mg_sfx_cooldown: 0.0,
-                prev_enemies_alive: 0,
                 kill_flash: 0.0,
+                glass_shards: Vec::new(),
                 prev_level_complete: false,
-                prev_boss_enraged: false,
-                prev_all_dead: false,
             };
             // `?floor=N`: jump straight into that floor (editor "play" button,
             // testing). Audio stays off until the first user gesture.
@@ -1292,49 +1346,126 @@ mod wasm_entry {
 
         /// (Re)build the world for `selected_level` and start its scenario.
         fn load_floor(&mut self) {
-            self.world.clear();
-            initialize_game(&mut self.world, self.selected_level);
+            self.finish_recording();
+            // A fresh world carrying the RNG on: the same build a replay
+            // does, entity handles included.
+            let seed = self.world.rng_state();
+            self.world = new_floor_world(self.selected_level, seed);
+            self.recording = Some(Replay::new(floor_def(self.selected_level).id, seed));
             self.scenario = Some(ScenarioState::new(floor_def(self.selected_level)));
             self.checkpoint = None;
+            self.score = Score::new();
+            self.stats = FloorStats::new();
+            self.level
+                .set_surface(floor_def(self.selected_level).surface);
+            self.reset_run_state();
+            self.persist_run();
+        }
+
+        /// Write the run as it stands to localStorage (floor start and
+        /// checkpoints), so a page reload can continue it.
+        fn persist_run(&mut self) {
+            if let Some(sc) = &self.scenario {
+                let save = crate::save::save_run(&self.world, sc);
+                set_setting(RUN_SAVE_KEY, &save);
+                self.saved_run = Some(save);
+            }
+        }
+
+        /// Keep the attempt just recorded (if it got anywhere) as the latest
+        /// replay in localStorage.
+        fn finish_recording(&mut self) {
+            if let Some(replay) = self.recording.take() {
+                if !replay.frames.is_empty() {
+                    set_setting(REPLAY_KEY, &replay.to_text());
+                }
+            }
+        }
+
+        /// Forget the persisted run (it reached the credits, or no longer
+        /// loads).
+        fn forget_run(&mut self) {
+            set_setting(RUN_SAVE_KEY, "");
+            self.saved_run = None;
+        }
+
+        /// Resume the persisted run: its world and scenario, with the save
+        /// itself as the checkpoint death returns to. Returns whether there
+        /// was a loadable save.
+        fn continue_run(&mut self) -> bool {
+            let Some(save) = &self.saved_run else {
+                return false;
+            };
+            self.recording = None;
+            // A save from another build version (or a mangled one) is
+            // dropped: the title stops offering it.
+            let Ok((world, scenario)) = crate::save::load_run(save) else {
+                self.forget_run();
+                return false;
+            };
+            self.selected_level = level_index_for_floor_id(scenario.floor().id).unwrap_or(0);
+            self.checkpoint = Some(Checkpoint {
+                world: world.clone(),
+                scenario: scenario.clone(),
+                score: Score::new(),
+            });
+            self.score = Score::new();
+            self.stats = FloorStats::new();
+            self.campaign = CampaignStats::new();
+            self.world = world;
+            self.scenario = Some(scenario);
             self.level
                 .set_surface(floor_def(self.selected_level).surface);
             self.reset_run_state();
+            self.enter_floor(false);
+            true
         }
 
         /// Restore the latest mid-floor `checkpoint` (same floor): the world
         /// and scenario come back exactly as snapshotted. Returns whether a
         /// checkpoint existed.
         fn restore_checkpoint(&mut self) -> bool {
+            // The attempt ends at the death either way.
+            self.finish_recording();
             let Some(cp) = &self.checkpoint else {
                 return false;
             };
-            self.world = cp.world.clone();
+            self.world.restore(&cp.world);
             self.scenario = Some(cp.scenario.clone());
+            self.score = self.score.retry(Some(&cp.score));
+            self.stats.restored();
             self.reset_run_state();
             true
         }
 
         /// Shared tail of `load_floor` / `restore_checkpoint`: camera, run
-        /// flags, and the previous-frame sound-effect trackers (seeded from
-        /// the fresh world so the first frame fires no spurious sounds).
+        /// flags, the rewind buffer, and the transition-sound bookmark
+        /// (moved to the fresh world's present so the first frame fires no
+        /// spurious sounds).
         fn reset_run_state(&mut self) {
+            self.rewind.clear();
+            self.scrub = None;
             self.camera.set_cinematic(None);
             self.extracting = None;
+            self.results = None;
             self.outro = None;
             self.death_time = 0.0;
             self.level_complete_time = 0.0;
             self.kill_flash = 0.0;
-            self.prev_player_alive = is_player_alive(&self.world);
+            self.glass_shards.clear();
+            self.sfx_last_run = self.world.change_tick();
             self.mg_sfx_cooldown = 0.0;
-            self.prev_enemies_alive = count_alive_enemies(&self.world);
             self.prev_level_complete = false;
-            self.prev_boss_enraged = any_boss_enraged(&self.world);
-            self.prev_all_dead = self.prev_enemies_alive == 0;
         }
 
         fn start_game(&mut self) {
             self.load_floor();
+            self.enter_floor(true);
+        }
 
+        /// Leave the menus for the loaded floor: its music, then the game
+        /// (or, entering the hidden floor fresh, the boss face-off first).
+        fn enter_floor(&mut self, intro: bool) {
             // Music (re)starts with every floor, on that floor's song. The
             // Enter keypress that got us here is a user gesture, so audio may
             // start; a `?floor=N` session has had none yet — `update` resumes
@@ -1349,7 +1480,7 @@ mod wasm_entry {
             self.audio.start_music();
 
             // The hidden floor opens with a face-off before the fight.
-            if self.selected_level == BOSS_LEVEL {
+            if intro && self.selected_level == BOSS_LEVEL {
                 self.boss_intro_line = 0;
                 self.screen = GameScreen::BossIntro;
             } else {
@@ -2379,7 +2510,7 @@ mod wasm_entry {
                 return;
             }
             ending::render_ride(graphics, &self.ending);
-            ending::draw_credits(graphics, &self.ending);
+            ending::draw_credits(graphics, &self.ending, &self.campaign);
             graphics.postfx(10, self.ending.warp_t(graphics.height()), ending::WARP_TINT);
         }
 
@@ -2418,9 +2549,14 @@ mod wasm_entry {
                     MenuOption::About => MenuOption::Settings,
                 };
             }
+            // C: continue the persisted run where it was last saved.
+            if input::is_key_pressed("c") && self.continue_run() {
+                return;
+            }
             if input::is_key_pressed("Enter") {
                 match self.selected_menu_option {
                     MenuOption::Play => {
+                        self.campaign = CampaignStats::new();
                         self.start_game();
                         return;
                     }
@@ -2561,6 +2697,14 @@ mod wasm_entry {
                 16.0,
                 Color::GRAY,
             );
+            if self.saved_run.is_some() {
+                graphics.draw_text(
+                    "C to continue your last run",
+                    Vec2::new(screen_width / 2.0 - 140.0, screen_height - 64.0),
+                    16.0,
+                    Color::GRAY,
+                );
+            }
         }
 
         /// The shared SETTINGS / ABOUT modal chrome over the live title
@@ -2849,7 +2993,313 @@ mod wasm_entry {
             }
         }
 
+        /// Draw the world layer (level, walls, props, elevators, actors)
+        /// under the camera: the live game, or a rewind snapshot.
+        fn draw_world(&mut self, graphics: &Graphics, accent: (u8, u8, u8), dt: f32) {
+            // EXPERIMENT `?pixel=N`: the whole world layer (everything between
+            // camera.apply and camera.reset) is rasterized at N-px art
+            // resolution and nearest-upscaled; the HUD below stays crisp. Note
+            // the robots / boss are already pixelated tiles, so inside the
+            // group they get quantized twice (tile px, then the group px).
+            if self.pixel_world >= 2 {
+                graphics.pixel_begin(self.pixel_world as f32, graphics.width(), graphics.height());
+            }
+
+            // Apply camera transform for world rendering
+            self.camera.apply(graphics);
+
+            // Render level (only the tiles visible in the camera viewport)
+            let (view_min, view_max) = self
+                .camera
+                .visible_bounds(graphics.width(), graphics.height());
+            // View culling for the expensive sprites (live 3D robots / guns /
+            // the boss) and the placed props: anything whose footprint lies
+            // fully outside these inflated bounds skips its commands.
+            let cull = crate::camera::ViewCull::new(view_min, view_max);
+            // Kill flash: the floor strobes red / blue / red / blue for a beat.
+            let tint = if self.kill_flash > 0.0 {
+                self.kill_flash = (self.kill_flash - dt).max(0.0);
+                let phase = ((KILL_FLASH_SECS - self.kill_flash) / KILL_FLASH_SECS
+                    * KILL_FLASH_STROBES as f32) as u32;
+                let fade = self.kill_flash / KILL_FLASH_SECS; // 1 -> 0
+                Some(if phase.is_multiple_of(2) {
+                    Color::new(0.85, 0.08, 0.16, 0.55 * fade)
+                } else {
+                    Color::new(0.10, 0.25, 0.95, 0.55 * fade)
+                })
+            } else {
+                None
+            };
+            self.level.render(graphics, view_min, view_max, tint);
+
+            // Render walls from the world
+            render_walls(&self.world, graphics, self.show_infos);
+            crate::render::update_glass_shards(&mut self.glass_shards, dt);
+            crate::render::draw_glass_shards(graphics, &self.glass_shards);
+
+            // Placed props: floor furniture over the tiles / walls, under the
+            // actors (decoration only, no collision).
+            crate::floor_props::render_floor_props(
+                graphics,
+                floor_def(self.selected_level).props,
+                self.last_time as f32 / 1000.0,
+                &cull,
+            );
+
+            // Elevators (recessed door frames; exits light up when open) and,
+            // in debug mode, the scenario trigger zones.
+            render_elevators(
+                &self.world,
+                graphics,
+                accent,
+                self.last_time as f32 / 1000.0,
+            );
+            if self.show_infos {
+                render_zones_debug(&self.world, graphics);
+            }
+
+            // Downed / dead bots first: the ground weapons (in
+            // render_entities below) draw OVER the corpses so they stay easy
+            // to spot, while everyone still standing draws over the guns.
+            draw_robot_entities(
+                &self.world,
+                graphics,
+                self.last_time as f32 / 1000.0,
+                true,
+                &cull,
+            );
+
+            // Render all entities except the player/rogue bots themselves
+            // (bullets, pickups, boss, debug overlays...).
+            render_entities(
+                &self.world,
+                graphics,
+                self.show_infos,
+                false,
+                self.last_time as f32 / 1000.0,
+                &cull,
+            );
+
+            // The upright player and rogues are the live 3D robot sprites,
+            // drawn while the camera transform (incl. zoom) is still applied
+            // so world-space positions and sizes land correctly.
+            draw_robot_entities(
+                &self.world,
+                graphics,
+                self.last_time as f32 / 1000.0,
+                false,
+                &cull,
+            );
+
+            // A pixelated arrow slowly floating over the active tutorial
+            // gate's target, so "swing the bar" always has an obvious victim.
+            if let Some(anchor) = self.scenario.as_ref().and_then(|sc| sc.gate_anchor()) {
+                let t = self.last_time as f32 / 1000.0;
+                // Bob in whole 2-px steps: floaty but still pixel-crisp.
+                let bob = ((t * 2.2).sin() * 3.0).floor() * 2.0;
+                draw_pixel_arrow(graphics, anchor.x, anchor.y - 58.0 + bob, accent);
+            }
+
+            // Reset camera for UI rendering
+            self.camera.reset(graphics);
+            if self.pixel_world >= 2 {
+                graphics.pixel_end(0.0, 0.0);
+            }
+        }
+
+        fn entity_position(&self, entity: Entity) -> Option<Vec2> {
+            self.world
+                .get_component::<crate::components::Position>(entity)
+                .map(|p| p.to_vec2())
+        }
+
+        /// Open the rewind scrubber on the newest snapshot (nothing to do
+        /// before the first one is taken).
+        fn open_rewind(&mut self) {
+            if self.scrub.is_some() || self.rewind.is_empty() {
+                return;
+            }
+            let index = self.rewind.len() - 1;
+            let Ok((world, scenario)) = self.rewind.snapshot(index) else {
+                return;
+            };
+            let selected = world.query::<crate::components::Player>().first().copied();
+            self.scrub = Some(Scrub {
+                index,
+                live_world: std::mem::replace(&mut self.world, world),
+                live_scenario: self.scenario.replace(scenario),
+                selected,
+                mark: None,
+            });
+        }
+
+        /// The rewind scrubber (`?debug`, T): the game stays paused on a
+        /// snapshot of the last ~30 s. Left / Right step through them (Shift
+        /// x10), a click picks the entity whose components the panel lists,
+        /// M marks the shown snapshot to diff that entity against, Enter
+        /// resumes play from the snapshot (the later ones are dropped), T or
+        /// Esc goes back to the live game untouched.
+        fn update_rewind(&mut self, graphics: &Graphics) {
+            let Some(scrub) = self.scrub.as_mut() else {
+                return;
+            };
+            if input::is_key_pressed("t") || input::is_key_pressed("Escape") {
+                let scrub = self.scrub.take().expect("scrubbing");
+                self.world = scrub.live_world;
+                self.scenario = scrub.live_scenario;
+                return;
+            }
+            if input::is_key_pressed("Enter") {
+                let scrub = self.scrub.take().expect("scrubbing");
+                let mut live = scrub.live_world;
+                live.restore(&self.world);
+                self.world = live;
+                if self.rewind.truncate_after(scrub.index).is_err() {
+                    self.rewind.clear();
+                }
+                // A resumed timeline no longer replays from the floor start.
+                self.recording = None;
+                self.extracting = None;
+                self.death_time = 0.0;
+                self.level_complete_time = 0.0;
+                self.sfx_last_run = self.world.change_tick();
+                return;
+            }
+
+            let step = if input::is_key_down(input::keys::SHIFT) {
+                10
+            } else {
+                1
+            };
+            let last = self.rewind.len() - 1;
+            let mut index = scrub.index;
+            if input::is_key_pressed("ArrowLeft") {
+                index = index.saturating_sub(step);
+            }
+            if input::is_key_pressed("ArrowRight") {
+                index = (index + step).min(last);
+            }
+            if index != scrub.index {
+                if let Ok((world, scenario)) = self.rewind.snapshot(index) {
+                    scrub.index = index;
+                    self.world = world;
+                    self.scenario = Some(scenario);
+                }
+            }
+            if input::is_key_pressed("m") {
+                scrub.mark = Some(scrub.index);
+            }
+
+            self.camera
+                .set_viewport(graphics.width(), graphics.height());
+            let mouse_world = self.camera.screen_to_world(input::mouse_position());
+            if input::is_mouse_button_pressed(input::mouse_buttons::LEFT) {
+                use crate::components::Position;
+                scrub.selected = self
+                    .world
+                    .query_ref::<(Entity, &Position)>()
+                    .map(|(e, p)| (e, p.to_vec2().distance(mouse_world)))
+                    .filter(|&(_, d)| d < 48.0)
+                    .min_by(|a, b| a.1.total_cmp(&b.1))
+                    .map(|(e, _)| e)
+                    .or(scrub.selected);
+            }
+            let (index, selected, mark) = (scrub.index, scrub.selected, scrub.mark);
+            let focus = selected
+                .and_then(|e| self.entity_position(e))
+                .or_else(|| get_player_position(&self.world));
+            if let Some(pos) = focus {
+                self.camera.follow_player(pos);
+            }
+
+            let accent = self
+                .scenario
+                .as_ref()
+                .map(|sc| sc.floor().accent_rgb())
+                .unwrap_or((217, 119, 87));
+            self.draw_world(graphics, accent, 0.0);
+            if let Some(pos) = selected.and_then(|e| self.entity_position(e)) {
+                self.camera.apply(graphics);
+                graphics.draw_circle(pos, 30.0, Color::new(1.0, 0.9, 0.2, 0.25));
+                self.camera.reset(graphics);
+            }
+
+            // The panel: where in the buffer, the controls, then the
+            // selected entity's components (or their diff against the mark).
+            let mut lines = vec![
+                format!(
+                    "REWIND  -{:.1}s  frame {}  [{}/{}]",
+                    self.rewind.age_of(index).unwrap_or(0.0),
+                    self.rewind.frame_at(index).unwrap_or(0),
+                    index + 1,
+                    self.rewind.len()
+                ),
+                "<- -> step (shift x10)  click pick  M mark  Enter resume  T back".to_string(),
+            ];
+            if let Some(entity) = selected {
+                match mark.filter(|&m| m != index) {
+                    Some(mark) => {
+                        lines.push(format!(
+                            "{entity:?}: frame {} -> {}",
+                            self.rewind.frame_at(mark).unwrap_or(0),
+                            self.rewind.frame_at(index).unwrap_or(0)
+                        ));
+                        let diff = self.rewind.entity_diff(mark, index, entity);
+                        for change in diff.unwrap_or_default() {
+                            lines.push(format!(
+                                "  {}: {} -> {}",
+                                change.component,
+                                change.before.as_deref().unwrap_or("-"),
+                                change.after.as_deref().unwrap_or("-")
+                            ));
+                        }
+                    }
+                    None => {
+                        lines.push(format!("{entity:?}"));
+                        for (name, row) in
+                            self.rewind.entity_rows(index, entity).unwrap_or_default()
+                        {
+                            lines.push(format!("  {name}: {row}"));
+                        }
+                    }
+                }
+            }
+            let (x, y, line_h) = (16.0, 16.0, 20.0);
+            graphics.draw_rectangle(
+                Vec2::new(x - 8.0, y - 8.0),
+                graphics.width().min(760.0),
+                lines.len() as f32 * line_h + 16.0,
+                Color::new(0.0, 0.0, 0.0, 0.7),
+            );
+            for (i, line) in lines.iter().enumerate() {
+                graphics.draw_text(
+                    line,
+                    Vec2::new(x, y + i as f32 * line_h),
+                    16.0,
+                    if i == 0 {
+                        Color::new(1.0, 0.9, 0.2, 1.0)
+                    } else {
+                        Color::new(0.9, 0.9, 0.9, 0.9)
+                    },
+                );
+            }
+        }
+
         fn update_game(&mut self, graphics: &Graphics, dt: f32) {
+            if self.scrub.is_some() {
+                self.update_rewind(graphics);
+                return;
+            }
+            // Debug: T pauses into the rewind scrubber (see `update_rewind`).
+            // The scrubber takes over from the next frame, which would read
+            // this same press as T closing it again.
+            if self.debug_enabled && input::is_key_pressed("t") {
+                self.open_rewind();
+                if self.scrub.is_some() {
+                    return;
+                }
+            }
+
             // Get player state for UI and camera
             let player_alive = is_player_alive(&self.world);
             let player_pos = get_player_position(&self.world);
@@ -2873,40 +3323,33 @@ mod wasm_entry {
             // Get mouse position in world coordinates
             let mouse_world_pos = self.camera.screen_to_world(mouse_screen_pos);
 
-            // A scenario `hold` — or an active `talk` conversation — locks
-            // movement / fire / throw / pickup (the world keeps running; Esc
-            // below still works).
-            let dialogue = self
-                .scenario
-                .as_ref()
-                .is_some_and(|sc| sc.dialogue_active());
-            let held = self
-                .scenario
-                .as_ref()
-                .is_some_and(|sc| sc.hold_active() || sc.dialogue_active());
-
-            // While a conversation is up, click / Space / Enter ADVANCES it
-            // (and, `held` being set, can never fire the weapon).
-            if dialogue
-                && player_alive
-                && (input::is_mouse_button_pressed(input::mouse_buttons::LEFT)
+            // This frame's input, as the shared dispatch (and the replay
+            // recorder) sees it.
+            let intents = PlayerIntents {
+                left_pressed: input::is_mouse_button_pressed(input::mouse_buttons::LEFT),
+                left_down: input::is_mouse_button_down(input::mouse_buttons::LEFT),
+                right_pressed: input::is_mouse_button_pressed(input::mouse_buttons::RIGHT),
+                e_pressed: input::is_key_pressed("e"),
+                mouse_world: mouse_world_pos,
+                move_dir: InputSystem::move_axis(),
+                // Click / Space / Enter advance a conversation.
+                advance_pressed: input::is_mouse_button_pressed(input::mouse_buttons::LEFT)
                     || input::is_key_pressed(input::keys::SPACE)
-                    || input::is_key_pressed("Enter"))
-            {
-                if let Some(sc) = self.scenario.as_mut() {
-                    sc.dialogue_advance();
-                }
+                    || input::is_key_pressed("Enter"),
+            };
+            if let Some(replay) = self.recording.as_mut() {
+                replay.record(dt, intents);
             }
 
-            // A running finisher locks the player out of everything: no
-            // movement, no aiming (they stay turned onto the victim), no
-            // fire / throw / pickup, until the animation completes.
-            let finishing = FinisherSystem::active(&self.world);
+            // Handle input (only until the player has left in the car): a
+            // conversation, finisher, tutorial gate or `hold` masks it — see
+            // `game::apply_player_input`, shared with the headless replays.
+            if self.extracting.is_none() {
+                apply_player_input(&mut self.world, self.scenario.as_mut(), &intents);
+            }
 
             // The active tutorial GATE, if any: the world freezes (only the
-            // player-driven systems run, below) and every input except the
-            // gated one is masked. Aim and movement stay live so the player
-            // can close the distance to the frozen target.
+            // player-driven systems run, below).
             let gate = self.scenario.as_ref().and_then(|sc| sc.gate_view());
 
             // The world holds its breath under a tutorial gate: the music
@@ -2922,63 +3365,6 @@ mod wasm_entry {
                 self.music_frozen = gate_active;
             }
 
-            // Handle input (only if the player is alive and hasn't left in
-            // the car yet)
-            if player_alive && self.extracting.is_none() && finishing {
-                stop_player(&mut self.world);
-            }
-            if player_alive && self.extracting.is_none() && !finishing {
-                if let Some(g) = gate {
-                    // Movement stays live so the player can close the distance
-                    // to the frozen target; everything else goes through the
-                    // shared, host-tested gate dispatch (game.rs).
-                    InputSystem::update_player_movement(&mut self.world);
-                    let intents = PlayerIntents {
-                        left_pressed: input::is_mouse_button_pressed(input::mouse_buttons::LEFT),
-                        left_down: input::is_mouse_button_down(input::mouse_buttons::LEFT),
-                        right_pressed: input::is_mouse_button_pressed(input::mouse_buttons::RIGHT),
-                        e_pressed: input::is_key_pressed("e"),
-                        mouse_world: mouse_world_pos,
-                    };
-                    gated_player_input(&mut self.world, g, &intents);
-                } else if held {
-                    InputSystem::update_player_rotation(&mut self.world, mouse_world_pos);
-                    stop_player(&mut self.world);
-                } else {
-                    InputSystem::update_player_rotation(&mut self.world, mouse_world_pos);
-                    InputSystem::update_player_movement(&mut self.world);
-                    // Fighting can be scenario-disabled (`combat: false` —
-                    // the parking-lot walk): fire / punch / finisher / throw
-                    // are masked; walking, aiming and E stay live. Gates
-                    // bypass this (their branch above).
-                    let combat_ok = self.scenario.as_ref().is_none_or(|sc| sc.combat_enabled());
-                    // A fresh click over a DOWNED enemy in reach executes a
-                    // FINISHER instead of a normal attack; otherwise the trigger
-                    // behaves exactly as before.
-                    let finisher_started = combat_ok
-                        && input::is_mouse_button_pressed(input::mouse_buttons::LEFT)
-                        && FinisherSystem::try_start(&mut self.world);
-                    if combat_ok && !finisher_started {
-                        InputSystem::handle_shoot_input(&mut self.world, mouse_world_pos);
-                    }
-
-                    // Press E to pick up / swap the weapon the player is standing on
-                    // (the Pickup event it emits plays the sound below).
-                    if input::is_key_pressed("e") {
-                        PickupSystem::swap_for_player(&mut self.world);
-                    }
-
-                    // Right-click to throw the held weapon toward the cursor (the
-                    // Throw event it emits plays the sound below).
-                    if combat_ok && input::is_mouse_button_pressed(input::mouse_buttons::RIGHT) {
-                        if let Some(player_pos) = get_player_position(&self.world) {
-                            let aim = mouse_world_pos - player_pos;
-                            ThrownWeaponSystem::throw_from_player(&mut self.world, aim);
-                        }
-                    }
-                }
-            }
-
             // Handle info display toggle
             if self.debug_enabled && input::is_key_pressed("i") {
                 self.show_infos = !self.show_infos;
@@ -2991,11 +3377,14 @@ mod wasm_entry {
             // the all-dead scenario steps / exit doors when testing a floor).
             if self.debug_enabled && self.show_infos && input::is_key_pressed("k") {
                 purge_all_enemies(&mut self.world);
+                // Debug edits are not inputs: the attempt no longer replays.
+                self.recording = None;
             }
             // Debug: B cracks the boss's mask (drops it to the enrage threshold)
             // to preview the mask-off transition / raw form without the fight.
             if self.debug_enabled && self.show_infos && input::is_key_pressed("b") {
                 crate::systems::boss::crack_boss_masks(&mut self.world);
+                self.recording = None;
             }
             // Debug: G skips the active tutorial gate (releases it as if the
             // gated input had succeeded) so a gate can never softlock.
@@ -3003,13 +3392,18 @@ mod wasm_entry {
                 if let Some(sc) = self.scenario.as_mut() {
                     sc.gate_skip(&mut self.world);
                 }
+                self.recording = None;
             }
 
             let sim_span = perf::span("sim");
+            // Run game systems (same schedule as the headless sim). TUTORIAL
+            // FREEZE: only the player-driven systems advance — see
+            // `sim::TickContext::gate_frozen`.
+            let tick = TickContext {
+                gate_frozen: gate.is_some(),
+            };
+            self.schedule.run(&mut self.world, &tick, dt);
             if gate.is_some() {
-                // TUTORIAL FREEZE: only the player-driven systems advance
-                // (same list as the headless sim — see sim::gate_frozen_step).
-                crate::sim::gate_frozen_step(&mut self.world, dt);
                 // Invisible walls: the player roams freely but only near the
                 // gate's target (see `scenario::tether_player`).
                 if let Some(anchor) = self.scenario.as_ref().and_then(|sc| sc.gate_anchor()) {
@@ -3019,21 +3413,6 @@ mod wasm_entry {
                         crate::scenario::GATE_TETHER_RADIUS,
                     );
                 }
-            } else {
-                // Run game systems (the finisher goes first so it can keep its
-                // victim pinned before the stun tick).
-                self.finisher_system.run(&mut self.world, dt);
-                self.stun_system.run(&mut self.world, dt);
-                self.weapon_system.run(&mut self.world, dt);
-                self.ai_system.run(&mut self.world, dt);
-                self.boss_system.run(&mut self.world, dt);
-                self.movement_system.run(&mut self.world, dt);
-                self.combat_system.run(&mut self.world, dt);
-                self.bullet_system.run(&mut self.world, dt);
-                self.thrown_system.run(&mut self.world, dt);
-                self.projectile_system.run(&mut self.world, dt);
-                // Drop weapons from downed enemies (player collects via the E key)
-                self.pickup_system.run(&mut self.world, dt);
             }
             drop(sim_span);
 
@@ -3062,8 +3441,12 @@ mod wasm_entry {
                 self.elevator_system.run(&mut self.world, dt);
             }
             if self.extracting.is_none() && player_alive {
+                self.score.tick(dt);
+                self.stats.tick(dt);
                 if let Some(to) = ElevatorSystem::extraction(&self.world) {
                     self.extracting = Some(to);
+                    self.results = Some(self.score.finish());
+                    self.campaign.add_floor(&self.stats);
                     self.level_complete_time = 0.0;
                     self.audio.play_elevator();
                 }
@@ -3080,113 +3463,7 @@ mod wasm_entry {
             // returns (floor restart, extraction) still close it.
             let _record_span = perf::span("record");
 
-            // EXPERIMENT `?pixel=N`: the whole world layer (everything between
-            // camera.apply and camera.reset) is rasterized at N-px art
-            // resolution and nearest-upscaled; the HUD below stays crisp. Note
-            // the robots / boss are already pixelated tiles, so inside the
-            // group they get quantized twice (tile px, then the group px).
-            if self.pixel_world >= 2 {
-                graphics.pixel_begin(self.pixel_world as f32, graphics.width(), graphics.height());
-            }
-
-            // Apply camera transform for world rendering
-            self.camera.apply(graphics);
-
-            // Render level (only the tiles visible in the camera viewport)
-            let (view_min, view_max) = self
-                .camera
-                .visible_bounds(graphics.width(), graphics.height());
-            // View culling for the expensive sprites (live 3D robots / guns /
-            // the boss) and the placed props: anything whose footprint lies
-            // fully outside these inflated bounds skips its commands.
-            let cull = crate::camera::ViewCull::new(view_min, view_max);
-            // Kill flash: the floor strobes red / blue / red / blue for a beat.
-            let tint = if self.kill_flash > 0.0 {
-                self.kill_flash = (self.kill_flash - dt).max(0.0);
-                let phase = ((KILL_FLASH_SECS - self.kill_flash) / KILL_FLASH_SECS
-                    * KILL_FLASH_STROBES as f32) as u32;
-                let fade = self.kill_flash / KILL_FLASH_SECS; // 1 -> 0
-                Some(if phase.is_multiple_of(2) {
-                    Color::new(0.85, 0.08, 0.16, 0.55 * fade)
-                } else {
-                    Color::new(0.10, 0.25, 0.95, 0.55 * fade)
-                })
-            } else {
-                None
-            };
-            self.level.render(graphics, view_min, view_max, tint);
-
-            // Render walls from the world
-            render_walls(&self.world, graphics, self.show_infos);
-
-            // Placed props: floor furniture over the tiles / walls, under the
-            // actors (decoration only, no collision).
-            crate::floor_props::render_floor_props(
-                graphics,
-                floor_def(self.selected_level).props,
-                self.last_time as f32 / 1000.0,
-                &cull,
-            );
-
-            // Elevators (recessed door frames; exits light up when open) and,
-            // in debug mode, the scenario trigger zones.
-            render_elevators(
-                &self.world,
-                graphics,
-                accent,
-                self.last_time as f32 / 1000.0,
-            );
-            if self.show_infos {
-                render_zones_debug(&self.world, graphics);
-            }
-
-            // Downed / dead bots first: the ground weapons (in
-            // render_entities below) draw OVER the corpses so they stay easy
-            // to spot, while everyone still standing draws over the guns.
-            draw_robot_entities(
-                &self.world,
-                graphics,
-                self.last_time as f32 / 1000.0,
-                true,
-                &cull,
-            );
-
-            // Render all entities except the player/rogue bots themselves
-            // (bullets, pickups, boss, debug overlays...).
-            render_entities(
-                &self.world,
-                graphics,
-                self.show_infos,
-                false,
-                self.last_time as f32 / 1000.0,
-                &cull,
-            );
-
-            // The upright player and rogues are the live 3D robot sprites,
-            // drawn while the camera transform (incl. zoom) is still applied
-            // so world-space positions and sizes land correctly.
-            draw_robot_entities(
-                &self.world,
-                graphics,
-                self.last_time as f32 / 1000.0,
-                false,
-                &cull,
-            );
-
-            // A pixelated arrow slowly floating over the active tutorial
-            // gate's target, so "swing the bar" always has an obvious victim.
-            if let Some(anchor) = self.scenario.as_ref().and_then(|sc| sc.gate_anchor()) {
-                let t = self.last_time as f32 / 1000.0;
-                // Bob in whole 2-px steps: floaty but still pixel-crisp.
-                let bob = ((t * 2.2).sin() * 3.0).floor() * 2.0;
-                draw_pixel_arrow(graphics, anchor.x, anchor.y - 58.0 + bob, accent);
-            }
-
-            // Reset camera for UI rendering
-            self.camera.reset(graphics);
-            if self.pixel_world >= 2 {
-                graphics.pixel_end(0.0, 0.0);
-            }
+            self.draw_world(graphics, accent, dt);
 
             // Get game state for UI
             let health = get_player_health(&self.world);
@@ -3213,11 +3490,23 @@ mod wasm_entry {
 
             // --- Sound effects ---
             // Gameplay events queued this frame by the systems (shots, hits,
-            // kills, pickups, throws...) drive the per-weapon SFX; only the
-            // whole-game transitions (death, mask crack, level clear) are still
-            // detected by comparing to the previous frame.
-            let player_alive_now = is_player_alive(&self.world);
-            let boss_enraged = any_boss_enraged(&self.world);
+            // kills, pickups, throws...) drive the per-weapon SFX; the
+            // whole-game transitions (death, mask crack, level clear) come from
+            // change detection against this listener's previous frame.
+            let (player_died, boss_cracked, floor_cleared) =
+                self.world.run_tracked(&mut self.sfx_last_run, |world| {
+                    use crate::components::{Downed, Enemy, Health, Player};
+                    use crate::ecs::{Added, Changed, With};
+                    let player_died = world
+                        .query_ref_filtered::<&Health, (With<Player>, Changed<Health>)>()
+                        .any(|h| h.is_dead());
+                    let enemy_gone = world
+                        .query_ref_filtered::<&Downed, Added<Downed>>()
+                        .next()
+                        .is_some()
+                        || world.removed::<Enemy>().next().is_some();
+                    (player_died, mask_cracked(world), all_dead && enemy_gone)
+                });
 
             // The machine gun fires a round every tick (0.1 s) while the trigger
             // is held, but `play_attack_machinegun` renders a whole 8-round
@@ -3228,15 +3517,10 @@ mod wasm_entry {
             // burst of kills) plays a few, not dozens.
             const MAX_SFX_PER_KIND: u32 = 3;
             self.mg_sfx_cooldown = (self.mg_sfx_cooldown - dt).max(0.0);
-            let mut fired = [0u32; 4];
-            let mut hits = [0u32; 4];
-            let mut counts = [0u32; 5];
-            let slot = |t: crate::components::WeaponType| match t {
-                crate::components::WeaponType::Pistol => 0,
-                crate::components::WeaponType::MachineGun => 1,
-                crate::components::WeaponType::Shotgun => 2,
-                crate::components::WeaponType::Melee => 3,
-            };
+            // Counted per sound set (a pistol and a silenced one share one).
+            let mut fired = [0u32; WeaponSound::COUNT];
+            let mut hits = [0u32; WeaponSound::COUNT];
+            let mut counts = [0u32; 6];
             // Split point: `record` ends here — everything below (event
             // drain, SFX voice creation in WebAudio, checkpoint snapshots,
             // death/restart handling) is the `events` span, so audio-driven
@@ -3244,6 +3528,8 @@ mod wasm_entry {
             drop(_record_span);
             let _events_span = perf::span("events");
             let events = self.world.drain_events();
+            self.score.observe(&events);
+            self.stats.observe(&events);
             // Bridge the frame's events into the scenario: a success on the
             // gated input releases the active tutorial gate (running the rest
             // of its step), and a `checkpoint` action that ran this frame is
@@ -3255,42 +3541,50 @@ mod wasm_entry {
                     self.checkpoint = Some(Checkpoint {
                         world: self.world.clone(),
                         scenario: sc.clone(),
+                        score: self.score.clone(),
                     });
+                    self.persist_run();
                 }
             }
+            // `?debug`: the frame joins the rewind buffer (T scrubs it).
+            if let Some(sc) = self.scenario.as_ref().filter(|_| self.debug_enabled) {
+                self.rewind.record(&self.world, sc, dt);
+            }
             drop(gate_notify_span);
             // `sfx` span: the one-shot voice creation for this frame's
             // events — WebAudio graph building, the suspected hitch source.
             let _sfx_span = perf::span("sfx");
             for event in events {
-                use crate::components::{GameEvent, WeaponType};
+                use crate::components::GameEvent;
                 match event {
-                    GameEvent::PlayerFired(t) => {
-                        let s = slot(t);
-                        if t == WeaponType::MachineGun {
+                    GameEvent::PlayerFired(t) | GameEvent::EnemyFired(t) => {
+                        let sound = t.def().sound;
+                        let s = sound as usize;
+                        if sound == WeaponSound::MachineGun {
                             if self.mg_sfx_cooldown <= 0.0 {
                                 self.audio.play_attack_machinegun();
                                 self.mg_sfx_cooldown = MG_SFX_PERIOD;
                             }
                         } else if fired[s] < MAX_SFX_PER_KIND {
                             fired[s] += 1;
-                            match t {
-                                WeaponType::Pistol => self.audio.play_attack_gun(),
-                                WeaponType::Shotgun => self.audio.play_attack_shotgun(),
-                                WeaponType::Melee => self.audio.play_attack_club(),
-                                WeaponType::MachineGun => {}
+                            match sound {
+                                WeaponSound::Gun => self.audio.play_attack_gun(),
+                                WeaponSound::Shotgun => self.audio.play_attack_shotgun(),
+                                WeaponSound::Club => self.audio.play_attack_club(),
+                                WeaponSound::MachineGun => {}
                             }
                         }
                     }
                     GameEvent::EnemyHit { by } => {
-                        let s = slot(by);
+                        let sound = by.def().sound;
+                        let s = sound as usize;
                         if hits[s] < MAX_SFX_PER_KIND {
                             hits[s] += 1;
-                            match by {
-                                WeaponType::Pistol => self.audio.play_hit_gun(),
-                                WeaponType::MachineGun => self.audio.play_hit_machinegun(),
-                                WeaponType::Shotgun => self.audio.play_hit_shotgun(),
-                                WeaponType::Melee => self.audio.play_hit_club(),
+                            match sound {
+                                WeaponSound::Gun => self.audio.play_hit_gun(),
+                                WeaponSound::MachineGun => self.audio.play_hit_machinegun(),
+                                WeaponSound::Shotgun => self.audio.play_hit_shotgun(),
+                                WeaponSound::Club => self.audio.play_hit_club(),
                             }
                         }
                     }
@@ -3301,7 +3595,7 @@ mod wasm_entry {
                             self.audio.play_enemy_down();
                         }
                     }
-                    GameEvent::PlayerHurt => {
+                    GameEvent::PlayerHurt { .. } => {
                         if counts[1] < MAX_SFX_PER_KIND {
                             counts[1] += 1;
                             self.audio.play_player_hurt();
@@ -3319,10 +3613,17 @@ mod wasm_entry {
                             self.audio.play_throw();
                         }
                     }
-                    GameEvent::ThrownImpact => {
+                    GameEvent::ThrownImpact | GameEvent::DoorSlam => {
                         if counts[4] < MAX_SFX_PER_KIND {
                             counts[4] += 1;
-                            self.audio.play_hit_club(); // reused: a weapon clonks a bot
+                            self.audio.play_hit_club(); // reused: a weapon or door clonks a bot
+                        }
+                    }
+                    GameEvent::GlassShatter(pane) => {
+                        crate::render::spawn_glass_shards(&mut self.glass_shards, &pane);
+                        if counts[5] < MAX_SFX_PER_KIND {
+                            counts[5] += 1;
+                            self.audio.play_glass_shatter();
                         }
                     }
                     GameEvent::DryFire => {
@@ -3333,32 +3634,40 @@ mod wasm_entry {
                     GameEvent::PunchLanded | GameEvent::StrikeLanded | GameEvent::FinisherDone => {}
                 }
             }
-            if boss_enraged && !self.prev_boss_enraged {
+            if boss_cracked {
                 self.audio.play_mask_crack();
             }
-            if !player_alive_now && self.prev_player_alive {
+            if player_died {
                 self.audio.play_death();
                 self.audio.stop_music();
             }
-            if all_dead && !self.prev_all_dead {
+            if floor_cleared {
                 self.audio.play_level_clear();
             }
 
-            self.prev_player_alive = player_alive_now;
-            self.prev_enemies_alive = enemies_alive;
             self.prev_level_complete = level_complete;
-            self.prev_boss_enraged = boss_enraged;
-            self.prev_all_dead = all_dead;
 
-            // Render UI — or, once extracted, the "EXFILTRATED // FLOOR N"
-            // card (which the outro fades out on the last floor).
-            if level_complete {
+            // Render UI — or, once extracted, the RESULTS card, then the
+            // "EXFILTRATED // FLOOR N" card (which the outro fades out on the
+            // last floor).
+            if level_complete && self.level_complete_time < RESULTS_CARD_SECS {
+                if let Some(results) = &self.results {
+                    ending::draw_results_card(
+                        graphics,
+                        &floor_title(self.selected_level),
+                        &self.stats,
+                        results,
+                        self.level_complete_time,
+                        1.0,
+                    );
+                }
+            } else if level_complete {
                 let card_alpha = self.outro.map(|o| o.card_alpha()).unwrap_or(1.0);
                 let home = self.extracting == Some(SURFACE_EXIT);
                 ending::draw_extract_card(
                     graphics,
                     &floor_title(self.selected_level),
-                    self.level_complete_time,
+                    self.level_complete_time - RESULTS_CARD_SECS,
                     card_alpha,
                     home,
                 );
@@ -3379,6 +3688,9 @@ mod wasm_entry {
             // Objective line under the HUD + the intercepted comms feed
             // (bottom-left, above the controls hint), both in screen space;
             // and the caption of a running `hold`, if it has one.
+            if player_alive && !level_complete {
+                render_combo(graphics, &self.score, accent);
+            }
             if let Some(sc) = self.scenario.as_ref() {
                 if player_alive && !level_complete {
                     render_objective(graphics, sc, accent, 150.0);
@@ -3477,7 +3789,7 @@ mod wasm_entry {
             // Extraction card done -> ride to the next floor (13's car jams
             // into 13½ and its boss intro; the boss floor's car goes home:
             // the outro — uplink comms, blur-out — then the credits).
-            if level_complete && self.level_complete_time >= EXTRACT_CARD_SECS {
+            if level_complete && self.level_complete_time >= RESULTS_CARD_SECS + EXTRACT_CARD_SECS {
                 match self.extracting.and_then(level_index_for_floor_id) {
                     Some(next) => {
                         self.selected_level = next;
@@ -3504,6 +3816,8 @@ mod wasm_entry {
                             graphics.postfx(0, t, ending::BLUR_COLOR);
                         }
                         if done {
+                            self.finish_recording();
+                            self.forget_run();
                             self.outro = None;
                             self.scenario = None;
                             self.extracting = None;
@@ -3523,7 +3837,9 @@ mod wasm_entry {
                 self.restart_hold += dt;
                 if self.restart_hold >= RESTART_HOLD_SECS {
                     self.restart_hold = 0.0;
+                    let (score, stats) = (self.score.retry(None), self.stats.clone());
                     self.load_floor();
+                    (self.score, self.stats) = (score, stats);
                     return;
                 }
             } else {
@@ -3536,7 +3852,9 @@ mod wasm_entry {
             // already played; R is the resume).
             if !player_alive && input::is_key_down("r") {
                 if !self.restore_checkpoint() {
+                    let (score, stats) = (self.score.retry(None), self.stats.clone());
                     self.load_floor();
+                    (self.score, self.stats) = (score, stats);
                 }
                 // Restart the music (it was stopped on death).
                 self.audio.start_music();
@@ -1,5 +1,7 @@
-use crate::collision::{circle_rect_collision, has_line_of_sight_with_padding};
-use crate::ecs::world::Wall;
+use crate::collision::{
+    circle_rect_collision, has_line_of_sight_with_padding, point_in_rect, DoorLeaf,
+};
+use crate::ecs::world::{Wall, World};
 use crate::math::Vec2;
 use std::cmp::Ordering;
 use std::collections::{BinaryHeap, HashMap, HashSet};
@@ -127,15 +129,28 @@ impl PartialOrd for AStarNode {
     }
 }
 
-/// Navigation grid representing walkable/blocked cells
+/// Navigation grid representing walkable/blocked cells.
+///
+/// One per level, shared as a world resource (see [`NavigationGrid::sync`])
+/// rather than rebuilt by each user.
+#[derive(Clone)]
 pub struct NavigationGrid {
     blocked_cells: HashSet<GridCoord>,
     walls: Vec<Wall>,
+    doorways: Vec<DoorLeaf>,
 }
 
 impl NavigationGrid {
     /// Create a new navigation grid from world walls
     pub fn new(walls: &[Wall]) -> Self {
+        Self::with_doorways(walls, &[])
+    }
+
+    /// A grid for `walls` that keeps every doorway (a door's leaf where it
+    /// lies shut) walkable: a door gap is narrower than the clearance the
+    /// cells keep from its jambs, and a rogue opens the door by walking
+    /// through it.
+    pub fn with_doorways(walls: &[Wall], doorways: &[DoorLeaf]) -> Self {
         let grid_width = (WORLD_WIDTH / GRID_CELL_SIZE) as i32;
         let grid_height = (WORLD_HEIGHT / GRID_CELL_SIZE) as i32;
         let mut blocked_cells = HashSet::new();
@@ -166,12 +181,45 @@ impl NavigationGrid {
             }
         }
 
+        // Reopen the cells a doorway runs through, short of the wall itself.
+        blocked_cells.retain(|coord| {
+            let c = coord.to_world_pos();
+            let in_doorway = doorways
+                .iter()
+                .any(|d| c.distance(d.closest_point(c).0) < GRID_CELL_SIZE * 0.5);
+            !in_doorway
+                || walls
+                    .iter()
+                    .any(|w| point_in_rect(c, w.x, w.y, w.width, w.height))
+        });
+
         NavigationGrid {
             blocked_cells,
             walls: walls.to_vec(),
+            doorways: doorways.to_vec(),
+        }
+    }
+
+    /// Make sure `world` holds a `NavigationGrid` resource built for its
+    /// current walls and doorways, (re)building it when missing or stale (a
+    /// level swap). Building scans every cell against every wall, far too
+    /// much work to redo each tick for what is static level geometry.
+    pub fn sync(world: &mut World) {
+        let doorways = crate::systems::door::doorways(world);
+        let stale = world
+            .resource::<NavigationGrid>()
+            .is_none_or(|grid| grid.walls != world.walls() || grid.doorways != doorways);
+        if stale {
+            let grid = NavigationGrid::with_doorways(world.walls(), &doorways);
+            world.insert_resource(grid);
         }
     }
 
+    /// The walls this grid was built for.
+    pub fn walls(&self) -> &[Wall] {
+        &self.walls
+    }
+
     /// Check if a grid cell is walkable
     pub fn is_walkable(&self, coord: &GridCoord) -> bool {
         coord.is_valid() && !self.blocked_cells.contains(coord)
@@ -440,6 +488,70 @@ impl NavigationGrid {
         // Empty path (already at goal) or all waypoints in the start cell
         goal
     }
+
+    /// The walkable cell standing for `pos`: its own cell, or the nearest
+    /// walkable one when it sits in a wall's inflation.
+    pub fn walkable_cell(&self, pos: Vec2) -> Option<GridCoord> {
+        self.nearest_walkable(GridCoord::from_world_pos(pos.x, pos.y))
+    }
+
+    /// Walking distance (px) from `from` to every walkable cell no further
+    /// than `reach` away, by a Dijkstra flood over the grid. Diagonal steps
+    /// are allowed where both cells they cut past are open, so the figure on
+    /// open ground stays close to the straight line, while a wall makes the
+    /// flood go round it. Empty when `from` has no walkable cell near it.
+    pub fn path_distances(&self, from: Vec2, reach: f32) -> HashMap<GridCoord, f32> {
+        // Costs in tenths of a cell (10 straight, 14 diagonal) keep the heap
+        // on integers.
+        const STRAIGHT: u32 = 10;
+        const DIAGONAL: u32 = 14;
+        let to_px = |cost: u32| cost as f32 * GRID_CELL_SIZE / STRAIGHT as f32;
+
+        let mut dist: HashMap<GridCoord, u32> = HashMap::new();
+        let Some(start) = self.walkable_cell(from) else {
+            return HashMap::new();
+        };
+        let mut open = BinaryHeap::new();
+        dist.insert(start, 0);
+        open.push(std::cmp::Reverse((0, start.i, start.j)));
+
+        while let Some(std::cmp::Reverse((cost, i, j))) = open.pop() {
+            if dist.get(&GridCoord::new(i, j)).is_some_and(|&d| d < cost) {
+                continue; // A stale duplicate
+            }
+            for di in -1..=1 {
+                for dj in -1..=1 {
+                    if di == 0 && dj == 0 {
+                        continue;
+                    }
+                    let next = GridCoord::new(i + di, j + dj);
+                    if !self.is_walkable(&next) {
+                        continue;
+                    }
+                    let step = if di != 0 && dj != 0 {
+                        // No cutting a wall's corner.
+                        if !self.is_walkable(&GridCoord::new(i + di, j))
+                            || !self.is_walkable(&GridCoord::new(i, j + dj))
+                        {
+                            continue;
+                        }
+                        DIAGONAL
+                    } else {
+                        STRAIGHT
+                    };
+                    let next_cost = cost + step;
+                    if to_px(next_cost) > reach || dist.get(&next).is_some_and(|&d| d <= next_cost)
+                    {
+                        continue;
+                    }
+                    dist.insert(next, next_cost);
+                    open.push(std::cmp::Reverse((next_cost, next.i, next.j)));
+                }
+            }
+        }
+
+        dist.into_iter().map(|(c, d)| (c, to_px(d))).collect()
+    }
 }
 
 #[cfg(test)]
@@ -552,6 +664,29 @@ mod tests {
         assert!(grid.is_walkable(&free_cell));
     }
 
+    #[test]
+    fn test_doorway_keeps_a_narrow_gap_walkable() {
+        // A wall across the whole world with a 60-wide gap at x 430..490,
+        // off the cell grid: both cells it straddles keep their clearance
+        // from a jamb.
+        let walls = vec![
+            Wall::new(0.0, 300.0, 430.0, 20.0),
+            Wall::new(490.0, 300.0, 1510.0, 20.0),
+        ];
+        let start = Vec2::new(450.0, 200.0);
+        let goal = Vec2::new(450.0, 450.0);
+        assert!(NavigationGrid::new(&walls).find_path(start, goal).is_none());
+
+        let door = DoorLeaf {
+            hinge: Vec2::new(430.0, 310.0),
+            tip: Vec2::new(490.0, 310.0),
+        };
+        let grid = NavigationGrid::with_doorways(&walls, &[door]);
+        assert!(grid.find_path(start, goal).is_some());
+        // The wall beside the doorway stays blocked.
+        assert!(!grid.is_walkable(&GridCoord::from_world_pos(300.0, 310.0)));
+    }
+
     #[test]
     fn test_find_path_straight_line() {
         // No walls, should find straight path
@@ -775,4 +910,23 @@ mod tests {
             assert_eq!(p1.len(), p2.len());
         }
     }
+
+    #[test]
+    fn test_path_distances_go_round_walls() {
+        // A wall from the top down to y 600 between x 400 and 420: the cell
+        // just across it is 50 px away as the crow flies, far more on foot.
+        let walls = vec![Wall::new(400.0, 0.0, 20.0, 600.0)];
+        let grid = NavigationGrid::new(&walls);
+        let from = Vec2::new(325.0, 125.0);
+        let dist = grid.path_distances(from, 2000.0);
+
+        let beside = GridCoord::from_world_pos(325.0, 325.0);
+        assert_eq!(dist[&beside], 200.0, "open ground: straight steps");
+        let across = GridCoord::from_world_pos(525.0, 125.0);
+        assert!(dist[&across] > 1000.0, "round the wall's end");
+
+        let near = grid.path_distances(from, 300.0);
+        assert!(near.contains_key(&beside));
+        assert!(!near.contains_key(&across), "out of reach");
+    }
 }
@@ -39,6 +39,9 @@ pub fn render_entities(
     // Render bullets
     render_bullets(world, graphics);
 
+    // Rogues winding up a shot: the aim line
+    render_aim_telegraphs(world, graphics);
+
     // Render weapons in flight
     render_thrown_weapons(world, graphics, cull);
 
@@ -114,10 +117,40 @@ pub fn render_walls(world: &World, graphics: &Graphics, show_infos: bool) {
             );
         }
 
-        draw_wall(graphics, wall.x, wall.y, wall.width, wall.height);
+        if wall.is_glass() {
+            draw_glass(graphics, wall.x, wall.y, wall.width, wall.height);
+        } else {
+            draw_wall(graphics, wall.x, wall.y, wall.width, wall.height);
+        }
+    }
+    for door in world.query_ref::<&Door>() {
+        draw_door(graphics, door.leaf());
     }
 }
 
+/// Draw one door leaf: a plank the wall's colours from hinge to tip, with
+/// the hinge pin marked — shared with the native level editor.
+pub fn draw_door(graphics: &Graphics, leaf: crate::collision::DoorLeaf) {
+    let thickness = crate::systems::door::DOOR_HALF_THICKNESS * 2.0;
+    graphics.draw_line(
+        leaf.hinge,
+        leaf.tip,
+        thickness + 2.0,
+        Color::new(100.0 / 255.0, 80.0 / 255.0, 90.0 / 255.0, 1.0),
+    );
+    graphics.draw_line(
+        leaf.hinge,
+        leaf.tip,
+        thickness - 2.0,
+        Color::new(140.0 / 255.0, 105.0 / 255.0, 80.0 / 255.0, 1.0),
+    );
+    graphics.draw_circle(
+        leaf.hinge,
+        3.0,
+        Color::new(60.0 / 255.0, 45.0 / 255.0, 55.0 / 255.0, 1.0),
+    );
+}
+
 /// Draw one wall rectangle the way the game does (dark purple slab with a
 /// lighter border) — shared with the native level editor.
 pub fn draw_wall(graphics: &Graphics, x: f32, y: f32, w: f32, h: f32) {
@@ -137,6 +170,92 @@ pub fn draw_wall(graphics: &Graphics, x: f32, y: f32, w: f32, h: f32) {
     );
 }
 
+/// Draw one glass partition: a pale see-through pane with a bright rim and
+/// a glint across it — shared with the native level editor.
+pub fn draw_glass(graphics: &Graphics, x: f32, y: f32, w: f32, h: f32) {
+    graphics.draw_rectangle(Vec2::new(x, y), w, h, Color::new(0.55, 0.8, 0.95, 0.25));
+    graphics.draw_rectangle_lines(Vec2::new(x, y), w, h, 2.0, Color::new(0.75, 0.92, 1.0, 0.8));
+    let glint = w.min(h) * 0.5;
+    graphics.draw_line(
+        Vec2::new(x + w * 0.3, y + h * 0.3 + glint * 0.5),
+        Vec2::new(x + w * 0.3 + glint, y + h * 0.3 - glint * 0.5),
+        1.0,
+        Color::new(1.0, 1.0, 1.0, 0.5),
+    );
+}
+
+/// Shards a shattered pane throws per 10 px of its length.
+const GLASS_SHARDS_PER_10PX: f32 = 1.5;
+
+/// Shards kept on the floor at most: the oldest are swept once a floor's
+/// worth of panes has burst.
+const GLASS_SHARDS_MAX: usize = 600;
+
+/// One sliver of a shattered pane: it skids out from where the pane stood,
+/// spinning, and comes to rest on the floor (cosmetic only — the sim never
+/// sees it).
+#[derive(Clone, Copy, Debug)]
+pub struct GlassShard {
+    pub pos: Vec2,
+    pub vel: Vec2,
+    pub angle: f32,
+    pub spin: f32,
+    pub size: f32,
+}
+
+/// Scatter `pane`'s shards, appending them to `shards`. The spread is drawn
+/// from the pane's coordinates, so a replayed shatter lands the same way.
+pub fn spawn_glass_shards(shards: &mut Vec<GlassShard>, pane: &crate::ecs::Wall) {
+    let mut seed =
+        (pane.x.to_bits() ^ pane.y.to_bits().rotate_left(16)).wrapping_mul(0x9E37_79B9) | 1;
+    let mut next = || {
+        seed ^= seed << 13;
+        seed ^= seed >> 17;
+        seed ^= seed << 5;
+        seed as f32 / u32::MAX as f32
+    };
+    let count = ((pane.width.max(pane.height) / 10.0) * GLASS_SHARDS_PER_10PX).ceil() as usize;
+    for _ in 0..count {
+        let pos = Vec2::new(pane.x + next() * pane.width, pane.y + next() * pane.height);
+        let heading = next() * std::f32::consts::TAU;
+        let speed = 60.0 + next() * 180.0;
+        shards.push(GlassShard {
+            pos,
+            vel: Vec2::new(heading.cos(), heading.sin()) * speed,
+            angle: next() * std::f32::consts::TAU,
+            spin: (next() - 0.5) * 20.0,
+            size: 2.0 + next() * 4.0,
+        });
+    }
+    if shards.len() > GLASS_SHARDS_MAX {
+        shards.drain(..shards.len() - GLASS_SHARDS_MAX);
+    }
+}
+
+/// Slide the shards on for `dt`: they skid, spin down and settle.
+pub fn update_glass_shards(shards: &mut [GlassShard], dt: f32) {
+    let drag = (-dt / 0.12).exp();
+    for shard in shards.iter_mut() {
+        shard.pos += shard.vel * dt;
+        shard.angle += shard.spin * dt;
+        shard.vel = shard.vel * drag;
+        shard.spin *= drag;
+    }
+}
+
+/// Draw the shards lying on the floor: short pale slivers.
+pub fn draw_glass_shards(graphics: &Graphics, shards: &[GlassShard]) {
+    for shard in shards {
+        let d = Vec2::new(shard.angle.cos(), shard.angle.sin()) * (shard.size * 0.5);
+        graphics.draw_line(
+            shard.pos - d,
+            shard.pos + d,
+            1.5,
+            Color::new(0.8, 0.93, 1.0, 0.7),
+        );
+    }
+}
+
 /// Render debug pathfinding visualization
 fn render_debug_pathfinding(world: &World, graphics: &Graphics) {
     use crate::components::{AIState, DebugPath, DebugTrail, Enemy, Position, AI};
@@ -264,25 +383,17 @@ fn render_enemy_vision_cones(world: &World, graphics: &Graphics) {
     }
 }
 
-/// Color used to represent a weapon type on the ground / in the UI.
+/// Color used to represent a weapon type on the ground / in the UI (its
+/// table `color`).
 fn weapon_color(weapon_type: WeaponType) -> Color {
-    match weapon_type {
-        WeaponType::Pistol => Color::new(0.9, 0.9, 0.9, 1.0), // Light gray
-        WeaponType::Shotgun => Color::new(1.0, 0.55, 0.1, 1.0), // Orange
-        WeaponType::MachineGun => Color::new(0.2, 0.8, 1.0, 1.0), // Cyan
-        WeaponType::Melee => Color::new(0.7, 0.7, 0.75, 1.0), // Steel
-    }
+    let [r, g, b] = weapon_type.def().color;
+    Color::new(r, g, b, 1.0)
 }
 
 /// GUNPICKUP weapon-model index for a weapon type (robot-core.js
 /// `GROUND_WEAPON_MODELS`: 0 bar, 1 pistol, 2 machinegun, 3 shotgun).
 fn ground_weapon_idx(weapon_type: WeaponType) -> u32 {
-    match weapon_type {
-        WeaponType::Melee => 0,
-        WeaponType::Pistol => 1,
-        WeaponType::MachineGun => 2,
-        WeaponType::Shotgun => 3,
-    }
+    weapon_type.def().model.index()
 }
 
 /// On-screen size (px) of a ground weapon's sprite quad. The 3D render frames
@@ -378,12 +489,47 @@ fn render_bullets(world: &World, graphics: &Graphics) {
             .map(|r| r.value)
             .unwrap_or(2.0);
 
-        // Yellow bullets
-        let color = Color::new(1.0, 0.9, 0.3, 1.0);
+        // Yellow bullets; a rogue's rounds burn red.
+        let hostile = world
+            .get_component::<Bullet>(entity)
+            .is_some_and(|b| b.shooter.is_some());
+        let color = if hostile {
+            Color::new(1.0, 0.25, 0.2, 1.0)
+        } else {
+            Color::new(1.0, 0.9, 0.3, 1.0)
+        };
         graphics.draw_circle(Vec2::new(pos.x, pos.y), radius, color);
     }
 }
 
+/// The wind-up telegraph: a thin red line from each rogue aiming at the
+/// player (see `AI::aim_timer`), brightening as the shot comes.
+fn render_aim_telegraphs(world: &World, graphics: &Graphics) {
+    let Some(target) = world
+        .query::<Player>()
+        .first()
+        .and_then(|&p| world.get_component::<Position>(p))
+        .copied()
+    else {
+        return;
+    };
+    for (ai, pos, weapon) in world.query_ref::<(&AI, &Position, &Weapon)>() {
+        let Some(gun) = crate::systems::combat::enemy_gun(weapon.weapon_type) else {
+            continue;
+        };
+        if ai.aim_timer <= 0.0 || ai.aim_timer >= gun.windup {
+            continue;
+        }
+        let t = ai.aim_timer / gun.windup;
+        graphics.draw_line(
+            Vec2::new(pos.x, pos.y),
+            Vec2::new(target.x, target.y),
+            1.0 + t,
+            Color::new(1.0, 0.15, 0.15, 0.15 + 0.5 * t),
+        );
+    }
+}
+
 /// Render weapons currently flying through the air after being thrown.
 fn render_thrown_weapons(world: &World, graphics: &Graphics, cull: &crate::camera::ViewCull) {
     let thrown: Vec<Entity> = world.query::<ThrownWeapon>();
@@ -606,3 +752,39 @@ pub fn render_ui(
         Color::GRAY,
     );
 }
+
+/// The running score, top centre, and under it the combo counter while a
+/// chain is going (`x3 COMBO`, its window draining as a bar).
+pub fn render_combo(graphics: &Graphics, score: &crate::score::Score, accent: (u8, u8, u8)) {
+    let cx = graphics.width() / 2.0;
+    let points = format!("{} PTS", score.points());
+    graphics.draw_text(
+        &points,
+        Vec2::new(cx - points.len() as f32 * 5.0, 32.0),
+        24.0,
+        Color::WHITE,
+    );
+    if score.combo() < 2 {
+        return;
+    }
+    let accent = Color::new(
+        accent.0 as f32 / 255.0,
+        accent.1 as f32 / 255.0,
+        accent.2 as f32 / 255.0,
+        1.0,
+    );
+    let label = format!("x{} COMBO", score.multiplier());
+    graphics.draw_text(
+        &label,
+        Vec2::new(cx - label.len() as f32 * 7.0, 66.0),
+        32.0,
+        accent,
+    );
+    let (bw, bh) = (120.0, 4.0);
+    graphics.draw_rectangle(
+        Vec2::new(cx - bw / 2.0, 76.0),
+        bw * score.combo_left(),
+        bh,
+        accent,
+    );
+}
@@ -0,0 +1,234 @@
+//! Input recordings: a floor attempt as its starting floor, the RNG seed it
+//! started from and every frame's [`PlayerIntents`] + `dt`. Re-running one
+//! through [`Simulation::play_replay`] reproduces the run bit-exactly, since
+//! both hosts build the floor the same way ([`crate::game::new_floor_world`])
+//! and feed the intents to the same dispatch
+//! ([`crate::game::apply_player_input`]).
+//!
+//! The browser records every attempt and keeps the latest in localStorage;
+//! a replay file attached to a bug report loads with [`Replay::parse`] in a
+//! test. Exact on the same build and target: wasm and native builds may
+//! round transcendental functions (`atan2`, `exp`) differently.
+//!
+//! File format (tokens as in [`crate::save`]): `OMREPLAY <version> <floor id>
+//! <seed> <frame count>`, then per frame `dt mouse_x mouse_y move_x move_y
+//! buttons`, `buttons` being a bit set (see [`ReplayFrame`]).
+
+use crate::game::PlayerIntents;
+use crate::math::Vec2;
+use crate::save::{floor_by_id, Persist, SaveError, SaveReader, SaveWriter};
+use crate::sim::{FloorRun, Simulation};
+
+/// Current replay format version. Other versions are refused (version 1
+/// replays seeded a world with a single RNG, before the named streams).
+pub const REPLAY_VERSION: u32 = 2;
+
+const MAGIC: &str = "OMREPLAY";
+
+const LEFT_PRESSED: u32 = 1;
+const LEFT_DOWN: u32 = 1 << 1;
+const RIGHT_PRESSED: u32 = 1 << 2;
+const E_PRESSED: u32 = 1 << 3;
+const ADVANCE_PRESSED: u32 = 1 << 4;
+
+/// One recorded frame: the step length and what the player did.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReplayFrame {
+    pub dt: f32,
+    pub intents: PlayerIntents,
+}
+
+impl Persist for ReplayFrame {
+    fn save(&self, w: &mut SaveWriter) {
+        let i = &self.intents;
+        let buttons = [
+            (i.left_pressed, LEFT_PRESSED),
+            (i.left_down, LEFT_DOWN),
+            (i.right_pressed, RIGHT_PRESSED),
+            (i.e_pressed, E_PRESSED),
+            (i.advance_pressed, ADVANCE_PRESSED),
+        ]
+        .iter()
+        .filter(|(on, _)| *on)
+        .fold(0, |bits, (_, bit)| bits | bit);
+        self.dt.save(w);
+        i.mouse_world.save(w);
+        i.move_dir.save(w);
+        buttons.save(w);
+    }
+
+    fn load(r: &mut SaveReader) -> Result<Self, SaveError> {
+        let dt = f32::load(r)?;
+        let mouse_world = Vec2::load(r)?;
+        let move_dir = Vec2::load(r)?;
+        let buttons = u32::load(r)?;
+        Ok(ReplayFrame {
+            dt,
+            intents: PlayerIntents {
+                left_pressed: buttons & LEFT_PRESSED != 0,
+                left_down: buttons & LEFT_DOWN != 0,
+                right_pressed: buttons & RIGHT_PRESSED != 0,
+                e_pressed: buttons & E_PRESSED != 0,
+                mouse_world,
+                move_dir,
+                advance_pressed: buttons & ADVANCE_PRESSED != 0,
+            },
+        })
+    }
+}
+
+/// A recorded floor attempt.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Replay {
+    /// The floor's id (not its level index, which shifts when floors are
+    /// added).
+    pub floor_id: usize,
+    /// World RNG state the floor was built from.
+    pub seed: u32,
+    pub frames: Vec<ReplayFrame>,
+}
+
+impl Replay {
+    pub fn new(floor_id: usize, seed: u32) -> Self {
+        Replay {
+            floor_id,
+            seed,
+            frames: Vec::new(),
+        }
+    }
+
+    /// Append one frame.
+    pub fn record(&mut self, dt: f32, intents: PlayerIntents) {
+        self.frames.push(ReplayFrame { dt, intents });
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut w = SaveWriter::new();
+        w.token(MAGIC);
+        w.token(REPLAY_VERSION);
+        self.floor_id.save(&mut w);
+        self.seed.save(&mut w);
+        self.frames.save(&mut w);
+        w.finish()
+    }
+
+    pub fn parse(text: &str) -> Result<Self, SaveError> {
+        let mut r = SaveReader::new(text);
+        if r.token().ok() != Some(MAGIC) {
+            return Err(SaveError::BadHeader);
+        }
+        let version: u32 = r.parse()?;
+        if version != REPLAY_VERSION {
+            return Err(SaveError::UnsupportedVersion(version));
+        }
+        let replay = Replay {
+            floor_id: usize::load(&mut r)?,
+            seed: u32::load(&mut r)?,
+            frames: Vec::load(&mut r)?,
+        };
+        floor_by_id(replay.floor_id)?;
+        if !r.at_end() {
+            return Err(SaveError::Corrupt("trailing data"));
+        }
+        Ok(replay)
+    }
+}
+
+impl Simulation {
+    /// Re-run a recording from its floor start, frame by frame, and hand
+    /// back the floor as it ended.
+    pub fn play_replay(replay: &Replay) -> Result<FloorRun, SaveError> {
+        let level = crate::levels::level_index_for_floor_id(replay.floor_id)
+            .ok_or(SaveError::UnknownFloor(replay.floor_id))?;
+        let mut run = FloorRun::start(level, replay.seed);
+        for frame in &replay.frames {
+            run.frame(&frame.intents, frame.dt);
+        }
+        Ok(run)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::levels::floor_def;
+    use crate::save::save_world;
+
+    fn world_text(run: &FloorRun) -> String {
+        let mut w = SaveWriter::new();
+        save_world(&run.sim.world, &mut w);
+        w.finish()
+    }
+
+    /// A scripted player: walk toward the nearest rogue, shoot or punch it
+    /// when close, click through conversations.
+    fn scripted(run: &mut FloorRun, frame: usize) -> PlayerIntents {
+        let me = run.sim.player_position().unwrap_or(Vec2::zero());
+        let target = run
+            .sim
+            .nearest_alive_enemy(me)
+            .map(|(_, p)| p)
+            .unwrap_or(Vec2::new(500.0, 500.0));
+        let d = target - me;
+        PlayerIntents {
+            left_pressed: frame.is_multiple_of(7),
+            left_down: d.length() < 200.0,
+            right_pressed: false,
+            e_pressed: frame.is_multiple_of(90),
+            mouse_world: target,
+            move_dir: Vec2::new(d.x.signum(), d.y.signum()),
+            advance_pressed: frame.is_multiple_of(20),
+        }
+    }
+
+    #[test]
+    fn test_replay_round_trips_and_reruns_bit_exact() {
+        let level = 3;
+        let seed = 987_654;
+        let mut live = FloorRun::start(level, seed);
+        let mut replay = Replay::new(floor_def(level).id, seed);
+        for frame in 0..900 {
+            let intents = scripted(&mut live, frame);
+            // Uneven frame times, as a browser produces.
+            let dt = if frame.is_multiple_of(5) {
+                0.021
+            } else {
+                1.0 / 60.0
+            };
+            replay.record(dt, intents);
+            live.frame(&intents, dt);
+        }
+        // The script actually played: it left the car and fought.
+        assert_ne!(
+            live.sim.player_position(),
+            Some(floor_def(level).player_spawn())
+        );
+        assert!(live.sim.enemies_alive() < live.sim.enemy_count());
+
+        let text = replay.to_text();
+        let parsed = Replay::parse(&text).expect("parses");
+        assert_eq!(parsed, replay);
+
+        let rerun = Simulation::play_replay(&parsed).expect("known floor");
+        assert_eq!(world_text(&rerun), world_text(&live));
+        assert_eq!(rerun.scenario.time(), live.scenario.time());
+        assert_eq!(rerun.frames, 900);
+    }
+
+    #[test]
+    fn test_rejects_bad_replays() {
+        assert_eq!(Replay::parse("OMSAVE 1").err(), Some(SaveError::BadHeader));
+        assert_eq!(
+            Replay::parse("OMREPLAY 1 0 0 0").err(),
+            Some(SaveError::UnsupportedVersion(1))
+        );
+        assert_eq!(
+            Replay::parse("OMREPLAY 2 999 0 0").err(),
+            Some(SaveError::UnknownFloor(999))
+        );
+        assert_eq!(
+            Replay::parse("OMREPLAY 2 0 0 2 0.016 0 0 0 0 0").err(),
+            Some(SaveError::UnexpectedEnd)
+        );
+    }
+}
@@ -0,0 +1,424 @@
+//! Headless floor and campaign runs, summed up as a JSON report — what the
+//! `open-miami-sim` binary prints, so a floor can be checked from a terminal.
+//!
+//! Two drivers: the campaign bot ([`FloorRun::bot_frame`]), which plays a
+//! floor through its scenario until it extracts (retrying a floor it dies
+//! on, and carrying the RNG from one floor to the next like the browser
+//! does), and a recorded [`Replay`], re-run frame by frame through
+//! [`FloorRun::frame`].
+
+use crate::components::GameEvent;
+use crate::levels::{floor_def, floor_title, level_index_for_floor_id};
+use crate::replay::Replay;
+use crate::save::SaveError;
+use crate::score::{FloorScore, Score};
+use crate::sim::FloorRun;
+use crate::stats::FloorStats;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// How a floor ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// The player extracted.
+    Cleared,
+    /// The player died (the bot on its last attempt).
+    Died,
+    /// Out of frames.
+    Timeout,
+    /// A replay that ran out of frames with the player alive on the floor.
+    Incomplete,
+}
+
+impl Outcome {
+    pub fn name(self) -> &'static str {
+        match self {
+            Outcome::Cleared => "cleared",
+            Outcome::Died => "died",
+            Outcome::Timeout => "timeout",
+            Outcome::Incomplete => "incomplete",
+        }
+    }
+}
+
+/// Knobs for a headless run.
+#[derive(Debug, Clone, Copy)]
+pub struct RunOptions {
+    /// Seconds per frame.
+    pub dt: f32,
+    /// Frame budget per floor attempt (bot only).
+    pub max_frames: usize,
+    /// Tries the bot gets per floor before the run stops there.
+    pub attempts: usize,
+}
+
+impl Default for RunOptions {
+    fn default() -> Self {
+        RunOptions {
+            dt: 1.0 / 60.0,
+            max_frames: 60 * 60 * 5,
+            attempts: 3,
+        }
+    }
+}
+
+/// One floor of a run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FloorReport {
+    pub floor_id: usize,
+    pub title: String,
+    pub outcome: Outcome,
+    pub attempts: usize,
+    /// Frames over every attempt.
+    pub frames: usize,
+    /// Simulated seconds over every attempt.
+    pub seconds: f32,
+    pub kills: usize,
+    pub deaths: usize,
+    /// The floor's score over every attempt (see [`Score::retry`]).
+    pub score: FloorScore,
+    /// The floor's statistics over every attempt.
+    pub stats: FloorStats,
+    /// Gameplay events by [`GameEvent::name`].
+    pub events: BTreeMap<&'static str, usize>,
+}
+
+impl FloorReport {
+    fn new(level: usize) -> Self {
+        FloorReport {
+            floor_id: floor_def(level).id,
+            title: floor_title(level),
+            outcome: Outcome::Timeout,
+            attempts: 0,
+            frames: 0,
+            seconds: 0.0,
+            kills: 0,
+            deaths: 0,
+            score: Score::new().finish(),
+            stats: FloorStats::new(),
+            events: BTreeMap::new(),
+        }
+    }
+
+    fn count(&mut self, events: &[GameEvent]) {
+        for event in events {
+            *self.events.entry(event.name()).or_default() += 1;
+            if *event == GameEvent::EnemyDown {
+                self.kills += 1;
+            }
+        }
+    }
+}
+
+/// A whole run: one or more floors in play order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunReport {
+    /// `"bot"` or `"replay"`.
+    pub driver: &'static str,
+    /// RNG state the first floor was built from.
+    pub seed: u32,
+    pub floors: Vec<FloorReport>,
+}
+
+impl RunReport {
+    /// The run's outcome: the first floor that was not cleared decides it.
+    pub fn outcome(&self) -> Outcome {
+        self.floors
+            .iter()
+            .map(|f| f.outcome)
+            .find(|&o| o != Outcome::Cleared)
+            .unwrap_or(Outcome::Cleared)
+    }
+
+    pub fn frames(&self) -> usize {
+        self.floors.iter().map(|f| f.frames).sum()
+    }
+
+    pub fn kills(&self) -> usize {
+        self.floors.iter().map(|f| f.kills).sum()
+    }
+
+    pub fn deaths(&self) -> usize {
+        self.floors.iter().map(|f| f.deaths).sum()
+    }
+
+    /// Every floor's score total.
+    pub fn score(&self) -> u32 {
+        self.floors.iter().map(|f| f.score.total).sum()
+    }
+
+    /// Every floor's statistics summed.
+    pub fn stats(&self) -> FloorStats {
+        let mut all = FloorStats::new();
+        for f in &self.floors {
+            all.add(&f.stats);
+        }
+        all
+    }
+
+    pub fn events(&self) -> BTreeMap<&'static str, usize> {
+        let mut all = BTreeMap::new();
+        for (&name, &n) in self.floors.iter().flat_map(|f| &f.events) {
+            *all.entry(name).or_default() += n;
+        }
+        all
+    }
+
+    /// The report as one JSON object (keys in a fixed order).
+    pub fn to_json(&self) -> String {
+        let mut out = String::new();
+        let _ = write!(
+            out,
+            "{{\"driver\":{},\"seed\":{},\"outcome\":{},\"frames\":{},\"seconds\":{},\
+             \"kills\":{},\"deaths\":{},\"score\":{},\"stats\":{},\"events\":{},\"floors\":[",
+            json_string(self.driver),
+            self.seed,
+            json_string(self.outcome().name()),
+            self.frames(),
+            self.floors.iter().map(|f| f.seconds).sum::<f32>(),
+            self.kills(),
+            self.deaths(),
+            self.score(),
+            json_stats(&self.stats()),
+            json_counts(&self.events()),
+        );
+        for (i, f) in self.floors.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            let _ = write!(
+                out,
+                "{{\"floor\":{},\"title\":{},\"outcome\":{},\"attempts\":{},\"frames\":{},\
+                 \"seconds\":{},\"kills\":{},\"deaths\":{},\"score\":{},\"grade\":{},\
+                 \"stats\":{},\"events\":{}}}",
+                f.floor_id,
+                json_string(&f.title),
+                json_string(f.outcome.name()),
+                f.attempts,
+                f.frames,
+                f.seconds,
+                f.kills,
+                f.deaths,
+                f.score.total,
+                json_string(f.score.grade.letter()),
+                json_stats(&f.stats),
+                json_counts(&f.events),
+            );
+        }
+        out.push_str("]}");
+        out
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Shots, hits, accuracy (`null` before the first shot), finishers,
+/// throws, checkpoint restores and kills by weapon.
+fn json_stats(stats: &FloorStats) -> String {
+    let kills_by = stats
+        .kills_by
+        .iter()
+        .map(|(&weapon, &n)| (weapon, n as usize))
+        .collect();
+    format!(
+        "{{\"shots\":{},\"hits\":{},\"accuracy\":{},\"finishers\":{},\"throws\":{},\
+         \"restores\":{},\"kills_by\":{}}}",
+        stats.shots,
+        stats.hits,
+        stats
+            .accuracy()
+            .map_or_else(|| "null".to_string(), |a| a.to_string()),
+        stats.finishers,
+        stats.throws,
+        stats.restores,
+        json_counts(&kills_by),
+    )
+}
+
+fn json_counts(counts: &BTreeMap<&'static str, usize>) -> String {
+    let fields: Vec<String> = counts
+        .iter()
+        .map(|(name, n)| format!("{}:{n}", json_string(name)))
+        .collect();
+    format!("{{{}}}", fields.join(","))
+}
+
+/// Play `levels` in order with the bot, starting from `seed`. A floor gets
+/// `opts.attempts` tries; the run stops at the first floor not cleared.
+/// `pace` is called after every frame with its `dt` (the binary sleeps in
+/// it to run at a chosen speed).
+pub fn run_bot(
+    levels: &[usize],
+    seed: u32,
+    opts: &RunOptions,
+    pace: &mut dyn FnMut(f32),
+) -> RunReport {
+    let mut report = RunReport {
+        driver: "bot",
+        seed,
+        floors: Vec::new(),
+    };
+    let mut seed = seed;
+    for &level in levels {
+        let mut floor = FloorReport::new(level);
+        let mut score = Score::new();
+        let mut stats = FloorStats::new();
+        while floor.attempts < opts.attempts.max(1) {
+            floor.attempts += 1;
+            let mut run = FloorRun::start(level, seed);
+            // A retry keeps the deaths and the clock (the bot has no
+            // checkpoints: every attempt starts the floor over).
+            run.score = score.retry(None);
+            run.sim.stats = stats;
+            floor.outcome = Outcome::Timeout;
+            for _ in 0..opts.max_frames {
+                run.bot_frame(opts.dt);
+                floor.frames += 1;
+                floor.seconds += opts.dt;
+                floor.count(run.sim.frame_events());
+                pace(opts.dt);
+                if run.extracted.is_some() {
+                    floor.outcome = Outcome::Cleared;
+                    break;
+                }
+                if !run.sim.player_alive() {
+                    floor.outcome = Outcome::Died;
+                    floor.deaths += 1;
+                    break;
+                }
+            }
+            // The next attempt (or floor) carries the RNG on.
+            seed = run.sim.world.rng_state();
+            floor.score = run.score.finish();
+            floor.stats = run.sim.stats.clone();
+            score = run.score;
+            stats = run.sim.stats;
+            if floor.outcome != Outcome::Died {
+                break;
+            }
+        }
+        let cleared = floor.outcome == Outcome::Cleared;
+        report.floors.push(floor);
+        if !cleared {
+            break;
+        }
+    }
+    report
+}
+
+/// Re-run a recorded floor attempt (see [`crate::replay`]) and report it.
+pub fn run_replay(replay: &Replay, pace: &mut dyn FnMut(f32)) -> Result<RunReport, SaveError> {
+    let level = level_index_for_floor_id(replay.floor_id)
+        .ok_or(SaveError::UnknownFloor(replay.floor_id))?;
+    let mut run = FloorRun::start(level, replay.seed);
+    let mut floor = FloorReport::new(level);
+    floor.attempts = 1;
+    for frame in &replay.frames {
+        run.frame(&frame.intents, frame.dt);
+        floor.frames += 1;
+        floor.seconds += frame.dt;
+        floor.count(run.sim.frame_events());
+        pace(frame.dt);
+    }
+    floor.outcome = if run.extracted.is_some() {
+        Outcome::Cleared
+    } else if !run.sim.player_alive() {
+        floor.deaths = 1;
+        Outcome::Died
+    } else {
+        Outcome::Incomplete
+    };
+    floor.score = run.score.finish();
+    floor.stats = run.sim.stats;
+    Ok(RunReport {
+        driver: "replay",
+        seed: replay.seed,
+        floors: vec![floor],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::PlayerIntents;
+    use crate::math::Vec2;
+
+    #[test]
+    fn test_bot_report_counts_and_serializes() {
+        let opts = RunOptions {
+            max_frames: 600,
+            attempts: 2,
+            ..RunOptions::default()
+        };
+        let mut paced = 0;
+        let report = run_bot(&[1], 42, &opts, &mut |_| paced += 1);
+        let floor = &report.floors[0];
+        assert_eq!(paced, report.frames());
+        assert_eq!(
+            floor.kills,
+            floor.events.get("EnemyDown").copied().unwrap_or(0)
+        );
+        assert!(floor.attempts >= 1 && floor.attempts <= 2);
+        assert_eq!(
+            floor.deaths,
+            floor.attempts - usize::from(floor.outcome != Outcome::Died)
+        );
+        // The score carries every attempt's deaths.
+        assert_eq!(floor.score.deaths as usize, floor.deaths);
+        assert!(floor.score.kills as usize <= floor.kills);
+        assert_eq!(floor.stats.deaths as usize, floor.deaths);
+        assert!(floor.stats.kills() as usize <= floor.kills);
+        assert_eq!(
+            floor.stats.throws as usize,
+            floor.events.get("Throw").copied().unwrap_or(0)
+        );
+
+        let json = report.to_json();
+        assert!(json.starts_with("{\"driver\":\"bot\",\"seed\":42,\"outcome\":"));
+        assert!(json.contains("\"title\":\"FLOOR 1\""));
+        assert!(json.contains(&format!("\"grade\":\"{}\"", floor.score.grade.letter())));
+        assert!(json.ends_with("}]}"));
+        // Same seed, same run.
+        assert_eq!(run_bot(&[1], 42, &opts, &mut |_| ()), report);
+    }
+
+    #[test]
+    fn test_replay_report() {
+        let mut replay = Replay::new(floor_def(2).id, 7);
+        let idle = PlayerIntents {
+            left_pressed: false,
+            left_down: false,
+            right_pressed: false,
+            e_pressed: false,
+            mouse_world: Vec2::zero(),
+            move_dir: Vec2::zero(),
+            advance_pressed: false,
+        };
+        for _ in 0..30 {
+            replay.record(0.5 / 30.0, idle);
+        }
+        let report = run_replay(&replay, &mut |_| ()).unwrap();
+        assert_eq!(report.driver, "replay");
+        assert_eq!(report.frames(), 30);
+        assert_eq!(report.outcome(), Outcome::Incomplete);
+    }
+
+    #[test]
+    fn test_json_string_escapes() {
+        assert_eq!(json_string("a\"b\\c\nd½"), "\"a\\\"b\\\\c\\u000ad½\"");
+    }
+}
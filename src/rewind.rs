@@ -0,0 +1,353 @@
+//! Rewind buffer for time-travel debugging (`?debug`, T): a ring of world +
+//! scenario snapshots taken every few frames over the last half minute of
+//! play, to scrub back and forth through, resume from, or compare one
+//! entity's components between two moments.
+//!
+//! Memory stays bounded by delta compression. Every
+//! [`KEYFRAME_EVERY`]th snapshot is a full clone; the ones in between keep
+//! only the component storages written since the snapshot before (see
+//! [`World::delta_since`]), plus the small rest of the world whole. A
+//! snapshot is rebuilt by patching its keyframe with the deltas up to it, so
+//! it is the world as it was, resources and all; the ring drops whole
+//! keyframe groups from the front so every kept delta still has its
+//! keyframe.
+
+use crate::ecs::{Entity, World, WorldDelta};
+use crate::save::{SaveError, WorldRows, COMPONENTS};
+use crate::scenario::ScenarioState;
+use std::collections::VecDeque;
+
+/// Snapshots per keyframe group (one full clone, the rest deltas).
+pub const KEYFRAME_EVERY: usize = 30;
+
+enum Body {
+    Key(Box<World>),
+    Delta(Box<WorldDelta>),
+}
+
+struct Snapshot {
+    /// Frames recorded before this one.
+    frame: usize,
+    /// Seconds of play recorded before this one.
+    time: f32,
+    /// The world's change tick when taken: the next delta keeps the storages
+    /// written since.
+    tick: u64,
+    scenario: ScenarioState,
+    body: Body,
+}
+
+/// One component of an entity that differs between two snapshots (see
+/// [`RewindBuffer::entity_diff`]); `None` = the entity did not have it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComponentChange {
+    pub component: &'static str,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+/// The ring of snapshots.
+pub struct RewindBuffer {
+    /// Frames between snapshots.
+    interval: usize,
+    /// Snapshots kept (at least; whole groups are dropped past it).
+    capacity: usize,
+    snapshots: VecDeque<Snapshot>,
+    frame: usize,
+    time: f32,
+}
+
+impl Default for RewindBuffer {
+    /// Ten snapshots a second (at 60 FPS) over the last thirty seconds.
+    fn default() -> Self {
+        RewindBuffer::new(6, 300)
+    }
+}
+
+impl RewindBuffer {
+    pub fn new(interval: usize, capacity: usize) -> Self {
+        RewindBuffer {
+            interval: interval.max(1),
+            capacity: capacity.max(1),
+            snapshots: VecDeque::new(),
+            frame: 0,
+            time: 0.0,
+        }
+    }
+
+    /// Forget everything (a new floor).
+    pub fn clear(&mut self) {
+        *self = RewindBuffer::new(self.interval, self.capacity);
+    }
+
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+
+    /// Call once per frame, after the tick: every `interval`th frame is
+    /// snapshotted.
+    pub fn record(&mut self, world: &World, scenario: &ScenarioState, dt: f32) {
+        if self.frame.is_multiple_of(self.interval) {
+            self.push(world, scenario);
+        }
+        self.frame += 1;
+        self.time += dt;
+    }
+
+    fn push(&mut self, world: &World, scenario: &ScenarioState) {
+        let group_start = self.snapshots.len().is_multiple_of(KEYFRAME_EVERY);
+        let body = match self.snapshots.back() {
+            Some(last) if !group_start => Body::Delta(Box::new(world.delta_since(last.tick))),
+            _ => Body::Key(Box::new(world.clone())),
+        };
+        self.snapshots.push_back(Snapshot {
+            frame: self.frame,
+            time: self.time,
+            tick: world.change_tick(),
+            scenario: scenario.clone(),
+            body,
+        });
+        // Keep groups whole: drop the oldest one once the rest still cover
+        // the capacity (groups are aligned, so the front is a keyframe).
+        while self.snapshots.len() >= self.capacity + KEYFRAME_EVERY {
+            self.snapshots.drain(..KEYFRAME_EVERY);
+        }
+    }
+
+    /// Frames recorded before snapshot `i` (oldest first).
+    pub fn frame_at(&self, i: usize) -> Option<usize> {
+        self.snapshots.get(i).map(|s| s.frame)
+    }
+
+    /// Seconds from snapshot `i` to now.
+    pub fn age_of(&self, i: usize) -> Option<f32> {
+        self.snapshots.get(i).map(|s| self.time - s.time)
+    }
+
+    fn key_of(&self, i: usize) -> usize {
+        i - i % KEYFRAME_EVERY
+    }
+
+    /// Snapshot `i`'s world: its keyframe patched with the deltas up to it.
+    fn world_at(&self, i: usize) -> Result<World, SaveError> {
+        if i >= self.snapshots.len() {
+            return Err(SaveError::Corrupt("no such snapshot"));
+        }
+        let key = self.key_of(i);
+        let mut world = match &self.snapshots[key].body {
+            Body::Key(world) => (**world).clone(),
+            Body::Delta(_) => return Err(SaveError::Corrupt("group without a keyframe")),
+        };
+        for snapshot in self.snapshots.range(key + 1..=i) {
+            if let Body::Delta(delta) = &snapshot.body {
+                world.apply_delta(delta);
+            }
+        }
+        Ok(world)
+    }
+
+    /// Snapshot `i`'s component rows.
+    pub fn rows_at(&self, i: usize) -> Result<WorldRows, SaveError> {
+        self.world_at(i).map(|world| WorldRows::of(&world))
+    }
+
+    /// Snapshot `i`'s world and scenario, to look at or play on from. Hand
+    /// the world to [`World::restore`] to resume the live one from it.
+    pub fn snapshot(&self, i: usize) -> Result<(World, ScenarioState), SaveError> {
+        let world = self.world_at(i)?;
+        Ok((world, self.snapshots[i].scenario.clone()))
+    }
+
+    /// Play resumes from snapshot `i`: every later snapshot belongs to the
+    /// abandoned timeline and goes.
+    pub fn truncate_after(&mut self, i: usize) -> Result<(), SaveError> {
+        let snapshot = self
+            .snapshots
+            .get(i)
+            .ok_or(SaveError::Corrupt("no such snapshot"))?;
+        self.frame = snapshot.frame + 1;
+        self.time = snapshot.time;
+        self.snapshots.truncate(i + 1);
+        Ok(())
+    }
+
+    /// `entity`'s components that differ between snapshots `a` and `b`, in
+    /// registry order.
+    pub fn entity_diff(
+        &self,
+        a: usize,
+        b: usize,
+        entity: Entity,
+    ) -> Result<Vec<ComponentChange>, SaveError> {
+        let (before, after) = (self.rows_at(a)?, self.rows_at(b)?);
+        let value = |rows: &WorldRows, i: usize| {
+            rows.rows
+                .get(&(i, entity.index()))
+                .filter(|(e, _)| *e == entity)
+                .map(|(_, text)| text.clone())
+        };
+        Ok(COMPONENTS
+            .iter()
+            .enumerate()
+            .filter_map(|(i, codec)| {
+                let (before, after) = (value(&before, i), value(&after, i));
+                (before != after).then_some(ComponentChange {
+                    component: codec.name,
+                    before,
+                    after,
+                })
+            })
+            .collect())
+    }
+
+    /// `entity`'s components at snapshot `i`, as (name, save text).
+    pub fn entity_rows(
+        &self,
+        i: usize,
+        entity: Entity,
+    ) -> Result<Vec<(&'static str, String)>, SaveError> {
+        let rows = self.rows_at(i)?;
+        Ok(COMPONENTS
+            .iter()
+            .enumerate()
+            .filter_map(|(c, codec)| {
+                rows.rows
+                    .get(&(c, entity.index()))
+                    .filter(|(e, _)| *e == entity)
+                    .map(|(_, text)| (codec.name, text.clone()))
+            })
+            .collect())
+    }
+
+    /// Component rows held by the snapshots, against what full clones would
+    /// hold (how well the compression does).
+    pub fn stored_rows(&self) -> (usize, usize) {
+        let mut stored = 0;
+        let mut full = 0;
+        for (i, snapshot) in self.snapshots.iter().enumerate() {
+            stored += match &snapshot.body {
+                Body::Key(world) => world.row_count(),
+                Body::Delta(delta) => delta.row_count(),
+            };
+            full += self.world_at(i).map_or(0, |world| world.row_count());
+        }
+        (stored, full)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{DebugPath, Position};
+    use crate::ecs::Vec2;
+    use crate::levels::floor_def;
+    use crate::save::save_run;
+    use crate::sim::FloorRun;
+
+    const DT: f32 = 1.0 / 60.0;
+
+    fn played(frames: usize, buffer: &mut RewindBuffer) -> FloorRun {
+        let mut run = FloorRun::start(3, 4242);
+        for _ in 0..frames {
+            run.bot_frame(DT);
+            buffer.record(&run.sim.world, &run.scenario, DT);
+        }
+        run
+    }
+
+    #[test]
+    fn test_snapshots_rebuild_exactly() {
+        let mut buffer = RewindBuffer::new(5, 1000);
+        let mut worlds = Vec::new();
+        let mut run = FloorRun::start(3, 4242);
+        let player = run.sim.player().unwrap();
+        for frame in 0..200 {
+            run.bot_frame(DT);
+            // State no save carries: a resource, and a debug-only component
+            // that turns up in the middle of a keyframe group.
+            let world = &mut run.sim.world;
+            world.insert_resource(frame as u32);
+            if frame == 42 {
+                world.add_component(player, DebugPath::new(vec![], Vec2::new(1.0, 2.0)));
+            }
+            buffer.record(world, &run.scenario, DT);
+            if frame % 5 == 0 {
+                worlds.push((world.clone(), save_run(world, &run.scenario)));
+            }
+        }
+        assert_eq!(buffer.len(), 40);
+        assert_eq!(buffer.frame_at(7), Some(35));
+        for i in [0, 1, 8, 9, 17, 29, 30, 31, 39] {
+            let (world, scenario) = buffer.snapshot(i).unwrap();
+            let (expected, text) = &worlds[i];
+            assert_eq!(save_run(&world, &scenario), *text, "snapshot {i}");
+            assert_eq!(world.entity_slots(), expected.entity_slots());
+            assert_eq!(world.change_tick(), expected.change_tick());
+            assert_eq!(world.row_count(), expected.row_count());
+            assert_eq!(world.resource::<u32>(), expected.resource::<u32>());
+            assert_eq!(
+                world.get_component::<DebugPath>(player),
+                expected.get_component::<DebugPath>(player),
+                "snapshot {i}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_ring_keeps_whole_groups_and_compresses() {
+        let mut buffer = RewindBuffer::new(1, 60);
+        let run = played(300, &mut buffer);
+        assert!(buffer.len() >= 60 && buffer.len() < 60 + KEYFRAME_EVERY);
+        assert_eq!(buffer.frame_at(buffer.len() - 1), Some(299));
+        // The oldest kept snapshot still rebuilds.
+        buffer.snapshot(0).unwrap();
+        let (newest, _) = buffer.snapshot(buffer.len() - 1).unwrap();
+        assert_eq!(WorldRows::of(&newest), WorldRows::of(&run.sim.world));
+        // Movers rewrite their storages every frame; doors, zones, pickups
+        // and the rest of the floor's furniture only come with keyframes.
+        let (stored, full) = buffer.stored_rows();
+        assert!(
+            stored * 3 < full * 2,
+            "deltas store {stored} of {full} rows"
+        );
+    }
+
+    #[test]
+    fn test_resume_from_a_snapshot_forks_the_timeline() {
+        let mut buffer = RewindBuffer::new(10, 1000);
+        let mut run = played(120, &mut buffer);
+        let (world, scenario) = buffer.snapshot(4).unwrap();
+        run.sim.world.restore(&world);
+        run.scenario = scenario;
+        buffer.truncate_after(4).unwrap();
+        assert_eq!(buffer.len(), 5);
+        for _ in 0..30 {
+            run.bot_frame(DT);
+            buffer.record(&run.sim.world, &run.scenario, DT);
+        }
+        assert_eq!(buffer.len(), 8);
+        assert_eq!(buffer.frame_at(5), Some(50));
+        let (newest, _) = buffer.snapshot(7).unwrap();
+        assert_eq!(WorldRows::of(&newest), WorldRows::of(&run.sim.world));
+        assert_eq!(run.scenario.floor().id, floor_def(3).id);
+    }
+
+    #[test]
+    fn test_entity_diff_names_what_moved() {
+        let mut buffer = RewindBuffer::new(30, 1000);
+        let run = played(120, &mut buffer);
+        let player = run.sim.player().unwrap();
+        let diff = buffer.entity_diff(0, 3, player).unwrap();
+        assert!(diff.iter().any(|c| c.component == "Position"), "{diff:?}");
+        assert!(buffer.entity_diff(2, 2, player).unwrap().is_empty());
+        let rows = buffer.entity_rows(3, player).unwrap();
+        let (_, pos) = rows.iter().find(|(name, _)| *name == "Position").unwrap();
+        let at = buffer.snapshot(3).unwrap().0;
+        let p = at.get_component::<Position>(player).unwrap();
+        assert!(pos.starts_with(&p.x.to_string()), "{pos} vs {p:?}");
+    }
+}
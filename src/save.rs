@@ -0,0 +1,942 @@
+//! Versioned save games: a whole [`World`] — the entity handle space, every
+//! gameplay component, walls and RNG streams — plus the floor's
+//! [`ScenarioState`], written to a compact text blob and loaded back.
+//!
+//! The browser keeps the latest run here through the `setSetting` /
+//! localStorage bridge ("continue run"); tests can load a save file the same
+//! way ([`load_run`]).
+//!
+//! Format: a `OMSAVE <version>` header, then whitespace-separated tokens.
+//! Numbers are written in Rust's shortest round-trip form, enum variants by
+//! name, strings as `<byte length>:<bytes>`. Components go through the
+//! [`COMPONENTS`] registry, one section per type that has any rows; a save
+//! naming a component this build does not know is rejected rather than
+//! half-loaded. Bump [`SAVE_VERSION`] whenever a registered type changes
+//! shape.
+//!
+//! Not saved: pending events and deferred commands (saves are taken between
+//! frames, after both are flushed), change-detection ticks, debug-only
+//! components, and resources — those are derived from the walls and rebuilt
+//! on first use.
+
+use crate::components::*;
+use crate::ecs::{Component, Entity, RngStream, RngStreams, Wall, WallMaterial, World};
+use crate::levels::{floor_def, level_index_for_floor_id};
+use crate::math::Vec2;
+use crate::scenario::{
+    ElevatorKind, FloorDef, GateDef, GateInput, HoldDef, LookAtDef, ScenarioState, TalkDef,
+};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashSet, VecDeque};
+use std::fmt;
+
+/// Current format version. Older (or newer) saves are refused.
+pub const SAVE_VERSION: u32 = 8;
+
+const MAGIC: &str = "OMSAVE";
+
+/// Why a save could not be loaded.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SaveError {
+    /// Not a save at all (missing `OMSAVE` header).
+    BadHeader,
+    UnsupportedVersion(u32),
+    UnexpectedEnd,
+    /// A token that does not parse as what was expected there.
+    BadToken(String),
+    UnknownComponent(String),
+    UnknownFloor(usize),
+    /// Parsed fine but inconsistent (a dangling handle, a table of the wrong
+    /// length for its floor...).
+    Corrupt(&'static str),
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveError::BadHeader => write!(f, "not a save file"),
+            SaveError::UnsupportedVersion(v) => {
+                write!(f, "save version {v} (this build reads {SAVE_VERSION})")
+            }
+            SaveError::UnexpectedEnd => write!(f, "save file truncated"),
+            SaveError::BadToken(t) => write!(f, "unexpected `{t}` in save file"),
+            SaveError::UnknownComponent(name) => write!(f, "unknown component `{name}`"),
+            SaveError::UnknownFloor(id) => write!(f, "unknown floor {id}"),
+            SaveError::Corrupt(what) => write!(f, "corrupt save: {what}"),
+        }
+    }
+}
+
+/// Token sink for [`Persist::save`].
+#[derive(Default)]
+pub struct SaveWriter {
+    out: String,
+}
+
+impl SaveWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn token(&mut self, value: impl fmt::Display) {
+        if !self.out.is_empty() {
+            self.out.push(' ');
+        }
+        self.out.push_str(&value.to_string());
+    }
+
+    pub fn string(&mut self, s: &str) {
+        self.token(format_args!("{}:{s}", s.len()));
+    }
+
+    pub fn finish(self) -> String {
+        self.out
+    }
+}
+
+/// Token source for [`Persist::load`].
+pub struct SaveReader<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> SaveReader<'a> {
+    pub fn new(src: &'a str) -> Self {
+        SaveReader { src, pos: 0 }
+    }
+
+    fn skip_space(&mut self) {
+        let rest = &self.src[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    pub fn token(&mut self) -> Result<&'a str, SaveError> {
+        self.skip_space();
+        let rest = &self.src[self.pos..];
+        let len = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        if len == 0 {
+            return Err(SaveError::UnexpectedEnd);
+        }
+        self.pos += len;
+        Ok(&rest[..len])
+    }
+
+    pub fn parse<T: std::str::FromStr>(&mut self) -> Result<T, SaveError> {
+        let token = self.token()?;
+        token
+            .parse()
+            .map_err(|_| SaveError::BadToken(token.to_string()))
+    }
+
+    pub fn string(&mut self) -> Result<&'a str, SaveError> {
+        self.skip_space();
+        let rest = &self.src[self.pos..];
+        let colon = rest.find(':').ok_or(SaveError::UnexpectedEnd)?;
+        let bad_len = || SaveError::BadToken(rest[..colon].to_string());
+        let len: usize = rest[..colon].parse().map_err(|_| bad_len())?;
+        // A corrupt prefix can be any size: never let it wrap.
+        let end = (colon + 1).checked_add(len).ok_or_else(bad_len)?;
+        let s = rest.get(colon + 1..end).ok_or(SaveError::UnexpectedEnd)?;
+        self.pos += end;
+        Ok(s)
+    }
+
+    /// Whether everything was consumed (trailing whitespace aside).
+    pub fn at_end(&self) -> bool {
+        self.src[self.pos..].trim().is_empty()
+    }
+}
+
+/// A value that can be written to and read back from a save.
+pub trait Persist: Sized {
+    fn save(&self, w: &mut SaveWriter);
+    fn load(r: &mut SaveReader) -> Result<Self, SaveError>;
+}
+
+macro_rules! persist_num {
+    ($($ty:ty),+) => {$(
+        impl Persist for $ty {
+            fn save(&self, w: &mut SaveWriter) {
+                w.token(self);
+            }
+
+            fn load(r: &mut SaveReader) -> Result<Self, SaveError> {
+                r.parse()
+            }
+        }
+    )+};
+}
+
+persist_num!(f32, i32, u32, u64, usize);
+
+impl Persist for bool {
+    fn save(&self, w: &mut SaveWriter) {
+        w.token(u8::from(*self));
+    }
+
+    fn load(r: &mut SaveReader) -> Result<Self, SaveError> {
+        match r.token()? {
+            "0" => Ok(false),
+            "1" => Ok(true),
+            other => Err(SaveError::BadToken(other.to_string())),
+        }
+    }
+}
+
+impl Persist for String {
+    fn save(&self, w: &mut SaveWriter) {
+        w.string(self);
+    }
+
+    fn load(r: &mut SaveReader) -> Result<Self, SaveError> {
+        r.string().map(str::to_string)
+    }
+}
+
+/// Names and lines from the floor data (`&'static str` throughout the
+/// components and the scenario) come back interned.
+impl Persist for &'static str {
+    fn save(&self, w: &mut SaveWriter) {
+        w.string(self);
+    }
+
+    fn load(r: &mut SaveReader) -> Result<Self, SaveError> {
+        r.string().map(intern)
+    }
+}
+
+impl<T: Persist> Persist for Option<T> {
+    fn save(&self, w: &mut SaveWriter) {
+        match self {
+            None => w.token('-'),
+            Some(value) => {
+                w.token('+');
+                value.save(w);
+            }
+        }
+    }
+
+    fn load(r: &mut SaveReader) -> Result<Self, SaveError> {
+        match r.token()? {
+            "-" => Ok(None),
+            "+" => T::load(r).map(Some),
+            other => Err(SaveError::BadToken(other.to_string())),
+        }
+    }
+}
+
+impl<T: Persist> Persist for Vec<T> {
+    fn save(&self, w: &mut SaveWriter) {
+        w.token(self.len());
+        for item in self {
+            item.save(w);
+        }
+    }
+
+    fn load(r: &mut SaveReader) -> Result<Self, SaveError> {
+        let len: usize = r.parse()?;
+        (0..len).map(|_| T::load(r)).collect()
+    }
+}
+
+impl<T: Persist> Persist for VecDeque<T> {
+    fn save(&self, w: &mut SaveWriter) {
+        w.token(self.len());
+        for item in self {
+            item.save(w);
+        }
+    }
+
+    fn load(r: &mut SaveReader) -> Result<Self, SaveError> {
+        Vec::load(r).map(VecDeque::from)
+    }
+}
+
+impl Persist for Entity {
+    fn save(&self, w: &mut SaveWriter) {
+        w.token(self.index());
+        w.token(self.generation());
+    }
+
+    fn load(r: &mut SaveReader) -> Result<Self, SaveError> {
+        Ok(Entity::new(r.parse()?, r.parse()?))
+    }
+}
+
+/// `Persist` for a struct, field by field in the listed order (the struct
+/// expression makes the compiler catch a forgotten field).
+macro_rules! persist_struct {
+    ($ty:ident { $($field:ident),* $(,)? }) => {
+        impl $crate::save::Persist for $ty {
+            #[allow(unused_variables)]
+            fn save(&self, w: &mut $crate::save::SaveWriter) {
+                $($crate::save::Persist::save(&self.$field, w);)*
+            }
+
+            #[allow(unused_variables)]
+            fn load(
+                r: &mut $crate::save::SaveReader,
+            ) -> Result<Self, $crate::save::SaveError> {
+                Ok($ty {
+                    $($field: $crate::save::Persist::load(r)?,)*
+                })
+            }
+        }
+    };
+}
+
+/// `Persist` for a fieldless enum, written by variant name.
+macro_rules! persist_enum {
+    ($ty:ident { $($variant:ident),+ $(,)? }) => {
+        impl $crate::save::Persist for $ty {
+            fn save(&self, w: &mut $crate::save::SaveWriter) {
+                w.token(match self {
+                    $($ty::$variant => stringify!($variant),)+
+                });
+            }
+
+            fn load(
+                r: &mut $crate::save::SaveReader,
+            ) -> Result<Self, $crate::save::SaveError> {
+                match r.token()? {
+                    $(stringify!($variant) => Ok($ty::$variant),)+
+                    other => Err($crate::save::SaveError::BadToken(other.to_string())),
+                }
+            }
+        }
+    };
+}
+
+pub(crate) use persist_struct;
+
+persist_struct!(Vec2 { x, y });
+persist_struct!(Position { x, y });
+persist_struct!(Velocity { x, y });
+persist_struct!(Health { current, max });
+persist_struct!(Speed { value });
+persist_struct!(Rotation { angle });
+persist_struct!(Radius { value });
+persist_struct!(Player {});
+persist_struct!(Enemy {});
+persist_struct!(Downed {});
+persist_enum!(EnemyType {
+    Idle,
+    Wandering,
+    Patrolling
+});
+persist_enum!(AIState {
+    Unaware,
+    SpottedUnsure,
+    SurePlayerSeen,
+    Confused,
+    Passive,
+    Idle,
+    Patrol,
+    Chase,
+    Attack,
+});
+persist_enum!(WanderState {
+    Moving,
+    LookingAround,
+    Waiting
+});
+persist_struct!(PassiveAI {
+    walk_to,
+    face,
+    group,
+    target,
+    fidget_timer,
+    fidget_heading,
+    arrived,
+});
+persist_struct!(AI {
+    state,
+    initial_type,
+    spawn_position,
+    last_known_player_position,
+    check_position,
+    detection_range,
+    attack_range,
+    attack_cooldown,
+    attack_timer,
+    aim_timer,
+    state_timer,
+    spot_duration,
+    unsure_check_duration,
+    lost_player_duration,
+    confusion_duration,
+    confusion_look_timer,
+    confusion_looks_remaining,
+    confusion_look_duration,
+    wander_timer,
+    wander_look_timer,
+    wander_state,
+    wander_direction,
+    movement_square_size,
+    passive,
+});
+// By weapons-table id, so a save survives weapons being added to the table.
+impl Persist for WeaponType {
+    fn save(&self, w: &mut SaveWriter) {
+        w.token(self.id());
+    }
+
+    fn load(r: &mut SaveReader) -> Result<Self, SaveError> {
+        let token = r.token()?;
+        WeaponType::from_id(token).ok_or_else(|| SaveError::BadToken(token.to_string()))
+    }
+}
+persist_struct!(WeaponPickup { weapon_type, ammo });
+persist_struct!(ThrownWeapon {
+    weapon_type,
+    ammo,
+    damage,
+    vx,
+    vy,
+    distance_remaining,
+    spin,
+});
+persist_struct!(Boss { enraged, reveal });
+persist_struct!(Stunned {
+    timer,
+    duration,
+    fall_angle
+});
+persist_struct!(Fists { timer });
+persist_struct!(Finisher {
+    target,
+    kind,
+    timer,
+    dir_x,
+    dir_y,
+    hits_done,
+});
+persist_struct!(Knockback { x, y });
+persist_struct!(Weapon {
+    weapon_type,
+    damage,
+    ammo,
+    max_ammo,
+    fire_rate,
+    fire_timer,
+});
+persist_struct!(ProjectileTrail {
+    start,
+    end,
+    lifetime,
+    max_lifetime
+});
+persist_struct!(Bullet {
+    weapon_type,
+    damage,
+    speed,
+    lifetime,
+    max_lifetime,
+    shooter,
+});
+persist_struct!(NavPath {
+    waypoints,
+    target,
+    next,
+    timer,
+    recomputes,
+});
+persist_enum!(ElevatorKind { Lift, Door, Gate });
+persist_enum!(WallMaterial { Solid, Glass });
+persist_struct!(Elevator {
+    id,
+    label,
+    x,
+    y,
+    w,
+    h,
+    is_exit,
+    open,
+    to,
+    dwell,
+    kind,
+});
+persist_struct!(Zone { id, x, y, w, h });
+persist_struct!(Door {
+    x,
+    y,
+    closed,
+    length,
+    swing,
+    spin
+});
+
+persist_enum!(GateInput {
+    Punch,
+    Finish,
+    Pickup,
+    Strike,
+    Fire,
+    Throw
+});
+persist_struct!(GateDef { input, text });
+persist_struct!(TalkDef { who, text });
+persist_struct!(HoldDef {
+    seconds,
+    text,
+    until_comms_idle
+});
+persist_struct!(LookAtDef { x, y, seconds });
+
+impl Persist for FinisherKind {
+    fn save(&self, w: &mut SaveWriter) {
+        match self {
+            FinisherKind::Pound => w.token("Pound"),
+            FinisherKind::Overhead => w.token("Overhead"),
+            FinisherKind::Execute(weapon) => {
+                w.token("Execute");
+                weapon.save(w);
+            }
+        }
+    }
+
+    fn load(r: &mut SaveReader) -> Result<Self, SaveError> {
+        match r.token()? {
+            "Pound" => Ok(FinisherKind::Pound),
+            "Overhead" => Ok(FinisherKind::Overhead),
+            "Execute" => WeaponType::load(r).map(FinisherKind::Execute),
+            other => Err(SaveError::BadToken(other.to_string())),
+        }
+    }
+}
+
+/// One registered component type: its name in the save and how to move its
+/// whole storage in and out.
+pub struct ComponentCodec {
+    pub name: &'static str,
+    save: fn(&World, &mut SaveWriter),
+    load: fn(&mut World, &mut SaveReader) -> Result<(), SaveError>,
+    count: fn(&World) -> usize,
+    rows: RowsFn,
+}
+
+/// Visits every row of one component (see [`ComponentCodec::for_each_row`]).
+type RowsFn = fn(&World, &mut dyn FnMut(Entity, &str));
+
+impl ComponentCodec {
+    /// Every row of this component in `world`, in entity index order, with
+    /// the row's value as save text (what [`crate::state_hash`] hashes).
+    pub fn for_each_row(&self, world: &World, each: &mut dyn FnMut(Entity, &str)) {
+        (self.rows)(world, each)
+    }
+}
+
+fn save_storage<T: Component + Persist>(world: &World, w: &mut SaveWriter) {
+    w.token(world.count::<T>());
+    for (entity, value) in world.query_ref::<(Entity, &T)>() {
+        entity.save(w);
+        value.save(w);
+    }
+}
+
+fn storage_rows<T: Component + Persist>(world: &World, each: &mut dyn FnMut(Entity, &str)) {
+    for (entity, value) in world.query_ref::<(Entity, &T)>() {
+        let mut w = SaveWriter::new();
+        value.save(&mut w);
+        each(entity, &w.out);
+    }
+}
+
+fn load_storage<T: Component + Clone + Persist>(
+    world: &mut World,
+    r: &mut SaveReader,
+) -> Result<(), SaveError> {
+    let rows: usize = r.parse()?;
+    for _ in 0..rows {
+        let entity = Entity::load(r)?;
+        let value = T::load(r)?;
+        if !world.is_alive(entity) {
+            return Err(SaveError::Corrupt("component on a dead entity"));
+        }
+        world.add_component(entity, value);
+    }
+    Ok(())
+}
+
+macro_rules! codec {
+    ($ty:ident) => {
+        ComponentCodec {
+            name: stringify!($ty),
+            save: save_storage::<$ty>,
+            load: load_storage::<$ty>,
+            count: World::count::<$ty>,
+            rows: storage_rows::<$ty>,
+        }
+    };
+}
+
+/// Every component a save carries. Debug-only visualisation components
+/// (`DebugPath`, `DebugTrail`) are left out; they rebuild themselves.
+pub static COMPONENTS: &[ComponentCodec] = &[
+    codec!(Position),
+    codec!(Velocity),
+    codec!(Health),
+    codec!(Speed),
+    codec!(Rotation),
+    codec!(Radius),
+    codec!(Player),
+    codec!(Enemy),
+    codec!(AI),
+    codec!(WeaponPickup),
+    codec!(ThrownWeapon),
+    codec!(Boss),
+    codec!(Stunned),
+    codec!(Fists),
+    codec!(Finisher),
+    codec!(Knockback),
+    codec!(Weapon),
+    codec!(ProjectileTrail),
+    codec!(Bullet),
+    codec!(Downed),
+    codec!(NavPath),
+    codec!(Elevator),
+    codec!(Zone),
+    codec!(Door),
+];
+
+thread_local! {
+    static INTERNED: RefCell<HashSet<&'static str>> = RefCell::default();
+}
+
+/// A `'static` copy of `s`, leaked once per distinct string. Saves only hold
+/// names and lines from the floor data, so the set stays small.
+fn intern(s: &str) -> &'static str {
+    INTERNED.with(|set| {
+        let mut set = set.borrow_mut();
+        if let Some(&interned) = set.get(s) {
+            return interned;
+        }
+        let interned: &'static str = Box::leak(s.to_owned().into_boxed_str());
+        set.insert(interned);
+        interned
+    })
+}
+
+/// Write `world`: the handle space, RNG streams, walls, then one section per
+/// registered component that has rows.
+pub fn save_world(world: &World, w: &mut SaveWriter) {
+    save_world_head(world, w);
+    let present: Vec<&ComponentCodec> = COMPONENTS
+        .iter()
+        .filter(|codec| (codec.count)(world) > 0)
+        .collect();
+    w.token(present.len());
+    for codec in present {
+        w.token(codec.name);
+        (codec.save)(world, w);
+    }
+}
+
+/// Everything [`save_world`] writes before the component sections.
+fn save_world_head(world: &World, w: &mut SaveWriter) {
+    let (slots, free) = world.entity_slots();
+    w.token(slots.len());
+    for (generation, alive, newest) in slots {
+        generation.save(w);
+        alive.save(w);
+        newest.save(w);
+    }
+    free.save(w);
+    for state in world.rng_streams().states() {
+        state.save(w);
+    }
+    w.token(world.walls().len());
+    for wall in world.walls() {
+        for v in [wall.x, wall.y, wall.width, wall.height] {
+            v.save(w);
+        }
+        wall.material.save(w);
+    }
+}
+
+/// A world as the save sees it, row by row (what [`crate::rewind`] diffs
+/// an entity's components through): `head` is the handle space, RNG streams
+/// and walls; `rows` maps (registry index, entity index) to the entity and
+/// its value's save text.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WorldRows {
+    pub head: String,
+    pub rows: BTreeMap<(usize, u32), (Entity, String)>,
+}
+
+impl WorldRows {
+    pub fn of(world: &World) -> Self {
+        let mut head = SaveWriter::new();
+        save_world_head(world, &mut head);
+        let mut rows = BTreeMap::new();
+        for (i, codec) in COMPONENTS.iter().enumerate() {
+            codec.for_each_row(world, &mut |entity, text| {
+                rows.insert((i, entity.index()), (entity, text.to_string()));
+            });
+        }
+        WorldRows {
+            head: head.finish(),
+            rows,
+        }
+    }
+}
+
+/// Read a world written by [`save_world`].
+pub fn load_world(r: &mut SaveReader) -> Result<World, SaveError> {
+    let slot_count: usize = r.parse()?;
+    let slots = (0..slot_count)
+        .map(|_| Ok((u32::load(r)?, bool::load(r)?, u32::load(r)?)))
+        .collect::<Result<Vec<_>, SaveError>>()?;
+    // A slot's newest generation is never behind its current one: a save
+    // that says so would hand out again handles a restore had retired.
+    if slots
+        .iter()
+        .any(|&(generation, _, newest)| newest < generation)
+    {
+        return Err(SaveError::Corrupt("entity slots"));
+    }
+    let free = Vec::<u32>::load(r)?;
+    let mut reused = HashSet::new();
+    for &index in &free {
+        let dead = slots
+            .get(index as usize)
+            .is_some_and(|&(_, alive, _)| !alive);
+        if !dead || !reused.insert(index) {
+            return Err(SaveError::Corrupt("free list"));
+        }
+    }
+    let mut world = World::new();
+    world.restore_entity_slots(&slots, &free);
+    let mut states = [0; RngStream::COUNT];
+    for state in &mut states {
+        *state = r.parse()?;
+    }
+    world.set_rng_streams(RngStreams::from_states(states));
+    let walls: usize = r.parse()?;
+    for _ in 0..walls {
+        let wall = Wall::new(r.parse()?, r.parse()?, r.parse()?, r.parse()?);
+        world.insert_wall(wall.with_material(WallMaterial::load(r)?));
+    }
+    let sections: usize = r.parse()?;
+    for _ in 0..sections {
+        let name = r.token()?;
+        let codec = COMPONENTS
+            .iter()
+            .find(|codec| codec.name == name)
+            .ok_or_else(|| SaveError::UnknownComponent(name.to_string()))?;
+        (codec.load)(&mut world, r)?;
+    }
+    Ok(world)
+}
+
+/// The floor a save refers to, by floor id.
+pub fn floor_by_id(id: usize) -> Result<&'static FloorDef, SaveError> {
+    level_index_for_floor_id(id)
+        .map(floor_def)
+        .ok_or(SaveError::UnknownFloor(id))
+}
+
+/// A whole run — world plus scenario — as one save string.
+pub fn save_run(world: &World, scenario: &ScenarioState) -> String {
+    let mut w = SaveWriter::new();
+    w.token(MAGIC);
+    w.token(SAVE_VERSION);
+    save_world(world, &mut w);
+    scenario.save(&mut w);
+    w.finish()
+}
+
+/// Load a run written by [`save_run`].
+pub fn load_run(text: &str) -> Result<(World, ScenarioState), SaveError> {
+    let mut r = SaveReader::new(text);
+    if r.token().ok() != Some(MAGIC) {
+        return Err(SaveError::BadHeader);
+    }
+    let version: u32 = r.parse()?;
+    if version != SAVE_VERSION {
+        return Err(SaveError::UnsupportedVersion(version));
+    }
+    let world = load_world(&mut r)?;
+    let scenario = ScenarioState::load(&mut r)?;
+    if !r.at_end() {
+        return Err(SaveError::Corrupt("trailing data"));
+    }
+    Ok((world, scenario))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::initialize_game;
+    use crate::sim::Simulation;
+
+    fn snapshot(world: &World) -> Vec<String> {
+        // Every registered storage, row by row, as its save text.
+        COMPONENTS
+            .iter()
+            .map(|codec| {
+                let mut w = SaveWriter::new();
+                (codec.save)(world, &mut w);
+                format!("{} {}", codec.name, w.finish())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_tokens_round_trip() {
+        let mut w = SaveWriter::new();
+        0.1f32.save(&mut w);
+        (-3i32).save(&mut w);
+        "two words: and a colon".save(&mut w);
+        Some(Vec2::new(1.5, -2.0)).save(&mut w);
+        None::<u32>.save(&mut w);
+        vec![true, false].save(&mut w);
+        FinisherKind::Execute(WeaponType::SHOTGUN).save(&mut w);
+        WallMaterial::Glass.save(&mut w);
+        let text = w.finish();
+
+        let mut r = SaveReader::new(&text);
+        assert_eq!(f32::load(&mut r), Ok(0.1));
+        assert_eq!(i32::load(&mut r), Ok(-3));
+        assert_eq!(<&str>::load(&mut r), Ok("two words: and a colon"));
+        assert_eq!(Option::<Vec2>::load(&mut r), Ok(Some(Vec2::new(1.5, -2.0))));
+        assert_eq!(Option::<u32>::load(&mut r), Ok(None));
+        assert_eq!(Vec::<bool>::load(&mut r), Ok(vec![true, false]));
+        assert_eq!(
+            FinisherKind::load(&mut r),
+            Ok(FinisherKind::Execute(WeaponType::SHOTGUN))
+        );
+        assert_eq!(WallMaterial::load(&mut r), Ok(WallMaterial::Glass));
+        assert!(r.at_end());
+        assert_eq!(u32::load(&mut r), Err(SaveError::UnexpectedEnd));
+
+        // Length prefixes that overrun the text, or would wrap past it.
+        let huge = format!("{}:x", usize::MAX);
+        assert_eq!(
+            <&str>::load(&mut SaveReader::new(&huge)),
+            Err(SaveError::BadToken(usize::MAX.to_string()))
+        );
+        assert_eq!(
+            <&str>::load(&mut SaveReader::new("5:abc")),
+            Err(SaveError::UnexpectedEnd)
+        );
+    }
+
+    #[test]
+    fn test_weapons_are_saved_by_table_id() {
+        let mut w = SaveWriter::new();
+        WeaponType::SILENCED_PISTOL.save(&mut w);
+        let text = w.finish();
+        assert!(text.contains("silenced_pistol"));
+        let mut r = SaveReader::new(&text);
+        assert_eq!(WeaponType::load(&mut r), Ok(WeaponType::SILENCED_PISTOL));
+
+        let mut r = SaveReader::new("bazooka");
+        assert_eq!(
+            WeaponType::load(&mut r),
+            Err(SaveError::BadToken("bazooka".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_run_round_trips_mid_floor() {
+        let mut world = World::new();
+        let level = 1;
+        initialize_game(&mut world, level);
+        let mut sim = Simulation::from_world(world);
+        let mut sc = ScenarioState::new(floor_def(level));
+        // Walk up to the desk and click through the conversation until the
+        // first tutorial gate holds: comms, dialogue and gate state all live.
+        let player = sim.player().unwrap();
+        *sim.world.get_component_mut::<Position>(player).unwrap() = Position::new(500.0, 420.0);
+        for _ in 0..600 {
+            if sc.gate_view().is_some() {
+                break;
+            }
+            if sc.dialogue_active() {
+                sc.dialogue_advance();
+            }
+            sim.scenario_step(&mut sc, 1.0 / 60.0);
+        }
+        assert!(sc.gate_view().is_some(), "reached the punch gate");
+        // Leave a recycled index and a stale free slot behind.
+        let e = sim.world.spawn();
+        sim.world.despawn(e);
+
+        let text = save_run(&sim.world, &sc);
+        let (loaded, loaded_sc) = load_run(&text).expect("loads");
+        assert_eq!(snapshot(&loaded), snapshot(&sim.world));
+        assert_eq!(loaded.entities(), sim.world.entities());
+        assert_eq!(loaded.walls(), sim.world.walls());
+        assert_eq!(loaded.rng_state(), sim.world.rng_state());
+        assert!(!loaded.is_alive(e));
+        assert_eq!(save_run(&loaded, &loaded_sc), text, "stable re-save");
+
+        // Both timelines play on identically.
+        let mut resumed = Simulation::from_world(loaded);
+        let mut resumed_sc = loaded_sc;
+        for _ in 0..60 {
+            sim.scenario_step(&mut sc, 1.0 / 60.0);
+            resumed.scenario_step(&mut resumed_sc, 1.0 / 60.0);
+        }
+        assert_eq!(snapshot(&resumed.world), snapshot(&sim.world));
+        assert_eq!(resumed_sc.time(), sc.time());
+        assert_eq!(resumed_sc.gate_view(), sc.gate_view());
+        assert_eq!(resumed_sc.comms, sc.comms);
+    }
+
+    #[test]
+    fn test_handles_a_restore_retired_stay_retired_across_a_save() {
+        let mut world = World::new();
+        let kept = world.spawn();
+        let before = world.clone();
+        world.despawn(kept);
+        let abandoned = world.spawn(); // the same index, one generation on
+        world.restore(&before);
+
+        let mut w = SaveWriter::new();
+        save_world(&world, &mut w);
+        let text = w.finish();
+        let mut loaded = load_world(&mut SaveReader::new(&text)).expect("loads");
+        // Freeing the index again skips the abandoned timeline's handle.
+        loaded.despawn(kept);
+        let next = loaded.spawn();
+        assert_eq!(next.index(), abandoned.index());
+        assert_ne!(next, abandoned);
+        assert!(!loaded.is_alive(abandoned));
+
+        // One slot: generation 0, alive, newest 1. A newest generation
+        // behind the current one is refused.
+        assert!(text.starts_with("1 0 1 1 "));
+        let behind = text.replacen("1 0 1 1 ", "1 2 1 1 ", 1);
+        assert_ne!(behind, text);
+        assert_eq!(
+            load_world(&mut SaveReader::new(&behind)).err(),
+            Some(SaveError::Corrupt("entity slots"))
+        );
+    }
+
+    #[test]
+    fn test_rejects_foreign_and_future_saves() {
+        assert_eq!(load_run("").err(), Some(SaveError::BadHeader));
+        assert_eq!(load_run("{}").err(), Some(SaveError::BadHeader));
+        assert_eq!(
+            load_run("OMSAVE 99 0").err(),
+            Some(SaveError::UnsupportedVersion(99))
+        );
+
+        let mut world = World::new();
+        initialize_game(&mut world, 0);
+        let text = save_run(&world, &ScenarioState::new(floor_def(0)));
+        let renamed = text.replacen(" Position ", " Teleporter ", 1);
+        assert_eq!(
+            load_run(&renamed).err(),
+            Some(SaveError::UnknownComponent("Teleporter".to_string()))
+        );
+        let truncated: Vec<&str> = text.split(' ').take(40).collect();
+        assert_eq!(
+            load_run(&truncated.join(" ")).err(),
+            Some(SaveError::UnexpectedEnd)
+        );
+    }
+}
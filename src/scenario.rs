@@ -9,9 +9,10 @@
 use std::collections::VecDeque;
 
 use crate::components::{Boss, Elevator, EnemyType, Health, Player, Position, WeaponType, Zone};
-use crate::ecs::World;
+use crate::ecs::{WallMaterial, World};
 use crate::game::spawn_enemy_with_type;
 use crate::math::Vec2;
+use crate::save::{persist_struct, Persist, SaveError, SaveReader, SaveWriter};
 use crate::systems::elevator::ElevatorSystem;
 
 // ---------------------------------------------------------------------------
@@ -41,6 +42,30 @@ impl Rect {
     }
 }
 
+/// A wall of a floor: its rectangle and what it is made of (`"material":
+/// "glass"` in the JSON; solid when the key is absent).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FloorWall {
+    pub rect: Rect,
+    pub material: WallMaterial,
+}
+
+impl FloorWall {
+    pub const fn solid(rect: Rect) -> Self {
+        FloorWall {
+            rect,
+            material: WallMaterial::Solid,
+        }
+    }
+
+    pub const fn glass(rect: Rect) -> Self {
+        FloorWall {
+            rect,
+            material: WallMaterial::Glass,
+        }
+    }
+}
+
 /// `ElevatorDef::to` value of the exit that ends the run (the surface):
 /// `"to": "surface"` in the JSON. Never a real floor id.
 pub const SURFACE_EXIT: usize = usize::MAX;
@@ -168,6 +193,23 @@ pub struct PickupDef {
     pub weapon: WeaponType,
 }
 
+/// A swinging door: a leaf hinged at `(x, y)`, `length` long, lying along
+/// `angle` (degrees, clockwise, +y down) when shut. Placed in a gap of a
+/// straight wall, hinged at the jamb with at least a leaf of wall behind it:
+/// it swings either way, kicked wide open folding back into that wall (see
+/// `components::Door`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DoorDef {
+    pub x: f32,
+    pub y: f32,
+    pub angle: f32,
+    pub length: f32,
+}
+
+/// Leaf length of a door whose JSON does not give one (`tools/gen_levels.py`'s
+/// `DOOR_LENGTH`): a standard gap in a wall.
+pub const DOOR_LENGTH: f32 = 100.0;
+
 /// A placed prop (`crate::props`): decoration drawn on the floor under the
 /// actors — no collision (phase 1). `rot` in degrees (clockwise, +y down),
 /// `size` in world units (100 = the prop's design box).
@@ -425,11 +467,13 @@ pub struct FloorDef {
     pub height: f32,
     pub entry: ElevatorDef,
     pub exits: &'static [ElevatorDef],
-    pub walls: &'static [Rect],
+    pub walls: &'static [FloorWall],
     pub rooms: &'static [RoomDef],
     pub zones: &'static [ZoneDef],
     pub spawns: &'static [SpawnDef],
     pub pickups: &'static [PickupDef],
+    /// Swinging doors.
+    pub doors: &'static [DoorDef],
     /// Placed props (decoration only).
     pub props: &'static [PropPlacement],
     pub scenario: &'static [StepDef],
@@ -858,7 +902,7 @@ impl ScenarioState {
 
     /// The active tutorial gate, if any (the world must be frozen: the game
     /// loop runs only the player-driven systems and masks every input but
-    /// the gated one — see `sim::gate_frozen_step`).
+    /// the gated one — see `sim::TickContext::gate_frozen`).
     pub fn gate_view(&self) -> Option<GateDef> {
         self.gate.map(|g| g.def)
     }
@@ -1228,6 +1272,129 @@ impl ScenarioState {
     }
 }
 
+persist_struct!(QueuedLine {
+    who,
+    text,
+    not_before
+});
+persist_struct!(CommsLine { who, text, age });
+persist_struct!(CommsFeed {
+    queue,
+    visible,
+    busy_until
+});
+persist_struct!(HoldState { def, until });
+persist_struct!(LookState { def, start });
+persist_struct!(DialogueState {
+    current,
+    queue,
+    line_age,
+    slide,
+    closing,
+    owners,
+});
+
+impl ScenarioState {
+    /// Write the runtime state for a save game (see [`crate::save`]). The
+    /// floor goes by id; pending one-shot sfx are transient and dropped.
+    pub fn save(&self, w: &mut SaveWriter) {
+        self.floor.id.save(w);
+        self.time.save(w);
+        self.fired_at.save(w);
+        self.auto_open_on_all_dead.save(w);
+        self.auto_opened.save(w);
+        self.opened_exits.save(w);
+        self.objective.save(w);
+        self.comms.save(w);
+        self.hold.save(w);
+        self.look.save(w);
+        self.dialogue.save(w);
+        self.talk_done_at.save(w);
+        // A gate's remaining actions are a suffix of its step's actions:
+        // saved as the offset into them.
+        self.gate.is_some().save(w);
+        if let Some(g) = &self.gate {
+            g.def.save(w);
+            g.step_idx.save(w);
+            let actions = self.floor.scenario[g.step_idx].actions;
+            (actions.len() - g.rest.len()).save(w);
+            g.anchor.save(w);
+        }
+        self.gate_done_at.save(w);
+        self.checkpoint_requested.save(w);
+        self.combat_enabled.save(w);
+    }
+
+    /// Read what [`ScenarioState::save`] wrote, against the floor data of
+    /// this build.
+    pub fn load(r: &mut SaveReader) -> Result<Self, SaveError> {
+        let floor = crate::save::floor_by_id(usize::load(r)?)?;
+        let steps = floor.scenario.len();
+        let per_step = |table: Vec<Option<f32>>| {
+            if table.len() == steps {
+                Ok(table)
+            } else {
+                Err(SaveError::Corrupt("step table length"))
+            }
+        };
+        let time = f32::load(r)?;
+        let fired_at = per_step(Vec::load(r)?)?;
+        let auto_open_on_all_dead = bool::load(r)?;
+        let auto_opened = bool::load(r)?;
+        let opened_exits = Vec::load(r)?;
+        let objective = String::load(r)?;
+        let comms = CommsFeed::load(r)?;
+        let hold = Option::load(r)?;
+        let look = Option::load(r)?;
+        let dialogue: Option<DialogueState> = Option::load(r)?;
+        if dialogue
+            .as_ref()
+            .is_some_and(|d| d.owners.iter().any(|&i| i >= steps))
+        {
+            return Err(SaveError::Corrupt("dialogue owner"));
+        }
+        let talk_done_at = per_step(Vec::load(r)?)?;
+        let gate = match bool::load(r)? {
+            false => None,
+            true => {
+                let def = GateDef::load(r)?;
+                let step_idx = usize::load(r)?;
+                let offset = usize::load(r)?;
+                let rest = floor
+                    .scenario
+                    .get(step_idx)
+                    .and_then(|step| step.actions.get(offset..))
+                    .ok_or(SaveError::Corrupt("gate step"))?;
+                Some(GateState {
+                    def,
+                    step_idx,
+                    rest,
+                    anchor: Option::load(r)?,
+                })
+            }
+        };
+        Ok(ScenarioState {
+            floor,
+            time,
+            fired_at,
+            auto_open_on_all_dead,
+            auto_opened,
+            opened_exits,
+            objective,
+            comms,
+            sfx: Vec::new(),
+            hold,
+            look,
+            dialogue,
+            talk_done_at,
+            gate,
+            gate_done_at: per_step(Vec::load(r)?)?,
+            checkpoint_requested: bool::load(r)?,
+            combat_enabled: bool::load(r)?,
+        })
+    }
+}
+
 /// Weight 0..1 of a `look_at` that started `elapsed` seconds ago and lasts
 /// `total`: eases in over [`LOOK_AT_EASE_SECS`], holds, eases out over the
 /// last [`LOOK_AT_EASE_SECS`] (smoothstep both ways). Short looks scale the
@@ -1450,6 +1617,7 @@ mod tests {
         zones: &T_ZONES,
         spawns: &T_SPAWNS,
         pickups: &[],
+        doors: &[],
         props: &[],
         scenario: &T_STEPS,
         surface: Surface::Checker,
@@ -1992,6 +2160,7 @@ mod tests {
         zones: &C_ZONES,
         spawns: &C_SPAWNS,
         pickups: &[],
+        doors: &[],
         scenario: &C_STEPS,
         surface: Surface::Asphalt,
 
@@ -2409,12 +2578,12 @@ mod tests {
 
         // The left click only works with the matching tool in hand.
         assert!(GateInput::Punch.allows_primary(None));
-        assert!(!GateInput::Punch.allows_primary(Some(WeaponType::Melee)));
-        assert!(GateInput::Strike.allows_primary(Some(WeaponType::Melee)));
-        assert!(!GateInput::Strike.allows_primary(Some(WeaponType::Pistol)));
+        assert!(!GateInput::Punch.allows_primary(Some(WeaponType::MELEE)));
+        assert!(GateInput::Strike.allows_primary(Some(WeaponType::MELEE)));
+        assert!(!GateInput::Strike.allows_primary(Some(WeaponType::PISTOL)));
         assert!(!GateInput::Strike.allows_primary(None));
-        assert!(GateInput::Fire.allows_primary(Some(WeaponType::Shotgun)));
-        assert!(!GateInput::Fire.allows_primary(Some(WeaponType::Melee)));
+        assert!(GateInput::Fire.allows_primary(Some(WeaponType::SHOTGUN)));
+        assert!(!GateInput::Fire.allows_primary(Some(WeaponType::MELEE)));
         assert!(!GateInput::Pickup.allows_primary(None));
         assert!(GateInput::Finish.allows_finisher());
         assert!(!GateInput::Punch.allows_finisher());
@@ -2435,11 +2604,11 @@ mod tests {
         assert!(GateInput::Pickup.satisfied_by(&GameEvent::Pickup));
         assert!(GateInput::Strike.satisfied_by(&GameEvent::StrikeLanded));
         assert!(!GateInput::Strike.satisfied_by(&GameEvent::EnemyHit {
-            by: WeaponType::Melee
+            by: WeaponType::MELEE
         }));
-        assert!(GateInput::Fire.satisfied_by(&GameEvent::PlayerFired(WeaponType::Pistol)));
+        assert!(GateInput::Fire.satisfied_by(&GameEvent::PlayerFired(WeaponType::PISTOL)));
         assert!(
-            !GateInput::Fire.satisfied_by(&GameEvent::PlayerFired(WeaponType::Melee)),
+            !GateInput::Fire.satisfied_by(&GameEvent::PlayerFired(WeaponType::MELEE)),
             "a melee swing is not a shot"
         );
         assert!(GateInput::Throw.satisfied_by(&GameEvent::ThrownImpact));
@@ -2663,7 +2832,7 @@ mod tests {
         assert!(!sim.player_alive());
 
         // Death -> restore the snapshot: the run is back at the gate.
-        sim.world = cp_world.clone();
+        sim.world.restore(&cp_world);
         sc = cp_sc.clone();
         assert!(sim.player_alive());
         assert_eq!(
@@ -2814,7 +2983,7 @@ mod tests {
 
         // TAKE THE BAR (the melee pickup by the desk).
         teleport(&mut sim, Vec2::new(420.0, 370.0));
-        assert_eq!(sim.player_pickup(), Some(WeaponType::Melee));
+        assert_eq!(sim.player_pickup(), Some(WeaponType::MELEE));
         run(&mut sim, &mut sc, 2);
         assert!(sc.step_fired("tut_strike"));
         assert_eq!(sc.gate_view().unwrap().input, GateInput::Strike);
@@ -2841,12 +3010,12 @@ mod tests {
             .find(|&e| {
                 sim.world
                     .get_component::<crate::components::WeaponPickup>(e)
-                    .is_some_and(|p| p.weapon_type == WeaponType::Melee)
+                    .is_some_and(|p| p.weapon_type == WeaponType::MELEE)
             })
             .expect("the thrown bar is on the floor");
         let bar_pos = *sim.world.get_component::<Position>(bar).unwrap();
         teleport(&mut sim, Vec2::new(bar_pos.x, bar_pos.y));
-        assert_eq!(sim.player_pickup(), Some(WeaponType::Melee));
+        assert_eq!(sim.player_pickup(), Some(WeaponType::MELEE));
         run(&mut sim, &mut sc, 2);
         assert!(sc.step_fired("tut_overhead"));
         assert_eq!(sc.gate_view().unwrap().input, GateInput::Finish);
@@ -2892,7 +3061,7 @@ mod tests {
         assert_eq!(sim.enemies_alive(), 7, "the wave is inside the snapshot");
         assert_eq!(
             crate::game::get_player_weapon(&sim.world),
-            Some(WeaponType::Melee),
+            Some(WeaponType::MELEE),
             "the bar came back with the snapshot"
         );
 
@@ -2933,6 +3102,8 @@ mod tests {
                 right_pressed: false,
                 e_pressed: false,
                 mouse_world: Vec2::zero(),
+                move_dir: Vec2::zero(),
+                advance_pressed: false,
             }
         }
         fn swing_at(mouse: Vec2) -> PlayerIntents {
@@ -3061,7 +3232,7 @@ mod tests {
         // TAKE THE BAR: walk to the pickup by the desk and press E.
         walk_to(&mut sim, &mut sc, Vec2::new(420.0, 370.0));
         frame(&mut sim, &mut sc, &press_e());
-        assert_eq!(get_player_weapon(&sim.world), Some(WeaponType::Melee));
+        assert_eq!(get_player_weapon(&sim.world), Some(WeaponType::MELEE));
         run(&mut sim, &mut sc, 2);
         assert!(sc.step_fired("tut_strike"));
         assert_eq!(sc.gate_view().unwrap().input, GateInput::Strike);
@@ -3125,7 +3296,7 @@ mod tests {
             .find(|&e| {
                 sim.world
                     .get_component::<crate::components::WeaponPickup>(e)
-                    .is_some_and(|p| p.weapon_type == WeaponType::Melee)
+                    .is_some_and(|p| p.weapon_type == WeaponType::MELEE)
             })
             .expect("the thrown bar is on the floor");
         let bar_pos = *sim.world.get_component::<Position>(bar).unwrap();
@@ -0,0 +1,500 @@
+//! Scoring: points for every rogue taken out, a combo chain, and the
+//! end-of-floor bonuses and letter grade.
+//!
+//! [`Score`] is fed the same [`GameEvent`] batches the host drains every
+//! frame ([`Score::observe`]) plus the frame's clock ([`Score::tick`]); it
+//! never looks at the world, so it runs the same in the browser, in
+//! [`crate::sim::FloorRun`] and in a plain unit test.
+//!
+//! `EnemyDown` carries no cause (it is announced once per dead rogue after
+//! every damage source of the tick, see
+//! [`crate::systems::PickupSystem::announce_downed`]), so a kill is credited
+//! to the last player attack seen in the same batch: a `FinisherDone` (it
+//! follows the finisher's own impact), a `ThrownImpact`, or the weapon of an
+//! `EnemyHit`. A rogue that dies with no attack of the player's in its frame
+//! (a feral one, a scenario purge) scores nothing.
+
+use crate::components::{GameEvent, WeaponType};
+
+/// Seconds a combo stays alive after the last kill or knockdown.
+pub const COMBO_WINDOW: f32 = 3.0;
+/// The combo multiplier stops growing here.
+pub const MAX_MULTIPLIER: u32 = 8;
+/// A thrown weapon knocking a rogue down, before the multiplier.
+pub const KNOCKDOWN_POINTS: u32 = 200;
+/// Seconds after a rogue fires during which a kill counts as bold.
+pub const BOLD_WINDOW: f32 = 1.5;
+/// Per kill made under fire.
+pub const BOLD_POINTS: u32 = 250;
+/// Per distinct way of killing used on the floor, past the first.
+pub const FLEX_POINTS: u32 = 300;
+/// Per second under the floor's par time.
+pub const SPEED_POINTS_PER_SEC: f32 = 50.0;
+/// Par time: this long for the floor...
+pub const PAR_BASE_SECS: f32 = 30.0;
+/// ...plus this long per kill.
+pub const PAR_SECS_PER_KILL: f32 = 10.0;
+/// Taken off the total for every death on the floor.
+pub const DEATH_PENALTY: u32 = 1000;
+/// The points per kill a floor is graded against (a B, give or take).
+pub const GRADE_PAR_PER_KILL: f32 = 1000.0;
+
+/// How a rogue was taken out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KillMethod {
+    /// Executed on the floor (see [`crate::systems::FinisherSystem`]).
+    Finisher,
+    /// The thrown weapon itself killed it.
+    Thrown,
+    Gun,
+    /// A swing or a punch.
+    Melee,
+}
+
+impl KillMethod {
+    pub const ALL: [KillMethod; 4] = [
+        KillMethod::Finisher,
+        KillMethod::Thrown,
+        KillMethod::Gun,
+        KillMethod::Melee,
+    ];
+
+    /// Base points for a kill, before the combo multiplier.
+    pub const fn points(self) -> u32 {
+        match self {
+            KillMethod::Finisher => 800,
+            KillMethod::Thrown => 600,
+            KillMethod::Gun => 400,
+            KillMethod::Melee => 600,
+        }
+    }
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            KillMethod::Finisher => "finisher",
+            KillMethod::Thrown => "thrown",
+            KillMethod::Gun => "gun",
+            KillMethod::Melee => "melee",
+        }
+    }
+}
+
+/// One rogue taken out by the player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Kill {
+    pub method: KillMethod,
+    /// The weapon of the blow (for a finisher, its impact); `None` for a
+    /// thrown weapon.
+    pub weapon: Option<WeaponType>,
+}
+
+/// Credits kills within one event batch: feed it the batch in order and
+/// every `EnemyDown` comes back as the [`Kill`] of the last player attack
+/// before it (see the module doc). Shared with [`crate::stats`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KillCredit {
+    method: Option<KillMethod>,
+    weapon: Option<WeaponType>,
+}
+
+impl KillCredit {
+    /// Take `event` into account; the kill it credits, for an `EnemyDown`
+    /// after a player attack.
+    pub fn see(&mut self, event: &GameEvent) -> Option<Kill> {
+        match *event {
+            GameEvent::EnemyHit { by } => {
+                self.method = Some(if by.is_melee() {
+                    KillMethod::Melee
+                } else {
+                    KillMethod::Gun
+                });
+                self.weapon = Some(by);
+            }
+            GameEvent::ThrownImpact => {
+                self.method = Some(KillMethod::Thrown);
+                self.weapon = None;
+            }
+            GameEvent::FinisherDone => self.method = Some(KillMethod::Finisher),
+            GameEvent::EnemyDown => {
+                return self.method.map(|method| Kill {
+                    method,
+                    weapon: self.weapon,
+                })
+            }
+            _ => {}
+        }
+        None
+    }
+}
+
+/// What a kill counts as for the flexibility bonus: finishers and throws
+/// are one style each, guns and melee weapons one per weapon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Style {
+    Finisher,
+    Thrown,
+    Weapon(WeaponType),
+}
+
+impl Style {
+    fn of(kill: Kill) -> Style {
+        match (kill.method, kill.weapon) {
+            (KillMethod::Finisher, _) => Style::Finisher,
+            (_, Some(weapon)) => Style::Weapon(weapon),
+            (_, None) => Style::Thrown,
+        }
+    }
+}
+
+/// The end-of-floor letter grade, best first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Grade {
+    S,
+    A,
+    B,
+    C,
+    D,
+    F,
+}
+
+impl Grade {
+    /// The grade for `total` points over `kills` kills: the total against
+    /// [`GRADE_PAR_PER_KILL`] per kill (a floor with no kills is graded as
+    /// if it had one).
+    pub fn for_points(total: u32, kills: u32) -> Grade {
+        let ratio = total as f32 / (kills.max(1) as f32 * GRADE_PAR_PER_KILL);
+        match ratio {
+            r if r >= 1.6 => Grade::S,
+            r if r >= 1.2 => Grade::A,
+            r if r >= 0.9 => Grade::B,
+            r if r >= 0.6 => Grade::C,
+            r if r >= 0.3 => Grade::D,
+            _ => Grade::F,
+        }
+    }
+
+    pub const fn letter(self) -> &'static str {
+        match self {
+            Grade::S => "S",
+            Grade::A => "A",
+            Grade::B => "B",
+            Grade::C => "C",
+            Grade::D => "D",
+            Grade::F => "F",
+        }
+    }
+}
+
+/// A floor's final tally (see [`Score::finish`]).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FloorScore {
+    /// Kill and knockdown points, combo multipliers included.
+    pub kill_points: u32,
+    pub speed_bonus: u32,
+    pub flexibility_bonus: u32,
+    pub boldness_bonus: u32,
+    pub death_penalty: u32,
+    /// Everything above, never below zero.
+    pub total: u32,
+    pub grade: Grade,
+    pub kills: u32,
+    pub best_combo: u32,
+    pub deaths: u32,
+    /// Seconds on the floor, every attempt included.
+    pub time: f32,
+}
+
+/// The running score of one floor.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Score {
+    /// Kill and knockdown points so far.
+    points: u32,
+    kills: u32,
+    kills_by: [u32; KillMethod::ALL.len()],
+    styles: Vec<Style>,
+    bold_kills: u32,
+    /// Kills in the running chain.
+    combo: u32,
+    /// Seconds left before the chain breaks.
+    combo_timer: f32,
+    best_combo: u32,
+    /// Seconds left of the last rogue shot's "under fire" window.
+    under_fire: f32,
+    deaths: u32,
+    time: f32,
+}
+
+impl Score {
+    pub fn new() -> Self {
+        Score::default()
+    }
+
+    /// A retry of the floor after a death (or a restart): the kills go back
+    /// to `checkpoint` (nothing, without one) while the deaths and the clock
+    /// carry on, so dying never improves a grade.
+    pub fn retry(&self, checkpoint: Option<&Score>) -> Score {
+        Score {
+            deaths: self.deaths,
+            time: self.time,
+            combo: 0,
+            combo_timer: 0.0,
+            under_fire: 0.0,
+            ..checkpoint.cloned().unwrap_or_default()
+        }
+    }
+
+    /// Advance the floor clock and let the combo run down.
+    pub fn tick(&mut self, dt: f32) {
+        self.time += dt;
+        self.under_fire = (self.under_fire - dt).max(0.0);
+        self.combo_timer -= dt;
+        if self.combo_timer <= 0.0 {
+            self.combo_timer = 0.0;
+            self.combo = 0;
+        }
+    }
+
+    /// Score one frame's events, in order.
+    pub fn observe(&mut self, events: &[GameEvent]) {
+        let mut credit = KillCredit::default();
+        let mut died = false;
+        for event in events {
+            if let Some(kill) = credit.see(event) {
+                self.kill(kill);
+            }
+            match event {
+                GameEvent::ThrownImpact => {
+                    self.points += KNOCKDOWN_POINTS * self.multiplier();
+                    self.combo_timer = COMBO_WINDOW;
+                }
+                GameEvent::EnemyFired(_) => self.under_fire = BOLD_WINDOW,
+                GameEvent::PlayerHurt { .. } => died = true,
+                _ => {}
+            }
+        }
+        // Every hit is fatal: one death however many connected.
+        if died {
+            self.deaths += 1;
+            self.combo = 0;
+            self.combo_timer = 0.0;
+        }
+    }
+
+    fn kill(&mut self, kill: Kill) {
+        let (method, style) = (kill.method, Style::of(kill));
+        self.combo += 1;
+        self.best_combo = self.best_combo.max(self.combo);
+        self.combo_timer = COMBO_WINDOW;
+        self.points += method.points() * self.multiplier();
+        self.kills += 1;
+        self.kills_by[method as usize] += 1;
+        if !self.styles.contains(&style) {
+            self.styles.push(style);
+        }
+        if self.under_fire > 0.0 {
+            self.bold_kills += 1;
+        }
+    }
+
+    /// The multiplier the next points are worth (the running chain, at
+    /// least 1).
+    pub fn multiplier(&self) -> u32 {
+        self.combo.clamp(1, MAX_MULTIPLIER)
+    }
+
+    /// Kills in the running chain (0 once it broke).
+    pub fn combo(&self) -> u32 {
+        self.combo
+    }
+
+    /// How much of the combo window is left, 1 right after a kill down to 0.
+    pub fn combo_left(&self) -> f32 {
+        (self.combo_timer / COMBO_WINDOW).clamp(0.0, 1.0)
+    }
+
+    /// Kill and knockdown points so far (the bonuses come at the end).
+    pub fn points(&self) -> u32 {
+        self.points
+    }
+
+    pub fn kills(&self) -> u32 {
+        self.kills
+    }
+
+    /// Kills credited to `method`.
+    pub fn kills_by(&self, method: KillMethod) -> u32 {
+        self.kills_by[method as usize]
+    }
+
+    pub fn deaths(&self) -> u32 {
+        self.deaths
+    }
+
+    /// Seconds on the floor so far.
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+
+    /// The final tally, on extraction.
+    pub fn finish(&self) -> FloorScore {
+        let par = PAR_BASE_SECS + PAR_SECS_PER_KILL * self.kills as f32;
+        let speed_bonus = ((par - self.time).max(0.0) * SPEED_POINTS_PER_SEC).round() as u32;
+        let flexibility_bonus = FLEX_POINTS * (self.styles.len() as u32).saturating_sub(1);
+        let boldness_bonus = BOLD_POINTS * self.bold_kills;
+        let death_penalty = DEATH_PENALTY * self.deaths;
+        let total = (self.points + speed_bonus + flexibility_bonus + boldness_bonus)
+            .saturating_sub(death_penalty);
+        FloorScore {
+            kill_points: self.points,
+            speed_bonus,
+            flexibility_bonus,
+            boldness_bonus,
+            death_penalty,
+            total,
+            grade: Grade::for_points(total, self.kills),
+            kills: self.kills,
+            best_combo: self.best_combo,
+            deaths: self.deaths,
+            time: self.time,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::Entity;
+
+    fn gun_kill() -> Vec<GameEvent> {
+        vec![
+            GameEvent::PlayerFired(WeaponType::PISTOL),
+            GameEvent::EnemyHit {
+                by: WeaponType::PISTOL,
+            },
+            GameEvent::EnemyDown,
+        ]
+    }
+
+    #[test]
+    fn test_kills_are_credited_to_the_attack_in_their_frame() {
+        let mut score = Score::new();
+        score.observe(&gun_kill());
+        // An overhead finisher: the melee impact, then the finisher itself.
+        score.observe(&[
+            GameEvent::EnemyHit {
+                by: WeaponType::MELEE,
+            },
+            GameEvent::FinisherDone,
+            GameEvent::EnemyDown,
+        ]);
+        score.observe(&[GameEvent::ThrownImpact, GameEvent::EnemyDown]);
+        score.observe(&[
+            GameEvent::EnemyHit {
+                by: WeaponType::KATANA,
+            },
+            GameEvent::StrikeLanded,
+            GameEvent::EnemyDown,
+        ]);
+        // Nobody's attack: a rogue that bled out on its own.
+        score.observe(&[GameEvent::EnemyDown]);
+
+        assert_eq!(score.kills(), 4);
+        for method in KillMethod::ALL {
+            assert_eq!(score.kills_by(method), 1, "{}", method.name());
+        }
+    }
+
+    #[test]
+    fn test_the_combo_multiplies_and_runs_out() {
+        let mut score = Score::new();
+        score.observe(&gun_kill());
+        score.tick(1.0);
+        score.observe(&gun_kill());
+        assert_eq!(score.combo(), 2);
+        // 400 x1, then 400 x2.
+        assert_eq!(score.points(), 400 + 800);
+
+        score.tick(COMBO_WINDOW + 0.1);
+        assert_eq!(score.combo(), 0);
+        assert_eq!(score.combo_left(), 0.0);
+        score.observe(&gun_kill());
+        assert_eq!(score.points(), 400 + 800 + 400, "back to x1");
+        assert_eq!(score.finish().best_combo, 2);
+    }
+
+    #[test]
+    fn test_a_thrown_knockdown_scores_and_keeps_the_chain_alive() {
+        let mut score = Score::new();
+        score.observe(&gun_kill());
+        score.tick(COMBO_WINDOW - 0.5);
+        score.observe(&[GameEvent::Throw, GameEvent::ThrownImpact]);
+        assert_eq!(score.points(), 400 + KNOCKDOWN_POINTS);
+        score.tick(COMBO_WINDOW - 0.5);
+        assert_eq!(score.combo(), 1, "the knockdown refreshed the window");
+        assert_eq!(score.kills(), 1);
+    }
+
+    #[test]
+    fn test_bonuses_for_speed_flexibility_and_boldness() {
+        let mut score = Score::new();
+        score.observe(&gun_kill());
+        score.observe(&[
+            GameEvent::EnemyFired(WeaponType::SHOTGUN),
+            GameEvent::EnemyHit {
+                by: WeaponType::MELEE,
+            },
+            GameEvent::EnemyDown,
+        ]);
+        score.tick(10.0);
+        let tally = score.finish();
+        // Par: 30 s + 2 kills x 10 s = 50 s, done in 10.
+        assert_eq!(tally.speed_bonus, (40.0 * SPEED_POINTS_PER_SEC) as u32);
+        assert_eq!(tally.flexibility_bonus, FLEX_POINTS, "pistol and bar");
+        assert_eq!(tally.boldness_bonus, BOLD_POINTS, "the second, under fire");
+        assert_eq!(
+            tally.total,
+            tally.kill_points + tally.speed_bonus + tally.flexibility_bonus + tally.boldness_bonus
+        );
+    }
+
+    #[test]
+    fn test_deaths_cost_points_and_survive_a_retry() {
+        let mut score = Score::new();
+        score.observe(&gun_kill());
+        let checkpoint = score.clone();
+        score.observe(&gun_kill());
+        // Well over par: no speed bonus to hide the penalty.
+        score.tick(60.0);
+        // Two rogues connect in the same frame: still one death.
+        score.observe(&[
+            GameEvent::PlayerHurt {
+                by: Entity::new(1, 0),
+            },
+            GameEvent::PlayerHurt {
+                by: Entity::new(2, 0),
+            },
+        ]);
+        assert_eq!(score.deaths(), 1);
+        assert_eq!(score.combo(), 0, "dying breaks the chain");
+
+        let retry = score.retry(Some(&checkpoint));
+        assert_eq!(retry.kills(), 1, "back to the checkpoint's kills");
+        assert_eq!(retry.deaths(), 1);
+        assert_eq!(retry.time(), 60.0);
+        assert_eq!(retry.finish().death_penalty, DEATH_PENALTY);
+
+        let restart = retry.retry(None);
+        assert_eq!((restart.kills(), restart.points()), (0, 0));
+        assert_eq!(restart.finish().total, 0, "never below zero");
+    }
+
+    #[test]
+    fn test_grades() {
+        assert_eq!(Grade::for_points(10_000, 5), Grade::S);
+        assert_eq!(Grade::for_points(5_000, 5), Grade::B);
+        assert_eq!(Grade::for_points(0, 5), Grade::F);
+        // No kills: graded as one.
+        assert_eq!(Grade::for_points(1_500, 0), Grade::A);
+        assert!(Grade::S < Grade::A);
+    }
+}
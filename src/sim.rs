@@ -12,82 +12,160 @@
 //!   frame count, or a bigger `dt`, to simulate minutes of play instantly),
 //! * with scripted player actions ([`Simulation::player_fire`],
 //!   [`Simulation::player_throw`], [`Simulation::player_pickup`],
-//!   [`Simulation::set_player_velocity`]).
+//!   [`Simulation::set_player_velocity`]),
+//! * or frame by frame from player intents, the browser's own input dispatch
+//!   included ([`FloorRun`]) — how recorded replays re-run ([`crate::replay`])
+//!   and how the campaign bot plays a floor's scenario through to extraction
+//!   ([`FloorRun::bot_frame`]),
+//! * or by an agent, gym-style ([`Env`]): fixed-size observations, rewards
+//!   from [`GameEvent`]s, for training or evaluating bots against floors.
 //!
 //! This is the "simulated e2e" layer: it exercises the whole engine end to end
 //! without a browser, complementing the Playwright tests that drive the real
 //! rendered game.
 
-use crate::collision::has_line_of_sight;
+use crate::collision::{has_line_of_sight, has_line_of_sight_with_padding, raycast_walls};
 use crate::components::{
-    Enemy, Health, Position, Speed, Stunned, Weapon, WeaponPickup, WeaponType,
+    AIState, Bullet, Elevator, Enemy, Health, Position, Rotation, Speed, Stunned, Weapon,
+    WeaponPickup, WeaponType, AI,
 };
-use crate::components::{Player, Velocity};
-use crate::ecs::{Entity, System, World};
+use crate::components::{GameEvent, Player, Velocity};
+use crate::ecs::system::FnSystem;
+use crate::ecs::{Entity, Schedule, System, With, World};
 use crate::game::{
-    count_alive_enemies, fire_player_weapon, get_player_position, initialize_game, is_player_alive,
+    apply_player_input, count_alive_enemies, fire_player_weapon, get_player_position,
+    get_player_weapon, initialize_game, is_player_alive, new_floor_world, PlayerIntents,
 };
+use crate::levels::floor_def;
 use crate::math::Vec2;
 use crate::pathfinding::NavigationGrid;
-use crate::scenario::ScenarioState;
+use crate::scenario::{GateInput, ScenarioState, Trigger};
+use crate::score::Score;
+use crate::spatial::SpatialIndex;
+use crate::state_hash::StateDigest;
+use crate::stats::FloorStats;
+use crate::systems::combat::{enemy_gun, PUNCH_RANGE};
+use crate::systems::door::door_leaves;
+use crate::systems::finisher::FINISHER_RANGE;
+use crate::systems::noise;
 use crate::systems::{
-    AISystem, BossSystem, BulletSystem, CombatSystem, FinisherSystem, MovementSystem, PickupSystem,
-    ProjectileTrailSystem, StunSystem, ThrownWeaponSystem, WeaponUpdateSystem,
+    AISystem, BossSystem, BulletSystem, CombatSystem, DoorSystem, ElevatorSystem, FinisherSystem,
+    MovementSystem, NoiseSystem, PickupSystem, ProjectileTrailSystem, StunSystem,
+    ThrownWeaponSystem, WeaponUpdateSystem,
 };
+use crate::weapons::WeaponModel;
 
 /// While a tutorial gate freezes the world, knockdown clocks tick for the
 /// fall animation but never drop below this floor — nobody gets back up
 /// under a freeze (the `finish` gate's victim stays down indefinitely).
 pub const GATE_STUN_FLOOR: f32 = 0.05;
 
-/// One engine tick while a tutorial `gate` FREEZES the world (see
-/// `scenario::GateDef`): only the PLAYER-DRIVEN systems advance — the
-/// finisher animation, weapon / fist cooldowns, movement (every enemy's
-/// velocity is pinned to zero first, so only the player and in-flight
-/// knockback shoves move), the player's bullets and thrown weapons, trails
-/// and pickups. Enemy AI, the boss, enemy attacks and the scenario clock do
-/// not run, and knockdown timers tick only down to [`GATE_STUN_FLOOR`].
-///
-/// Shared verbatim by the browser loop (`lib.rs`) and the headless
-/// [`Simulation::scenario_step`], so the freeze semantics are host-testable.
-pub fn gate_frozen_step(world: &mut World, dt: f32) {
-    FinisherSystem.run(world, dt);
-    WeaponUpdateSystem.run(world, dt);
+/// What the tick schedule's run conditions read: set by the host each frame.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TickContext {
+    /// A tutorial `gate` FREEZES the world (see `scenario::GateDef`): only
+    /// the PLAYER-DRIVEN systems advance — the finisher animation, weapon /
+    /// fist cooldowns, movement (every enemy's velocity is pinned to zero
+    /// first, so only the player and in-flight knockback shoves move), the
+    /// doors they push, the player's bullets and thrown weapons, trails and
+    /// pickups. Enemy AI, the boss, enemy attacks and the scenario clock do
+    /// not run, and knockdown timers tick only down to [`GATE_STUN_FLOOR`].
+    pub gate_frozen: bool,
+}
+
+fn live(ctx: &TickContext) -> bool {
+    !ctx.gate_frozen
+}
+
+fn frozen(ctx: &TickContext) -> bool {
+    ctx.gate_frozen
+}
+
+/// The engine tick: every gameplay system, in order, with the gate-freeze
+/// variants. Built once per host — the browser loop (`lib.rs`) and the
+/// headless [`Simulation`] both run this, so their order cannot drift and
+/// the freeze semantics are host-testable.
+pub fn tick_schedule() -> Schedule<TickContext> {
+    let mut schedule = Schedule::new(&["control", "think", "move", "resolve"]);
+
+    // The finisher runs before the stun tick so it can keep its victim
+    // pinned.
+    schedule.add_system("control", "finisher", FinisherSystem);
+    schedule
+        .add_system("control", "stun", StunSystem)
+        .after("finisher")
+        .run_if(live);
+    schedule.add_system("control", "weapon", WeaponUpdateSystem);
+
+    // Rogues hear this tick's noises before they think.
+    schedule
+        .add_system("think", "noise", NoiseSystem)
+        .run_if(live);
+    schedule
+        .add_system("think", "hush", FnSystem::new(noise::hush))
+        .run_if(frozen);
+    schedule.add_system("think", "ai", AISystem).run_if(live);
+    schedule
+        .add_system("think", "boss", BossSystem)
+        .run_if(live);
     // Pin the cast: enemies keep their pose but never walk under a freeze.
-    for enemy in world.query::<Enemy>() {
-        if let Some(v) = world.get_component_mut::<Velocity>(enemy) {
-            v.x = 0.0;
-            v.y = 0.0;
-        }
+    schedule
+        .add_system("think", "pin_enemies", FnSystem::new(pin_enemies))
+        .run_if(frozen);
+
+    schedule.add_system("move", "movement", MovementSystem);
+    // Doors swing after everyone moved: shoved by where they ended up.
+    schedule
+        .add_system("move", "door", DoorSystem)
+        .after("movement");
+
+    schedule
+        .add_system("resolve", "combat", CombatSystem)
+        .run_if(live);
+    schedule.add_system("resolve", "bullet", BulletSystem);
+    schedule.add_system("resolve", "thrown", ThrownWeaponSystem);
+    schedule.add_system("resolve", "trail", ProjectileTrailSystem);
+    // Drop weapons from enemies downed this tick (player collects via E).
+    schedule
+        .add_system("resolve", "pickup", PickupSystem)
+        .after("bullet")
+        .after("thrown");
+    schedule
+        .add_system("resolve", "stun_floor", FnSystem::new(floor_stun_timers))
+        .after("pickup")
+        .run_if(frozen);
+    schedule
+}
+
+fn pin_enemies(world: &mut World, _dt: f32) {
+    for v in world.query_mut_filtered::<&mut Velocity, With<Enemy>>() {
+        v.x = 0.0;
+        v.y = 0.0;
     }
-    MovementSystem.run(world, dt);
-    BulletSystem.run(world, dt);
-    ThrownWeaponSystem.run(world, dt);
-    ProjectileTrailSystem.run(world, dt);
-    PickupSystem.run(world, dt);
-    // Knockdowns: the fall animation plays (age advances) but the timer is
-    // floored so nobody stands back up while the world holds its breath.
-    for entity in world.query::<Stunned>() {
-        if let Some(stun) = world.get_component_mut::<Stunned>(entity) {
-            stun.timer = (stun.timer - dt).max(GATE_STUN_FLOOR);
-        }
+}
+
+/// Knockdowns under a freeze: the fall animation plays (age advances) but the
+/// timer is floored so nobody stands back up while the world holds its breath.
+fn floor_stun_timers(world: &mut World, dt: f32) {
+    for stun in world.query_mut::<&mut Stunned>() {
+        stun.timer = (stun.timer - dt).max(GATE_STUN_FLOOR);
     }
 }
 
+/// How well the headless bot plays (see [`Simulation::bot_intents`]). The
+/// default is the perfect bot: it reacts on the frame and never misses.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct BotSkill {
+    /// Seconds from deciding to attack to the first press.
+    pub reaction: f32,
+    /// Worst aim error, radians either side of the target.
+    pub aim_error: f32,
+}
+
 /// A headless instance of the full game engine.
 pub struct Simulation {
     pub world: World,
-    finisher: FinisherSystem,
-    stun: StunSystem,
-    weapon: WeaponUpdateSystem,
-    ai: AISystem,
-    boss: BossSystem,
-    movement: MovementSystem,
-    combat: CombatSystem,
-    bullet: BulletSystem,
-    thrown: ThrownWeaponSystem,
-    projectile: ProjectileTrailSystem,
-    pickup: PickupSystem,
+    schedule: Schedule<TickContext>,
     /// Bot navigation state: the player position at the previous `bot_step`,
     /// used to detect when the bot is wedged against geometry.
     bot_prev_pos: Option<Vec2>,
@@ -95,20 +173,42 @@ pub struct Simulation {
     /// along `bot_unstick_dir` instead of steering straight at its target.
     bot_unstick_timer: f32,
     bot_unstick_dir: Vec2,
-    /// Lazily-built fine navigation grid for the bot (walkable flags on a grid
-    /// finer than the AI's `NavigationGrid`, so it can path through the sub-cell
-    /// gaps a 15px-radius player physically fits through). Cached because the
-    /// walls never change during a run.
-    bot_fine_grid: Option<FineGrid>,
+    /// The enemy the bot engaged last tick.
+    bot_target: Option<Entity>,
+    /// How well the bot plays (a perfect shot by default).
+    pub bot_skill: BotSkill,
+    /// Seconds the bot has wanted to attack without a break.
+    bot_engaged: f32,
+    /// Seconds the bot has stood waiting for its charging target to come
+    /// round a corner.
+    bot_ambush: f32,
+    /// The bot's own LCG state (aim scatter), apart from the world's so a
+    /// clumsier bot does not reshuffle the rogues.
+    bot_rng: u32,
+    /// Per-tick state digests, while [`Simulation::log_hashes`] is on.
+    hash_log: Option<Vec<StateDigest>>,
+    /// The events the last [`Simulation::scenario_step`] drained.
+    frame_events: Vec<GameEvent>,
+    /// The floor's statistics, counted off every
+    /// [`Simulation::scenario_step`]'s events.
+    pub stats: FloorStats,
 }
 
 /// A fine walkability grid used only by the headless bot's navigation. Cells are
 /// [`FineGrid::CELL`] wide and a cell is walkable if a player-radius circle at
-/// its centre clears every wall.
+/// its centre clears every wall. Finer than the AI's `NavigationGrid`, so the
+/// bot can path through the sub-cell gaps a 15px-radius player physically
+/// fits through.
+///
+/// Built lazily and kept as a world resource (checkpoints carry it along);
+/// rebuilt when the walls change (a glass wall shattered).
+#[derive(Clone)]
 struct FineGrid {
     cols: usize,
     rows: usize,
     walkable: Vec<bool>,
+    /// The walls it was built for.
+    walls: Vec<crate::ecs::world::Wall>,
 }
 
 impl FineGrid {
@@ -119,6 +219,18 @@ impl FineGrid {
     /// it can physically pass (avoids being overly conservative at cell centres).
     const CLEARANCE: f32 = 13.0;
 
+    /// Insert the grid for `world`'s walls unless it already has one for
+    /// them.
+    fn sync(world: &mut World) {
+        let stale = world
+            .resource::<FineGrid>()
+            .is_none_or(|grid| grid.walls != world.walls());
+        if stale {
+            let grid = FineGrid::build(world.walls());
+            world.insert_resource(grid);
+        }
+    }
+
     fn build(walls: &[crate::ecs::world::Wall]) -> Self {
         let cols = (Self::W / Self::CELL) as usize;
         let rows = (Self::H / Self::CELL) as usize;
@@ -146,6 +258,7 @@ impl FineGrid {
             cols,
             rows,
             walkable,
+            walls: walls.to_vec(),
         }
     }
 
@@ -264,42 +377,49 @@ impl Simulation {
 
     /// Wrap an already-populated world (useful for bespoke test scenarios).
     pub fn from_world(world: World) -> Self {
+        let bot_rng = world.rng_state() ^ BOT_RNG_SALT;
         Simulation {
             world,
-            finisher: FinisherSystem,
-            stun: StunSystem,
-            weapon: WeaponUpdateSystem,
-            ai: AISystem::default(),
-            boss: BossSystem,
-            movement: MovementSystem,
-            combat: CombatSystem,
-            bullet: BulletSystem,
-            thrown: ThrownWeaponSystem,
-            projectile: ProjectileTrailSystem,
-            pickup: PickupSystem,
+            schedule: tick_schedule(),
             bot_prev_pos: None,
             bot_unstick_timer: 0.0,
             bot_unstick_dir: Vec2::zero(),
-            bot_fine_grid: None,
+            bot_target: None,
+            bot_skill: BotSkill::default(),
+            bot_engaged: 0.0,
+            bot_ambush: 0.0,
+            bot_rng,
+            hash_log: None,
+            frame_events: Vec::new(),
+            stats: FloorStats::new(),
+        }
+    }
+
+    /// Start keeping a [`StateDigest`] of the world after every tick (see
+    /// [`crate::state_hash`]). Debugging aid: each digest holds a hash per
+    /// component row.
+    pub fn log_hashes(&mut self) {
+        self.hash_log = Some(Vec::new());
+    }
+
+    /// The digests logged so far, one per tick (empty unless
+    /// [`Simulation::log_hashes`] is on).
+    pub fn hash_log(&self) -> &[StateDigest] {
+        self.hash_log.as_deref().unwrap_or_default()
+    }
+
+    fn log_state(&mut self) {
+        if let Some(log) = self.hash_log.as_mut() {
+            log.push(StateDigest::of(&self.world));
         }
     }
 
-    /// Advance the whole simulation by `dt` seconds — one engine tick, running
-    /// every gameplay system in the same order as the browser loop.
+    /// Advance the whole simulation by `dt` seconds — one engine tick of
+    /// [`tick_schedule`], the same schedule the browser loop runs.
     pub fn step(&mut self, dt: f32) {
-        // The finisher runs before the stun tick so it can keep its victim
-        // pinned (same order as the browser loop).
-        self.finisher.run(&mut self.world, dt);
-        self.stun.run(&mut self.world, dt);
-        self.weapon.run(&mut self.world, dt);
-        self.ai.run(&mut self.world, dt);
-        self.boss.run(&mut self.world, dt);
-        self.movement.run(&mut self.world, dt);
-        self.combat.run(&mut self.world, dt);
-        self.bullet.run(&mut self.world, dt);
-        self.thrown.run(&mut self.world, dt);
-        self.projectile.run(&mut self.world, dt);
-        self.pickup.run(&mut self.world, dt);
+        self.schedule
+            .run(&mut self.world, &TickContext::default(), dt);
+        self.log_state();
     }
 
     /// Run `frames` ticks of `dt` seconds each (fast-forward).
@@ -310,16 +430,19 @@ impl Simulation {
     }
 
     /// One full frame WITH a floor scenario, mirroring the browser loop's
-    /// order: the frozen path while a tutorial gate is active
-    /// ([`gate_frozen_step`]), otherwise the normal [`Simulation::step`];
-    /// then the scenario tick, then the frame's events are drained and fed
-    /// to the gate ([`ScenarioState::gate_notify`]). Returns `true` when a
-    /// `checkpoint` action requested a snapshot this frame — the caller
-    /// clones the world + scenario (both are `Clone`) and restores them on
-    /// death.
+    /// order: the schedule's frozen variant while a tutorial gate is active
+    /// ([`TickContext::gate_frozen`]), otherwise the normal
+    /// [`Simulation::step`];
+    /// then the scenario tick, then the frame's events are drained, fed
+    /// to the gate ([`ScenarioState::gate_notify`]) and counted into
+    /// [`Simulation::stats`] (whose clock runs while the player is alive and
+    /// not yet extracted). Returns `true` when a `checkpoint` action
+    /// requested a snapshot this frame — the caller clones the world +
+    /// scenario (both are `Clone`) and restores them on death.
     pub fn scenario_step(&mut self, sc: &mut ScenarioState, dt: f32) -> bool {
         if sc.gate_view().is_some() {
-            gate_frozen_step(&mut self.world, dt);
+            let ctx = TickContext { gate_frozen: true };
+            self.schedule.run(&mut self.world, &ctx, dt);
             // Invisible walls: keep the player near the gate's target (the
             // browser loop does the same).
             if let Some(anchor) = sc.gate_anchor() {
@@ -330,14 +453,26 @@ impl Simulation {
                 );
             }
         } else {
-            self.step(dt);
+            self.schedule
+                .run(&mut self.world, &TickContext::default(), dt);
         }
         sc.tick(&mut self.world, dt);
-        let events = self.world.drain_events();
-        sc.gate_notify(&mut self.world, &events);
+        if self.player_alive() && ElevatorSystem::extraction(&self.world).is_none() {
+            self.stats.tick(dt);
+        }
+        self.frame_events = self.world.drain_events();
+        self.stats.observe(&self.frame_events);
+        sc.gate_notify(&mut self.world, &self.frame_events);
+        self.log_state();
         sc.take_checkpoint_request()
     }
 
+    /// The gameplay events of the last [`Simulation::scenario_step`] (what
+    /// the browser turns into sounds and the gate listens to).
+    pub fn frame_events(&self) -> &[GameEvent] {
+        &self.frame_events
+    }
+
     // --- Scripted player actions (stand-ins for keyboard/mouse input) ---
 
     /// The player entity, if one exists.
@@ -401,27 +536,19 @@ impl Simulation {
 
     // --- Query helpers used by the headless bot / gate tests ---
 
-    /// Position of the nearest *alive* enemy to the given point, if any.
-    pub fn nearest_alive_enemy(&self, from: Vec2) -> Option<(Entity, Vec2)> {
-        self.world
-            .query::<Enemy>()
-            .into_iter()
-            .filter(|&e| {
-                self.world
+    /// Position of the nearest *alive* enemy to the given point, if any
+    /// (through the spatial index, so a close enemy is found without
+    /// walking the whole floor).
+    pub fn nearest_alive_enemy(&mut self, from: Vec2) -> Option<(Entity, Vec2)> {
+        SpatialIndex::sync(&mut self.world);
+        let world = &self.world;
+        let enemy = SpatialIndex::of(world).nearest(from, |e| {
+            world.has_component::<Enemy>(e)
+                && world
                     .get_component::<Health>(e)
-                    .map(|h| h.is_alive())
-                    .unwrap_or(false)
-            })
-            .filter_map(|e| {
-                self.world
-                    .get_component::<Position>(e)
-                    .map(|p| (e, p.to_vec2()))
-            })
-            .min_by(|(_, a), (_, b)| {
-                from.distance(*a)
-                    .partial_cmp(&from.distance(*b))
-                    .unwrap_or(std::cmp::Ordering::Equal)
-            })
+                    .is_some_and(|h| h.is_alive())
+        })?;
+        Some((enemy, world.get_component::<Position>(enemy)?.to_vec2()))
     }
 
     /// All alive enemies as `(entity, position)`, sorted nearest-first from
@@ -453,9 +580,23 @@ impl Simulation {
 
     /// Position of the nearest weapon pickup to the given point, if any.
     pub fn nearest_pickup(&self, from: Vec2) -> Option<Vec2> {
+        self.nearest_pickup_where(from, |_| true)
+    }
+
+    /// [`Simulation::nearest_pickup`], among the pickups that `fit`.
+    fn nearest_pickup_where(
+        &self,
+        from: Vec2,
+        fit: impl Fn(&WeaponPickup) -> bool,
+    ) -> Option<Vec2> {
         self.world
             .query::<WeaponPickup>()
             .into_iter()
+            .filter(|&e| {
+                self.world
+                    .get_component::<WeaponPickup>(e)
+                    .is_some_and(&fit)
+            })
             .filter_map(|e| self.world.get_component::<Position>(e).map(|p| p.to_vec2()))
             .min_by(|a, b| {
                 from.distance(*a)
@@ -464,12 +605,32 @@ impl Simulation {
             })
     }
 
-    /// The player's current movement speed (defaults to a sane value if missing).
-    fn player_speed(&self) -> f32 {
-        self.player()
-            .and_then(|p| self.world.get_component::<Speed>(p))
-            .map(|s| s.value)
-            .unwrap_or(200.0)
+    /// Head for the nearest pickup that `fits`, pressing E once on top of it
+    /// (the swap takes whatever lies underfoot, so not on the way). Returns
+    /// the point to walk toward, if there is such a pickup.
+    fn bot_fetch(
+        &self,
+        me: Vec2,
+        intents: &mut PlayerIntents,
+        fit: impl Fn(&WeaponPickup) -> bool,
+    ) -> Option<Vec2> {
+        let pickup = self.nearest_pickup_where(me, fit)?;
+        intents.e_pressed = me.distance(pickup) <= BOT_PICKUP_RADIUS;
+        Some(pickup)
+    }
+
+    /// Position of the alive enemy nearest `near` that is knocked down
+    /// (`downed`) or on its feet (`!downed`), if any.
+    fn enemy_near(&self, near: Vec2, downed: bool) -> Option<Vec2> {
+        self.alive_enemies_by_distance(near)
+            .into_iter()
+            .find(|&(e, _)| {
+                self.world
+                    .get_component::<Stunned>(e)
+                    .is_some_and(|s| s.is_active())
+                    == downed
+            })
+            .map(|(_, p)| p)
     }
 
     /// Whether the player currently holds a weapon that can actually deal damage
@@ -479,7 +640,7 @@ impl Simulation {
             .player()
             .and_then(|p| self.world.get_component::<Weapon>(p))
         {
-            Some(w) => w.weapon_type == WeaponType::Melee || w.ammo > 0,
+            Some(w) => w.weapon_type == WeaponType::MELEE || w.ammo > 0,
             None => false,
         }
     }
@@ -494,60 +655,51 @@ impl Simulation {
         target: Vec2,
         walls: &[crate::ecs::world::Wall],
     ) -> Vec2 {
-        // Clear shot: just go straight.
-        if has_line_of_sight(from, target, walls) {
+        // Clear way: just go straight (a door in the way swings open; glass
+        // does not, hence no bare sight check). The pixel of padding keeps a
+        // line running flush along a wall's face from counting as clear.
+        if has_line_of_sight_with_padding(from, target, walls, 1.0) {
             return (target - from).normalize();
         }
 
         // Fine BFS grid (built once, cached) — matches player physics closely.
-        if self.bot_fine_grid.is_none() {
-            self.bot_fine_grid = Some(FineGrid::build(walls));
-        }
-        if let Some(grid) = &self.bot_fine_grid {
-            if let Some(wp) = grid.next_step(from, target) {
-                let d = wp - from;
-                if d.length() > 1.0 {
-                    return d.normalize();
-                }
+        FineGrid::sync(&mut self.world);
+        if let Some(wp) = self
+            .world
+            .resource::<FineGrid>()
+            .and_then(|grid| grid.next_step(from, target))
+        {
+            let d = wp - from;
+            if d.length() > 1.0 {
+                return d.normalize();
             }
         }
 
-        // Coarse grid fallback.
-        let grid = NavigationGrid::new(walls);
-        if let Some(wp) = grid.get_next_waypoint(from, target) {
+        // Coarse grid fallback: the AI's shared grid.
+        NavigationGrid::sync(&mut self.world);
+        if let Some(wp) = self
+            .world
+            .resource::<NavigationGrid>()
+            .and_then(|grid| grid.get_next_waypoint(from, target))
+        {
             return (wp - from).normalize();
         }
         (target - from).normalize()
     }
 
-    /// Drive the player toward a world point, with anti-stuck handling.
-    fn navigate_toward(&mut self, target: Vec2) {
-        let from = match self.player_position() {
-            Some(p) => p,
-            None => return,
-        };
-        let speed = self.player_speed();
-        let walls = self.world.walls().to_vec();
-
-        // If currently unsticking, strafe along the chosen direction.
-        if self.bot_unstick_timer > 0.0 {
-            let dir = self.bot_unstick_dir;
-            if dir.length() > 0.0 {
-                self.set_player_velocity(dir * speed);
-                return;
-            }
-        }
-
-        let dir = self.nav_direction(from, target, &walls);
-        if dir.length() > 0.0 {
-            self.set_player_velocity(dir * speed);
-        } else {
-            self.set_player_velocity(Vec2::zero());
+    /// The direction the bot walks this tick to head toward `target`: the
+    /// unstick strafe while one runs, otherwise [`Simulation::nav_direction`].
+    fn bot_move_dir(&mut self, from: Vec2, target: Vec2) -> Vec2 {
+        if self.bot_unstick_timer > 0.0 && self.bot_unstick_dir.length() > 0.0 {
+            return self.bot_unstick_dir;
         }
+        let walls = self.world.walls().to_vec();
+        self.nav_direction(from, target, &walls)
     }
 
     /// Detect whether the bot barely moved since the last tick while it *wanted*
-    /// to move, and if so kick off an unstick strafe perpendicular to its goal.
+    /// to move, and if so kick off an unstick maneuver: the fine grid's way
+    /// round, or a strafe perpendicular to its goal.
     fn update_stuck_state(&mut self, want_move_toward: Option<Vec2>, dt: f32) {
         if self.bot_unstick_timer > 0.0 {
             self.bot_unstick_timer -= dt;
@@ -558,123 +710,938 @@ impl Simulation {
         };
         if let (Some(prev), Some(goal)) = (self.bot_prev_pos, want_move_toward) {
             let moved = cur.distance(prev);
-            // Wanted to move but barely did => wedged. Start/refresh a strafe.
-            if moved < 0.5 && self.bot_unstick_timer <= 0.0 {
-                let to_goal = (goal - cur).normalize();
-                // Perpendicular strafe; pick the side using a cheap deterministic
-                // hash of the position so it varies but replays identically.
-                let perp = Vec2::new(-to_goal.y, to_goal.x);
-                let sign = if ((cur.x as i64) ^ (cur.y as i64)) & 1 == 0 {
-                    1.0
-                } else {
-                    -1.0
+            let progress = prev.distance(goal) - cur.distance(goal);
+            // Wanted to move but barely did, or only crept along a wall
+            // getting no nearer => wedged. Start/refresh a strafe.
+            let wedged = moved < 0.5 || (moved < 1.0 && progress < 0.1);
+            if wedged && self.bot_unstick_timer <= 0.0 {
+                // Wedged on a corner the bare sight line cut: the fine grid
+                // (which knows the player's width) leads round it. Failing
+                // that, strafe perpendicular to the goal; pick the side using
+                // a cheap deterministic hash of the position so it varies but
+                // replays identically.
+                let cut = !has_line_of_sight_with_padding(
+                    cur,
+                    goal,
+                    self.world.walls(),
+                    FineGrid::CLEARANCE,
+                );
+                FineGrid::sync(&mut self.world);
+                let step = self
+                    .world
+                    .resource::<FineGrid>()
+                    .filter(|_| cut)
+                    .and_then(|grid| grid.next_step(cur, goal))
+                    .filter(|&wp| wp.distance(cur) > 1.0);
+                self.bot_unstick_dir = match step {
+                    Some(wp) => (wp - cur).normalize(),
+                    None => {
+                        let to_goal = (goal - cur).normalize();
+                        let perp = Vec2::new(-to_goal.y, to_goal.x);
+                        let sign = if ((cur.x as i64) ^ (cur.y as i64)) & 1 == 0 {
+                            1.0
+                        } else {
+                            -1.0
+                        };
+                        (perp * sign + to_goal * 0.3).normalize()
+                    }
                 };
-                self.bot_unstick_dir = (perp * sign + to_goal * 0.3).normalize();
                 self.bot_unstick_timer = 0.3;
             }
         }
         self.bot_prev_pos = Some(cur);
     }
 
-    /// One tick of a simple, robust heuristic bot, then advance the simulation
-    /// by `dt`. One call == one advanced frame.
-    ///
-    /// Priorities each tick:
-    /// 1. If there are no alive enemies, stop and just tick.
-    /// 2. If the player has no usable weapon, head for the nearest weapon pickup
-    ///    (picking it up when close); if there are none, advance on the enemy to
-    ///    melee / recover a dropped weapon.
-    /// 3. If there's a clear line of sight to the nearest enemy, aim and fire
-    ///    (closing the distance a little if far away).
-    /// 4. Otherwise navigate toward the enemy via the pathfinder.
+    /// One tick of a simple, robust heuristic bot that hunts every rogue
+    /// (no scenario: see [`FloorRun::bot_frame`] for the campaign bot), then
+    /// advance the simulation by `dt`. One call == one advanced frame.
     pub fn bot_step(&mut self, dt: f32) {
-        let player_pos = match self.player_position() {
-            Some(p) => p,
+        let intents = self.bot_intents(None, dt);
+        apply_player_input(&mut self.world, None, &intents);
+        self.step(dt);
+    }
+
+    /// What the bot does this tick, as the [`PlayerIntents`] a player would
+    /// produce — so it drives the same input dispatch as the browser.
+    ///
+    /// Without a scenario it only fights (see [`Simulation::bot_hunt`]).
+    /// With one it plays the floor: it clicks through conversations, waits
+    /// out `hold`s, satisfies the active tutorial gate, walks into an open
+    /// exit, fights the hostile rogues, walks into the zone the next
+    /// `enter_zone` step waits for, and finally clears out what is left.
+    pub fn bot_intents(&mut self, scenario: Option<&ScenarioState>, dt: f32) -> PlayerIntents {
+        let Some(me) = self.player_position() else {
+            return PlayerIntents::idle(Vec2::zero());
+        };
+        // Idle aim: straight ahead, so standing still does not turn the player.
+        let facing = self
+            .player()
+            .and_then(|p| self.world.get_component::<Rotation>(p))
+            .map_or(0.0, |r| r.angle);
+        let mut intents = PlayerIntents::idle(me + Vec2::new(facing.cos(), facing.sin()));
+
+        let goal = match scenario {
             None => {
-                self.step(dt);
-                return;
+                let targets = self.alive_enemies_by_distance(me);
+                if targets.is_empty() {
+                    None
+                } else {
+                    self.bot_hunt(me, &targets, &mut intents, dt)
+                }
+            }
+            Some(sc) if sc.dialogue_active() => {
+                intents.advance_pressed = true;
+                None
             }
+            Some(sc) if sc.hold_active() => None,
+            Some(sc) => match sc.gate_view() {
+                Some(gate) => self.bot_gate(gate.input, sc.gate_anchor(), me, &mut intents),
+                None => self.bot_scenario_goal(sc, me, &mut intents, dt),
+            },
         };
+        // Close enough counts as there: no strafing around a reached goal.
+        let goal = goal.filter(|&g| me.distance(g) > BOT_ARRIVE_RADIUS);
+        if let Some(goal) = goal {
+            intents.move_dir = self.bot_move_dir(me, goal);
+        }
+        // Detect wedging and (next tick) strafe out of it.
+        self.update_stuck_state(goal, dt);
+        self.apply_bot_skill(me, &mut intents, dt);
+        intents
+    }
 
-        let candidates = self.alive_enemies_by_distance(player_pos);
-        if candidates.is_empty() {
-            // Nothing to fight; stand still.
-            self.set_player_velocity(Vec2::zero());
-            self.bot_prev_pos = Some(player_pos);
-            self.step(dt);
+    /// Play like a person: hold the buttons for the first
+    /// [`BotSkill::reaction`] seconds of an engagement, and scatter the aim
+    /// by up to [`BotSkill::aim_error`] either way.
+    fn apply_bot_skill(&mut self, me: Vec2, intents: &mut PlayerIntents, dt: f32) {
+        let attacking = intents.left_pressed || intents.left_down || intents.right_pressed;
+        if !attacking {
+            self.bot_engaged = 0.0;
             return;
         }
+        self.bot_engaged += dt;
+        if self.bot_engaged < self.bot_skill.reaction {
+            intents.left_pressed = false;
+            intents.left_down = false;
+            intents.right_pressed = false;
+        }
+        if self.bot_skill.aim_error > 0.0 {
+            self.bot_rng = self.bot_rng.wrapping_mul(1664525).wrapping_add(1013904223);
+            let r = self.bot_rng as f32 / u32::MAX as f32;
+            let off = (r * 2.0 - 1.0) * self.bot_skill.aim_error;
+            let aim = intents.mouse_world - me;
+            let (sin, cos) = off.sin_cos();
+            intents.mouse_world =
+                me + Vec2::new(aim.x * cos - aim.y * sin, aim.x * sin + aim.y * cos);
+        }
+    }
 
+    /// Fight: engage the nearest of `targets` (alive enemies, nearest first,
+    /// never empty) the bot can actually reach. Returns the point to walk
+    /// toward, if any.
+    ///
+    /// 1. If the player has no usable weapon, head for the nearest weapon
+    ///    pickup (pressing E on the way); if there are none, advance on the
+    ///    enemy and punch it.
+    /// 2. If there's a clear line of sight to the enemy, aim and fire
+    ///    (closing the distance if far — to swing range with a melee weapon).
+    /// 3. Otherwise navigate toward the enemy via the pathfinder.
+    fn bot_hunt(
+        &mut self,
+        me: Vec2,
+        targets: &[(Entity, Vec2)],
+        intents: &mut PlayerIntents,
+        dt: f32,
+    ) -> Option<Vec2> {
         // Prefer the nearest enemy we can actually engage: one with a clear shot,
         // or one the fine navigation grid can path to. This lets the bot skip
         // over an enemy it currently can't reach (e.g. one that wandered into an
         // awkward spot) and clear the rest, rather than fixating and stalling.
-        let walls = self.world.walls().to_vec();
-        if self.bot_fine_grid.is_none() {
-            self.bot_fine_grid = Some(FineGrid::build(&walls));
-        }
-        let mut chosen: Option<Vec2> = None;
-        for &(_e, pos) in &candidates {
-            let reachable = has_line_of_sight(player_pos, pos, &walls)
-                || self
-                    .bot_fine_grid
-                    .as_ref()
-                    .map(|g| g.next_step(player_pos, pos).is_some())
-                    .unwrap_or(false);
-            if reachable {
-                chosen = Some(pos);
-                break;
+        FineGrid::sync(&mut self.world);
+        let walls = self.world.walls();
+        let doors = door_leaves(&self.world);
+        let fine_grid = self.world.resource::<FineGrid>();
+        let reachable = |pos: Vec2| {
+            has_line_of_sight(me, pos, walls, &doors)
+                || fine_grid.is_some_and(|g| g.next_step(me, pos).is_some())
+        };
+        // Stick with last tick's target while it stays reachable — re-picking
+        // the nearest every tick dithers between two enemies at about the
+        // same distance, one step toward each — unless one in plain sight
+        // is clearly nearer (it is the one shooting).
+        let kept = targets
+            .iter()
+            .find(|&&(e, pos)| Some(e) == self.bot_target && reachable(pos));
+        let in_sight = targets
+            .iter()
+            .find(|&&(_, pos)| has_line_of_sight(me, pos, walls, &doors));
+        // A rogue winding up a shot at us (the red telegraph) trumps both:
+        // answer it first.
+        let aiming = targets.iter().find(|&&(e, pos)| {
+            has_line_of_sight(me, pos, walls, &doors)
+                && self
+                    .world
+                    .get_component::<AI>(e)
+                    .is_some_and(|ai| ai.aim_timer > 0.0)
+        });
+        let chosen = match (aiming, kept, in_sight) {
+            (Some(a), _, _) => Some(a),
+            (None, Some(k), Some(s))
+                if me.distance(s.1) + BOT_RETARGET_MARGIN < me.distance(k.1) =>
+            {
+                Some(s)
             }
-        }
+            (None, Some(k), _) => Some(k),
+            (None, None, _) => targets.iter().find(|&&(_, pos)| reachable(pos)),
+        };
         // If none look reachable, just target the nearest and try anyway.
-        let enemy_pos = chosen.unwrap_or(candidates[0].1);
-
-        // The point (if any) the bot is actively trying to walk toward this tick;
-        // used for stuck detection.
-        let mut move_goal: Option<Vec2> = None;
-
-        if !self.player_has_usable_weapon() {
-            // Need a weapon: grab the nearest pickup, or advance to melee range.
-            if let Some(pickup_pos) = self.nearest_pickup(player_pos) {
-                // Try to grab it every tick; the swap only succeeds once we're
-                // physically overlapping it. Keep closing the distance until it
-                // does (a fixed "close enough" threshold can leave the bot parked
-                // just outside the real pickup radius, deadlocked).
-                if self.player_pickup().is_some() {
-                    self.set_player_velocity(Vec2::zero());
-                } else {
-                    move_goal = Some(pickup_pos);
-                }
-            } else {
-                // No pickups exist: close on the enemy (a dropped weapon may
-                // appear, or a melee weapon may still connect).
-                move_goal = Some(enemy_pos);
+        let (enemy, enemy_pos) = *chosen.unwrap_or(&targets[0]);
+        let retargeted = self.bot_target != Some(enemy);
+        self.bot_target = Some(enemy);
+        // A shot grazing a wall corner hits the wall: keep a margin. Glass
+        // in the way does not count (the round shatters it and flies on).
+        let solid: Vec<_> = walls.iter().filter(|w| !w.is_glass()).copied().collect();
+        let clear_shot = has_line_of_sight_with_padding(me, enemy_pos, &solid, BOT_SHOT_MARGIN)
+            && !doors.iter().any(|door| door.crosses(me, enemy_pos));
+        if retargeted || clear_shot {
+            self.bot_ambush = 0.0;
+        }
+        intents.mouse_world = enemy_pos;
+
+        let held = get_player_weapon(&self.world);
+        let ranged = held.is_some_and(|w| !w.is_melee());
+        // A rogue winding up on us that we cannot hit first — no clean
+        // shot, no loaded gun and out of reach, or it fires before our
+        // round lands — wins a trade from here: duck out of its sight (the
+        // ambush below then takes it as it comes round).
+        let armed = ranged && self.player_has_usable_weapon();
+        let strike_reach = held.and_then(|w| w.def().reach).unwrap_or(PUNCH_RANGE) * 0.8;
+        let cover = aiming
+            .filter(|&&(e, pos)| {
+                let range = me.distance(pos);
+                !clear_shot
+                    || if armed {
+                        self.bot_outdrawn(e, range, dt)
+                    } else {
+                        range > strike_reach
+                    }
+            })
+            .and_then(|&(_, shooter)| self.bot_cover(me, shooter));
+        if cover.is_some() {
+            self.bot_ambush = 0.0;
+            return cover;
+        }
+        let reach = if !self.player_has_usable_weapon() {
+            // Need a weapon: grab the nearest one that works (an emptied gun
+            // is worth nothing).
+            let goal = self.bot_fetch(me, intents, |w| w.weapon_type.is_melee() || w.ammo > 0);
+            if goal.is_some() {
+                return goal;
+            }
+            // None left: close on the enemy and punch (a dropped weapon may
+            // appear; the click finishes a downed one), throwing the empty
+            // gun first to free the hands.
+            let throw_range = held.map_or(0.0, |w| w.def().throw.range);
+            if ranged && clear_shot && me.distance(enemy_pos) <= throw_range * 0.5 {
+                intents.right_pressed = true;
+            }
+            PUNCH_RANGE * 0.8
+        } else if ranged && aiming.is_some() {
+            // Trade shots from where we stand rather than walk into its cone.
+            f32::INFINITY
+        } else if ranged {
+            BOT_FIRE_RANGE
+        } else {
+            // A loaded gun beats the bar: take the detour for one close by.
+            let gun = self
+                .nearest_pickup_where(me, |w| !w.weapon_type.is_melee() && w.ammo > 0)
+                .filter(|&p| me.distance(p) <= BOT_GUN_DETOUR);
+            if let Some(gun) = gun {
+                intents.e_pressed = me.distance(gun) <= BOT_PICKUP_RADIUS;
+                return Some(gun);
             }
+            strike_reach
+        };
+        if clear_shot && me.distance(enemy_pos) <= reach {
+            // In range: hold position and keep attacking.
+            intents.left_pressed = true;
+            intents.left_down = true;
+            None
+        } else if ranged
+            && !clear_shot
+            && self.bot_target_charging(enemy)
+            && self.bot_ambush < BOT_AMBUSH_PATIENCE
+        {
+            // It is coming round the corner: let it, gun up (for a while —
+            // one wedged on the corner never will).
+            self.bot_ambush += dt;
+            None
         } else {
-            // Armed: engage the enemy.
-            if has_line_of_sight(player_pos, enemy_pos, &walls) {
-                // Fire at the target.
-                let _ = self.player_fire(enemy_pos);
-                // Close in if far, otherwise hold position and keep firing.
-                if player_pos.distance(enemy_pos) > 250.0 {
-                    move_goal = Some(enemy_pos);
+            // A gun fires on the way in; otherwise path toward the enemy.
+            if clear_shot && ranged {
+                intents.left_down = true;
+            }
+            Some(enemy_pos)
+        }
+    }
+
+    /// Whether the rogue `enemy`, `range` px off and winding up on the
+    /// player, fires before a round from the player's gun could reach it:
+    /// the gun has to come ready, the trigger to be read on the next tick
+    /// and the round to fly.
+    fn bot_outdrawn(&self, enemy: Entity, range: f32, dt: f32) -> bool {
+        let Some(lands_in) = self
+            .player()
+            .and_then(|p| self.world.get_component::<Weapon>(p))
+            .map(|w| w.fire_timer.max(0.0) + dt + range / Bullet::new(w.weapon_type, 0).speed)
+        else {
+            return false;
+        };
+        let fires_in = self
+            .world
+            .get_component::<AI>(enemy)
+            .zip(self.world.get_component::<Weapon>(enemy))
+            .and_then(|(ai, w)| enemy_gun(w.weapon_type).map(|gun| gun.windup - ai.aim_timer));
+        fires_in.is_some_and(|t| lands_in >= t)
+    }
+
+    /// The nearest spot within [`BOT_COVER_RANGE`] the bot can walk straight
+    /// to that a rogue at `shooter` cannot see, if any.
+    fn bot_cover(&self, me: Vec2, shooter: Vec2) -> Option<Vec2> {
+        let grid = self.world.resource::<FineGrid>()?;
+        let walls = self.world.walls();
+        let doors = door_leaves(&self.world);
+        let (ci, cj) = grid.cell_of(me);
+        let r = (BOT_COVER_RANGE / FineGrid::CELL).ceil() as i32;
+        let mut best: Option<(f32, Vec2)> = None;
+        for j in cj - r..=cj + r {
+            for i in ci - r..=ci + r {
+                if !grid.is_walkable(i, j) {
+                    continue;
+                }
+                let spot = grid.center(i, j);
+                let d = me.distance(spot);
+                if d > BOT_COVER_RANGE || best.is_some_and(|(nearest, _)| d >= nearest) {
+                    continue;
+                }
+                // Hidden with room to spare: the walls still block its view
+                // shrunk by a margin (the bot stops a few px short).
+                if !has_line_of_sight_with_padding(shooter, spot, walls, -BOT_COVER_MARGIN)
+                    && has_line_of_sight(me, spot, walls, &doors)
+                {
+                    best = Some((d, spot));
+                }
+            }
+        }
+        best.map(|(_, spot)| spot)
+    }
+
+    /// Whether `enemy` has seen the player for sure and is on its way.
+    fn bot_target_charging(&self, enemy: Entity) -> bool {
+        self.world
+            .get_component::<AI>(enemy)
+            .is_some_and(|ai| ai.state == AIState::SurePlayerSeen)
+    }
+
+    /// Satisfy the active tutorial gate (see [`GateInput`]): fetch the weapon
+    /// its action needs, walk up to its target — the enemy nearest the
+    /// gate's anchor — and do the one thing it waits for. Returns the point
+    /// to walk toward, if any.
+    fn bot_gate(
+        &mut self,
+        input: GateInput,
+        anchor: Option<Vec2>,
+        me: Vec2,
+        intents: &mut PlayerIntents,
+    ) -> Option<Vec2> {
+        let near = anchor.unwrap_or(me);
+        match input {
+            GateInput::Pickup => self.bot_fetch(me, intents, |_| true),
+            GateInput::Finish => {
+                let target = self.enemy_near(near, true)?;
+                intents.mouse_world = target;
+                intents.left_pressed = true;
+                (me.distance(target) > FINISHER_RANGE * 0.5).then_some(target)
+            }
+            GateInput::Punch | GateInput::Strike | GateInput::Fire | GateInput::Throw => {
+                let weapon = get_player_weapon(&self.world);
+                let armed = match input {
+                    // Nothing to fetch: the step disarms the player first.
+                    GateInput::Punch => true,
+                    GateInput::Throw => weapon.is_some(),
+                    _ => input.allows_primary(weapon),
+                };
+                if !armed {
+                    return self.bot_fetch(me, intents, |w| match input {
+                        GateInput::Strike => w.weapon_type.is_melee(),
+                        GateInput::Fire => !w.weapon_type.is_melee() && w.ammo > 0,
+                        _ => true,
+                    });
+                }
+                let target = self.enemy_near(near, false)?;
+                intents.mouse_world = target;
+                let reach = match input {
+                    GateInput::Punch => PUNCH_RANGE * 0.8,
+                    GateInput::Strike => {
+                        weapon.and_then(|w| w.def().reach).unwrap_or(PUNCH_RANGE) * 0.8
+                    }
+                    _ => BOT_FIRE_RANGE,
+                };
+                let doors = door_leaves(&self.world);
+                if me.distance(target) <= reach
+                    && has_line_of_sight(me, target, self.world.walls(), &doors)
+                {
+                    if input == GateInput::Throw {
+                        intents.right_pressed = true;
+                    } else {
+                        intents.left_pressed = true;
+                        intents.left_down = true;
+                    }
+                    None
                 } else {
-                    self.set_player_velocity(Vec2::zero());
+                    Some(target)
                 }
-            } else {
-                // No line of sight: path toward the enemy.
-                move_goal = Some(enemy_pos);
             }
         }
+    }
 
-        if let Some(goal) = move_goal {
-            self.navigate_toward(goal);
+    /// The campaign bot's plan while no gate or conversation is up: ride an
+    /// open exit out (answering any rogue winding up a shot on the way);
+    /// else fight the hostile rogues (when the scenario allows combat); else
+    /// walk into the zone of the next pending `enter_zone` step; else clear
+    /// out what is left — passive bots included, since `all_dead` counts
+    /// them.
+    fn bot_scenario_goal(
+        &mut self,
+        sc: &ScenarioState,
+        me: Vec2,
+        intents: &mut PlayerIntents,
+        dt: f32,
+    ) -> Option<Vec2> {
+        let exit = self
+            .world
+            .query::<Elevator>()
+            .into_iter()
+            .filter_map(|e| self.world.get_component::<Elevator>(e))
+            .filter(|elev| elev.is_exit && elev.open)
+            .map(|elev| Vec2::new(elev.x + elev.w / 2.0, elev.y + elev.h / 2.0))
+            .min_by(|a, b| me.distance(*a).total_cmp(&me.distance(*b)));
+        let enemies = self.alive_enemies_by_distance(me);
+        if exit.is_some() {
+            // Turning our back on a rogue about to fire loses the run in
+            // sight of the lift.
+            let walls = self.world.walls();
+            let doors = door_leaves(&self.world);
+            let aiming: Vec<(Entity, Vec2)> = enemies
+                .iter()
+                .copied()
+                .filter(|&(e, pos)| {
+                    self.world
+                        .get_component::<AI>(e)
+                        .is_some_and(|ai| ai.aim_timer > 0.0)
+                        && has_line_of_sight(me, pos, walls, &doors)
+                })
+                .collect();
+            if !aiming.is_empty() {
+                return self.bot_hunt(me, &aiming, intents, dt);
+            }
+            return exit;
         }
 
-        // Detect wedging and (next tick) strafe out of it.
-        self.update_stuck_state(move_goal, dt);
+        if sc.combat_enabled() {
+            let hostile: Vec<(Entity, Vec2)> = enemies
+                .iter()
+                .copied()
+                .filter(|&(e, _)| {
+                    self.world
+                        .get_component::<AI>(e)
+                        .is_none_or(|ai| ai.state != AIState::Passive)
+                })
+                .collect();
+            if !hostile.is_empty() {
+                return self.bot_hunt(me, &hostile, intents, dt);
+            }
+        }
+        if let Some(zone) = pending_zone(sc) {
+            return Some(zone);
+        }
+        if sc.combat_enabled() && !enemies.is_empty() {
+            return self.bot_hunt(me, &enemies, intents, dt);
+        }
+        None
+    }
+}
 
-        self.step(dt);
+/// Mixed into the seed of the bot's own RNG.
+const BOT_RNG_SALT: u32 = 0x05ee_db07;
+
+/// The campaign bot stops walking this close to its goal.
+const BOT_ARRIVE_RADIUS: f32 = 4.0;
+
+/// Clearance the bot wants around a wall corner before it shoots past it
+/// (a bullet has a radius).
+const BOT_SHOT_MARGIN: f32 = 4.0;
+
+/// Seconds the bot waits for a charging enemy to come round a corner
+/// before it goes in after it.
+const BOT_AMBUSH_PATIENCE: f32 = 3.0;
+
+/// How far (px) the bot looks for cover from a rogue about to outdraw it.
+const BOT_COVER_RANGE: f32 = 80.0;
+
+/// How far (px) into a wall's shadow cover has to be.
+const BOT_COVER_MARGIN: f32 = 6.0;
+
+/// How far out of its way a bot holding a melee weapon walks for a gun.
+const BOT_GUN_DETOUR: f32 = 300.0;
+
+/// How much nearer an enemy in sight must be before the bot switches to it.
+const BOT_RETARGET_MARGIN: f32 = 40.0;
+
+/// The bot presses E this close to the pickup it is after: well inside the
+/// player's own radius, so the swap cannot grab a neighbouring one.
+const BOT_PICKUP_RADIUS: f32 = 8.0;
+
+/// The bot walks up to this distance of its target before it stands and
+/// shoots.
+const BOT_FIRE_RANGE: f32 = 250.0;
+
+/// The centre of the zone the first `enter_zone` step still to fire waits
+/// for, in step order. Steps with a `before` are left out: they react to
+/// wandering off the path, they are never the way forward.
+fn pending_zone(sc: &ScenarioState) -> Option<Vec2> {
+    let floor = sc.floor();
+    floor
+        .scenario
+        .iter()
+        .filter(|step| !sc.step_fired(step.id))
+        .find_map(|step| match step.trigger {
+            Trigger::EnterZone { zone, before: None } => floor.zone(zone).map(|z| z.rect.center()),
+            _ => None,
+        })
+}
+
+/// One floor attempt driven frame by frame the way the browser loop drives
+/// it: the player's [`PlayerIntents`] go through the shared dispatch
+/// ([`apply_player_input`]), then the scenario frame
+/// ([`Simulation::scenario_step`]), the elevators, and the extraction check,
+/// with the frame's events scored ([`Score`]). What [`crate::replay`] re-runs
+/// recordings through.
+pub struct FloorRun {
+    pub sim: Simulation,
+    pub scenario: ScenarioState,
+    /// Frames run so far.
+    pub frames: usize,
+    /// Set once the player has extracted: the destination floor id. Input is
+    /// ignored from then on, as in the browser.
+    pub extracted: Option<usize>,
+    /// The attempt's score; its clock stops at extraction.
+    pub score: Score,
+}
+
+impl FloorRun {
+    /// Start `level` from a fresh world seeded with `seed` (see
+    /// [`new_floor_world`]).
+    pub fn start(level: usize, seed: u32) -> Self {
+        FloorRun {
+            sim: Simulation::from_world(new_floor_world(level, seed)),
+            scenario: ScenarioState::new(floor_def(level)),
+            frames: 0,
+            extracted: None,
+            score: Score::new(),
+        }
+    }
+
+    /// One frame with the bot at the controls ([`Simulation::bot_intents`],
+    /// scenario-aware). Returns what it pressed, for recording a
+    /// [`crate::replay::Replay`].
+    pub fn bot_frame(&mut self, dt: f32) -> PlayerIntents {
+        let intents = self.sim.bot_intents(Some(&self.scenario), dt);
+        self.frame(&intents, dt);
+        intents
+    }
+
+    /// Advance one frame of `dt` seconds with the player doing `intents`.
+    pub fn frame(&mut self, intents: &PlayerIntents, dt: f32) {
+        let player_alive = self.sim.player_alive();
+        if self.extracted.is_none() {
+            apply_player_input(&mut self.sim.world, Some(&mut self.scenario), intents);
+        }
+        let gated = self.scenario.gate_view().is_some();
+        self.sim.scenario_step(&mut self.scenario, dt);
+        if !gated {
+            ElevatorSystem.run(&mut self.sim.world, dt);
+        }
+        if self.extracted.is_none() && player_alive {
+            self.score.tick(dt);
+        }
+        self.score.observe(&self.sim.frame_events);
+        if self.extracted.is_none() && player_alive {
+            self.extracted = ElevatorSystem::extraction(&self.sim.world);
+        }
+        self.frames += 1;
+    }
+}
+
+/// Rays cast from the player against the walls, evenly spaced from east,
+/// clockwise (screen y points down).
+pub const OBS_RAYS: usize = 16;
+/// How far rays and enemy offsets reach; observations are scaled by it.
+pub const OBS_RANGE: f32 = 600.0;
+/// Nearest enemies observed.
+pub const OBS_ENEMIES: usize = 6;
+/// The AI states an enemy slot one-hot encodes (the legacy states read as
+/// none of them).
+const OBS_AI_STATES: [AIState; 5] = [
+    AIState::Unaware,
+    AIState::SpottedUnsure,
+    AIState::SurePlayerSeen,
+    AIState::Confused,
+    AIState::Passive,
+];
+/// The held weapon is seen by its model, so a weapon added to the table
+/// reads like the one it is drawn as.
+const OBS_WEAPONS: [WeaponModel; WeaponModel::COUNT] = [
+    WeaponModel::Pistol,
+    WeaponModel::Shotgun,
+    WeaponModel::MachineGun,
+    WeaponModel::Bar,
+];
+const OBS_PLAYER: usize = 6;
+const OBS_WEAPON: usize = OBS_WEAPONS.len() + 2;
+const OBS_ENEMY: usize = 5 + OBS_AI_STATES.len();
+const OBS_SCENARIO: usize = 4;
+/// Length of an [`Observation`].
+pub const OBS_LEN: usize =
+    OBS_RAYS + OBS_PLAYER + OBS_WEAPON + OBS_ENEMIES * OBS_ENEMY + OBS_SCENARIO;
+
+/// What an [`Env`] agent sees, every value roughly in `[-1, 1]`, in order:
+///
+/// * `OBS_RAYS` wall (or door leaf) distances / `OBS_RANGE` (1 = nothing in
+///   range);
+/// * the player: health fraction, facing (cos, sin), velocity / speed (x,
+///   y), finisher running;
+/// * the held weapon: one-hot by model, pistol / shotgun / machine gun /
+///   bar (all zero bare-handed), ammo / magazine, ready to fire;
+/// * `OBS_ENEMIES` nearest live enemies, nearest first, each: present,
+///   offset / `OBS_RANGE` (x, y), knocked down (finishable), in line of
+///   sight, one-hot AI state (unaware, spotted, sure, confused, passive);
+///   absent slots are all zero, and corpses never take one;
+/// * the scenario: tutorial gate active, input held (`hold` / talk), combat
+///   allowed, an exit open.
+pub type Observation = [f32; OBS_LEN];
+
+/// How the player moves during an [`Action`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Move {
+    /// `0` stands still; `1..=8` walk east, south-east, south, south-west,
+    /// west, north-west, north, north-east — the eight keyboard directions.
+    Discrete(u8),
+    /// Walk along any direction at full speed (zero stands still).
+    Continuous(Vec2),
+}
+
+/// One [`Env::step`] worth of player input.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Action {
+    pub movement: Move,
+    /// Facing, radians (0 = east, clockwise).
+    pub aim: f32,
+    /// Hold the trigger.
+    pub fire: bool,
+    /// Throw the held weapon.
+    pub throw: bool,
+    /// Pick up / swap the weapon underfoot.
+    pub pickup: bool,
+    /// Start a finisher on a downed enemy in reach.
+    pub finisher: bool,
+    /// Advance the dialogue on screen (a `talk` step masks every other
+    /// input until it is read through).
+    pub advance: bool,
+}
+
+impl Action {
+    /// Stand still facing `aim`, hands off.
+    pub fn idle(aim: f32) -> Self {
+        Action {
+            movement: Move::Discrete(0),
+            aim,
+            fire: false,
+            throw: false,
+            pickup: false,
+            finisher: false,
+            advance: false,
+        }
+    }
+}
+
+/// What an [`Env`] rewards, per [`GameEvent`] and per outcome.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RewardWeights {
+    pub enemy_down: f32,
+    pub enemy_hit: f32,
+    pub finisher_done: f32,
+    pub player_hurt: f32,
+    pub dry_fire: f32,
+    /// Once, when the player dies.
+    pub death: f32,
+    /// Once, when the player extracts.
+    pub extraction: f32,
+    /// Per simulated second (a negative value hurries the agent).
+    pub per_second: f32,
+}
+
+impl Default for RewardWeights {
+    fn default() -> Self {
+        RewardWeights {
+            enemy_down: 1.0,
+            enemy_hit: 0.1,
+            finisher_done: 0.5,
+            player_hurt: -0.5,
+            dry_fire: -0.01,
+            death: -2.0,
+            extraction: 5.0,
+            per_second: -0.01,
+        }
+    }
+}
+
+impl RewardWeights {
+    pub fn event(&self, event: &GameEvent) -> f32 {
+        match event {
+            GameEvent::EnemyDown => self.enemy_down,
+            GameEvent::EnemyHit { .. } => self.enemy_hit,
+            GameEvent::FinisherDone => self.finisher_done,
+            GameEvent::PlayerHurt { .. } => self.player_hurt,
+            GameEvent::DryFire => self.dry_fire,
+            _ => 0.0,
+        }
+    }
+}
+
+/// [`Env`] settings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EnvOptions {
+    /// Seconds per simulated frame.
+    pub dt: f32,
+    /// Frames each action is held for (presses — throw, pickup, finisher,
+    /// advance — land on the first only).
+    pub frame_skip: usize,
+    /// Steps before an episode is cut off.
+    pub max_steps: usize,
+    pub rewards: RewardWeights,
+}
+
+impl Default for EnvOptions {
+    fn default() -> Self {
+        EnvOptions {
+            dt: 1.0 / 60.0,
+            frame_skip: 4,
+            max_steps: 60 * 60 * 3 / 4,
+            rewards: RewardWeights::default(),
+        }
+    }
+}
+
+/// A gym-style environment over a [`FloorRun`]: [`Env::reset`] a floor,
+/// then [`Env::step`] actions until `done` (the player died or extracted,
+/// or `max_steps` ran out). Actions go through the browser's own input
+/// dispatch, so scenario masks (gates, holds, `combat: false`) apply.
+pub struct Env {
+    pub options: EnvOptions,
+    run: Option<FloorRun>,
+    steps: usize,
+}
+
+impl Env {
+    pub fn new(options: EnvOptions) -> Self {
+        Env {
+            options,
+            run: None,
+            steps: 0,
+        }
+    }
+
+    /// Start a new episode on `level` (a level index, as [`Simulation::new`]
+    /// takes) from RNG state `seed`.
+    pub fn reset(&mut self, level: usize, seed: u32) -> Observation {
+        let run = FloorRun::start(level, seed);
+        let obs = observe(&run.sim.world, Some(&run.scenario));
+        self.run = Some(run);
+        self.steps = 0;
+        obs
+    }
+
+    /// Apply `action` for `frame_skip` frames: the new observation, the
+    /// reward earned meanwhile and whether the episode is over.
+    pub fn step(&mut self, action: &Action) -> (Observation, f32, bool) {
+        let opts = self.options;
+        let run = self.run.as_mut().expect("Env::reset not called");
+        let mut reward = 0.0;
+        let mut done = false;
+        for frame in 0..opts.frame_skip.max(1) {
+            let intents = action_intents(&run.sim.world, action, frame == 0);
+            let was_alive = run.sim.player_alive();
+            let was_extracted = run.extracted.is_some();
+            run.frame(&intents, opts.dt);
+            reward += run
+                .sim
+                .frame_events()
+                .iter()
+                .map(|e| opts.rewards.event(e))
+                .sum::<f32>();
+            reward += opts.rewards.per_second * opts.dt;
+            if was_alive && !run.sim.player_alive() {
+                reward += opts.rewards.death;
+            }
+            if !was_extracted && run.extracted.is_some() {
+                reward += opts.rewards.extraction;
+            }
+            if !run.sim.player_alive() || run.extracted.is_some() {
+                done = true;
+                break;
+            }
+        }
+        self.steps += 1;
+        let done = done || self.steps >= opts.max_steps;
+        (observe(&run.sim.world, Some(&run.scenario)), reward, done)
+    }
+
+    /// The floor being played (after [`Env::reset`]).
+    pub fn run(&self) -> Option<&FloorRun> {
+        self.run.as_ref()
+    }
+}
+
+/// The intents an [`Action`] stands for; `press` is whether one-shot presses
+/// count this frame.
+fn action_intents(world: &World, action: &Action, press: bool) -> PlayerIntents {
+    const DIRS: [(f32, f32); 9] = [
+        (0.0, 0.0),
+        (1.0, 0.0),
+        (1.0, 1.0),
+        (0.0, 1.0),
+        (-1.0, 1.0),
+        (-1.0, 0.0),
+        (-1.0, -1.0),
+        (0.0, -1.0),
+        (1.0, -1.0),
+    ];
+    let move_dir = match action.movement {
+        Move::Discrete(n) => {
+            let (x, y) = DIRS.get(n as usize).copied().unwrap_or_default();
+            Vec2::new(x, y)
+        }
+        Move::Continuous(dir) => dir,
+    };
+    // The cursor, a little ahead of the player along the aim.
+    let from = get_player_position(world).unwrap_or(Vec2::zero());
+    let mouse_world = from + Vec2::new(action.aim.cos(), action.aim.sin()) * 100.0;
+    PlayerIntents {
+        left_pressed: press && action.finisher,
+        left_down: action.fire,
+        right_pressed: press && action.throw,
+        e_pressed: press && action.pickup,
+        mouse_world,
+        move_dir,
+        advance_pressed: press && action.advance,
+    }
+}
+
+/// Build the [`Observation`] of `world` (and its floor scenario, if any).
+pub fn observe(world: &World, scenario: Option<&ScenarioState>) -> Observation {
+    let mut obs = [0.0; OBS_LEN];
+    let Some(player) = world.query::<Player>().first().copied() else {
+        return obs;
+    };
+    let me = get_player_position(world).unwrap_or(Vec2::zero());
+    let walls = world.walls();
+    let doors = door_leaves(world);
+    let mut out = obs.iter_mut();
+    let mut put = |v: f32| {
+        if let Some(slot) = out.next() {
+            *slot = v;
+        }
+    };
+
+    for i in 0..OBS_RAYS {
+        let angle = i as f32 * std::f32::consts::TAU / OBS_RAYS as f32;
+        let dir = Vec2::new(angle.cos(), angle.sin());
+        put(raycast_walls(me, dir, OBS_RANGE, walls, &doors) / OBS_RANGE);
+    }
+
+    let health = world
+        .get_component::<Health>(player)
+        .map(|h| h.current.max(0) as f32 / h.max.max(1) as f32)
+        .unwrap_or(0.0);
+    let facing = world
+        .get_component::<Rotation>(player)
+        .map_or(0.0, |r| r.angle);
+    let speed = world
+        .get_component::<Speed>(player)
+        .map_or(1.0, |s| s.value.max(1.0));
+    let velocity = world
+        .get_component::<Velocity>(player)
+        .map_or(Vec2::zero(), |v| Vec2::new(v.x, v.y));
+    put(health);
+    put(facing.cos());
+    put(facing.sin());
+    put(velocity.x / speed);
+    put(velocity.y / speed);
+    put(flag(FinisherSystem::active(world)));
+
+    let weapon = world.get_component::<Weapon>(player);
+    for kind in OBS_WEAPONS {
+        put(flag(
+            weapon.is_some_and(|w| w.weapon_type.def().model == kind),
+        ));
+    }
+    put(weapon.map_or(0.0, |w| {
+        w.ammo.max(0) as f32 / w.weapon_type.magazine().max(1) as f32
+    }));
+    put(flag(weapon.is_some_and(|w| w.fire_timer <= 0.0)));
+
+    let mut enemies: Vec<(Entity, Vec2)> = world
+        .query_ref_filtered::<(Entity, &Position, &Health), With<Enemy>>()
+        .filter(|(_, _, h)| h.is_alive())
+        .map(|(e, p, _)| (e, p.to_vec2()))
+        .filter(|(_, p)| me.distance(*p) <= OBS_RANGE)
+        .collect();
+    enemies.sort_by(|a, b| me.distance(a.1).total_cmp(&me.distance(b.1)));
+    for slot in 0..OBS_ENEMIES {
+        let Some(&(enemy, pos)) = enemies.get(slot) else {
+            for _ in 0..OBS_ENEMY {
+                put(0.0);
+            }
+            continue;
+        };
+        let state = world.get_component::<AI>(enemy).map(|ai| ai.state);
+        put(1.0);
+        put((pos.x - me.x) / OBS_RANGE);
+        put((pos.y - me.y) / OBS_RANGE);
+        put(flag(world.has_component::<Stunned>(enemy)));
+        put(flag(has_line_of_sight(me, pos, walls, &doors)));
+        for s in OBS_AI_STATES {
+            put(flag(state == Some(s)));
+        }
+    }
+
+    put(flag(scenario.is_some_and(|sc| sc.gate_view().is_some())));
+    put(flag(
+        scenario.is_some_and(|sc| sc.hold_active() || sc.dialogue_active()),
+    ));
+    put(flag(scenario.is_none_or(|sc| sc.combat_enabled())));
+    put(flag(
+        world.query_ref::<&Elevator>().any(|e| e.is_exit && e.open),
+    ));
+    obs
+}
+
+fn flag(on: bool) -> f32 {
+    if on {
+        1.0
+    } else {
+        0.0
     }
 }
 
@@ -686,6 +1653,52 @@ mod tests {
 
     const DT: f32 = 0.016; // 60 FPS
 
+    #[test]
+    fn test_tick_schedule_order() {
+        // The order both hosts run (the frozen-only systems slot in where the
+        // old hand-written gate tick put them).
+        assert_eq!(
+            tick_schedule().order(),
+            vec![
+                "finisher",
+                "stun",
+                "weapon",
+                "noise",
+                "hush",
+                "ai",
+                "boss",
+                "pin_enemies",
+                "movement",
+                "door",
+                "combat",
+                "bullet",
+                "thrown",
+                "trail",
+                "pickup",
+                "stun_floor",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_frozen_tick_pins_enemies_and_floors_stuns() {
+        let mut world = World::new();
+        spawn_player(&mut world, Vec2::new(100.0, 100.0));
+        let enemy = spawn_enemy(&mut world, Vec2::new(400.0, 400.0));
+        world.get_component_mut::<Velocity>(enemy).unwrap().x = 200.0;
+        world.add_component(enemy, Stunned::new(0.1));
+
+        let mut schedule = tick_schedule();
+        let ctx = TickContext { gate_frozen: true };
+        for _ in 0..30 {
+            schedule.run(&mut world, &ctx, DT);
+        }
+        let pos = world.get_component::<Position>(enemy).unwrap();
+        assert_eq!((pos.x, pos.y), (400.0, 400.0), "the cast holds still");
+        let stun = world.get_component::<Stunned>(enemy).unwrap();
+        assert_eq!(stun.timer, GATE_STUN_FLOOR, "nobody gets back up");
+    }
+
     #[test]
     fn test_sim_runs_many_frames_without_panicking() {
         // Fast-forward a full level for ~17 seconds of game time.
@@ -778,7 +1791,7 @@ mod tests {
         world.add_component(player, Speed::new(200.0));
         world.add_component(player, Radius::new(15.0));
         world.add_component(player, Rotation::new(0.0));
-        world.add_component(player, Weapon::new(WeaponType::Pistol));
+        world.add_component(player, Weapon::new(WeaponType::PISTOL));
 
         let enemy = world.spawn();
         world.add_component(enemy, Enemy);
@@ -817,4 +1830,128 @@ mod tests {
             .query::<crate::components::WeaponPickup>()
             .is_empty());
     }
+
+    #[test]
+    fn test_observation_layout() {
+        let mut world = World::new();
+        spawn_player(&mut world, Vec2::new(100.0, 100.0));
+        world.add_wall(200.0, 0.0, 20.0, 400.0);
+        let enemy = spawn_enemy(&mut world, Vec2::new(150.0, 100.0));
+        let obs = observe(&world, None);
+
+        // East ray stops at the wall; west sees nothing in range.
+        assert_eq!(obs[0], 100.0 / OBS_RANGE);
+        assert_eq!(obs[OBS_RAYS / 2], 1.0);
+        // Full health.
+        assert_eq!(obs[OBS_RAYS], 1.0);
+        // Enemy slot 0: present, 50px east, standing, in sight, unaware.
+        let slot = OBS_RAYS + OBS_PLAYER + OBS_WEAPON;
+        assert_eq!(
+            obs[slot..slot + OBS_ENEMY],
+            [
+                1.0,
+                50.0 / OBS_RANGE,
+                0.0,
+                0.0,
+                1.0,
+                1.0,
+                0.0,
+                0.0,
+                0.0,
+                0.0
+            ]
+        );
+        // Slot 1 empty.
+        assert!(obs[slot + OBS_ENEMY..slot + 2 * OBS_ENEMY]
+            .iter()
+            .all(|&v| v == 0.0));
+        // No scenario: no gate, no hold, combat on, no exit.
+        assert_eq!(obs[OBS_LEN - 4..], [0.0, 0.0, 1.0, 0.0]);
+
+        world.add_component(enemy, crate::components::Stunned::new(1.0));
+        assert_eq!(observe(&world, None)[slot + 3], 1.0);
+        // A corpse frees its slot for the living.
+        world.get_component_mut::<Health>(enemy).unwrap().current = 0;
+        assert!(observe(&world, None)[slot..slot + OBS_ENEMY]
+            .iter()
+            .all(|&v| v == 0.0));
+
+        // A shut door across the east ray stops it short of the wall.
+        let door = world.spawn();
+        world.add_component(
+            door,
+            crate::components::Door {
+                x: 130.0,
+                y: 80.0,
+                closed: std::f32::consts::FRAC_PI_2,
+                length: 40.0,
+                swing: 0.0,
+                spin: 0.0,
+            },
+        );
+        assert!((observe(&world, None)[0] - 30.0 / OBS_RANGE).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_env_steps_deterministically() {
+        let episode = || {
+            let mut env = Env::new(EnvOptions::default());
+            let start = env.reset(2, 99);
+            let p0 = env.run().unwrap().sim.player_position().unwrap();
+            let walk_east = Action {
+                movement: Move::Discrete(1),
+                ..Action::idle(0.0)
+            };
+            let (obs, reward, done) = env.step(&walk_east);
+            let p1 = env.run().unwrap().sim.player_position().unwrap();
+            assert!(p1.x > p0.x, "walked east");
+            assert!(!done);
+            assert!(reward < 0.0, "only the time cost so far");
+            let mut trace = vec![(start, 0.0), (obs, reward)];
+            for i in 0..200 {
+                let action = Action {
+                    movement: Move::Continuous(Vec2::new(0.0, 1.0)),
+                    fire: true,
+                    pickup: i % 10 == 0,
+                    ..Action::idle(i as f32 * 0.1)
+                };
+                let (obs, reward, done) = env.step(&action);
+                trace.push((obs, reward));
+                if done {
+                    break;
+                }
+            }
+            trace
+        };
+        assert_eq!(episode(), episode());
+    }
+
+    #[test]
+    fn test_env_advance_reads_the_intro_dialogue_through() {
+        let mut env = Env::new(EnvOptions::default());
+        env.reset(0, 7);
+        let talking = |env: &Env| env.run().unwrap().scenario.dialogue_active();
+        for _ in 0..200 {
+            if talking(&env) {
+                break;
+            }
+            env.step(&Action::idle(0.0));
+        }
+        assert!(talking(&env), "floor 00 opens with a conversation");
+        for _ in 0..50 {
+            env.step(&Action::idle(0.0));
+        }
+        assert!(talking(&env), "idling never closes it");
+        let advance = Action {
+            advance: true,
+            ..Action::idle(0.0)
+        };
+        for _ in 0..200 {
+            if !talking(&env) {
+                break;
+            }
+            env.step(&advance);
+        }
+        assert!(!talking(&env), "advance reads it through");
+    }
 }
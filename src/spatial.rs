@@ -0,0 +1,302 @@
+//! Uniform-grid spatial index over every entity with a [`Position`], held as
+//! a world resource. Its circle is its [`Radius`]; an entity without one is
+//! filed as a point.
+//!
+//! `MovementSystem` refreshes it at the end of each run, so everything later
+//! in the tick (bullets, thrown weapons, pickups, finishers) asks "who is
+//! within r of p" or "who does this segment cross" without scanning every
+//! entity. Code that needs it calls [`SpatialIndex::sync`] first, which
+//! rebuilds only when a `Position` or `Radius` was added, mutably borrowed or
+//! removed since the last rebuild — two change-tick reads (see
+//! `World::changed_since`), so a teleport between frames or a test that
+//! places entities by hand still gets exact answers without a per-entity
+//! check.
+
+use crate::collision::{swept_circle_circle_collision, swept_circle_circle_toi};
+use crate::components::{Position, Radius};
+use crate::ecs::{Entity, World};
+use crate::math::Vec2;
+
+/// The circle of an indexed entity that has no [`Radius`]: a point.
+pub const DEFAULT_RADIUS: f32 = 0.0;
+
+/// Side of one grid cell, px (a bit over two bot diameters).
+pub const CELL_SIZE: f32 = 64.0;
+
+/// Cells per axis. The grid covers `[0, GRID_CELLS * CELL_SIZE)` on both
+/// axes — the whole playable floor; anything outside is filed in the edge
+/// cells, which keeps answers exact, just slower out there.
+const GRID_CELLS: usize = 32;
+
+#[derive(Debug, Clone, Default)]
+pub struct SpatialIndex {
+    /// Every indexed entity with the position and radius it was filed
+    /// under, in entity index order (the order queries answer in).
+    entries: Vec<(Entity, Vec2, f32)>,
+    /// Per cell (row-major), indices into `entries` of the circles touching
+    /// it.
+    cells: Vec<Vec<u32>>,
+    /// Change-detection bookmark of the last rebuild: a `Position` or
+    /// `Radius` write stamped after it makes the index stale.
+    synced: u64,
+}
+
+impl SpatialIndex {
+    /// Make sure `world` holds an index matching its current positions and
+    /// radii, rebuilding it when missing or stale.
+    pub fn sync(world: &mut World) {
+        let fresh = world.resource::<SpatialIndex>().is_some_and(|index| {
+            !world.changed_since::<Position>(index.synced)
+                && !world.changed_since::<Radius>(index.synced)
+        });
+        if !fresh {
+            let mut index = world.remove_resource::<SpatialIndex>().unwrap_or_default();
+            // The rebuild is a change-detection reader of its own: every
+            // write after it carries a later tick than its bookmark.
+            let mut synced = 0;
+            world.run_tracked(&mut synced, |world| index.rebuild(world));
+            index.synced = synced;
+            world.insert_resource(index);
+        }
+    }
+
+    /// The synced index of `world` (call [`SpatialIndex::sync`] first).
+    pub fn of(world: &World) -> &SpatialIndex {
+        world
+            .resource::<SpatialIndex>()
+            .expect("SpatialIndex::sync not called")
+    }
+
+    fn rebuild(&mut self, world: &World) {
+        self.entries.clear();
+        self.entries.extend(
+            world
+                .query_ref::<(Entity, &Position, Option<&Radius>)>()
+                .map(|(entity, pos, radius)| {
+                    let radius = radius.map_or(DEFAULT_RADIUS, |r| r.value);
+                    (entity, pos.to_vec2(), radius)
+                }),
+        );
+        self.cells.resize_with(GRID_CELLS * GRID_CELLS, Vec::new);
+        for cell in &mut self.cells {
+            cell.clear();
+        }
+        for (i, &(_, pos, radius)) in self.entries.iter().enumerate() {
+            for cell in Self::cells_over(
+                pos - Vec2::new(radius, radius),
+                pos + Vec2::new(radius, radius),
+            ) {
+                self.cells[cell].push(i as u32);
+            }
+        }
+    }
+
+    /// Number of indexed entities.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Entities whose circle overlaps (or touches) the disc of radius `r`
+    /// around `p`, in entity index order.
+    pub fn within(&self, p: Vec2, r: f32) -> Vec<Entity> {
+        self.collect(p - Vec2::new(r, r), p + Vec2::new(r, r), |pos, radius| {
+            p.distance(pos) <= r + radius
+        })
+    }
+
+    /// Entities a circle of radius `r` touches as it sweeps from `a` to `b`
+    /// (a bullet's flight this frame), in entity index order.
+    pub fn along_segment(&self, a: Vec2, b: Vec2, r: f32) -> Vec<Entity> {
+        let min = Vec2::new(a.x.min(b.x) - r, a.y.min(b.y) - r);
+        let max = Vec2::new(a.x.max(b.x) + r, a.y.max(b.y) + r);
+        self.collect(min, max, |pos, radius| {
+            swept_circle_circle_collision(a, b, r, pos, radius)
+        })
+    }
+
+    /// The first entity `accept` lets through that a circle of radius `r`
+    /// touches as it sweeps from `a` to `b`, with the fraction of the sweep
+    /// at which it does (see [`swept_circle_circle_toi`]); ties go to the
+    /// lowest entity index.
+    pub fn first_along_segment(
+        &self,
+        a: Vec2,
+        b: Vec2,
+        r: f32,
+        accept: impl Fn(Entity) -> bool,
+    ) -> Option<(Entity, f32)> {
+        let min = Vec2::new(a.x.min(b.x) - r, a.y.min(b.y) - r);
+        let max = Vec2::new(a.x.max(b.x) + r, a.y.max(b.y) + r);
+        self.candidates(min, max)
+            .into_iter()
+            .map(|i| self.entries[i as usize])
+            .filter(|&(entity, _, _)| accept(entity))
+            .filter_map(|(entity, pos, radius)| {
+                swept_circle_circle_toi(a, b, r, pos, radius).map(|t| (entity, t))
+            })
+            .min_by(|x, y| x.1.total_cmp(&y.1))
+    }
+
+    /// The entity nearest `from` (centre distance) among those `accept`
+    /// lets through; ties go to the lowest entity index. Searches outward
+    /// in growing discs, so a close hit is found without touching the rest
+    /// of the floor. Nothing is nearest a non-finite `from`.
+    pub fn nearest(&self, from: Vec2, accept: impl Fn(Entity) -> bool) -> Option<Entity> {
+        // A NaN never satisfies the whole-grid stop below.
+        if !(from.x.is_finite() && from.y.is_finite()) {
+            return None;
+        }
+        let span = GRID_CELLS as f32 * CELL_SIZE;
+        let mut r = CELL_SIZE;
+        loop {
+            let best = self
+                .candidates(from - Vec2::new(r, r), from + Vec2::new(r, r))
+                .into_iter()
+                .map(|i| self.entries[i as usize])
+                .filter(|&(entity, _, _)| accept(entity))
+                .map(|(entity, pos, _)| (entity, from.distance(pos)))
+                .min_by(|a, b| a.1.total_cmp(&b.1));
+            // Everything within `r` was a candidate, so a best inside it is
+            // the global best. Once the disc's box covers the whole grid,
+            // every entry was a candidate.
+            let whole_grid =
+                from.x - r <= 0.0 && from.y - r <= 0.0 && from.x + r >= span && from.y + r >= span;
+            match best {
+                Some((entity, d)) if d <= r => return Some(entity),
+                best if whole_grid => return best.map(|(entity, _)| entity),
+                _ => r *= 2.0,
+            }
+        }
+    }
+
+    fn collect(&self, min: Vec2, max: Vec2, hit: impl Fn(Vec2, f32) -> bool) -> Vec<Entity> {
+        self.candidates(min, max)
+            .into_iter()
+            .map(|i| self.entries[i as usize])
+            .filter(|&(_, pos, radius)| hit(pos, radius))
+            .map(|(entity, _, _)| entity)
+            .collect()
+    }
+
+    /// Indices of the entries filed in any cell the box `min..max` covers,
+    /// ascending and without duplicates.
+    fn candidates(&self, min: Vec2, max: Vec2) -> Vec<u32> {
+        let mut found: Vec<u32> = Self::cells_over(min, max)
+            .flat_map(|cell| self.cells.get(cell).into_iter().flatten().copied())
+            .collect();
+        found.sort_unstable();
+        found.dedup();
+        found
+    }
+
+    /// Row-major ids of the cells the box `min..max` covers, out-of-grid
+    /// coordinates clamped to the edge cells.
+    fn cells_over(min: Vec2, max: Vec2) -> impl Iterator<Item = usize> {
+        let cell = |v: f32| ((v / CELL_SIZE).floor().max(0.0) as usize).min(GRID_CELLS - 1);
+        let (x0, x1) = (cell(min.x), cell(max.x));
+        let (y0, y1) = (cell(min.y), cell(max.y));
+        (y0..=y1).flat_map(move |y| (x0..=x1).map(move |x| y * GRID_CELLS + x))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::Health;
+
+    fn place(world: &mut World, x: f32, y: f32, r: f32) -> Entity {
+        let e = world.spawn();
+        world.add_component(e, Position::new(x, y));
+        world.add_component(e, Radius::new(r));
+        e
+    }
+
+    #[test]
+    fn test_radius_and_segment_queries() {
+        let mut world = World::new();
+        let a = place(&mut world, 100.0, 100.0, 10.0);
+        let b = place(&mut world, 130.0, 100.0, 10.0);
+        let far = place(&mut world, 900.0, 900.0, 10.0);
+        // A big circle filed in many cells is still reported once.
+        let big = place(&mut world, 300.0, 100.0, 150.0);
+        // No radius: filed as a point.
+        let bare = world.spawn();
+        world.add_component(bare, Position::new(100.0, 120.0));
+        SpatialIndex::sync(&mut world);
+        let index = SpatialIndex::of(&world);
+
+        assert_eq!(index.len(), 5);
+        assert_eq!(index.within(Vec2::new(100.0, 100.0), 5.0), vec![a]);
+        assert_eq!(index.within(Vec2::new(100.0, 125.0), 5.0), vec![bare]);
+        assert_eq!(index.within(Vec2::new(115.0, 100.0), 5.0), vec![a, b]);
+        assert_eq!(index.within(Vec2::new(150.0, 100.0), 15.0), vec![b, big]);
+        assert_eq!(
+            index.along_segment(Vec2::new(0.0, 100.0), Vec2::new(120.0, 100.0), 2.0),
+            vec![a, b]
+        );
+        assert_eq!(
+            index.along_segment(Vec2::new(0.0, 0.0), Vec2::new(2000.0, 2000.0), 2.0),
+            vec![a, far, big]
+        );
+        assert_eq!(index.nearest(Vec2::new(880.0, 880.0), |_| true), Some(far));
+        assert_eq!(
+            index.nearest(Vec2::new(880.0, 880.0), |e| e != far),
+            Some(big)
+        );
+        assert_eq!(index.nearest(Vec2::new(0.0, 0.0), |_| false), None);
+        assert_eq!(index.nearest(Vec2::new(f32::NAN, 0.0), |_| true), None);
+        assert_eq!(index.nearest(Vec2::new(0.0, f32::INFINITY), |_| true), None);
+    }
+
+    #[test]
+    fn test_sync_follows_moves_spawns_and_despawns() {
+        let mut world = World::new();
+        let a = place(&mut world, 100.0, 100.0, 10.0);
+        SpatialIndex::sync(&mut world);
+
+        world.get_component_mut::<Position>(a).unwrap().x = 1500.0;
+        let b = place(&mut world, -300.0, 5000.0, 10.0);
+        SpatialIndex::sync(&mut world);
+        let index = SpatialIndex::of(&world);
+        assert!(index.within(Vec2::new(100.0, 100.0), 50.0).is_empty());
+        assert_eq!(index.within(Vec2::new(1500.0, 100.0), 1.0), vec![a]);
+        // Off the grid: filed in an edge cell, found all the same.
+        assert_eq!(index.within(Vec2::new(-300.0, 5000.0), 1.0), vec![b]);
+
+        world.despawn(a);
+        SpatialIndex::sync(&mut world);
+        assert_eq!(SpatialIndex::of(&world).len(), 1);
+
+        world.get_component_mut::<Radius>(b).unwrap().value = 500.0;
+        SpatialIndex::sync(&mut world);
+        assert_eq!(
+            SpatialIndex::of(&world).within(Vec2::new(-300.0, 4600.0), 1.0),
+            vec![b]
+        );
+    }
+
+    #[test]
+    fn test_sync_skips_the_rebuild_when_nothing_changed() {
+        let mut world = World::new();
+        let a = place(&mut world, 100.0, 100.0, 10.0);
+        SpatialIndex::sync(&mut world);
+        let synced = SpatialIndex::of(&world).synced;
+
+        // Other components, reads and spawns without a position: untouched.
+        world.add_component(a, Health::new(10));
+        world.get_component_mut::<Health>(a).unwrap().current = 5;
+        let _ = world.get_component::<Position>(a);
+        world.spawn();
+        SpatialIndex::sync(&mut world);
+        assert_eq!(SpatialIndex::of(&world).synced, synced);
+
+        // A mutable borrow counts, whether or not it moved anything.
+        let _ = world.get_component_mut::<Position>(a);
+        SpatialIndex::sync(&mut world);
+        assert!(SpatialIndex::of(&world).synced > synced);
+    }
+}
@@ -0,0 +1,271 @@
+//! World state hashing and desync detection.
+//!
+//! [`World::state_hash`] folds everything a save carries — the RNG streams, the
+//! walls and every registered gameplay component ([`crate::save::COMPONENTS`])
+//! — into one `u64`, in a stable order (component registry order, then entity
+//! index) with a fixed hash (FNV-1a), so it is comparable across runs,
+//! processes and builds. Values are hashed through their save text, which is
+//! exact for floats (shortest round-trip form).
+//!
+//! For *finding* a determinism bug the single hash is not enough, so the
+//! hash is built from a [`StateDigest`]: one hash per component row.
+//! [`Simulation::log_hashes`] keeps a digest per tick, and [`first_desync`]
+//! compares two logs and names the first frame, component and entity that
+//! differ — a `HashMap` iteration order or a float path that diverges shows
+//! up where it happens instead of as a different final position many frames
+//! later.
+
+use crate::ecs::{Entity, RngStream, World};
+use crate::save::COMPONENTS;
+use crate::sim::Simulation;
+use std::fmt;
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+/// FNV-1a, 64-bit: tiny, and the same on every platform and Rust version
+/// (unlike `DefaultHasher`).
+#[derive(Clone, Copy)]
+struct Fnv(u64);
+
+impl Fnv {
+    fn new() -> Self {
+        Fnv(FNV_OFFSET)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 ^= b as u64;
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    fn write_u64(&mut self, v: u64) {
+        self.write(&v.to_le_bytes());
+    }
+}
+
+/// One component row's hash.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StateRow {
+    /// The component's registered name.
+    pub component: &'static str,
+    pub entity: Entity,
+    pub hash: u64,
+}
+
+/// A world's state, hashed piecewise (see the module docs).
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateDigest {
+    /// Every RNG stream's state, in [`RngStream::ALL`] order.
+    pub rng: [u32; RngStream::COUNT],
+    /// Hash of the walls, in insertion order.
+    pub walls: u64,
+    /// Every component row, in registry order then entity index order.
+    pub rows: Vec<StateRow>,
+}
+
+impl StateDigest {
+    pub fn of(world: &World) -> Self {
+        let mut walls = Fnv::new();
+        for wall in world.walls() {
+            for v in [wall.x, wall.y, wall.width, wall.height] {
+                walls.write(&v.to_bits().to_le_bytes());
+            }
+            // Solid walls hash as they always did (recorded digests stay
+            // valid).
+            if wall.is_glass() {
+                walls.write(b"glass");
+            }
+        }
+        let mut rows = Vec::new();
+        for codec in COMPONENTS {
+            codec.for_each_row(world, &mut |entity, text| {
+                let mut h = Fnv::new();
+                h.write(text.as_bytes());
+                rows.push(StateRow {
+                    component: codec.name,
+                    entity,
+                    hash: h.0,
+                });
+            });
+        }
+        StateDigest {
+            rng: world.rng_streams().states(),
+            walls: walls.0,
+            rows,
+        }
+    }
+
+    /// The whole digest folded into one hash.
+    pub fn hash(&self) -> u64 {
+        let mut h = Fnv::new();
+        for state in self.rng {
+            h.write_u64(state as u64);
+        }
+        h.write_u64(self.walls);
+        for row in &self.rows {
+            h.write(row.component.as_bytes());
+            h.write_u64(row.entity.to_bits());
+            h.write_u64(row.hash);
+        }
+        h.0
+    }
+
+    /// The first thing that differs between two digests: the component (an
+    /// RNG stream's name, e.g. `"rng:ai"`, or `"walls"`) and, for a component
+    /// row, the entity.
+    /// A row only one side has counts as a difference too.
+    pub fn diff(&self, other: &StateDigest) -> Option<(&'static str, Option<Entity>)> {
+        for (i, stream) in RngStream::ALL.into_iter().enumerate() {
+            if self.rng[i] != other.rng[i] {
+                return Some((stream.name(), None));
+            }
+        }
+        if self.walls != other.walls {
+            return Some(("walls", None));
+        }
+        let mut a = self.rows.iter();
+        let mut b = other.rows.iter();
+        loop {
+            let row = match (a.next(), b.next()) {
+                (None, None) => return None,
+                (Some(row), None) | (None, Some(row)) => row,
+                (Some(x), Some(y)) if x == y => continue,
+                // Same key, different value; else whichever row sorts first
+                // is the one missing on the other side.
+                (Some(x), Some(y)) => std::cmp::min_by_key(x, y, |r| row_key(r)),
+            };
+            return Some((row.component, Some(row.entity)));
+        }
+    }
+}
+
+fn row_key(row: &StateRow) -> (usize, u32, u32) {
+    let component = COMPONENTS
+        .iter()
+        .position(|c| c.name == row.component)
+        .unwrap_or(usize::MAX);
+    (component, row.entity.index(), row.entity.generation())
+}
+
+impl World {
+    /// Hash of every gameplay component, the walls and the RNG streams (see
+    /// [`crate::state_hash`]). Equal worlds hash equal on every platform.
+    pub fn state_hash(&self) -> u64 {
+        StateDigest::of(self).hash()
+    }
+}
+
+/// Where two runs first disagree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Desync {
+    /// Index into the hash logs: the tick after which the states differ.
+    pub frame: usize,
+    /// The component that differs (an RNG stream's name / `"walls"` for
+    /// those).
+    pub component: &'static str,
+    /// The entity whose row differs (none for the RNG / walls).
+    pub entity: Option<Entity>,
+}
+
+impl fmt::Display for Desync {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "desync at frame {}: {}", self.frame, self.component)?;
+        if let Some(entity) = self.entity {
+            write!(f, " of {entity:?}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The first divergence between two per-tick digest logs, over the frames
+/// both cover (a run that simply stopped earlier is not a desync).
+pub fn first_desync(a: &[StateDigest], b: &[StateDigest]) -> Option<Desync> {
+    a.iter().zip(b).enumerate().find_map(|(frame, (x, y))| {
+        x.diff(y).map(|(component, entity)| Desync {
+            frame,
+            component,
+            entity,
+        })
+    })
+}
+
+impl Simulation {
+    /// The first divergence between this run's hash log and `other`'s (both
+    /// need [`Simulation::log_hashes`] on from the same start).
+    pub fn first_desync(&self, other: &Simulation) -> Option<Desync> {
+        first_desync(self.hash_log(), other.hash_log())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{Position, Velocity};
+    use crate::game::spawn_enemy;
+    use crate::math::Vec2;
+
+    fn floor(level: usize) -> Simulation {
+        Simulation::from_world(crate::game::new_floor_world(level, 12345))
+    }
+
+    #[test]
+    fn test_state_hash_is_stable_and_sensitive() {
+        let a = floor(1);
+        let mut b = floor(1);
+        assert_eq!(a.world.state_hash(), b.world.state_hash());
+
+        b.world.rng(RngStream::Loot).next_u32();
+        assert_ne!(a.world.state_hash(), b.world.state_hash());
+        assert_eq!(
+            StateDigest::of(&a.world).diff(&StateDigest::of(&b.world)),
+            Some(("rng:loot", None))
+        );
+        b.world.set_rng_streams(a.world.rng_streams().clone());
+
+        // One ULP on one coordinate.
+        let p = b.player().unwrap();
+        let pos = b.world.get_component_mut::<Position>(p).unwrap();
+        pos.x = f32::from_bits(pos.x.to_bits() + 1);
+        assert_ne!(a.world.state_hash(), b.world.state_hash());
+        assert_eq!(
+            StateDigest::of(&a.world).diff(&StateDigest::of(&b.world)),
+            Some(("Position", Some(p)))
+        );
+    }
+
+    #[test]
+    fn test_first_desync_names_frame_component_and_entity() {
+        let mut a = floor(2);
+        let mut b = floor(2);
+        a.log_hashes();
+        b.log_hashes();
+        for _ in 0..10 {
+            a.step(1.0 / 60.0);
+            b.step(1.0 / 60.0);
+        }
+        assert_eq!(a.first_desync(&b), None);
+
+        // Nudge one velocity in b only: it shows up on the next tick.
+        let p = b.player().unwrap();
+        b.world.get_component_mut::<Velocity>(p).unwrap().x += 1.0;
+        a.step(1.0 / 60.0);
+        b.step(1.0 / 60.0);
+        let desync = a.first_desync(&b).expect("diverged");
+        assert_eq!(desync.frame, 10);
+        assert_eq!(desync.component, "Position");
+        assert_eq!(desync.entity, Some(p));
+
+        // A row only one side has: an extra enemy in b.
+        let mut a = floor(2);
+        let mut b = floor(2);
+        a.log_hashes();
+        b.log_hashes();
+        let extra = spawn_enemy(&mut b.world, Vec2::new(-500.0, -500.0));
+        a.step(0.0);
+        b.step(0.0);
+        let desync = a.first_desync(&b).expect("diverged");
+        assert_eq!((desync.frame, desync.entity), (0, Some(extra)));
+    }
+}
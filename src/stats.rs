@@ -0,0 +1,250 @@
+//! Run statistics: what the player did on a floor, counted off the ECS
+//! event stream.
+//!
+//! [`FloorStats`] takes the same [`GameEvent`] batches as the score
+//! ([`FloorStats::observe`]) and the frame's clock ([`FloorStats::tick`]).
+//! Unlike [`crate::score::Score`] it is a plain record: a death or a
+//! checkpoint restore does not take anything back, every attempt on the
+//! floor counts. [`CampaignStats`] sums the floors of a run for the credits.
+
+use crate::components::GameEvent;
+use crate::score::{KillCredit, KillMethod};
+use std::collections::BTreeMap;
+
+/// The key [`FloorStats::kills_by`] counts thrown-weapon kills under.
+pub const THROWN_KEY: &str = "thrown";
+
+/// What happened on one floor, over every attempt.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct FloorStats {
+    /// Seconds on the floor (alive, before extraction).
+    pub time: f32,
+    /// Kills by the weapon id of the blow ([`THROWN_KEY`] for a thrown
+    /// weapon). A rogue that died of nothing the player did is left out.
+    pub kills_by: BTreeMap<&'static str, u32>,
+    /// Gun rounds fired: every pellet a trigger pull sends
+    /// ([`crate::weapons::WeaponDef::pellets`]).
+    pub shots: u32,
+    /// Gun rounds that struck a rogue (a round stops at the first one).
+    pub hits: u32,
+    pub finishers: u32,
+    pub throws: u32,
+    pub deaths: u32,
+    /// Deaths resumed from a mid-floor checkpoint rather than a restart.
+    pub restores: u32,
+}
+
+impl FloorStats {
+    pub fn new() -> Self {
+        FloorStats::default()
+    }
+
+    /// Advance the floor clock (the host calls it while the player is alive
+    /// and on the floor).
+    pub fn tick(&mut self, dt: f32) {
+        self.time += dt;
+    }
+
+    /// Count one frame's events.
+    pub fn observe(&mut self, events: &[GameEvent]) {
+        let mut credit = KillCredit::default();
+        let mut died = false;
+        for event in events {
+            if let Some(kill) = credit.see(event) {
+                let key = match kill.weapon {
+                    Some(weapon) if kill.method != KillMethod::Thrown => weapon.id(),
+                    _ => THROWN_KEY,
+                };
+                *self.kills_by.entry(key).or_default() += 1;
+            }
+            match *event {
+                GameEvent::PlayerFired(weapon) if !weapon.is_melee() => {
+                    self.shots += weapon.def().pellets
+                }
+                GameEvent::EnemyHit { by } if !by.is_melee() => self.hits += 1,
+                GameEvent::FinisherDone => self.finishers += 1,
+                GameEvent::Throw => self.throws += 1,
+                GameEvent::PlayerHurt { .. } => died = true,
+                _ => {}
+            }
+        }
+        // Every hit is fatal: one death however many connected.
+        if died {
+            self.deaths += 1;
+        }
+    }
+
+    /// A death resumed from the floor's checkpoint.
+    pub fn restored(&mut self) {
+        self.restores += 1;
+    }
+
+    pub fn kills(&self) -> u32 {
+        self.kills_by.values().sum()
+    }
+
+    /// Rounds that struck over rounds fired, 0..1; `None` before the first
+    /// shot.
+    pub fn accuracy(&self) -> Option<f32> {
+        (self.shots > 0).then(|| self.hits as f32 / self.shots as f32)
+    }
+
+    /// Fold `other` into these totals.
+    pub fn add(&mut self, other: &FloorStats) {
+        self.time += other.time;
+        for (&weapon, &n) in &other.kills_by {
+            *self.kills_by.entry(weapon).or_default() += n;
+        }
+        self.shots += other.shots;
+        self.hits += other.hits;
+        self.finishers += other.finishers;
+        self.throws += other.throws;
+        self.deaths += other.deaths;
+        self.restores += other.restores;
+    }
+}
+
+/// The floors of a campaign summed up, for the credits.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CampaignStats {
+    /// Floors extracted from.
+    pub floors: u32,
+    pub totals: FloorStats,
+}
+
+impl CampaignStats {
+    pub fn new() -> Self {
+        CampaignStats::default()
+    }
+
+    /// Count a floor the player extracted from.
+    pub fn add_floor(&mut self, floor: &FloorStats) {
+        self.floors += 1;
+        self.totals.add(floor);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{Weapon, WeaponType};
+    use crate::ecs::{Entity, System, World};
+    use crate::game::{fire_player_weapon, spawn_enemy, spawn_player};
+    use crate::math::Vec2;
+    use crate::systems::BulletSystem;
+
+    /// Pull the trigger of `gun` once toward `aim`, a rogue standing 100 px
+    /// down +x, and fly the rounds out: the stats of the real event stream.
+    fn fire_once(gun: WeaponType, aim: Vec2) -> FloorStats {
+        let mut world = World::new();
+        let player = spawn_player(&mut world, Vec2::new(0.0, 0.0));
+        *world.get_component_mut::<Weapon>(player).unwrap() = Weapon::new(gun);
+        spawn_enemy(&mut world, Vec2::new(100.0, 0.0));
+
+        let mut stats = FloorStats::new();
+        fire_player_weapon(&mut world, aim);
+        stats.observe(&world.drain_events());
+        for _ in 0..60 {
+            BulletSystem.run(&mut world, 0.016);
+            world.apply_deferred();
+            stats.observe(&world.drain_events());
+        }
+        stats
+    }
+
+    #[test]
+    fn test_kills_by_weapon_shots_and_accuracy() {
+        let mut stats = FloorStats::new();
+        // A shotgun round strikes and the rogue drops; a pistol round misses.
+        stats.observe(&[GameEvent::PlayerFired(WeaponType::SHOTGUN)]);
+        stats.observe(&[
+            GameEvent::EnemyHit {
+                by: WeaponType::SHOTGUN,
+            },
+            GameEvent::EnemyDown,
+        ]);
+        stats.observe(&[GameEvent::PlayerFired(WeaponType::PISTOL)]);
+        // A swing that kills: not a shot.
+        stats.observe(&[
+            GameEvent::PlayerFired(WeaponType::KATANA),
+            GameEvent::EnemyHit {
+                by: WeaponType::KATANA,
+            },
+            GameEvent::EnemyDown,
+        ]);
+        stats.observe(&[GameEvent::Throw]);
+        stats.observe(&[GameEvent::ThrownImpact, GameEvent::EnemyDown]);
+        // Nobody's attack.
+        stats.observe(&[GameEvent::EnemyDown]);
+
+        assert_eq!(stats.kills(), 3);
+        assert_eq!(stats.kills_by["shotgun"], 1);
+        assert_eq!(stats.kills_by["katana"], 1);
+        assert_eq!(stats.kills_by[THROWN_KEY], 1);
+        assert_eq!((stats.shots, stats.hits), (2, 1));
+        assert_eq!(stats.accuracy(), Some(0.5));
+        assert_eq!(stats.throws, 1);
+    }
+
+    #[test]
+    fn test_every_round_fired_is_a_shot() {
+        // Each gun's trigger pull, rounds flown out by the bullet system:
+        // as many shots as pellets left, never more hits than shots.
+        for gun in WeaponType::all().filter(|w| !w.is_melee()) {
+            let stats = fire_once(gun, Vec2::new(100.0, 0.0));
+            assert_eq!(stats.shots, gun.def().pellets, "{gun:?}");
+            assert!((1..=stats.shots).contains(&stats.hits), "{gun:?}");
+        }
+
+        let mut stats = fire_once(WeaponType::PISTOL, Vec2::new(100.0, 0.0));
+        stats.add(&fire_once(WeaponType::PISTOL, Vec2::new(0.0, 100.0)));
+        assert_eq!((stats.shots, stats.hits), (2, 1));
+        assert_eq!(stats.accuracy(), Some(0.5));
+    }
+
+    #[test]
+    fn test_finishers_deaths_and_restores() {
+        let mut stats = FloorStats::new();
+        assert_eq!(stats.accuracy(), None);
+        // An execution: the point-blank round counts as a shot that hit.
+        stats.observe(&[
+            GameEvent::PlayerFired(WeaponType::PISTOL),
+            GameEvent::EnemyHit {
+                by: WeaponType::PISTOL,
+            },
+            GameEvent::FinisherDone,
+            GameEvent::EnemyDown,
+        ]);
+        stats.observe(&[
+            GameEvent::PlayerHurt {
+                by: Entity::new(1, 0),
+            },
+            GameEvent::PlayerHurt {
+                by: Entity::new(2, 0),
+            },
+        ]);
+        stats.restored();
+        assert_eq!(stats.finishers, 1);
+        assert_eq!(stats.kills_by["pistol"], 1, "by the finishing weapon");
+        assert_eq!(stats.accuracy(), Some(1.0));
+        assert_eq!((stats.deaths, stats.restores), (1, 1));
+    }
+
+    #[test]
+    fn test_campaign_totals_sum_the_floors() {
+        let mut floor = FloorStats::new();
+        floor.observe(&[
+            GameEvent::EnemyHit {
+                by: WeaponType::MELEE,
+            },
+            GameEvent::EnemyDown,
+        ]);
+        floor.tick(12.5);
+        let mut campaign = CampaignStats::new();
+        campaign.add_floor(&floor);
+        campaign.add_floor(&floor);
+        assert_eq!(campaign.floors, 2);
+        assert_eq!(campaign.totals.kills_by["melee"], 2);
+        assert_eq!(campaign.totals.time, 25.0);
+    }
+}
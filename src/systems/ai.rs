@@ -4,7 +4,7 @@ use crate::components::{
     Speed, Stunned, Velocity, WanderState, AI,
 };
 use crate::ecs::world::Wall;
-use crate::ecs::{Entity, System, World};
+use crate::ecs::{Entity, Rng, RngStream, System, World};
 use crate::math::Vec2;
 use crate::pathfinding::{GridCoord, NavigationGrid};
 use std::f32::consts::PI;
@@ -25,14 +25,17 @@ pub(crate) const REPATH_INTERVAL: f32 = 0.2;
 pub(crate) const PASSIVE_REPATH_INTERVAL: f32 = 0.7;
 /// Recompute immediately when the target is this far from the cached goal.
 const REPATH_TARGET_DRIFT: f32 = 80.0;
-/// Per-entity stagger: entity id modulo this many slots...
-const REPATH_STAGGER_SLOTS: u64 = 8;
+/// Per-entity stagger: entity index modulo this many slots...
+const REPATH_STAGGER_SLOTS: u32 = 8;
 /// ...times this much extra delay per slot (one 60 Hz tick).
 const REPATH_STAGGER_STEP: f32 = 1.0 / 60.0;
 /// A cached waypoint counts as reached within this distance (px); the
 /// follower then advances to the next one.
 const WAYPOINT_ARRIVE: f32 = 20.0;
 
+/// Speed multiplier for a rogue walking over to check a noise it heard.
+const INVESTIGATE_SPEED_MULT: f32 = 0.5;
+
 /// The movement target for an entity whose padded line of sight to `target`
 /// is blocked: follow the cached [`NavPath`], recomputing it only on the
 /// throttle described above. Writes the [`DebugPath`] visualization only when
@@ -52,7 +55,7 @@ pub(crate) fn throttled_path_target(
         world.add_component(entity, NavPath::default());
     }
     let (waypoint, debug_waypoints) = {
-        let stagger = (entity.0 % REPATH_STAGGER_SLOTS) as f32 * REPATH_STAGGER_STEP;
+        let stagger = (entity.index() % REPATH_STAGGER_SLOTS) as f32 * REPATH_STAGGER_STEP;
         let cache = world.get_component_mut::<NavPath>(entity).unwrap();
         cache.timer -= dt;
         // Walked the cached path to its end but LOS is still blocked.
@@ -118,27 +121,6 @@ pub(crate) fn clear_path_cache(world: &mut World, entity: Entity) {
     }
 }
 
-// Pseudo-random number generation. The RNG state lives in the `World` (so every
-// `Simulation` is independently reproducible); these free helpers operate on a
-// borrowed copy of that state threaded through the AI update. They use the same
-// LCG constants as the world so the produced sequence is unchanged.
-fn next_random(state: &mut u32) -> u32 {
-    *state = state.wrapping_mul(1664525).wrapping_add(1013904223);
-    *state
-}
-
-/// Random float between min and max.
-fn random_range(state: &mut u32, min: f32, max: f32) -> f32 {
-    let r = next_random(state) as f32 / u32::MAX as f32;
-    r * (max - min) + min
-}
-
-/// Random integer between min and max (inclusive).
-fn random_int_range(state: &mut u32, min: i32, max: i32) -> i32 {
-    let range = (max - min + 1) as u32;
-    min + (next_random(state) % range) as i32
-}
-
 // --- Feral (DRIFTER / `EnemyType::Wandering`) tuning -------------------------
 // The corruptor's soldiers (Idle/Patrolling) pursue with discipline: they
 // pathfind toward the player and hold at weapon range. The feral drifter has no
@@ -164,14 +146,11 @@ const FERAL_LUNGE_SELF_DAMAGE: i32 = 1;
 const FERAL_WANDER_MIN: f32 = 0.20;
 const FERAL_WANDER_MAX: f32 = 0.70;
 
-/// System that handles enemy AI behavior
+/// System that handles enemy AI behavior. Paths on the world's shared
+/// [`NavigationGrid`] resource (built on first use, see
+/// [`NavigationGrid::sync`]).
 #[derive(Default)]
-pub struct AISystem {
-    /// Cached navigation grid, rebuilt only when the wall layout changes.
-    /// Building the grid scans every cell against every wall, far too much
-    /// work to redo each tick for what is static level geometry.
-    nav_cache: Option<(Vec<Wall>, NavigationGrid)>,
-}
+pub struct AISystem;
 
 impl AISystem {
     fn find_player_position(world: &World) -> Option<Position> {
@@ -219,7 +198,7 @@ impl AISystem {
 
     /// Find the direction with the most open space using 36 direction rays
     fn find_most_open_direction(
-        rng: &mut u32,
+        rng: &mut Rng,
         pos: &Position,
         spawn: &Position,
         square_size: f32,
@@ -268,10 +247,10 @@ impl AISystem {
         }
 
         // 50% chance: use best direction, 50% chance: random direction
-        if next_random(rng).is_multiple_of(2) {
+        if rng.next_u32().is_multiple_of(2) {
             best_direction
         } else {
-            (next_random(rng) as f32 / u32::MAX as f32) * PI * 2.0
+            (rng.next_u32() as f32 / u32::MAX as f32) * PI * 2.0
         }
     }
 }
@@ -284,29 +263,30 @@ impl System for AISystem {
             None => return, // No player, nothing to do
         };
 
-        // Pull the world's RNG state into a local so it can be threaded through
-        // the AI update without conflicting with component borrows of `world`.
-        // It is written back at the end so the sequence continues across ticks.
-        let mut rng = world.rng_state();
-
-        // Reuse the cached navigation grid unless the walls changed (level
-        // swap). The cache's owned wall list doubles as the wall slice used
-        // through the enemy loop below (it is borrowed from `self`, not
-        // `world`, so it can outlive the mutable component borrows): the
-        // wall clone is only paid when the walls actually changed.
-        let walls_changed = match &self.nav_cache {
-            Some((cached_walls, _)) => cached_walls.as_slice() != world.walls(),
-            None => true,
-        };
-        if walls_changed {
-            let walls = world.walls().to_vec();
-            let grid = NavigationGrid::new(&walls);
-            self.nav_cache = Some((walls, grid));
-        }
-        let (walls, nav_grid) = {
-            let cache = self.nav_cache.as_ref().unwrap();
-            (cache.0.as_slice(), &cache.1)
-        };
+        // Path on the level's shared grid. It is lifted out of the world for
+        // the enemy loop, whose mutable component borrows it must outlive.
+        NavigationGrid::sync(world);
+        world.resource_scope::<NavigationGrid, _>(|world, nav_grid| {
+            Self::update_enemies(world, nav_grid, player_pos, dt);
+        });
+    }
+}
+
+impl AISystem {
+    /// One tick of every enemy's brain against `player_pos`, pathing on
+    /// `nav_grid` (whose wall list doubles as the wall slice used
+    /// throughout).
+    fn update_enemies(world: &mut World, nav_grid: &NavigationGrid, player_pos: Position, dt: f32) {
+        // Pull the AI and crowd streams into locals so they can be threaded
+        // through the update without conflicting with component borrows of
+        // `world`. They are written back at the end so the sequences continue
+        // across ticks.
+        let mut rng = *world.rng(RngStream::Ai);
+        let mut crowd = *world.rng(RngStream::Crowd);
+
+        let walls = nav_grid.walls();
+        // Rogues see past neither walls nor door leaves.
+        let doors = crate::systems::door::door_leaves(world);
 
         // Query all enemies
         let enemies: Vec<Entity> = world.query::<Enemy>();
@@ -333,7 +313,7 @@ impl System for AISystem {
                 .is_some_and(|ai| ai.state == AIState::Passive)
             {
                 if crate::systems::passive::update_passive(
-                    world, entity, &mut rng, nav_grid, walls, dt,
+                    world, entity, &mut crowd, nav_grid, walls, dt,
                 ) {
                     passive_hurt = true;
                 }
@@ -360,7 +340,8 @@ impl System for AISystem {
 
             // Calculate distance to player and line of sight
             let distance = enemy_pos.distance_to(&player_pos);
-            let has_los = has_line_of_sight(enemy_pos.to_vec2(), player_pos.to_vec2(), walls);
+            let has_los =
+                has_line_of_sight(enemy_pos.to_vec2(), player_pos.to_vec2(), walls, &doors);
 
             // Get enemy rotation to check vision cone
             let enemy_rotation = world
@@ -419,6 +400,13 @@ impl System for AISystem {
                     }
                     AIState::SpottedUnsure => {
                         if can_see_player {
+                            // Sent to check a noise and just laid eyes on the
+                            // player: the spotting starts now, as for a rogue
+                            // standing watch.
+                            if ai.investigating() {
+                                ai.state_timer = ai.spot_duration;
+                                ai.check_position = Some(enemy_pos);
+                            }
                             ai.last_known_player_position = Some(player_pos);
                             if ai.state_timer <= 0.0 {
                                 // Seen player long enough, transition to sure
@@ -456,7 +444,7 @@ impl System for AISystem {
                             if ai.state_timer <= 0.0 {
                                 // Been at last known position too long, get confused
                                 ai.state = AIState::Confused;
-                                ai.confusion_looks_remaining = random_int_range(&mut rng, 2, 3);
+                                ai.confusion_looks_remaining = rng.int_range(2, 3);
                                 ai.confusion_look_timer = ai.confusion_look_duration;
                             }
                         }
@@ -477,7 +465,7 @@ impl System for AISystem {
                                     // Done looking, transition based on initial type
                                     ai.state = AIState::Unaware;
                                     ai.wander_state = WanderState::Waiting;
-                                    ai.wander_timer = random_range(&mut rng, 1.0, 2.0);
+                                    ai.wander_timer = rng.range(1.0, 2.0);
                                 } else {
                                     // Look in another direction
                                     ai.confusion_look_timer = ai.confusion_look_duration;
@@ -491,9 +479,9 @@ impl System for AISystem {
 
             // A feral that just finished a lunge burns a chip of itself out.
             if feral_lunge_completed {
-                if let Some(hp) = world.get_component_mut::<Health>(entity) {
-                    hp.take_damage(FERAL_LUNGE_SELF_DAMAGE);
-                }
+                world.update_component::<Health, _>(entity, |hp| {
+                    hp.take_damage(FERAL_LUNGE_SELF_DAMAGE)
+                });
             }
 
             // Get updated AI state and compute movement
@@ -531,6 +519,12 @@ impl System for AISystem {
                 }
                 AIState::SpottedUnsure => {
                     let target = ai.last_known_player_position.unwrap_or(player_pos);
+                    // Checking a noise is a careful walk, not a charge.
+                    let speed = if ai.investigating() {
+                        Speed::new(speed.value * INVESTIGATE_SPEED_MULT)
+                    } else {
+                        speed
+                    };
 
                     // Use inflated walls for pathfinding decision to prevent wall grinding
                     // If target is close to a wall, we'll use pathfinding instead of direct movement
@@ -658,7 +652,7 @@ impl System for AISystem {
                 }
                 AIState::Confused => {
                     let rot = if ai.confusion_look_timer == ai.confusion_look_duration {
-                        random_range(&mut rng, 0.0, PI * 2.0)
+                        rng.range(0.0, PI * 2.0)
                     } else {
                         world
                             .get_component::<Rotation>(entity)
@@ -708,15 +702,14 @@ impl System for AISystem {
 
         // Persist the advanced RNG state back into the world so the next tick
         // continues the same deterministic sequence.
-        world.set_rng_state(rng);
+        *world.rng(RngStream::Ai) = rng;
+        *world.rng(RngStream::Crowd) = crowd;
 
         if passive_hurt {
             crate::systems::passive::alert_passives(world, crate::scenario::AlertTarget::All);
         }
     }
-}
 
-impl AISystem {
     /// Reset a feral's lunge cadence so its next tick winds up and dashes at the
     /// player. No-op for soldiers. Called at the moment a drifter locks on.
     fn arm_feral_lunge(ai: &mut AI) {
@@ -730,7 +723,7 @@ impl AISystem {
     /// look-around sweep. On hitting a wall or its leash edge it just picks a new
     /// fully-random heading — twitchy and objective-less, unlike the soldier
     /// patrol which deliberately seeks the most open direction.
-    fn update_feral_wander(rng: &mut u32, ai: &mut AI, pos: &Position, walls: &[Wall], dt: f32) {
+    fn update_feral_wander(rng: &mut Rng, ai: &mut AI, pos: &Position, walls: &[Wall], dt: f32) {
         ai.wander_state = WanderState::Moving;
         ai.wander_timer -= dt;
 
@@ -751,8 +744,8 @@ impl AISystem {
         );
 
         if ai.wander_timer <= 0.0 || hit_wall || outside_square {
-            ai.wander_direction = random_range(rng, 0.0, PI * 2.0);
-            ai.wander_timer = random_range(rng, FERAL_WANDER_MIN, FERAL_WANDER_MAX);
+            ai.wander_direction = rng.range(0.0, PI * 2.0);
+            ai.wander_timer = rng.range(FERAL_WANDER_MIN, FERAL_WANDER_MAX);
         }
     }
 
@@ -760,7 +753,7 @@ impl AISystem {
     /// locks a heading and dashes for a short burst. Returns `true` on the tick a
     /// dash completes, so the caller can apply burn-out self-damage.
     fn update_feral_lunge(
-        rng: &mut u32,
+        rng: &mut Rng,
         ai: &mut AI,
         enemy_pos: &Position,
         target: &Position,
@@ -772,7 +765,7 @@ impl AISystem {
                 // Mid-dash: keep charging the locked heading until the burst ends.
                 if ai.wander_timer <= 0.0 {
                     ai.wander_state = WanderState::Waiting;
-                    ai.wander_timer = random_range(rng, FERAL_RECOVER_MIN, FERAL_RECOVER_MAX);
+                    ai.wander_timer = rng.range(FERAL_RECOVER_MIN, FERAL_RECOVER_MAX);
                     return true;
                 }
                 false
@@ -785,7 +778,7 @@ impl AISystem {
                     let dy = target.y - enemy_pos.y;
                     ai.wander_direction = dy.atan2(dx);
                     ai.wander_state = WanderState::Moving;
-                    ai.wander_timer = random_range(rng, FERAL_LUNGE_MIN, FERAL_LUNGE_MAX);
+                    ai.wander_timer = rng.range(FERAL_LUNGE_MIN, FERAL_LUNGE_MAX);
                 }
                 false
             }
@@ -793,7 +786,7 @@ impl AISystem {
     }
 
     /// Update wandering/patrolling behavior
-    fn update_wander_behavior(rng: &mut u32, ai: &mut AI, pos: &Position, walls: &[Wall], dt: f32) {
+    fn update_wander_behavior(rng: &mut Rng, ai: &mut AI, pos: &Position, walls: &[Wall], dt: f32) {
         match ai.wander_state {
             WanderState::Moving => {
                 ai.wander_timer -= dt;
@@ -839,7 +832,7 @@ impl AISystem {
                 if ai.wander_look_timer <= 0.0 {
                     // Done looking, wait before moving
                     ai.wander_state = WanderState::Waiting;
-                    ai.wander_timer = random_range(rng, 1.0, 2.0);
+                    ai.wander_timer = rng.range(1.0, 2.0);
                 }
             }
             WanderState::Waiting => {
@@ -847,7 +840,7 @@ impl AISystem {
                 if ai.wander_timer <= 0.0 {
                     // Start moving in new direction
                     ai.wander_state = WanderState::Moving;
-                    ai.wander_timer = random_range(rng, 1.0, 2.0);
+                    ai.wander_timer = rng.range(1.0, 2.0);
                     ai.wander_direction = Self::find_most_open_direction(
                         rng,
                         pos,
@@ -885,7 +878,7 @@ mod tests {
         world.add_component(enemy, Health::new(100));
         world.add_component(enemy, Rotation::new(0.0));
 
-        let mut system = AISystem::default();
+        let mut system = AISystem;
         // Run multiple frames to trigger state transitions (need > 0.3s to go from Unaware -> SpottedUnsure -> SurePlayerSeen)
         for _ in 0..30 {
             system.run(&mut world, 0.016);
@@ -918,7 +911,7 @@ mod tests {
         world.add_component(enemy, Health::new(100));
         world.add_component(enemy, Rotation::new(0.0));
 
-        let mut system = AISystem::default();
+        let mut system = AISystem;
         // Run multiple frames to trigger state transitions
         for _ in 0..30 {
             system.run(&mut world, 0.016);
@@ -950,7 +943,7 @@ mod tests {
         world.add_component(enemy, Speed::new(100.0));
         world.add_component(enemy, Health::new(100));
 
-        let mut system = AISystem::default();
+        let mut system = AISystem;
         system.run(&mut world, 0.016);
 
         let ai = world.get_component::<AI>(enemy).unwrap();
@@ -979,7 +972,7 @@ mod tests {
         world.add_component(enemy, Speed::new(100.0));
         world.add_component(enemy, Health::new(100));
 
-        let mut system = AISystem::default();
+        let mut system = AISystem;
         system.run(&mut world, 0.5);
 
         let ai = world.get_component::<AI>(enemy).unwrap();
@@ -1005,7 +998,7 @@ mod tests {
             world.add_component(enemy, Health::new(100));
         }
 
-        let mut system = AISystem::default();
+        let mut system = AISystem;
         system.run(&mut world, 0.016);
 
         // All enemies should have updated AI states
@@ -1035,7 +1028,7 @@ mod tests {
         world.add_component(enemy, Health::new(100));
         world.add_component(enemy, Rotation::new(0.0));
 
-        let mut system = AISystem::default();
+        let mut system = AISystem;
         // Run multiple frames for state transition
         for _ in 0..30 {
             system.run(&mut world, 0.016);
@@ -1078,7 +1071,7 @@ mod tests {
         world.add_component(enemy, Speed::new(100.0));
         world.add_component(enemy, Health::new(100));
 
-        let mut system = AISystem::default();
+        let mut system = AISystem;
         system.run(&mut world, 0.016);
 
         // Enemy should NOT detect player (line of sight blocked by wall)
@@ -1115,7 +1108,7 @@ mod tests {
         world.add_component(enemy, Speed::new(100.0));
         world.add_component(enemy, Health::new(100));
 
-        let mut system = AISystem::default();
+        let mut system = AISystem;
         system.run(&mut world, 0.016);
 
         // Enemy should NOT detect player (line of sight blocked by walls)
@@ -1152,7 +1145,7 @@ mod tests {
         world.add_component(enemy, Speed::new(100.0));
         world.add_component(enemy, Health::new(100));
 
-        let mut system = AISystem::default();
+        let mut system = AISystem;
         system.run(&mut world, 0.016);
 
         // Enemy should NOT detect player (line of sight blocked by walls)
@@ -1190,7 +1183,7 @@ mod tests {
         let player_pos_clone = *world.get_component::<Position>(player).unwrap();
         let initial_distance = initial_pos.distance_to(&player_pos_clone);
 
-        let mut system = AISystem::default();
+        let mut system = AISystem;
 
         // Simulate multiple frames
         for _ in 0..10 {
@@ -1237,7 +1230,7 @@ mod tests {
         world.add_component(enemy, Health::new(100));
         world.add_component(enemy, Rotation::new(0.0));
 
-        let mut system = AISystem::default();
+        let mut system = AISystem;
         // Run multiple frames for state transition
         for _ in 0..30 {
             system.run(&mut world, 0.016);
@@ -1286,7 +1279,7 @@ mod tests {
         world.add_component(fast_enemy, Health::new(100));
         world.add_component(fast_enemy, Rotation::new(0.0));
 
-        let mut system = AISystem::default();
+        let mut system = AISystem;
         // Run multiple frames for state transition
         for _ in 0..30 {
             system.run(&mut world, 0.016);
@@ -1333,7 +1326,7 @@ mod tests {
         world.add_component(enemy, Speed::new(100.0));
         world.add_component(enemy, Health::new(100));
 
-        let mut system = AISystem::default();
+        let mut system = AISystem;
         system.run(&mut world, 0.016);
 
         // Enemy should NOT detect player through wall (line of sight blocked)
@@ -1369,7 +1362,7 @@ mod tests {
         world.add_component(enemy, Health::new(100));
         world.add_component(enemy, Rotation::new(std::f32::consts::PI / 2.0)); // Facing down
 
-        let mut system = AISystem::default();
+        let mut system = AISystem;
         // Run multiple frames
         for _ in 0..30 {
             system.run(&mut world, 0.016);
@@ -1417,7 +1410,7 @@ mod tests {
         world.add_component(enemy, Health::new(50));
         world.add_component(enemy, Rotation::new(0.0)); // facing +x, toward player
 
-        let mut system = AISystem::default();
+        let mut system = AISystem;
         for _ in 0..frames {
             system.run(&mut world, 0.016);
         }
@@ -1451,7 +1444,7 @@ mod tests {
         world.add_component(enemy, Health::new(50));
         world.add_component(enemy, Rotation::new(0.0));
 
-        let mut system = AISystem::default();
+        let mut system = AISystem;
         for _ in 0..120 {
             system.run(&mut world, 0.016);
             let mag = vel_mag(&world, enemy);
@@ -1511,7 +1504,7 @@ mod tests {
             world.add_component(enemy, Speed::new(base));
             world.add_component(enemy, Health::new(50));
             world.add_component(enemy, Rotation::new(0.0));
-            let mut system = AISystem::default();
+            let mut system = AISystem;
             for _ in 0..90 {
                 system.run(&mut world, 0.016);
                 *peak = peak.max(vel_mag(&world, enemy));
@@ -1586,7 +1579,7 @@ mod tests {
         world.add_component(enemy, Health::new(50));
         world.add_component(enemy, Rotation::new(0.0));
 
-        let mut system = AISystem::default();
+        let mut system = AISystem;
         let mut directions = std::collections::HashSet::new();
         for _ in 0..600 {
             system.run(&mut world, 0.016);
@@ -1605,6 +1598,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_crowd_draws_leave_rogue_wander_alone() {
+        // Same feral, two worlds; one also burns draws on the crowd stream
+        // (as a busier civilian would). The rogue's wander must not notice.
+        let feral_world = || {
+            let mut world = World::new();
+            let player = world.spawn();
+            world.add_component(player, Player);
+            world.add_component(player, Position::new(5000.0, 5000.0));
+            let enemy = world.spawn();
+            world.add_component(enemy, Enemy);
+            let epos = Position::new(400.0, 400.0);
+            world.add_component(enemy, epos);
+            world.add_component(enemy, AI::new_with_type(EnemyType::Wandering, epos));
+            world.add_component(enemy, Velocity::zero());
+            world.add_component(enemy, Speed::new(100.0));
+            world.add_component(enemy, Health::new(50));
+            world.add_component(enemy, Rotation::new(0.0));
+            (world, enemy)
+        };
+        let (mut a, enemy) = feral_world();
+        let (mut b, _) = feral_world();
+        let mut system = AISystem;
+        for tick in 0..300 {
+            if tick % 7 == 0 {
+                b.rng(RngStream::Crowd).next_u32();
+            }
+            system.run(&mut a, 0.016);
+            system.run(&mut b, 0.016);
+            let heading = |w: &World| w.get_component::<AI>(enemy).unwrap().wander_direction;
+            assert_eq!(heading(&a), heading(&b), "tick {tick}");
+        }
+        assert_ne!(a.rng(RngStream::Crowd), b.rng(RngStream::Crowd));
+    }
+
     /// Pin the enemy into the chase state (like the perf harness's
     /// `alert_all`) so throttle tests exercise the pathfinding branch every
     /// tick regardless of vision.
@@ -1648,7 +1676,7 @@ mod tests {
             .unwrap()
             .distance_to(&player_pos);
 
-        let mut system = AISystem::default();
+        let mut system = AISystem;
         for _ in 0..600 {
             pin_chase(&mut world, enemy, player_pos);
             system.run(&mut world, 0.016);
@@ -1692,7 +1720,7 @@ mod tests {
         world.add_component(enemy, Speed::new(100.0));
         world.add_component(enemy, Health::new(100));
 
-        let mut system = AISystem::default();
+        let mut system = AISystem;
         for _ in 0..60 {
             pin_chase(&mut world, enemy, player_pos);
             system.run(&mut world, 1.0 / 60.0);
@@ -1716,7 +1744,7 @@ mod tests {
         // The drift rule: when the chase target jumps far from the cached
         // path's goal, the recompute happens NOW, not at the next 5 Hz slot.
         let (mut world, enemy, player_pos) = corner_world();
-        let mut system = AISystem::default();
+        let mut system = AISystem;
         pin_chase(&mut world, enemy, player_pos);
         system.run(&mut world, 0.016);
         let before = world.get_component::<NavPath>(enemy).unwrap().recomputes;
@@ -1738,7 +1766,7 @@ mod tests {
     fn test_debug_structures_gated_by_debug_flag() {
         // Flag off (the default): chasing must not record DebugPath/DebugTrail.
         let (mut world, enemy, player_pos) = corner_world();
-        let mut system = AISystem::default();
+        let mut system = AISystem;
         for _ in 0..30 {
             pin_chase(&mut world, enemy, player_pos);
             system.run(&mut world, 0.016);
@@ -1797,7 +1825,7 @@ mod tests {
         world.add_component(enemy, Health::new(100));
         world.add_component(enemy, Rotation::new(0.0)); // Facing right
 
-        let mut system = AISystem::default();
+        let mut system = AISystem;
         // Run a few frames - enemy shouldn't see player yet
         for _ in 0..5 {
             system.run(&mut world, 0.016);
@@ -1,7 +1,7 @@
 use crate::components::{
     AIState, Boss, Health, Player, Position, Rotation, Speed, Stunned, Velocity, AI,
 };
-use crate::ecs::{Entity, System, World};
+use crate::ecs::{Changed, Entity, System, World};
 
 /// Boss tuning.
 pub const BOSS_MAX_HEALTH: i32 = 360;
@@ -67,10 +67,10 @@ impl System for BossSystem {
 
             // Crack the mask off at half health -> enrage (once).
             let crack_at = (health.max as f32 * MASK_CRACK_FRACTION) as i32;
-            let mut enraged = world
-                .get_component::<Boss>(boss)
-                .map(|b| b.enraged)
-                .unwrap_or(false);
+            let Some(&state) = world.get_component::<Boss>(boss) else {
+                continue;
+            };
+            let mut enraged = state.enraged;
             if !enraged && health.current <= crack_at {
                 enraged = true;
                 if let Some(b) = world.get_component_mut::<Boss>(boss) {
@@ -79,9 +79,11 @@ impl System for BossSystem {
                 if let Some(s) = world.get_component_mut::<Speed>(boss) {
                     s.value = BOSS_ENRAGED_SPEED;
                 }
-            }
-            // The mask-off animation runs its course once cracked.
-            if enraged {
+            } else if enraged && state.reveal < 1.0 && dt > 0.0 {
+                // The mask-off animation runs its course from the tick after
+                // the crack, so the crack tick is the only one on which the
+                // boss changes with `reveal == 0` (what `mask_cracked` keys
+                // on). Only touched while it moves.
                 if let Some(b) = world.get_component_mut::<Boss>(boss) {
                     b.reveal = (b.reveal + dt / BOSS_MASK_OFF_SECS).min(1.0);
                 }
@@ -130,15 +132,21 @@ impl System for BossSystem {
 /// (the debug **B** key; the next `BossSystem` tick flips `enraged`).
 pub fn crack_boss_masks(world: &mut World) {
     for boss in world.query::<Boss>() {
-        if let Some(h) = world.get_component_mut::<Health>(boss) {
+        world.update_component::<Health, _>(boss, |h| {
             let crack_at = (h.max as f32 * MASK_CRACK_FRACTION) as i32;
-            if h.current > crack_at {
-                h.current = crack_at;
-            }
-        }
+            h.current = h.current.min(crack_at);
+        });
     }
 }
 
+/// Whether a boss cracked its mask since the current change-detection
+/// reader's baseline (see `World::run_tracked`) — the mask-crack sound cue.
+pub fn mask_cracked(world: &World) -> bool {
+    world
+        .query_ref_filtered::<&Boss, Changed<Boss>>()
+        .any(|b| b.enraged && b.reveal == 0.0)
+}
+
 /// Whether any boss in the world has cracked its mask (enraged). Used for
 /// audio/visual cues.
 pub fn any_boss_enraged(world: &World) -> bool {
@@ -217,7 +225,10 @@ mod tests {
         );
         assert!(any_boss_enraged(&world));
 
-        // The mask-off animation then runs 0 -> 1 over BOSS_MASK_OFF_SECS.
+        // The mask-off animation then runs 0 -> 1 over BOSS_MASK_OFF_SECS,
+        // starting the tick after the crack.
+        assert_eq!(world.get_component::<Boss>(boss).unwrap().reveal, 0.0);
+        BossSystem.run(&mut world, 0.016);
         let r0 = world.get_component::<Boss>(boss).unwrap().reveal;
         assert!(r0 > 0.0 && r0 < 1.0);
         for _ in 0..400 {
@@ -226,6 +237,25 @@ mod tests {
         assert_eq!(world.get_component::<Boss>(boss).unwrap().reveal, 1.0);
     }
 
+    #[test]
+    fn test_mask_cracked_reports_the_crack_tick_only() {
+        let mut world = World::new();
+        spawn_test_player(&mut world, Vec2::new(0.0, 0.0));
+        let boss = spawn_test_boss(&mut world, Vec2::new(100.0, 0.0));
+        let (mut system_run, mut listener_run) = (0, 0);
+        let mut tick = |world: &mut World| {
+            world.run_tracked(&mut system_run, |w| BossSystem.run(w, 0.016));
+            world.run_tracked(&mut listener_run, |w| mask_cracked(w))
+        };
+
+        assert!(!tick(&mut world));
+        world.get_component_mut::<Health>(boss).unwrap().current = BOSS_MAX_HEALTH / 2;
+        assert!(tick(&mut world));
+        for _ in 0..10 {
+            assert!(!tick(&mut world), "the animation is not a new crack");
+        }
+    }
+
     #[test]
     fn test_debug_crack_boss_masks_enrages_without_killing() {
         let mut world = World::new();
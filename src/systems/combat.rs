@@ -1,8 +1,9 @@
+use crate::collision::has_line_of_sight;
 use crate::components::{
-    AIState, Boss, Enemy, GameEvent, Health, Knockback, Player, Position, Radius, Stunned,
-    WeaponType, AI,
+    AIState, Boss, Bullet, Enemy, EnemyType, GameEvent, Health, Knockback, Player, Position,
+    Radius, Stunned, Velocity, WanderState, Weapon, WeaponType, AI,
 };
-use crate::ecs::{Entity, System, World};
+use crate::ecs::{Entity, RngStream, System, With, Without, World};
 
 // The player dies to ANY connected enemy hit (one-hit death — the genre's
 // core loop; death is cheap: checkpoint restore / hold-R restart). The old
@@ -21,17 +22,8 @@ pub const MELEE_KNOCKBACK: f32 = 1000.0;
 /// is a scratch (it still flips passive civilians hostile, like any hurt).
 pub const PUNCH_DAMAGE: i32 = 10;
 
-/// Reach of the player's armed melee swing (the metal bar), centre to centre.
-/// Robots are drawn as 60 px tiles, so two sprites standing visibly adjacent
-/// are ~60 px apart at the centres: the old 50 px reach forced the player to
-/// physically overlap the target before a swing could connect (a whiff gives
-/// no feedback beyond the whoosh), which soft-locked the floor-1 `strike`
-/// tutorial gate in the browser. 70 px lands the swing from a natural
-/// point-blank stance while still demanding contact range.
-pub const MELEE_RANGE: f32 = 70.0;
-
-/// Reach of a punch: a touch shorter than the metal bar's
-/// [`MELEE_RANGE`] swing.
+/// Reach of a punch: a touch shorter than the metal bar's 70 px swing (the
+/// weapons table's `reach`).
 pub const PUNCH_RANGE: f32 = 65.0;
 
 /// The shove of a punch: between a bullet and the metal bar.
@@ -44,6 +36,14 @@ pub const KNOCKDOWN_SECS: f32 = 3.0;
 /// How hard an enemy's contact attack shoves the player straight away from it.
 pub const PLAYER_KNOCKBACK: f32 = 650.0;
 
+pub use crate::weapons::EnemyGun;
+
+/// The gun handling of a rogue carrying `weapon` (its table `enemy` block);
+/// `None` for melee (a rogue without a gun keeps the contact hit).
+pub fn enemy_gun(weapon: WeaponType) -> Option<EnemyGun> {
+    weapon.def().enemy
+}
+
 /// System that handles combat damage dealing
 pub struct CombatSystem;
 
@@ -155,9 +155,11 @@ impl CombatSystem {
                 enemy_radius.value,
             ) {
                 // Deal damage
-                if let Some(health) = world.get_component_mut::<Health>(enemy) {
+                let dealt = world.update_component::<Health, _>(enemy, |health| {
                     health.take_damage(damage);
-                    let killed = health.is_dead();
+                    health.is_dead()
+                });
+                if let Some(killed) = dealt {
                     // Shove the enemy along the bullet's travel direction
                     // (shooter -> target), i.e. away from the shooter.
                     let dir_x = target_pos.x - shooter_pos.x;
@@ -199,13 +201,14 @@ impl CombatSystem {
     }
 
     /// Process melee attack in a cone. Emits one [`GameEvent::EnemyHit`] (by
-    /// melee) per enemy struck.
+    /// `weapon`, the one swung) per enemy struck.
     pub fn process_melee(
         world: &mut World,
         attacker_pos: Position,
         target_pos: Position,
         damage: i32,
         range: f32,
+        weapon: WeaponType,
     ) -> bool {
         let enemies: Vec<Entity> = world.query::<Enemy>();
 
@@ -231,14 +234,14 @@ impl CombatSystem {
             }
 
             if Self::in_melee_cone(attacker_pos, target_angle, enemy_pos, range) {
-                let mut killed = false;
-                if let Some(health) = world.get_component_mut::<Health>(enemy) {
+                let dealt = world.update_component::<Health, _>(enemy, |health| {
                     health.take_damage(damage);
-                    killed = health.is_dead();
+                    health.is_dead()
+                });
+                let killed = dealt == Some(true);
+                if dealt.is_some() {
                     hit_any = true;
-                    world.push_event(GameEvent::EnemyHit {
-                        by: WeaponType::Melee,
-                    });
+                    world.push_event(GameEvent::EnemyHit { by: weapon });
                     world.push_event(GameEvent::StrikeLanded);
                 }
                 // Shove the enemy away from the attacker (attacker -> enemy).
@@ -259,8 +262,7 @@ impl CombatSystem {
     /// chip damage ([`PUNCH_DAMAGE`]), a shove, and a [`Stunned`] sprawl of
     /// [`KNOCKDOWN_SECS`] falling AWAY from the blow (attacker -> enemy). The
     /// boss shrugs the knockdown off (it only takes the chip damage). Emits one
-    /// [`GameEvent::EnemyHit`] (by melee — a fist clanging on a metal bot) per
-    /// enemy struck.
+    /// [`GameEvent::EnemyHit`] (by [`WeaponType::FISTS`]) per enemy struck.
     pub fn process_punch(
         world: &mut World,
         attacker_pos: Position,
@@ -290,11 +292,13 @@ impl CombatSystem {
             }
 
             if Self::in_melee_cone(attacker_pos, target_angle, enemy_pos, range) {
-                if let Some(health) = world.get_component_mut::<Health>(enemy) {
-                    health.take_damage(damage);
+                if world
+                    .update_component::<Health, _>(enemy, |health| health.take_damage(damage))
+                    .is_some()
+                {
                     hit_any = true;
                     world.push_event(GameEvent::EnemyHit {
-                        by: WeaponType::Melee,
+                        by: WeaponType::FISTS,
                     });
                     world.push_event(GameEvent::PunchLanded);
                 }
@@ -314,7 +318,90 @@ impl CombatSystem {
         hit_any
     }
 
-    /// Process enemy attacks on player
+    /// Gun-carrying rogues that are sure of the player and see it within
+    /// their gun's range aim (the [`AI::aim_timer`] telegraph), then fire
+    /// real rounds at it: [`EnemyGun::pellets`] per trigger pull, each
+    /// leaving within the gun's spread, every [`EnemyGun::cadence`] seconds
+    /// until the magazine is spent, then a reload and a fresh wind-up.
+    /// Losing sight of the player drops the aim. The rounds themselves hit
+    /// in `BulletSystem`.
+    fn process_enemy_fire(world: &mut World, dt: f32) {
+        let Some(&player) = world.query::<Player>().first() else {
+            return;
+        };
+        let player_pos = match world.get_component::<Position>(player) {
+            Some(pos) => *pos,
+            None => return,
+        };
+        let player_down = world
+            .get_component::<Health>(player)
+            .is_none_or(|h| h.is_dead());
+
+        let doors = crate::systems::door::door_leaves(world);
+        let mut shots = Vec::new();
+        let (query, walls) = world.query_mut_with_walls::<
+            (Entity, &mut AI, &Position, &Health, &mut Weapon),
+            (With<Enemy>, Without<Stunned>),
+        >();
+        for (enemy, ai, enemy_pos, health, weapon) in query {
+            let Some(gun) = enemy_gun(weapon.weapon_type) else {
+                continue;
+            };
+            // The reload runs out on the fire timer: only then is the
+            // magazine full again.
+            if weapon.ammo <= 0 && weapon.fire_timer <= 0.0 {
+                weapon.ammo = weapon.max_ammo;
+            }
+            // A feral drifter mid-lunge is charging, not aiming.
+            let lunging =
+                ai.initial_type == EnemyType::Wandering && ai.wander_state == WanderState::Moving;
+            let on_target = !player_down
+                && !lunging
+                && health.is_alive()
+                && ai.state == AIState::SurePlayerSeen
+                && enemy_pos.distance_to(&player_pos) <= gun.range
+                && has_line_of_sight(enemy_pos.to_vec2(), player_pos.to_vec2(), walls, &doors);
+            if !on_target {
+                ai.aim_timer = 0.0;
+                continue;
+            }
+            ai.aim_timer += dt;
+            if ai.aim_timer < gun.windup || weapon.fire_timer > 0.0 || weapon.ammo <= 0 {
+                continue;
+            }
+            weapon.ammo -= 1;
+            if weapon.ammo > 0 {
+                weapon.fire_timer = gun.cadence;
+            } else {
+                // Last round: the magazine stays empty through the reload,
+                // and the rogue telegraphs again after it.
+                weapon.fire_timer = gun.reload;
+                ai.aim_timer = 0.0;
+            }
+            shots.push((enemy, *enemy_pos, weapon.weapon_type, weapon.damage, gun));
+        }
+
+        for (enemy, from, weapon_type, damage, gun) in shots {
+            world.push_event(GameEvent::EnemyFired(weapon_type));
+            let aim = (player_pos.y - from.y).atan2(player_pos.x - from.x);
+            for _ in 0..gun.pellets {
+                let half = gun.spread / 2.0;
+                let angle = aim + world.rng(RngStream::Spread).range(-half, half);
+                let bullet = Bullet::enemy(weapon_type, damage, enemy);
+                let round = world.spawn();
+                world.add_component(
+                    round,
+                    Velocity::new(angle.cos() * bullet.speed, angle.sin() * bullet.speed),
+                );
+                world.add_component(round, bullet);
+                world.add_component(round, from);
+                world.add_component(round, Radius::new(2.0));
+            }
+        }
+    }
+
+    /// Process enemy attacks on player: the contact hit of rogues without a
+    /// gun (the boss, a disarmed rogue).
     fn process_enemy_attacks(world: &mut World) {
         // Find player
         let player_entity = match world.query::<Player>().first() {
@@ -335,58 +422,54 @@ impl CombatSystem {
             return;
         }
 
-        // Check all enemies in attack state
-        let enemies: Vec<Entity> = world.query::<Enemy>();
-
-        for enemy in enemies {
-            let (ai, enemy_pos, enemy_health) = match (
-                world.get_component::<AI>(enemy),
-                world.get_component::<Position>(enemy),
-                world.get_component::<Health>(enemy),
-            ) {
-                (Some(ai), Some(pos), Some(hp)) => (*ai, *pos, *hp),
-                _ => continue,
-            };
-
-            // Skip dead enemies
-            if enemy_health.is_dead() {
-                continue;
-            }
-
-            // Knocked-down enemies can't attack.
-            if world.has_component::<Stunned>(enemy) {
+        // Enemies in attack state swing in place (cooldown reset on the spot);
+        // the hits land on the player after the sweep, in enemy id order.
+        let mut hits = Vec::new();
+        for (enemy, ai, enemy_pos, enemy_health, weapon) in world.query_mut_filtered::<(
+            Entity,
+            &mut AI,
+            &Position,
+            &Health,
+            Option<&Weapon>,
+        ), (With<Enemy>, Without<Stunned>)>(
+        ) {
+            // Skip dead enemies (knocked-down ones are filtered out above:
+            // they can't attack), and gunners: they shoot instead.
+            if enemy_health.is_dead() || weapon.is_some_and(|w| enemy_gun(w.weapon_type).is_some())
+            {
                 continue;
             }
 
             // Attack if in SurePlayerSeen state and within range
-            if ai.state == AIState::SurePlayerSeen && ai.can_attack() {
-                let distance = enemy_pos.distance_to(&player_pos);
-                if distance < ai.attack_range {
-                    // ONE-HIT DEATH: any connected hit ends the run — the
-                    // genre's whole loop (die instantly, R restarts in a
-                    // heartbeat). Boss and rogue alike.
-                    if let Some(health) = world.get_component_mut::<Health>(player_entity) {
-                        health.take_damage(health.max.max(health.current));
-                    }
-                    world.push_event(GameEvent::PlayerHurt);
-
-                    // Shove the player directly away from the attacking enemy.
-                    let dir_x = player_pos.x - enemy_pos.x;
-                    let dir_y = player_pos.y - enemy_pos.y;
-                    Self::apply_knockback(world, player_entity, dir_x, dir_y, PLAYER_KNOCKBACK);
-
-                    // Reset cooldown
-                    if let Some(ai) = world.get_component_mut::<AI>(enemy) {
-                        ai.reset_attack_timer();
-                    }
-                }
+            if ai.state == AIState::SurePlayerSeen
+                && ai.can_attack()
+                && enemy_pos.distance_to(&player_pos) < ai.attack_range
+            {
+                ai.reset_attack_timer();
+                hits.push((enemy, *enemy_pos));
             }
         }
+
+        for (enemy, enemy_pos) in hits {
+            // ONE-HIT DEATH: any connected hit ends the run — the genre's
+            // whole loop (die instantly, R restarts in a heartbeat). Boss and
+            // rogue alike.
+            world.update_component::<Health, _>(player_entity, |health| {
+                health.take_damage(health.max.max(health.current))
+            });
+            world.push_event(GameEvent::PlayerHurt { by: enemy });
+
+            // Shove the player directly away from the attacking enemy.
+            let dir_x = player_pos.x - enemy_pos.x;
+            let dir_y = player_pos.y - enemy_pos.y;
+            Self::apply_knockback(world, player_entity, dir_x, dir_y, PLAYER_KNOCKBACK);
+        }
     }
 }
 
 impl System for CombatSystem {
-    fn run(&mut self, world: &mut World, _dt: f32) {
+    fn run(&mut self, world: &mut World, dt: f32) {
+        Self::process_enemy_fire(world, dt);
         Self::process_enemy_attacks(world);
     }
 }
@@ -511,7 +594,14 @@ mod tests {
         let attacker_pos = Position::new(0.0, 0.0);
         let target_pos = Position::new(100.0, 0.0);
 
-        let hit = CombatSystem::process_melee(&mut world, attacker_pos, target_pos, 50, 50.0);
+        let hit = CombatSystem::process_melee(
+            &mut world,
+            attacker_pos,
+            target_pos,
+            50,
+            50.0,
+            WeaponType::MELEE,
+        );
 
         assert!(hit);
         let health = world.get_component::<Health>(enemy).unwrap();
@@ -533,16 +623,18 @@ mod tests {
             Position::new(100.0, 0.0),
             50,
             50.0,
+            WeaponType::KATANA,
         );
+        // Credited to the weapon swung.
         assert_eq!(
             world.drain_events(),
             vec![
                 GameEvent::EnemyHit {
-                    by: WeaponType::Melee
+                    by: WeaponType::KATANA
                 },
                 GameEvent::StrikeLanded,
                 GameEvent::EnemyHit {
-                    by: WeaponType::Melee
+                    by: WeaponType::KATANA
                 },
                 GameEvent::StrikeLanded,
             ]
@@ -566,7 +658,10 @@ mod tests {
 
         let mut system = CombatSystem;
         system.run(&mut world, 0.016);
-        assert_eq!(world.drain_events(), vec![GameEvent::PlayerHurt]);
+        assert_eq!(
+            world.drain_events(),
+            vec![GameEvent::PlayerHurt { by: enemy }]
+        );
         // Cooldown: no second hit, no second event.
         system.run(&mut world, 0.016);
         assert!(world.drain_events().is_empty());
@@ -585,7 +680,14 @@ mod tests {
         let attacker_pos = Position::new(0.0, 0.0);
         let target_pos = Position::new(100.0, 0.0);
 
-        let hit = CombatSystem::process_melee(&mut world, attacker_pos, target_pos, 50, 50.0);
+        let hit = CombatSystem::process_melee(
+            &mut world,
+            attacker_pos,
+            target_pos,
+            50,
+            50.0,
+            WeaponType::MELEE,
+        );
 
         assert!(!hit);
         let health = world.get_component::<Health>(enemy).unwrap();
@@ -658,6 +760,7 @@ mod tests {
             Position::new(100.0, 0.0),
             50,
             50.0,
+            WeaponType::MELEE,
         );
 
         let kb = world.get_component::<Knockback>(enemy).unwrap();
@@ -719,4 +822,56 @@ mod tests {
         let player_health = world.get_component::<Health>(player).unwrap();
         assert_eq!(player_health.current, 100); // No damage due to cooldown
     }
+
+    #[test]
+    fn test_enemy_gun_reloads_after_its_magazine() {
+        let mut world = World::new();
+        let player = world.spawn();
+        world.add_component(player, Player);
+        world.add_component(player, Position::new(0.0, 0.0));
+        world.add_component(player, Health::new(100));
+
+        let enemy = world.spawn();
+        world.add_component(enemy, Enemy);
+        world.add_component(enemy, Position::new(200.0, 0.0));
+        world.add_component(enemy, Health::new(100));
+        let mut ai = AI::new();
+        ai.state = AIState::SurePlayerSeen;
+        world.add_component(enemy, ai);
+        let mut weapon = Weapon::new(WeaponType::SHOTGUN);
+        weapon.ammo = 2;
+        world.add_component(enemy, weapon);
+
+        let gun = enemy_gun(WeaponType::SHOTGUN).unwrap();
+        let dt = 1.0 / 60.0;
+        let mut shots = Vec::new();
+        let mut reloading_empty = false;
+        for frame in 0..(6.0 / dt) as usize {
+            crate::systems::WeaponUpdateSystem.run(&mut world, dt);
+            CombatSystem::process_enemy_fire(&mut world, dt);
+            if world
+                .drain_events()
+                .contains(&GameEvent::EnemyFired(WeaponType::SHOTGUN))
+            {
+                shots.push(frame as f32 * dt);
+            }
+            let ammo = world.get_component::<Weapon>(enemy).unwrap().ammo;
+            if shots.len() == 2 {
+                // Mid-reload: the magazine really is empty.
+                assert_eq!(ammo, 0);
+                reloading_empty = true;
+            }
+        }
+        assert!(reloading_empty);
+        // Two rounds left: wind-up, shot, cadence, shot — then a reload and
+        // a fresh wind-up before the third.
+        assert!(shots.len() >= 3);
+        assert!(shots[0] >= gun.windup - dt);
+        assert!((shots[1] - shots[0] - gun.cadence).abs() < 2.0 * dt);
+        assert!(shots[2] - shots[1] >= gun.reload - dt);
+        assert_eq!(
+            world.query::<Bullet>().len(),
+            shots.len() * gun.pellets as usize
+        );
+    }
 }
@@ -0,0 +1,310 @@
+use crate::collision::DoorLeaf;
+use crate::components::{
+    Door, Enemy, GameEvent, Health, Knockback, Player, Position, Radius, Stunned, Velocity,
+};
+use crate::ecs::{Entity, System, World};
+use crate::math::Vec2;
+use crate::systems::thrown::STUN_DURATION;
+
+/// How far a leaf swings from shut either way: all the way round, so a door
+/// kicked wide open folds back into the wall behind its hinge instead of
+/// standing out in the room at a stop.
+pub const DOOR_MAX_SWING: f32 = std::f32::consts::PI;
+
+/// Half the thickness of a leaf: actors collide with it this far out.
+pub const DOOR_HALF_THICKNESS: f32 = 4.0;
+
+/// A shove sends the leaf off faster than the shover walks: the contact point
+/// leaves at this multiple of the actor's speed into the door.
+const DOOR_KICK: f32 = 1.5;
+
+/// Time constant (seconds) of the leaf's spin decay: a shoved door swings on
+/// for a moment, then settles where it stopped.
+const DOOR_SPIN_TAU: f32 = 0.35;
+
+/// A leaf reaching a rogue at this speed (px/s at the contact point) or
+/// faster knocks it down. A walking shove (the player's 200 px/s, kicked
+/// on) clears it comfortably near the middle of the leaf.
+pub const DOOR_SLAM_SPEED: f32 = 150.0;
+
+/// Impulse (px/s) a slam throws the rogue back with.
+const DOOR_SLAM_KNOCKBACK: f32 = 400.0;
+
+/// Share of its spin a leaf keeps after slamming into a rogue.
+const DOOR_SLAM_DAMPING: f32 = 0.6;
+
+/// Below this spin (rad/s) a leaf has settled: it stops dead rather than
+/// creeping on for ever.
+const DOOR_REST_SPIN: f32 = 0.01;
+
+/// The leaf of every door on the floor, as sight and bullets see them.
+pub fn door_leaves(world: &World) -> Vec<DoorLeaf> {
+    world.query_ref::<&Door>().map(Door::leaf).collect()
+}
+
+/// The doorway of every door on the floor (each leaf where it lies when
+/// shut): the gaps the navigation grid keeps walkable.
+pub fn doorways(world: &World) -> Vec<DoorLeaf> {
+    world.query_ref::<&Door>().map(Door::doorway).collect()
+}
+
+/// Someone a leaf collides with: the player or a rogue still on its feet.
+struct Actor {
+    entity: Entity,
+    pos: Vec2,
+    radius: f32,
+    velocity: Vec2,
+    enemy: bool,
+    /// Pushed back by a leaf this frame.
+    moved: bool,
+}
+
+/// System that swings the doors: each leaf carries its spin on, is shoved
+/// open by whoever walks into it, knocks down the rogues it slams into, and
+/// pushes back anyone it cannot swing away from (at its stop).
+pub struct DoorSystem;
+
+impl System for DoorSystem {
+    fn run(&mut self, world: &mut World, dt: f32) {
+        let doors = world.query::<Door>();
+        if doors.is_empty() {
+            return;
+        }
+        let mut actors: Vec<Actor> = world
+            .query_ref::<(Entity, &Position, &Radius, Option<&Velocity>)>()
+            .filter(|&(e, ..)| {
+                (world.has_component::<Player>(e) || world.has_component::<Enemy>(e))
+                    && !world.has_component::<Stunned>(e)
+                    && world
+                        .get_component::<Health>(e)
+                        .is_none_or(|h| h.is_alive())
+            })
+            .map(|(entity, pos, radius, vel)| Actor {
+                entity,
+                pos: pos.to_vec2(),
+                radius: radius.value,
+                velocity: vel.map_or(Vec2::zero(), |v| Vec2::new(v.x, v.y)),
+                enemy: world.has_component::<Enemy>(entity),
+                moved: false,
+            })
+            .collect();
+
+        let mut slammed = Vec::new();
+        for entity in doors {
+            let Some(before) = world.get_component::<Door>(entity).copied() else {
+                continue;
+            };
+            let mut door = before;
+            swing(&mut door, dt);
+            for actor in actors.iter_mut() {
+                if slammed.iter().any(|&(e, _)| e == actor.entity) {
+                    continue;
+                }
+                if let Some(away) = collide(&mut door, actor) {
+                    slammed.push((actor.entity, away));
+                }
+            }
+            // A door at rest stays unwritten, so it does not read as changed.
+            if door != before {
+                if let Some(d) = world.get_component_mut::<Door>(entity) {
+                    *d = door;
+                }
+            }
+        }
+
+        for actor in actors.iter().filter(|a| a.moved) {
+            if let Some(pos) = world.get_component_mut::<Position>(actor.entity) {
+                pos.x = actor.pos.x;
+                pos.y = actor.pos.y;
+            }
+        }
+        for (enemy, away) in slammed {
+            let fall = away.y.atan2(away.x);
+            world.add_component(enemy, Stunned::with_fall(STUN_DURATION, fall));
+            world.add_component(
+                enemy,
+                Knockback::new(away.x * DOOR_SLAM_KNOCKBACK, away.y * DOOR_SLAM_KNOCKBACK),
+            );
+            world.push_event(GameEvent::DoorSlam);
+        }
+    }
+}
+
+/// Carry the leaf's spin on for `dt`: it decays, and stops dead at either
+/// stop or once it has settled.
+fn swing(door: &mut Door, dt: f32) {
+    if door.spin == 0.0 {
+        return;
+    }
+    door.swing += door.spin * dt;
+    if door.swing.abs() >= DOOR_MAX_SWING {
+        door.swing = door.swing.clamp(-DOOR_MAX_SWING, DOOR_MAX_SWING);
+        door.spin = 0.0;
+    }
+    door.spin *= (-dt / DOOR_SPIN_TAU).exp();
+    if door.spin.abs() < DOOR_REST_SPIN {
+        door.spin = 0.0;
+    }
+}
+
+/// Resolve `actor` against the leaf. A rogue the leaf is sweeping into fast
+/// enough is slammed: returns the direction it is thrown. Otherwise the
+/// actor shoves the leaf away from itself (setting its spin), and whatever
+/// overlap the leaf cannot swing out of at its stop pushes the actor back.
+fn collide(door: &mut Door, actor: &mut Actor) -> Option<Vec2> {
+    let leaf = door.leaf();
+    let (contact, along) = leaf.closest_point(actor.pos);
+    let offset = actor.pos - contact;
+    let dist = offset.length();
+    let reach = actor.radius + DOOR_HALF_THICKNESS;
+    if dist >= reach || dist <= f32::EPSILON {
+        return None;
+    }
+    let normal = offset * (1.0 / dist);
+    // Which face of the leaf the actor is on: positive spin sweeps the
+    // leaf toward the positive side.
+    let heading = (leaf.tip - leaf.hinge) * (1.0 / door.length.max(f32::EPSILON));
+    let rel = actor.pos - leaf.hinge;
+    let side = (heading.x * rel.y - heading.y * rel.x).signum();
+    let arm = along.max(actor.radius);
+
+    if actor.enemy && door.spin * side * arm >= DOOR_SLAM_SPEED {
+        door.spin *= DOOR_SLAM_DAMPING;
+        return Some(normal);
+    }
+
+    let into = (-actor.velocity.dot(normal)).max(0.0);
+    let shove = -side * into * DOOR_KICK / arm;
+    if shove != 0.0 && (door.spin * shove <= 0.0 || shove.abs() > door.spin.abs()) {
+        door.spin = shove;
+    }
+    let wanted = -side * (reach - dist) / arm;
+    let before = door.swing;
+    door.swing = (door.swing + wanted).clamp(-DOOR_MAX_SWING, DOOR_MAX_SWING);
+    let blocked = (wanted - (door.swing - before)).abs() * arm;
+    if blocked > 0.0 {
+        actor.pos += normal * blocked;
+        actor.moved = true;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scenario::DoorDef;
+
+    /// A door hinged at the origin, shut along +x, 60 long.
+    fn door_world() -> (World, Entity) {
+        let mut world = World::new();
+        let door = world.spawn();
+        world.add_component(
+            door,
+            Door::from_def(&DoorDef {
+                x: 0.0,
+                y: 0.0,
+                angle: 0.0,
+                length: 60.0,
+            }),
+        );
+        (world, door)
+    }
+
+    fn actor(world: &mut World, at: Vec2, velocity: Vec2, enemy: bool) -> Entity {
+        let e = world.spawn();
+        if enemy {
+            world.add_component(e, Enemy);
+        } else {
+            world.add_component(e, Player);
+        }
+        world.add_component(e, Position::from_vec2(at));
+        world.add_component(e, Velocity::new(velocity.x, velocity.y));
+        world.add_component(e, Radius::new(12.0));
+        world.add_component(e, Health::new(100));
+        e
+    }
+
+    #[test]
+    fn walking_into_a_door_swings_it_away() {
+        let (mut world, door) = door_world();
+        // Below the leaf (+y), walking up into it.
+        let player = actor(
+            &mut world,
+            Vec2::new(30.0, 14.0),
+            Vec2::new(0.0, -200.0),
+            false,
+        );
+        DoorSystem.run(&mut world, 1.0 / 60.0);
+        let d = *world.get_component::<Door>(door).unwrap();
+        assert!(d.swing < 0.0 && d.spin < 0.0, "swung up, away: {d:?}");
+        for _ in 0..60 {
+            DoorSystem.run(&mut world, 1.0 / 60.0);
+        }
+        let d = *world.get_component::<Door>(door).unwrap();
+        assert_eq!(d.swing, -DOOR_MAX_SWING, "carried on to its stop");
+        assert!(!world.has_component::<Stunned>(player));
+    }
+
+    #[test]
+    fn a_door_at_its_stop_pushes_back() {
+        let (mut world, door) = door_world();
+        world.get_component_mut::<Door>(door).unwrap().swing = -DOOR_MAX_SWING;
+        // The leaf now points back along -x; stand against its upper face,
+        // the one that would push it on past the stop.
+        let player = actor(&mut world, Vec2::new(-30.0, -10.0), Vec2::zero(), false);
+        DoorSystem.run(&mut world, 1.0 / 60.0);
+        let pos = world.get_component::<Position>(player).unwrap();
+        assert!(
+            pos.y <= -(12.0 + DOOR_HALF_THICKNESS) + 0.01,
+            "pushed out: {pos:?}"
+        );
+    }
+
+    #[test]
+    fn a_fast_swing_knocks_a_rogue_down() {
+        let (mut world, door) = door_world();
+        // Swinging up (negative spin) hard, a rogue just above the leaf.
+        world.get_component_mut::<Door>(door).unwrap().spin = -10.0;
+        let rogue = actor(&mut world, Vec2::new(40.0, -18.0), Vec2::zero(), true);
+        DoorSystem.run(&mut world, 1.0 / 60.0);
+        assert!(world.has_component::<Stunned>(rogue));
+        let kb = world.get_component::<Knockback>(rogue).unwrap();
+        assert!(kb.y < 0.0, "thrown away from the leaf: {kb:?}");
+        assert_eq!(world.drain_events(), vec![GameEvent::DoorSlam]);
+    }
+
+    #[test]
+    fn only_what_a_door_moves_is_written_back() {
+        let (mut world, door) = door_world();
+        actor(&mut world, Vec2::new(300.0, 0.0), Vec2::zero(), false);
+        // Setup writes are history: the door frame runs one tick later.
+        let since = world.change_tick();
+        world.run_tracked(&mut 0, |_| ());
+        DoorSystem.run(&mut world, 1.0 / 60.0);
+        assert!(!world.changed_since::<Position>(since), "nobody pushed");
+        assert!(!world.changed_since::<Door>(since), "the door is at rest");
+
+        // A shove swings the door, and it settles again.
+        world.get_component_mut::<Door>(door).unwrap().spin = 2.0;
+        for _ in 0..300 {
+            DoorSystem.run(&mut world, 1.0 / 60.0);
+        }
+        let settled = *world.get_component::<Door>(door).unwrap();
+        assert_eq!(settled.spin, 0.0);
+        assert!(settled.swing > 0.0);
+        let since = world.change_tick();
+        world.run_tracked(&mut 0, |_| ());
+        DoorSystem.run(&mut world, 1.0 / 60.0);
+        assert!(!world.changed_since::<Door>(since));
+    }
+
+    #[test]
+    fn a_slow_leaf_only_nudges_a_rogue() {
+        let (mut world, door) = door_world();
+        world.get_component_mut::<Door>(door).unwrap().spin = -1.0;
+        let rogue = actor(&mut world, Vec2::new(40.0, -15.0), Vec2::zero(), true);
+        DoorSystem.run(&mut world, 1.0 / 60.0);
+        assert!(!world.has_component::<Stunned>(rogue));
+        assert!(world.drain_events().is_empty());
+    }
+}
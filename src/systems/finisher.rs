@@ -7,12 +7,13 @@
 //! The flavour depends on what the player holds ([`FinisherSystem::kind_for`]):
 //!
 //! * unarmed — POUND: drop onto the bot and hammer it, three quick hits;
-//! * metal bar (or an empty gun swung like one) — OVERHEAD: one heavy blow;
+//! * bar or blade (or an empty gun swung like one) — OVERHEAD: one heavy blow;
 //! * loaded gun — EXECUTE: one point-blank shot straight down (costs a round).
 //!
 //! The system then ticks the animation: at every scheduled impact the victim
 //! is shoved and an [`GameEvent::EnemyHit`] rings out (the existing per-weapon
-//! impact SFX); the FINAL impact is the kill — the victim dies at that moment,
+//! impact SFX), credited to what struck it ([`FinisherSystem::blow_weapon`]);
+//! the FINAL impact is the kill — the victim dies at that moment,
 //! not at the start. The victim's [`Stunned`] timer is topped up every frame so
 //! it can never get back up mid-execution. The boss can never be downed (see
 //! `combat::process_punch` / `boss::BossSystem`), and `try_start` skips it
@@ -23,7 +24,10 @@ use crate::components::{
     Velocity, Weapon, WeaponType,
 };
 use crate::ecs::{Entity, System, World};
+use crate::spatial::SpatialIndex;
 use crate::systems::combat::CombatSystem;
+use crate::systems::noise::{loudness, make_noise};
+use crate::weapons::FinisherStyle;
 
 /// How close (px, centre to centre) the player must be to a downed enemy to
 /// finish it: player radius 15 + enemy radius 12 + a hand's reach of slack —
@@ -46,18 +50,37 @@ impl FinisherSystem {
     pub fn kind_for(weapon: Option<&Weapon>) -> FinisherKind {
         match weapon {
             None => FinisherKind::Pound,
-            Some(w) if w.weapon_type.is_melee() => FinisherKind::Overhead,
-            Some(w) if w.ammo > 0 => FinisherKind::Execute(w.weapon_type),
-            // An empty gun has nothing to fire point-blank: swing it instead.
-            Some(_) => FinisherKind::Overhead,
+            Some(w) => match w.weapon_type.def().finisher {
+                FinisherStyle::Overhead => FinisherKind::Overhead,
+                FinisherStyle::Execute if w.ammo > 0 => FinisherKind::Execute(w.weapon_type),
+                // An empty gun has nothing to fire point-blank: swing it instead.
+                FinisherStyle::Execute => FinisherKind::Overhead,
+            },
+        }
+    }
+
+    /// What a pound or overhead blow is credited to: the bare fists, the melee
+    /// weapon swung — or the bar, for an empty gun swung like one (it is not a
+    /// shot).
+    pub fn blow_weapon(kind: FinisherKind, weapon: Option<&Weapon>) -> WeaponType {
+        match (kind, weapon) {
+            (FinisherKind::Execute(gun), _) => gun,
+            (FinisherKind::Pound, _) | (_, None) => WeaponType::FISTS,
+            (FinisherKind::Overhead, Some(w)) if w.weapon_type.is_melee() => w.weapon_type,
+            (FinisherKind::Overhead, Some(_)) => WeaponType::MELEE,
         }
     }
 
     /// The nearest downed enemy in finisher range of `from`: alive, currently
-    /// [`Stunned`], not the boss. `None` if there is none in range.
+    /// [`Stunned`], not the boss. `None` if there is none in range. Reads the
+    /// spatial index: [`SpatialIndex::sync`] it first.
     pub fn downed_target(world: &World, from: Position) -> Option<Entity> {
         let mut best: Option<(Entity, f32)> = None;
-        for enemy in world.query::<Enemy>() {
+        let near = SpatialIndex::of(world).within(from.to_vec2(), FINISHER_RANGE);
+        for enemy in near {
+            if !world.has_component::<Enemy>(enemy) {
+                continue;
+            }
             if world.has_component::<Boss>(enemy) {
                 continue; // the shoggoth cannot be finished
             }
@@ -102,6 +125,7 @@ impl FinisherSystem {
             Some(p) => *p,
             None => return false,
         };
+        SpatialIndex::sync(world);
         let victim = match Self::downed_target(world, player_pos) {
             Some(v) => v,
             None => return false,
@@ -120,7 +144,9 @@ impl FinisherSystem {
             (1.0, 0.0)
         };
 
-        let kind = Self::kind_for(world.get_component::<Weapon>(player));
+        let held = world.get_component::<Weapon>(player);
+        let kind = Self::kind_for(held);
+        let swung = Self::blow_weapon(kind, held);
 
         // Face the victim and stop dead for the whole animation.
         if let Some(rot) = world.get_component_mut::<Rotation>(player) {
@@ -130,10 +156,10 @@ impl FinisherSystem {
             vel.x = 0.0;
             vel.y = 0.0;
         }
-        // The bar (or the empty gun used as one) goes up with a swing whoosh;
-        // pound and execute make their noise at the impact itself.
+        // The blade / bar (or the empty gun used as one) goes up with a swing
+        // whoosh; pound and execute make their noise at the impact itself.
         if kind == FinisherKind::Overhead {
-            world.push_event(GameEvent::PlayerFired(WeaponType::Melee));
+            world.push_event(GameEvent::PlayerFired(swung));
         }
 
         world.add_component(
@@ -167,11 +193,10 @@ impl FinisherSystem {
             FINISHER_IMPACT_KNOCKBACK,
         );
         match fin.kind {
-            // A fist / bar slamming a metal bot: the melee impact clank.
+            // A fist / blade / bar slamming a metal bot: the melee impact.
             FinisherKind::Pound | FinisherKind::Overhead => {
-                world.push_event(GameEvent::EnemyHit {
-                    by: WeaponType::Melee,
-                });
+                let by = Self::blow_weapon(fin.kind, world.get_component::<Weapon>(player));
+                world.push_event(GameEvent::EnemyHit { by });
             }
             // The point-blank shot: gunshot + that gun's impact, one round gone.
             FinisherKind::Execute(gun) => {
@@ -181,12 +206,16 @@ impl FinisherSystem {
                 }
                 world.push_event(GameEvent::PlayerFired(gun));
                 world.push_event(GameEvent::EnemyHit { by: gun });
+                if let Some(pos) = world.get_component::<Position>(player) {
+                    let at = pos.to_vec2();
+                    make_noise(world, at, loudness(gun));
+                }
             }
         }
         if kill {
-            if let Some(health) = world.get_component_mut::<Health>(fin.target) {
-                health.take_damage(health.max.max(health.current));
-            }
+            world.update_component::<Health, _>(fin.target, |health| {
+                health.take_damage(health.max.max(health.current))
+            });
         }
     }
 }
@@ -315,7 +344,7 @@ mod tests {
             world.drain_events(),
             vec![
                 GameEvent::EnemyHit {
-                    by: WeaponType::Melee
+                    by: WeaponType::FISTS
                 },
                 GameEvent::PunchLanded,
             ]
@@ -384,13 +413,14 @@ mod tests {
         // The metal bar's damage (100) kills a 50 hp rogue outright. The
         // corpse carries a `Stunned` purely as the recorded fall (sprawled
         // along the blow, attacker -> victim) — it is dead, not downed.
-        let bar = Weapon::new(WeaponType::Melee);
+        let bar = Weapon::new(WeaponType::MELEE);
         CombatSystem::process_melee(
             &mut world,
             Position::new(0.0, 0.0),
             Position::new(100.0, 0.0),
             bar.damage,
             50.0,
+            bar.weapon_type,
         );
 
         assert!(world.get_component::<Health>(enemy).unwrap().is_dead());
@@ -408,6 +438,7 @@ mod tests {
             Position::new(100.0, 0.0),
             bar.damage,
             50.0,
+            bar.weapon_type,
         );
         let health = world.get_component::<Health>(tank).unwrap();
         assert!(health.is_alive());
@@ -476,13 +507,13 @@ mod tests {
         assert!(world.get_component::<Health>(victim).unwrap().is_dead());
         assert!(!world.has_component::<Finisher>(player));
 
-        // Three pound clanks rang out.
+        // Three pound clanks rang out, all by the fists.
         let clanks = world
             .drain_events()
             .into_iter()
             .filter(|e| {
                 *e == GameEvent::EnemyHit {
-                    by: WeaponType::Melee,
+                    by: WeaponType::FISTS,
                 }
             })
             .count();
@@ -510,7 +541,7 @@ mod tests {
     #[test]
     fn test_overhead_finisher_with_the_bar() {
         let mut world = World::new();
-        let player = spawn_player_at(&mut world, 0.0, 0.0, Some(Weapon::new(WeaponType::Melee)));
+        let player = spawn_player_at(&mut world, 0.0, 0.0, Some(Weapon::new(WeaponType::MELEE)));
         let victim = spawn_downed_enemy(&mut world, 30.0, 0.0);
 
         assert!(FinisherSystem::try_start(&mut world));
@@ -527,16 +558,43 @@ mod tests {
         assert!(!world.has_component::<Finisher>(player));
     }
 
+    #[test]
+    fn test_overhead_finisher_credits_the_blade_swung() {
+        let mut world = World::new();
+        let katana = Some(Weapon::new(WeaponType::KATANA));
+        let player = spawn_player_at(&mut world, 0.0, 0.0, katana);
+        spawn_downed_enemy(&mut world, 30.0, 0.0);
+
+        assert!(FinisherSystem::try_start(&mut world));
+        assert_eq!(
+            world.get_component::<Finisher>(player).unwrap().kind,
+            FinisherKind::Overhead
+        );
+        run_secs(&mut world, 0.6);
+        let events = world.drain_events();
+        assert!(events.contains(&GameEvent::PlayerFired(WeaponType::KATANA)));
+        assert!(events.contains(&GameEvent::EnemyHit {
+            by: WeaponType::KATANA
+        }));
+        assert!(!events.iter().any(|e| matches!(
+            e,
+            GameEvent::PlayerFired(WeaponType::MELEE)
+                | GameEvent::EnemyHit {
+                    by: WeaponType::MELEE
+                }
+        )));
+    }
+
     #[test]
     fn test_gun_finisher_costs_one_round() {
         let mut world = World::new();
-        let player = spawn_player_at(&mut world, 0.0, 0.0, Some(Weapon::new(WeaponType::Pistol)));
+        let player = spawn_player_at(&mut world, 0.0, 0.0, Some(Weapon::new(WeaponType::PISTOL)));
         let victim = spawn_downed_enemy(&mut world, 30.0, 0.0);
 
         assert!(FinisherSystem::try_start(&mut world));
         assert_eq!(
             world.get_component::<Finisher>(player).unwrap().kind,
-            FinisherKind::Execute(WeaponType::Pistol)
+            FinisherKind::Execute(WeaponType::PISTOL)
         );
 
         run_secs(&mut world, 0.5);
@@ -544,16 +602,16 @@ mod tests {
         assert_eq!(world.get_component::<Weapon>(player).unwrap().ammo, 11);
         // The shot and its point-blank impact were announced.
         let events = world.drain_events();
-        assert!(events.contains(&GameEvent::PlayerFired(WeaponType::Pistol)));
+        assert!(events.contains(&GameEvent::PlayerFired(WeaponType::PISTOL)));
         assert!(events.contains(&GameEvent::EnemyHit {
-            by: WeaponType::Pistol
+            by: WeaponType::PISTOL
         }));
     }
 
     #[test]
     fn test_empty_gun_pistol_whips_instead_of_firing() {
         let mut world = World::new();
-        let empty = Weapon::with_ammo(WeaponType::Shotgun, 0);
+        let empty = Weapon::with_ammo(WeaponType::SHOTGUN, 0);
         let player = spawn_player_at(&mut world, 0.0, 0.0, Some(empty));
         let victim = spawn_downed_enemy(&mut world, 30.0, 0.0);
 
@@ -566,6 +624,17 @@ mod tests {
         assert!(world.get_component::<Health>(victim).unwrap().is_dead());
         // No round appeared from nowhere.
         assert_eq!(world.get_component::<Weapon>(player).unwrap().ammo, 0);
+        // Swung like the bar, not fired: a bar swing and a bar impact.
+        assert_eq!(
+            world.drain_events(),
+            vec![
+                GameEvent::PlayerFired(WeaponType::MELEE),
+                GameEvent::EnemyHit {
+                    by: WeaponType::MELEE
+                },
+                GameEvent::FinisherDone,
+            ]
+        );
     }
 
     #[test]
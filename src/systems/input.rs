@@ -1,6 +1,6 @@
-use crate::components::{Player, Position, Rotation, Speed, Velocity};
+use crate::components::{Player, Position, Rotation};
 use crate::ecs::{Entity, World};
-use crate::game::fire_player_weapon;
+use crate::game::{fire_player_weapon, set_player_movement};
 use crate::input;
 use crate::math::Vec2;
 
@@ -33,45 +33,28 @@ impl InputSystem {
         }
     }
 
-    /// Update player velocity based on WASD input
-    pub fn update_player_movement(world: &mut World) {
-        let player = match Self::find_player(world) {
-            Some(e) => e,
-            None => return,
-        };
-
-        let speed = match world.get_component::<Speed>(player) {
-            Some(s) => s.value,
-            None => return,
-        };
-
-        let mut move_x: f32 = 0.0;
-        let mut move_y: f32 = 0.0;
-
+    /// The WASD / arrow keys as a movement axis (see
+    /// [`crate::game::PlayerIntents::move_dir`]).
+    pub fn move_axis() -> Vec2 {
+        let mut axis = Vec2::zero();
         if input::is_key_down(input::keys::W) || input::is_key_down(input::keys::ARROW_UP) {
-            move_y -= 1.0;
+            axis.y -= 1.0;
         }
         if input::is_key_down(input::keys::S) || input::is_key_down(input::keys::ARROW_DOWN) {
-            move_y += 1.0;
+            axis.y += 1.0;
         }
         if input::is_key_down(input::keys::A) || input::is_key_down(input::keys::ARROW_LEFT) {
-            move_x -= 1.0;
+            axis.x -= 1.0;
         }
         if input::is_key_down(input::keys::D) || input::is_key_down(input::keys::ARROW_RIGHT) {
-            move_x += 1.0;
-        }
-
-        // Normalize diagonal movement
-        let len = (move_x * move_x + move_y * move_y).sqrt();
-        if len > 0.0 {
-            move_x /= len;
-            move_y /= len;
+            axis.x += 1.0;
         }
+        axis
+    }
 
-        if let Some(velocity) = world.get_component_mut::<Velocity>(player) {
-            velocity.x = move_x * speed;
-            velocity.y = move_y * speed;
-        }
+    /// Update player velocity based on WASD input
+    pub fn update_player_movement(world: &mut World) {
+        set_player_movement(world, Self::move_axis());
     }
 
     /// Handle shooting input: fire on left mouse button, delegating to the
@@ -2,11 +2,13 @@
 pub mod ai;
 pub mod boss;
 pub mod combat;
+pub mod door;
 pub mod elevator;
 pub mod finisher;
 #[cfg(target_arch = "wasm32")]
 pub mod input;
 pub mod movement;
+pub mod noise;
 pub mod passive;
 pub mod pickup;
 pub mod projectile;
@@ -17,11 +19,13 @@ pub mod weapon;
 pub use ai::AISystem;
 pub use boss::BossSystem;
 pub use combat::CombatSystem;
+pub use door::DoorSystem;
 pub use elevator::ElevatorSystem;
 pub use finisher::FinisherSystem;
 #[cfg(target_arch = "wasm32")]
 pub use input::InputSystem;
 pub use movement::MovementSystem;
+pub use noise::NoiseSystem;
 pub use pickup::PickupSystem;
 pub use projectile::{BulletSystem, ProjectileTrailSystem};
 pub use stun::StunSystem;
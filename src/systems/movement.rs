@@ -1,7 +1,8 @@
 use crate::collision::circle_rect_collision;
 use crate::components::{Knockback, Position, Radius, Velocity};
-use crate::ecs::{System, World};
+use crate::ecs::{Entity, System, World};
 use crate::math::Vec2;
+use crate::spatial::SpatialIndex;
 
 /// Size of the playable world (matches the rendered floor). Entities are kept
 /// inside `[0, WORLD_SIZE]` on both axes so nothing (notably wandering enemies)
@@ -39,30 +40,26 @@ pub struct MovementSystem;
 
 impl System for MovementSystem {
     fn run(&mut self, world: &mut World, dt: f32) {
-        // Query all entities with both Position and Velocity
-        let entities: Vec<_> = world.query_with::<Position, Velocity>();
-
-        for entity in entities {
-            // Get velocity, any knockback impulse, and radius (immutable
-            // borrows), copy the values.
-            let vel = world.get_component::<Velocity>(entity).copied();
-            let knockback = world.get_component::<Knockback>(entity).copied();
-            let radius = world
-                .get_component::<Radius>(entity)
-                .map(|r| r.value)
-                .unwrap_or(0.0);
-
-            let pos0 = world.get_component::<Position>(entity).copied();
-            let (Some(pos0), Some(vel)) = (pos0, vel) else {
-                continue;
-            };
+        // Every mover, with its (optional) knockback impulse and radius,
+        // borrowed in place alongside the walls: no per-entity lookups.
+        let mut spent = Vec::new();
+        let (movers, walls) = world.query_mut_with_walls::<(
+            Entity,
+            &mut Position,
+            &Velocity,
+            Option<&mut Knockback>,
+            Option<&Radius>,
+        ), ()>();
+
+        for (entity, pos, vel, knockback, radius) in movers {
+            let radius = radius.map(|r| r.value).unwrap_or(0.0);
 
             // Layer the knockback impulse on top of the normal velocity so the
             // shove travels through the same wall/bounds clamping below. The
             // impulse is worked on locally: a bounce below rewrites it.
-            let (mut kb_x, mut kb_y) = knockback.map(|k| (k.x, k.y)).unwrap_or((0.0, 0.0));
-            let mut final_x = pos0.x;
-            let mut final_y = pos0.y;
+            let (mut kb_x, mut kb_y) = knockback.as_ref().map(|k| (k.x, k.y)).unwrap_or((0.0, 0.0));
+            let mut final_x = pos.x;
+            let mut final_y = pos.y;
 
             // Sub-step the motion so a fast mover can never jump clean over a
             // thin wall in one integration step: each sub-step advances at most
@@ -78,11 +75,6 @@ impl System for MovementSystem {
             };
             let sub_dt = dt / steps as f32;
 
-            // Borrow the walls in place for the sub-step loop (no per-tick
-            // clone): the loop only touches locals, and the shared borrow
-            // ends before the component writes below.
-            let walls = world.walls();
-
             for _ in 0..steps {
                 final_x += (vel.x + kb_x) * sub_dt;
                 final_y += (vel.y + kb_y) * sub_dt;
@@ -152,27 +144,29 @@ impl System for MovementSystem {
             }
 
             // Keep entities within the world bounds.
-            if let Some(pos) = world.get_component_mut::<Position>(entity) {
-                pos.x = final_x.clamp(0.0, WORLD_SIZE);
-                pos.y = final_y.clamp(0.0, WORLD_SIZE);
-            }
+            pos.x = final_x.clamp(0.0, WORLD_SIZE);
+            pos.y = final_y.clamp(0.0, WORLD_SIZE);
 
             // Write back the (possibly bounced) impulse, decay it
             // (frame-rate independent), and drop it once it is spent so it
             // stops nudging the entity forever.
-            if knockback.is_some() {
+            if let Some(kb) = knockback {
                 let decay = (-dt / KNOCKBACK_DECAY_TAU).exp();
-                let mut spent = false;
-                if let Some(kb) = world.get_component_mut::<Knockback>(entity) {
-                    kb.x = kb_x * decay;
-                    kb.y = kb_y * decay;
-                    spent = kb.magnitude() < KNOCKBACK_MIN_SPEED;
-                }
-                if spent {
-                    world.remove_component::<Knockback>(entity);
+                kb.x = kb_x * decay;
+                kb.y = kb_y * decay;
+                if kb.magnitude() < KNOCKBACK_MIN_SPEED {
+                    spent.push(entity);
                 }
             }
         }
+
+        for entity in spent {
+            world.remove_component::<Knockback>(entity);
+        }
+
+        // Everyone has moved for the tick: refile them in the spatial index
+        // the resolve stage (bullets, thrown weapons) queries.
+        SpatialIndex::sync(world);
     }
 }
 
@@ -280,4 +274,22 @@ mod tests {
         assert_eq!(pos.x, 10.0);
         assert_eq!(pos.y, 20.0); // Position unchanged
     }
+
+    #[test]
+    fn test_movement_system_refiles_the_spatial_index() {
+        let mut world = World::new();
+        let entity = world.spawn();
+        world.add_component(entity, Position::new(100.0, 100.0));
+        world.add_component(entity, Velocity::new(500.0, 0.0));
+        world.add_component(entity, Radius::new(10.0));
+
+        let mut system = MovementSystem;
+        system.run(&mut world, 1.0);
+
+        let index = world
+            .resource::<SpatialIndex>()
+            .expect("index kept by movement");
+        assert!(index.within(Vec2::new(100.0, 100.0), 5.0).is_empty());
+        assert_eq!(index.within(Vec2::new(600.0, 100.0), 5.0), vec![entity]);
+    }
 }
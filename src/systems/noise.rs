@@ -0,0 +1,295 @@
+//! Noise: gunfire, rounds striking home and breaking glass carry through the
+//! floor and bring the rogues who hear them over to look.
+//!
+//! A noise is a point and a loudness: how far (px, on foot) it carries. It
+//! spreads over the [`NavigationGrid`] rather than in a straight line, so a
+//! wall muffles it by making it go round, and a shot fired in a closed room
+//! reaches the corridor through the door. The loud moments queue a noise with
+//! [`make_noise`] where they happen; [`NoiseSystem`] hears the queue once per
+//! tick, ahead of the AI.
+
+use crate::components::{AIState, Enemy, Health, Position, Stunned, WeaponType, AI};
+use crate::ecs::{Entity, System, World};
+use crate::math::Vec2;
+use crate::pathfinding::NavigationGrid;
+
+/// How far (px, on foot) a pane of glass is heard going.
+pub const GLASS_LOUDNESS: f32 = 200.0;
+
+/// How far (px, on foot) a shot from `weapon` is heard (its table
+/// `loudness`). A blade or a fist makes no noise anyone hears.
+pub fn loudness(weapon: WeaponType) -> f32 {
+    weapon.def().loudness
+}
+
+/// How far (px, on foot) a round from `weapon` is heard striking a rogue:
+/// half as far as the shot itself.
+pub fn impact_loudness(weapon: WeaponType) -> f32 {
+    loudness(weapon) * 0.5
+}
+
+/// One noise waiting to be heard.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Noise {
+    pub at: Vec2,
+    pub loudness: f32,
+}
+
+/// The noises made since [`NoiseSystem`] last listened (a world resource).
+#[derive(Debug, Clone, Default)]
+pub struct Noises(pub Vec<Noise>);
+
+/// Make a noise at `at`, heard `loudness` px away on foot. Silent noises
+/// (melee) are dropped.
+pub fn make_noise(world: &mut World, at: Vec2, loudness: f32) {
+    if loudness <= 0.0 {
+        return;
+    }
+    let noise = Noise { at, loudness };
+    match world.resource_mut::<Noises>() {
+        Some(noises) => noises.0.push(noise),
+        None => world.insert_resource(Noises(vec![noise])),
+    }
+}
+
+/// Shatter the glass wall at `index` (see [`World::shatter_wall`]) and make
+/// the noise of it at the pane's centre. Returns false (and does nothing) for
+/// a solid wall or an index out of range.
+pub fn shatter_glass(world: &mut World, index: usize) -> bool {
+    let Some(pane) = world.walls().get(index).copied() else {
+        return false;
+    };
+    if !world.shatter_wall(index) {
+        return false;
+    }
+    let centre = Vec2::new(pane.x + pane.width / 2.0, pane.y + pane.height / 2.0);
+    make_noise(world, centre, GLASS_LOUDNESS);
+    true
+}
+
+/// Drop every queued noise unheard: the freeze variant of [`NoiseSystem`],
+/// so nothing fired under a freeze is heard the moment it lifts.
+pub fn hush(world: &mut World, _dt: f32) {
+    if let Some(noises) = world.resource_mut::<Noises>() {
+        noises.0.clear();
+    }
+}
+
+/// System that hears the queued noises. A rogue on its feet, not already
+/// hunting the player, that stands within a noise's reach (walking distance)
+/// turns unsure and goes to look where it came from (the nearest, when
+/// several reach it). Passive bots do not investigate, and a rogue sure of
+/// the player, or unsure but watching them, is not distracted: a shot would
+/// send the watcher off to check it and restart its spotting.
+pub struct NoiseSystem;
+
+impl System for NoiseSystem {
+    fn run(&mut self, world: &mut World, _dt: f32) {
+        let noises = match world.resource_mut::<Noises>() {
+            Some(noises) if !noises.0.is_empty() => std::mem::take(&mut noises.0),
+            _ => return,
+        };
+
+        let listeners: Vec<(Entity, Vec2)> = world
+            .query_ref::<(Entity, &Position, &AI, &Health)>()
+            .filter(|&(e, _, ai, health)| {
+                world.has_component::<Enemy>(e)
+                    && !world.has_component::<Stunned>(e)
+                    && health.is_alive()
+                    && match ai.state {
+                        AIState::Unaware | AIState::Confused => true,
+                        // Already checking a noise: a fresh one redirects it.
+                        AIState::SpottedUnsure => ai.investigating(),
+                        _ => false,
+                    }
+            })
+            .map(|(e, pos, ..)| (e, pos.to_vec2()))
+            .collect();
+        if listeners.is_empty() {
+            return;
+        }
+
+        NavigationGrid::sync(world);
+        let Some(grid) = world.resource::<NavigationGrid>() else {
+            return;
+        };
+        // The nearest noise each listener hears (its distance and source).
+        let mut heard: Vec<Option<(f32, Vec2)>> = vec![None; listeners.len()];
+        for noise in &noises {
+            let reach = grid.path_distances(noise.at, noise.loudness);
+            for (slot, &(_, pos)) in heard.iter_mut().zip(&listeners) {
+                let Some(&d) = grid.walkable_cell(pos).and_then(|c| reach.get(&c)) else {
+                    continue;
+                };
+                if slot.is_none_or(|(best, _)| d < best) {
+                    *slot = Some((d, noise.at));
+                }
+            }
+        }
+
+        for ((entity, _), heard) in listeners.into_iter().zip(heard) {
+            let Some((_, source)) = heard else {
+                continue;
+            };
+            if let Some(ai) = world.get_component_mut::<AI>(entity) {
+                if ai.state != AIState::SpottedUnsure {
+                    ai.state = AIState::SpottedUnsure;
+                    ai.state_timer = ai.spot_duration;
+                }
+                // Walk over and check: the AI settles back to unaware once
+                // there without having seen anyone.
+                let source = Position::from_vec2(source);
+                ai.last_known_player_position = Some(source);
+                ai.check_position = Some(source);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::world::Wall;
+    use crate::game::{spawn_enemy, spawn_player};
+    use crate::systems::{AISystem, MovementSystem};
+
+    fn state(world: &World, e: Entity) -> AIState {
+        world.get_component::<AI>(e).unwrap().state
+    }
+
+    #[test]
+    fn test_shot_down_an_open_corridor_brings_a_rogue_over() {
+        let mut world = World::new();
+        let rogue = spawn_enemy(&mut world, Vec2::new(300.0, 200.0));
+        make_noise(
+            &mut world,
+            Vec2::new(100.0, 200.0),
+            loudness(WeaponType::PISTOL),
+        );
+
+        NoiseSystem.run(&mut world, 0.016);
+
+        let ai = world.get_component::<AI>(rogue).unwrap();
+        assert_eq!(ai.state, AIState::SpottedUnsure);
+        let source = ai.last_known_player_position.unwrap();
+        assert_eq!((source.x, source.y), (100.0, 200.0), "goes to look");
+        assert!(
+            world.resource::<Noises>().unwrap().0.is_empty(),
+            "heard once"
+        );
+    }
+
+    #[test]
+    fn test_a_wall_in_the_way_muffles_the_shot() {
+        let mut world = World::new();
+        // 200 px apart, but the wall between them runs 700 px down.
+        world.add_wall(190.0, 0.0, 20.0, 700.0);
+        let rogue = spawn_enemy(&mut world, Vec2::new(300.0, 200.0));
+        make_noise(
+            &mut world,
+            Vec2::new(100.0, 200.0),
+            loudness(WeaponType::PISTOL),
+        );
+
+        NoiseSystem.run(&mut world, 0.016);
+        assert_eq!(state(&world, rogue), AIState::Unaware);
+
+        // A noise loud enough carries round the end of it.
+        make_noise(&mut world, Vec2::new(100.0, 200.0), 2000.0);
+        NoiseSystem.run(&mut world, 0.016);
+        assert_eq!(state(&world, rogue), AIState::SpottedUnsure);
+    }
+
+    #[test]
+    fn test_a_rogue_checks_the_spot_and_settles_down() {
+        let mut world = World::new();
+        // The player is long gone, out of sight behind a wall.
+        spawn_player(&mut world, Vec2::new(600.0, 600.0));
+        world.add_wall(400.0, 400.0, 400.0, 20.0);
+        let rogue = spawn_enemy(&mut world, Vec2::new(300.0, 200.0));
+        make_noise(
+            &mut world,
+            Vec2::new(100.0, 200.0),
+            loudness(WeaponType::PISTOL),
+        );
+        NoiseSystem.run(&mut world, 0.016);
+        assert!(world.get_component::<AI>(rogue).unwrap().investigating());
+
+        for _ in 0..300 {
+            AISystem.run(&mut world, 0.016);
+            MovementSystem.run(&mut world, 0.016);
+        }
+        let pos = world.get_component::<Position>(rogue).unwrap();
+        assert!(pos.to_vec2().distance(Vec2::new(100.0, 200.0)) < 10.0);
+        assert_eq!(state(&world, rogue), AIState::Unaware);
+    }
+
+    #[test]
+    fn test_melee_is_silent() {
+        let mut world = World::new();
+        let rogue = spawn_enemy(&mut world, Vec2::new(150.0, 200.0));
+        make_noise(
+            &mut world,
+            Vec2::new(100.0, 200.0),
+            loudness(WeaponType::MELEE),
+        );
+
+        NoiseSystem.run(&mut world, 0.016);
+        assert_eq!(state(&world, rogue), AIState::Unaware);
+    }
+
+    #[test]
+    fn test_breaking_glass_is_heard() {
+        let mut world = World::new();
+        world.insert_wall(
+            Wall::new(300.0, 180.0, 10.0, 40.0)
+                .with_material(crate::ecs::world::WallMaterial::Glass),
+        );
+        let rogue = spawn_enemy(&mut world, Vec2::new(420.0, 200.0));
+
+        assert!(shatter_glass(&mut world, 0));
+        NoiseSystem.run(&mut world, 0.016);
+        assert_eq!(state(&world, rogue), AIState::SpottedUnsure);
+    }
+
+    #[test]
+    fn test_a_rogue_sure_of_the_player_is_not_distracted() {
+        let mut world = World::new();
+        let rogue = spawn_enemy(&mut world, Vec2::new(500.0, 200.0));
+        world.get_component_mut::<AI>(rogue).unwrap().state = AIState::SurePlayerSeen;
+        make_noise(
+            &mut world,
+            Vec2::new(100.0, 200.0),
+            loudness(WeaponType::SHOTGUN),
+        );
+
+        NoiseSystem.run(&mut world, 0.016);
+        let ai = world.get_component::<AI>(rogue).unwrap();
+        assert_eq!(ai.state, AIState::SurePlayerSeen);
+        assert!(ai.last_known_player_position.is_none());
+    }
+
+    #[test]
+    fn test_sustained_fire_does_not_keep_a_watching_rogue_unsure() {
+        let mut world = World::new();
+        let player = Vec2::new(100.0, 200.0);
+        spawn_player(&mut world, player);
+        let rogue = spawn_enemy(&mut world, Vec2::new(300.0, 200.0));
+        world
+            .get_component_mut::<crate::components::Rotation>(rogue)
+            .unwrap()
+            .angle = std::f32::consts::PI;
+        AISystem.run(&mut world, 0.016);
+        assert_eq!(state(&world, rogue), AIState::SpottedUnsure);
+
+        // The player keeps shooting in plain view: the rogue is already
+        // watching, so the shots must not restart its spotting.
+        let spot = world.get_component::<AI>(rogue).unwrap().spot_duration;
+        for _ in 0..(spot / 0.016) as usize + 5 {
+            make_noise(&mut world, player, loudness(WeaponType::MACHINEGUN));
+            NoiseSystem.run(&mut world, 0.016);
+            AISystem.run(&mut world, 0.016);
+        }
+        assert_eq!(state(&world, rogue), AIState::SurePlayerSeen);
+    }
+}
@@ -27,7 +27,7 @@ use crate::components::{
     Velocity, Weapon, Zone, AI,
 };
 use crate::ecs::world::Wall;
-use crate::ecs::{Entity, World};
+use crate::ecs::{Entity, Rng, World};
 use crate::game::weapon_for_enemy;
 use crate::math::Vec2;
 use crate::pathfinding::NavigationGrid;
@@ -51,16 +51,6 @@ const TURN_RATE: f32 = 6.0;
 /// Idle fidget: max deviation (radians) from the settled heading.
 const FIDGET_SWING: f32 = 0.45;
 
-fn next_random(state: &mut u32) -> u32 {
-    *state = state.wrapping_mul(1664525).wrapping_add(1013904223);
-    *state
-}
-
-fn random_range(state: &mut u32, min: f32, max: f32) -> f32 {
-    let r = next_random(state) as f32 / u32::MAX as f32;
-    r * (max - min) + min
-}
-
 /// Wrap an angle difference into `-PI..=PI`.
 fn wrap_angle(mut a: f32) -> f32 {
     while a > PI {
@@ -90,7 +80,6 @@ pub fn spawn_passive(world: &mut World, def: &SpawnDef) -> Entity {
     let mut ai = AI::new_with_type(def.kind, pos);
     ai.state = AIState::Passive;
     let mut brief = PassiveAI::new(def.walk_to, face, def.group);
-    brief.last_health = 50;
     brief.fidget_heading = face.unwrap_or(-PI / 2.0);
     ai.passive = Some(brief);
     world.add_component(entity, ai);
@@ -194,12 +183,13 @@ pub fn make_hostile(world: &mut World, entity: Entity, player_pos: Option<Positi
 /// writing `Velocity` and `Rotation`. Returns `true` when the bot took damage
 /// since the previous tick (the caller then alerts the whole floor).
 ///
-/// `rng` is the world RNG state threaded through the AI update; `nav_grid`
-/// and `walls` are the AI system's cached level geometry.
+/// `rng` is the world's crowd stream ([`crate::ecs::RngStream::Crowd`]),
+/// threaded through the AI update; `nav_grid` and `walls` are the AI
+/// system's cached level geometry.
 pub fn update_passive(
     world: &mut World,
     entity: Entity,
-    rng: &mut u32,
+    rng: &mut Rng,
     nav_grid: &NavigationGrid,
     walls: &[Wall],
     dt: f32,
@@ -221,10 +211,9 @@ pub fn update_passive(
         .passive
         .unwrap_or_else(|| PassiveAI::new(None, None, None));
 
-    // Damage since last tick? (Knockback alone does not count.) A bot downed
-    // outright still reports it once — the crowd turns on a kill too.
-    let hurt = health.current < brief.last_health;
-    brief.last_health = health.current;
+    // Damage since the AI last ran? (Knockback alone does not count.) A bot
+    // downed outright still reports it — the crowd turns on a kill too.
+    let hurt = world.is_changed::<Health>(entity) && health.current < health.max;
     if health.is_dead() || world.has_component::<Stunned>(entity) {
         // Down or knocked over: no brain, no motion; keep the brief current.
         ai.passive = Some(brief);
@@ -255,7 +244,7 @@ pub fn update_passive(
                     brief.arrived = true;
                     brief.target = None;
                     brief.fidget_heading = brief.face.unwrap_or(rot);
-                    brief.fidget_timer = random_range(rng, 1.0, 3.0);
+                    brief.fidget_timer = rng.range(1.0, 3.0);
                     goal_heading = brief.fidget_heading;
                 } else {
                     let clear = crate::collision::has_line_of_sight_with_padding(
@@ -303,8 +292,8 @@ pub fn update_passive(
         brief.fidget_timer -= dt;
         if brief.fidget_timer <= 0.0 {
             let base = brief.face.unwrap_or(brief.fidget_heading);
-            brief.fidget_heading = base + random_range(rng, -FIDGET_SWING, FIDGET_SWING);
-            brief.fidget_timer = random_range(rng, 1.2, 3.5);
+            brief.fidget_heading = base + rng.range(-FIDGET_SWING, FIDGET_SWING);
+            brief.fidget_timer = rng.range(1.2, 3.5);
         }
         goal_heading = brief.fidget_heading;
     } else {
@@ -315,15 +304,15 @@ pub fn update_passive(
             if brief.target.is_some() {
                 // End of a step: pause.
                 brief.target = None;
-                brief.fidget_timer = random_range(rng, 1.0, 2.5);
+                brief.fidget_timer = rng.range(1.0, 2.5);
             } else {
                 // Start a step toward a nearby point inside the leash.
                 let sp = ai.spawn_position;
                 let leash = PASSIVE_WANDER_LEASH;
-                let tx = (pos.x + random_range(rng, -60.0, 60.0)).clamp(sp.x - leash, sp.x + leash);
-                let ty = (pos.y + random_range(rng, -60.0, 60.0)).clamp(sp.y - leash, sp.y + leash);
+                let tx = (pos.x + rng.range(-60.0, 60.0)).clamp(sp.x - leash, sp.x + leash);
+                let ty = (pos.y + rng.range(-60.0, 60.0)).clamp(sp.y - leash, sp.y + leash);
                 brief.target = Some(Position::new(tx, ty));
-                brief.fidget_timer = random_range(rng, 0.8, 1.6);
+                brief.fidget_timer = rng.range(0.8, 1.6);
             }
         }
         if let Some(t) = brief.target {
@@ -348,7 +337,7 @@ pub fn update_passive(
                 brief.fidget_heading = goal_heading;
             } else {
                 brief.target = None;
-                brief.fidget_timer = random_range(rng, 1.0, 2.5);
+                brief.fidget_timer = rng.range(1.0, 2.5);
             }
         }
     }
@@ -373,7 +362,7 @@ pub fn update_passive(
 
 /// A random point inside the zone with that id (inset from its edges), or
 /// `None` if the floor has no such zone.
-fn pick_point_in_zone(world: &World, zone_id: &str, rng: &mut u32) -> Option<Position> {
+fn pick_point_in_zone(world: &World, zone_id: &str, rng: &mut Rng) -> Option<Position> {
     let z = world.query::<Zone>().into_iter().find_map(|e| {
         world
             .get_component::<Zone>(e)
@@ -382,8 +371,8 @@ fn pick_point_in_zone(world: &World, zone_id: &str, rng: &mut u32) -> Option<Pos
     })?;
     let inset_x = ZONE_INSET.min(z.w / 2.0);
     let inset_y = ZONE_INSET.min(z.h / 2.0);
-    let x = random_range(rng, z.x + inset_x, z.x + z.w - inset_x);
-    let y = random_range(rng, z.y + inset_y, z.y + z.h - inset_y);
+    let x = rng.range(z.x + inset_x, z.x + z.w - inset_x);
+    let y = rng.range(z.y + inset_y, z.y + z.h - inset_y);
     Some(Position::new(x, y))
 }
 
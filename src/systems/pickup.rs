@@ -1,7 +1,8 @@
 use crate::components::{
     Downed, Enemy, GameEvent, Health, Player, Position, Radius, Weapon, WeaponPickup, WeaponType,
 };
-use crate::ecs::{Entity, System, World};
+use crate::ecs::{Commands, Entity, RngStream, System, With, World};
+use crate::spatial::SpatialIndex;
 
 /// Rogue drops are never a full magazine: a downed rogue's weapon lands with a
 /// random 30–100 % of its magazine (rounded up, at least one round), so
@@ -36,36 +37,30 @@ impl PickupSystem {
     /// Spawn a weapon pickup for any dead enemy that still carries a weapon.
     /// The weapon is removed from the enemy so it is only dropped once. The
     /// drop carries a partial magazine (see [`DROP_AMMO_MIN_PERCENT`]).
+    /// Deferred: the drops land at the stage boundary.
     pub fn drop_from_dead_enemies(world: &mut World) {
-        let enemies: Vec<Entity> = world.query::<Enemy>();
+        let mut commands = Commands::new();
 
-        for enemy in enemies {
-            let is_dead = match world.get_component::<Health>(enemy) {
-                Some(h) => h.is_dead(),
-                None => continue,
-            };
-            if !is_dead {
+        for (enemy, health, weapon, pos) in
+            world.query_ref_filtered::<(Entity, &Health, &Weapon, &Position), With<Enemy>>()
+        {
+            if !health.is_dead() {
                 continue;
             }
 
-            let weapon = match world.get_component::<Weapon>(enemy) {
-                Some(w) => *w,
-                None => continue, // already dropped (or never carried one)
-            };
-            let pos = match world.get_component::<Position>(enemy) {
-                Some(p) => *p,
-                None => continue,
-            };
-
             // Remove the weapon so this enemy does not drop again next frame.
-            world.remove_component::<Weapon>(enemy);
-
-            let ammo = Self::drop_ammo(world, weapon.weapon_type);
-            let pickup = world.spawn();
-            world.add_component(pickup, WeaponPickup::with_ammo(weapon.weapon_type, ammo));
-            world.add_component(pickup, pos);
-            world.add_component(pickup, Radius::new(14.0));
+            commands.remove::<Weapon>(enemy);
+
+            let (weapon_type, pos) = (weapon.weapon_type, *pos);
+            commands.spawn(move |world, pickup| {
+                let ammo = Self::drop_ammo(world, weapon_type);
+                world.add_component(pickup, WeaponPickup::with_ammo(weapon_type, ammo));
+                world.add_component(pickup, pos);
+                world.add_component(pickup, Radius::new(14.0));
+            });
         }
+
+        world.defer(commands);
     }
 
     /// Rounds in a downed rogue's dropped weapon: a random 30–100 % of a
@@ -75,7 +70,9 @@ impl PickupSystem {
         if weapon_type.is_melee() {
             return mag;
         }
-        let percent = world.random_int_range(DROP_AMMO_MIN_PERCENT, 100);
+        let percent = world
+            .rng(RngStream::Loot)
+            .int_range(DROP_AMMO_MIN_PERCENT, 100);
         ((mag * percent + 99) / 100).clamp(1, mag)
     }
 
@@ -97,7 +94,12 @@ impl PickupSystem {
             .unwrap_or(15.0);
         let current_weapon = world.get_component::<Weapon>(player).copied();
 
-        let pickups: Vec<Entity> = world.query::<WeaponPickup>();
+        SpatialIndex::sync(world);
+        let pickups: Vec<Entity> = SpatialIndex::of(world)
+            .within(player_pos.to_vec2(), player_radius)
+            .into_iter()
+            .filter(|&e| world.has_component::<WeaponPickup>(e))
+            .collect();
         for pickup in pickups {
             let (pickup_pos, pickup_radius, found) = match (
                 world.get_component::<Position>(pickup),
@@ -181,16 +183,17 @@ mod tests {
         world.add_component(p, Speed::new(200.0));
         world.add_component(p, Radius::new(15.0));
         world.add_component(p, Rotation::new(0.0));
-        world.add_component(p, Weapon::new(WeaponType::Pistol));
+        world.add_component(p, Weapon::new(WeaponType::PISTOL));
         p
     }
 
     #[test]
     fn test_dead_enemy_drops_weapon_pickup() {
         let mut world = World::new();
-        let enemy = spawn_dead_enemy(&mut world, Vec2::new(100.0, 100.0), WeaponType::Shotgun);
+        let enemy = spawn_dead_enemy(&mut world, Vec2::new(100.0, 100.0), WeaponType::SHOTGUN);
 
         PickupSystem::drop_from_dead_enemies(&mut world);
+        world.apply_deferred();
 
         // Enemy no longer carries the weapon
         assert!(!world.has_component::<Weapon>(enemy));
@@ -200,9 +203,9 @@ mod tests {
         assert_eq!(pickups.len(), 1);
         let pickup = pickups[0];
         let dropped = *world.get_component::<WeaponPickup>(pickup).unwrap();
-        assert_eq!(dropped.weapon_type, WeaponType::Shotgun);
+        assert_eq!(dropped.weapon_type, WeaponType::SHOTGUN);
         // Rogue drops carry a partial magazine: 30-100 %, at least one round.
-        let mag = WeaponType::Shotgun.magazine();
+        let mag = WeaponType::SHOTGUN.magazine();
         assert!(dropped.ammo >= 1 && dropped.ammo <= mag, "{}", dropped.ammo);
         assert!(dropped.ammo * 100 >= mag * DROP_AMMO_MIN_PERCENT - 99);
         let pos = world.get_component::<Position>(pickup).unwrap();
@@ -213,10 +216,12 @@ mod tests {
     #[test]
     fn test_dead_enemy_drops_only_once() {
         let mut world = World::new();
-        spawn_dead_enemy(&mut world, Vec2::new(0.0, 0.0), WeaponType::Pistol);
+        spawn_dead_enemy(&mut world, Vec2::new(0.0, 0.0), WeaponType::PISTOL);
 
         PickupSystem::drop_from_dead_enemies(&mut world);
+        world.apply_deferred();
         PickupSystem::drop_from_dead_enemies(&mut world);
+        world.apply_deferred();
 
         assert_eq!(world.query::<WeaponPickup>().len(), 1);
     }
@@ -228,9 +233,10 @@ mod tests {
         world.add_component(enemy, Enemy);
         world.add_component(enemy, Position::new(0.0, 0.0));
         world.add_component(enemy, Health::new(50)); // alive
-        world.add_component(enemy, Weapon::new(WeaponType::Pistol));
+        world.add_component(enemy, Weapon::new(WeaponType::PISTOL));
 
         PickupSystem::drop_from_dead_enemies(&mut world);
+        world.apply_deferred();
 
         assert!(world.has_component::<Weapon>(enemy));
         assert_eq!(world.query::<WeaponPickup>().len(), 0);
@@ -244,23 +250,23 @@ mod tests {
         world.get_component_mut::<Weapon>(player).unwrap().ammo = 7;
 
         let pickup = world.spawn();
-        world.add_component(pickup, WeaponPickup::with_ammo(WeaponType::MachineGun, 19));
+        world.add_component(pickup, WeaponPickup::with_ammo(WeaponType::MACHINEGUN, 19));
         world.add_component(pickup, Position::new(55.0, 50.0)); // within radius sum
         world.add_component(pickup, Radius::new(14.0));
 
         let swapped = PickupSystem::swap_for_player(&mut world);
 
         // Player now holds the machine gun, with exactly the rounds it had...
-        assert_eq!(swapped, Some(WeaponType::MachineGun));
+        assert_eq!(swapped, Some(WeaponType::MACHINEGUN));
         let held = world.get_component::<Weapon>(player).unwrap();
-        assert_eq!(held.weapon_type, WeaponType::MachineGun);
+        assert_eq!(held.weapon_type, WeaponType::MACHINEGUN);
         assert_eq!(held.ammo, 19);
         // ...and their old pistol is dropped in place with ITS remaining ammo
         // (still one pickup, now a 7-round pistol).
         let pickups = world.query::<WeaponPickup>();
         assert_eq!(pickups.len(), 1);
         let dropped = world.get_component::<WeaponPickup>(pickups[0]).unwrap();
-        assert_eq!(dropped.weapon_type, WeaponType::Pistol);
+        assert_eq!(dropped.weapon_type, WeaponType::PISTOL);
         assert_eq!(dropped.ammo, 7);
         assert_eq!(world.drain_events(), vec![GameEvent::Pickup]);
     }
@@ -273,23 +279,23 @@ mod tests {
         let player = spawn_test_player(&mut world, Vec2::new(0.0, 0.0));
         world.get_component_mut::<Weapon>(player).unwrap().ammo = 7;
         let pickup = world.spawn();
-        world.add_component(pickup, WeaponPickup::with_ammo(WeaponType::Shotgun, 2));
+        world.add_component(pickup, WeaponPickup::with_ammo(WeaponType::SHOTGUN, 2));
         world.add_component(pickup, Position::new(0.0, 0.0));
         world.add_component(pickup, Radius::new(14.0));
 
         assert_eq!(
             PickupSystem::swap_for_player(&mut world),
-            Some(WeaponType::Shotgun)
+            Some(WeaponType::SHOTGUN)
         );
         assert_eq!(world.get_component::<Weapon>(player).unwrap().ammo, 2);
         assert_eq!(
             PickupSystem::swap_for_player(&mut world),
-            Some(WeaponType::Pistol)
+            Some(WeaponType::PISTOL)
         );
         let held = world.get_component::<Weapon>(player).unwrap();
-        assert_eq!((held.weapon_type, held.ammo), (WeaponType::Pistol, 7));
+        assert_eq!((held.weapon_type, held.ammo), (WeaponType::PISTOL, 7));
         let floor = world.get_component::<WeaponPickup>(pickup).unwrap();
-        assert_eq!((floor.weapon_type, floor.ammo), (WeaponType::Shotgun, 2));
+        assert_eq!((floor.weapon_type, floor.ammo), (WeaponType::SHOTGUN, 2));
     }
 
     #[test]
@@ -299,30 +305,32 @@ mod tests {
         let player = spawn_test_player(&mut world, Vec2::new(0.0, 0.0));
         world.get_component_mut::<Weapon>(player).unwrap().ammo = 0;
         let pickup = world.spawn();
-        world.add_component(pickup, WeaponPickup::new(WeaponType::Shotgun));
+        world.add_component(pickup, WeaponPickup::new(WeaponType::SHOTGUN));
         world.add_component(pickup, Position::new(0.0, 0.0));
         world.add_component(pickup, Radius::new(14.0));
 
         PickupSystem::swap_for_player(&mut world);
         let held = world.get_component::<Weapon>(player).unwrap();
         assert!(held.can_fire());
-        assert_eq!(held.ammo, WeaponType::Shotgun.magazine());
+        assert_eq!(held.ammo, WeaponType::SHOTGUN.magazine());
         // The empty pistol lies there, still empty.
         let floor = world.get_component::<WeaponPickup>(pickup).unwrap();
-        assert_eq!((floor.weapon_type, floor.ammo), (WeaponType::Pistol, 0));
+        assert_eq!((floor.weapon_type, floor.ammo), (WeaponType::PISTOL, 0));
     }
 
     #[test]
     fn test_announce_downed_emits_once_per_enemy() {
         let mut world = World::new();
-        let e = spawn_dead_enemy(&mut world, Vec2::new(0.0, 0.0), WeaponType::Pistol);
+        let e = spawn_dead_enemy(&mut world, Vec2::new(0.0, 0.0), WeaponType::PISTOL);
         let alive = world.spawn();
         world.add_component(alive, Enemy);
         world.add_component(alive, Health::new(50));
 
         let mut system = PickupSystem;
         system.run(&mut world, 0.016);
+        world.apply_deferred();
         system.run(&mut world, 0.016);
+        world.apply_deferred();
         assert_eq!(world.drain_events(), vec![GameEvent::EnemyDown]);
         assert!(world.has_component::<Downed>(e));
         assert!(!world.has_component::<Downed>(alive));
@@ -333,6 +341,7 @@ mod tests {
             .unwrap()
             .take_damage(99);
         system.run(&mut world, 0.016);
+        world.apply_deferred();
         assert_eq!(world.drain_events(), vec![GameEvent::EnemyDown]);
     }
 
@@ -346,19 +355,19 @@ mod tests {
         // No Weapon component: the player is unarmed.
 
         let pickup = world.spawn();
-        world.add_component(pickup, WeaponPickup::new(WeaponType::Shotgun));
+        world.add_component(pickup, WeaponPickup::new(WeaponType::SHOTGUN));
         world.add_component(pickup, Position::new(5.0, 0.0));
         world.add_component(pickup, Radius::new(14.0));
 
         let swapped = PickupSystem::swap_for_player(&mut world);
 
-        assert_eq!(swapped, Some(WeaponType::Shotgun));
+        assert_eq!(swapped, Some(WeaponType::SHOTGUN));
         // Nothing to drop, so the pickup is consumed…
         assert_eq!(world.query::<WeaponPickup>().len(), 0);
         // …and the player now actually holds it (regression: it used to vanish).
         assert_eq!(
             world.get_component::<Weapon>(player).map(|w| w.weapon_type),
-            Some(WeaponType::Shotgun)
+            Some(WeaponType::SHOTGUN)
         );
     }
 
@@ -368,7 +377,7 @@ mod tests {
         let player = spawn_test_player(&mut world, Vec2::new(0.0, 0.0));
 
         let pickup = world.spawn();
-        world.add_component(pickup, WeaponPickup::new(WeaponType::Shotgun));
+        world.add_component(pickup, WeaponPickup::new(WeaponType::SHOTGUN));
         world.add_component(pickup, Position::new(500.0, 500.0));
         world.add_component(pickup, Radius::new(14.0));
 
@@ -377,7 +386,7 @@ mod tests {
         assert_eq!(swapped, None);
         assert_eq!(
             world.get_component::<Weapon>(player).unwrap().weapon_type,
-            WeaponType::Pistol // unchanged
+            WeaponType::PISTOL // unchanged
         );
         assert_eq!(world.query::<WeaponPickup>().len(), 1);
     }
@@ -399,19 +408,20 @@ mod tests {
         );
         world.add_component(enemy, Rotation::new(0.0));
         world.add_component(enemy, AI::new());
-        world.add_component(enemy, Weapon::new(WeaponType::Shotgun));
+        world.add_component(enemy, Weapon::new(WeaponType::SHOTGUN));
 
         let mut system = PickupSystem;
         // Frame update drops the dead enemy's shotgun on the floor.
         system.run(&mut world, 0.016);
+        world.apply_deferred();
         assert_eq!(world.query::<WeaponPickup>().len(), 1);
 
         // Player presses pick-up: swaps pistol for shotgun, pistol left behind.
         let swapped = PickupSystem::swap_for_player(&mut world);
-        assert_eq!(swapped, Some(WeaponType::Shotgun));
+        assert_eq!(swapped, Some(WeaponType::SHOTGUN));
         assert_eq!(
             world.get_component::<Weapon>(player).unwrap().weapon_type,
-            WeaponType::Shotgun
+            WeaponType::SHOTGUN
         );
         let pickups = world.query::<WeaponPickup>();
         assert_eq!(pickups.len(), 1);
@@ -420,7 +430,7 @@ mod tests {
                 .get_component::<WeaponPickup>(pickups[0])
                 .unwrap()
                 .weapon_type,
-            WeaponType::Pistol
+            WeaponType::PISTOL
         );
     }
 }
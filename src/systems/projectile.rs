@@ -1,47 +1,68 @@
 use crate::collision;
 use crate::components::{
-    Bullet, Enemy, GameEvent, Health, Position, ProjectileTrail, Radius, Velocity,
+    Bullet, Enemy, GameEvent, Health, Player, Position, ProjectileTrail, Radius, Velocity,
 };
-use crate::ecs::{Entity, System, World};
+use crate::ecs::{Commands, Entity, System, World};
+use crate::spatial::SpatialIndex;
+use crate::systems::noise::{impact_loudness, make_noise, shatter_glass};
 
 /// System that updates and removes projectile trails
 pub struct ProjectileTrailSystem;
 
 impl System for ProjectileTrailSystem {
     fn run(&mut self, world: &mut World, dt: f32) {
-        let entities: Vec<_> = world.query::<ProjectileTrail>();
-
-        // Update lifetimes and collect dead entities
-        let mut dead_entities = Vec::new();
-
-        for entity in entities {
-            if let Some(trail) = world.get_component_mut::<ProjectileTrail>(entity) {
-                trail.lifetime -= dt;
-
-                if !trail.is_alive() {
-                    dead_entities.push(entity);
-                }
+        // Update lifetimes; dead trails are removed at the stage boundary.
+        let mut commands = Commands::new();
+        for (entity, trail) in world.query_mut::<(Entity, &mut ProjectileTrail)>() {
+            trail.lifetime -= dt;
+            if !trail.is_alive() {
+                commands.despawn(entity);
             }
         }
-
-        // Remove dead trails
-        for entity in dead_entities {
-            world.despawn(entity);
-        }
+        world.defer(commands);
     }
 }
 
 /// System that updates bullets - movement, wall collision, enemy damage
+/// (player rounds) and player hits (rogue rounds)
 pub struct BulletSystem;
 
+/// Sweep radius of a round spawned without a [`Radius`] (every gun gives its
+/// rounds one; this is the size they are given).
+const BULLET_RADIUS: f32 = 2.0;
+
+impl BulletSystem {
+    /// A rogue's round strikes the player: as lethal as any enemy blow
+    /// (one-hit death), it shoves the player along the round's flight and is
+    /// announced as [`GameEvent::PlayerHurt`] by `shooter`.
+    fn hit_player(world: &mut World, player: Entity, velocity: Velocity, shooter: Entity) {
+        world.update_component::<Health, _>(player, |health| {
+            health.take_damage(health.max.max(health.current))
+        });
+        world.push_event(GameEvent::PlayerHurt { by: shooter });
+        crate::systems::combat::CombatSystem::apply_knockback(
+            world,
+            player,
+            velocity.x,
+            velocity.y,
+            crate::systems::combat::PLAYER_KNOCKBACK,
+        );
+    }
+}
+
 impl System for BulletSystem {
     fn run(&mut self, world: &mut World, dt: f32) {
         let bullets: Vec<Entity> = world.query::<Bullet>();
-        // One enemy-list query for the whole tick (query allocates + sorts):
-        // nothing spawns mid-loop, and enemies killed by an earlier bullet
-        // are skipped by the per-bullet Health check below.
-        let enemies: Vec<Entity> = world.query::<Enemy>();
-        let mut bullets_to_remove = Vec::new();
+        // Enemies are looked up through the spatial index (fresh after the
+        // movement stage; nothing it files moves mid-loop), and enemies
+        // killed by an earlier bullet are skipped by the Health check below.
+        SpatialIndex::sync(world);
+        // Spent bullets are despawned at the stage boundary.
+        let mut commands = Commands::new();
+        // A door leaf stops a round like a wall.
+        let doors = crate::systems::door::door_leaves(world);
+        // Glass walls the rounds went through: shattered once all moved.
+        let mut panes = Vec::new();
 
         for bullet_entity in bullets {
             let (bullet, bullet_pos, bullet_vel) = match (
@@ -55,7 +76,7 @@ impl System for BulletSystem {
 
             // Check if bullet has expired
             if !bullet.is_alive() {
-                bullets_to_remove.push(bullet_entity);
+                commands.despawn(bullet_entity);
                 continue;
             }
 
@@ -63,71 +84,78 @@ impl System for BulletSystem {
             let new_x = bullet_pos.x + bullet_vel.x * dt;
             let new_y = bullet_pos.y + bullet_vel.y * dt;
 
-            // Check wall collision (bullets are small, use 2.0 radius).
-            // Swept old->new segment check: a bullet covers ~13 px per 60 Hz
-            // frame, so an endpoint-only test would tunnel straight through
-            // walls thinner than that.
-            let bullet_radius = 2.0;
-            let walls = world.walls();
-            let mut hit_wall = false;
-
-            for wall in walls {
-                if collision::swept_circle_rect_collision(
-                    crate::math::Vec2::new(bullet_pos.x, bullet_pos.y),
-                    crate::math::Vec2::new(new_x, new_y),
+            // Where along this frame's flight (0..1) the round stops. Swept
+            // old->new: a bullet covers ~13 px per 60 Hz frame, so an
+            // endpoint-only test would tunnel straight through walls thinner
+            // than that, or through a bot rushing it head-on.
+            let from = crate::math::Vec2::new(bullet_pos.x, bullet_pos.y);
+            let to = crate::math::Vec2::new(new_x, new_y);
+            let bullet_radius = world
+                .get_component::<Radius>(bullet_entity)
+                .map_or(BULLET_RADIUS, |r| r.value);
+            let mut stop = f32::INFINITY;
+            // Glass breaks and the round flies on — if it gets that far.
+            let mut glass = Vec::new();
+            for (i, wall) in world.walls().iter().enumerate() {
+                let Some(t) = collision::swept_circle_rect_toi(
+                    from,
+                    to,
                     bullet_radius,
                     wall.x,
                     wall.y,
                     wall.width,
                     wall.height,
-                ) {
-                    hit_wall = true;
-                    break;
+                ) else {
+                    continue;
+                };
+                if wall.is_glass() {
+                    glass.push((i, t));
+                } else {
+                    stop = stop.min(t);
                 }
             }
-
-            if hit_wall {
-                bullets_to_remove.push(bullet_entity);
-                continue;
+            for door in &doors {
+                if let Some(t) = door.crossing(from, to) {
+                    stop = stop.min(t);
+                }
             }
 
-            // Check enemy collision
-            let mut hit_enemy = false;
-
-            for &enemy_entity in &enemies {
-                let (enemy_pos, enemy_radius, enemy_health) = match (
-                    world.get_component::<Position>(enemy_entity),
-                    world.get_component::<Radius>(enemy_entity),
-                    world.get_component::<Health>(enemy_entity),
-                ) {
-                    (Some(p), Some(r), Some(h)) => (*p, *r, *h),
-                    _ => continue,
-                };
+            // A rogue's round only hits the player, the player's only rogues:
+            // the first one it reaches before a wall does.
+            let shooter = bullet.shooter;
+            let struck = SpatialIndex::of(world)
+                .first_along_segment(from, to, bullet_radius, |e| {
+                    let target = match shooter {
+                        Some(_) => world.has_component::<Player>(e),
+                        None => world.has_component::<Enemy>(e),
+                    };
+                    target
+                        && world
+                            .get_component::<Health>(e)
+                            .is_some_and(|h| h.is_alive())
+                })
+                .filter(|&(_, t)| t < stop);
+            if let Some((_, t)) = struck {
+                stop = t;
+            }
+            panes.extend(glass.into_iter().filter(|&(_, t)| t < stop).map(|(i, _)| i));
 
-                // Skip dead enemies
-                if enemy_health.is_dead() {
-                    continue;
+            match (struck, shooter) {
+                (Some((player, _)), Some(shooter)) => {
+                    Self::hit_player(world, player, bullet_vel, shooter);
                 }
-
-                // Swept old->new check: at closing speeds above the
-                // combined radii per frame (a bullet meeting a rushing bot
-                // head-on) an endpoint-only test tunnels straight through.
-                if collision::swept_circle_circle_collision(
-                    crate::math::Vec2::new(bullet_pos.x, bullet_pos.y),
-                    crate::math::Vec2::new(new_x, new_y),
-                    bullet_radius,
-                    crate::math::Vec2::new(enemy_pos.x, enemy_pos.y),
-                    enemy_radius.value,
-                ) {
+                (Some((enemy_entity, _)), None) => {
                     // Deal damage
-                    let mut killed = false;
-                    if let Some(health) = world.get_component_mut::<Health>(enemy_entity) {
-                        health.take_damage(bullet.damage);
-                        killed = health.is_dead();
-                    }
+                    let killed = world
+                        .update_component::<Health, _>(enemy_entity, |health| {
+                            health.take_damage(bullet.damage);
+                            health.is_dead()
+                        })
+                        .unwrap_or(false);
                     world.push_event(GameEvent::EnemyHit {
                         by: bullet.weapon_type,
                     });
+                    make_noise(world, to, impact_loudness(bullet.weapon_type));
                     // Shove the enemy along the bullet's travel direction — the
                     // live combat knockback (process_shoot is test-only; real
                     // bullet damage resolves here in BulletSystem).
@@ -148,13 +176,11 @@ impl System for BulletSystem {
                             bullet_vel.y,
                         );
                     }
-                    hit_enemy = true;
-                    break;
                 }
+                (None, _) => {}
             }
-
-            if hit_enemy {
-                bullets_to_remove.push(bullet_entity);
+            if stop.is_finite() {
+                commands.despawn(bullet_entity);
                 continue;
             }
 
@@ -169,17 +195,19 @@ impl System for BulletSystem {
             }
         }
 
-        // Remove bullets that hit something or expired
-        for bullet_entity in bullets_to_remove {
-            world.despawn(bullet_entity);
+        panes.sort_unstable();
+        panes.dedup();
+        for &pane in panes.iter().rev() {
+            shatter_glass(world, pane);
         }
+        world.defer(commands);
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::components::Position;
+    use crate::components::{Position, Radius};
 
     #[test]
     fn test_projectile_trail_system_decreases_lifetime() {
@@ -192,6 +220,7 @@ mod tests {
 
         let mut system = ProjectileTrailSystem;
         system.run(&mut world, 0.1);
+        world.apply_deferred();
 
         let trail = world.get_component::<ProjectileTrail>(entity).unwrap();
         assert!((trail.lifetime - 0.05).abs() < 0.001); // 0.15 - 0.1 = 0.05
@@ -209,9 +238,10 @@ mod tests {
         let mut system = ProjectileTrailSystem;
         // Run for longer than trail lifetime
         system.run(&mut world, 0.2);
+        world.apply_deferred();
 
-        // Trail should be removed
-        assert!(world.get_component::<ProjectileTrail>(entity).is_none());
+        // Trail (and its entity) should be gone
+        assert!(!world.is_alive(entity));
     }
 
     #[test]
@@ -225,7 +255,7 @@ mod tests {
         world.add_component(enemy, Health::new(100));
 
         let bullet = world.spawn();
-        world.add_component(bullet, Bullet::new(WeaponType::Shotgun, 30));
+        world.add_component(bullet, Bullet::new(WeaponType::SHOTGUN, 30));
         world.add_component(bullet, Position::new(0.0, 0.0));
         world.add_component(bullet, Velocity::new(800.0, 0.0));
         world.add_component(bullet, Radius::new(2.0));
@@ -233,6 +263,7 @@ mod tests {
         let mut system = BulletSystem;
         for _ in 0..10 {
             system.run(&mut world, 0.016);
+            world.apply_deferred();
             if world.query::<Bullet>().is_empty() {
                 break;
             }
@@ -242,18 +273,83 @@ mod tests {
         assert_eq!(
             world.drain_events(),
             vec![GameEvent::EnemyHit {
-                by: WeaponType::Shotgun
+                by: WeaponType::SHOTGUN
             }]
         );
     }
 
+    #[test]
+    fn test_enemy_round_passes_enemies_and_hits_player() {
+        use crate::components::{Player, WeaponType};
+        let mut world = World::new();
+        let shooter = world.spawn();
+        let ally = world.spawn();
+        world.add_component(ally, Enemy);
+        world.add_component(ally, Position::new(40.0, 0.0));
+        world.add_component(ally, Radius::new(12.0));
+        world.add_component(ally, Health::new(100));
+        let player = world.spawn();
+        world.add_component(player, Player);
+        world.add_component(player, Position::new(120.0, 0.0));
+        world.add_component(player, Radius::new(12.0));
+        world.add_component(player, Health::new(100));
+
+        let bullet = world.spawn();
+        world.add_component(bullet, Bullet::enemy(WeaponType::PISTOL, 50, shooter));
+        world.add_component(bullet, Position::new(0.0, 0.0));
+        world.add_component(bullet, Velocity::new(800.0, 0.0));
+        world.add_component(bullet, Radius::new(2.0));
+
+        let mut system = BulletSystem;
+        for _ in 0..20 {
+            system.run(&mut world, 0.016);
+            world.apply_deferred();
+        }
+        assert!(world.query::<Bullet>().is_empty(), "bullet consumed on hit");
+        assert_eq!(world.get_component::<Health>(ally).unwrap().current, 100);
+        assert!(world.get_component::<Health>(player).unwrap().is_dead());
+        assert_eq!(
+            world.drain_events(),
+            vec![GameEvent::PlayerHurt { by: shooter }]
+        );
+    }
+
+    #[test]
+    fn test_round_sweeps_with_its_own_radius() {
+        use crate::components::{Player, WeaponType};
+        // A round flying 17 px beside the player's centre: a 2 px round
+        // clears a 12 px player, an 8 px one does not.
+        let grazes = |radius: f32| {
+            let mut world = World::new();
+            let shooter = world.spawn();
+            let player = world.spawn();
+            world.add_component(player, Player);
+            world.add_component(player, Position::new(120.0, 17.0));
+            world.add_component(player, Radius::new(12.0));
+            world.add_component(player, Health::new(100));
+            let bullet = world.spawn();
+            world.add_component(bullet, Bullet::enemy(WeaponType::PISTOL, 50, shooter));
+            world.add_component(bullet, Position::new(0.0, 0.0));
+            world.add_component(bullet, Velocity::new(800.0, 0.0));
+            world.add_component(bullet, Radius::new(radius));
+            let mut system = BulletSystem;
+            for _ in 0..20 {
+                system.run(&mut world, 0.016);
+                world.apply_deferred();
+            }
+            world.get_component::<Health>(player).unwrap().is_dead()
+        };
+        assert!(!grazes(2.0));
+        assert!(grazes(8.0));
+    }
+
     #[test]
     fn test_bullet_into_wall_is_silent() {
         use crate::components::WeaponType;
         let mut world = World::new();
         world.add_wall(40.0, -50.0, 20.0, 100.0);
         let bullet = world.spawn();
-        world.add_component(bullet, Bullet::new(WeaponType::Pistol, 30));
+        world.add_component(bullet, Bullet::new(WeaponType::PISTOL, 30));
         world.add_component(bullet, Position::new(0.0, 0.0));
         world.add_component(bullet, Velocity::new(800.0, 0.0));
         world.add_component(bullet, Radius::new(2.0));
@@ -261,11 +357,49 @@ mod tests {
         let mut system = BulletSystem;
         for _ in 0..10 {
             system.run(&mut world, 0.016);
+            world.apply_deferred();
         }
         assert!(world.query::<Bullet>().is_empty());
         assert!(world.drain_events().is_empty());
     }
 
+    #[test]
+    fn test_bullet_shatters_glass_and_flies_on() {
+        use crate::components::WeaponType;
+        use crate::ecs::{Wall, WallMaterial};
+        let mut world = World::new();
+        let pane = Wall::new(40.0, -50.0, 10.0, 100.0).with_material(WallMaterial::Glass);
+        world.insert_wall(pane);
+        let enemy = world.spawn();
+        world.add_component(enemy, Enemy);
+        world.add_component(enemy, Position::new(120.0, 0.0));
+        world.add_component(enemy, Radius::new(12.0));
+        world.add_component(enemy, Health::new(100));
+
+        let bullet = world.spawn();
+        world.add_component(bullet, Bullet::new(WeaponType::PISTOL, 30));
+        world.add_component(bullet, Position::new(0.0, 0.0));
+        world.add_component(bullet, Velocity::new(800.0, 0.0));
+        world.add_component(bullet, Radius::new(2.0));
+
+        let mut system = BulletSystem;
+        for _ in 0..20 {
+            system.run(&mut world, 0.016);
+            world.apply_deferred();
+        }
+        assert!(world.walls().is_empty(), "the pane is gone");
+        assert_eq!(world.get_component::<Health>(enemy).unwrap().current, 70);
+        assert_eq!(
+            world.drain_events(),
+            vec![
+                GameEvent::GlassShatter(pane),
+                GameEvent::EnemyHit {
+                    by: WeaponType::PISTOL
+                }
+            ]
+        );
+    }
+
     /// TASK-3 compass rig: a shooter placed at `offset` from the victim
     /// fires a pistol round straight AT the victim (one hit kills). Asserts
     /// the knockback impulse points attacker -> victim (per-axis signs and a
@@ -295,6 +429,7 @@ mod tests {
         let mut system = BulletSystem;
         for _ in 0..60 {
             system.run(&mut world, 0.016);
+            world.apply_deferred();
             if world.query::<Bullet>().is_empty() {
                 break;
             }
@@ -376,6 +511,54 @@ mod tests {
         corpse_falls_away_from(crate::math::Vec2::new(100.0, 100.0));
     }
 
+    #[test]
+    fn test_round_only_breaks_glass_before_what_stops_it() {
+        use crate::components::WeaponType;
+        use crate::ecs::{Wall, WallMaterial};
+        // One long frame: the round's sweep reaches every obstacle at once.
+        let fire = |world: &mut World| {
+            let bullet = world.spawn();
+            world.add_component(bullet, Bullet::new(WeaponType::PISTOL, 30));
+            world.add_component(bullet, Position::new(0.0, 0.0));
+            world.add_component(bullet, Velocity::new(800.0, 0.0));
+            world.add_component(bullet, Radius::new(2.0));
+            BulletSystem.run(world, 0.5);
+            world.apply_deferred();
+        };
+        let pane = |x: f32| Wall::new(x, -50.0, 10.0, 100.0).with_material(WallMaterial::Glass);
+
+        // Glass behind concrete stays whole (whatever the wall order);
+        // glass in front of it breaks.
+        let mut world = World::new();
+        world.insert_wall(pane(250.0));
+        world.insert_wall(pane(50.0));
+        world.insert_wall(Wall::new(150.0, -50.0, 10.0, 100.0));
+        fire(&mut world);
+        assert_eq!(
+            world.drain_events(),
+            vec![GameEvent::GlassShatter(pane(50.0))]
+        );
+        assert_eq!(world.walls().len(), 2);
+
+        // Glass behind the rogue the round struck stays whole too.
+        let mut world = World::new();
+        world.insert_wall(pane(250.0));
+        let enemy = world.spawn();
+        world.add_component(enemy, Enemy);
+        world.add_component(enemy, Position::new(120.0, 0.0));
+        world.add_component(enemy, Radius::new(12.0));
+        world.add_component(enemy, Health::new(100));
+        fire(&mut world);
+        assert_eq!(
+            world.drain_events(),
+            vec![GameEvent::EnemyHit {
+                by: WeaponType::PISTOL
+            }]
+        );
+        assert_eq!(world.walls().len(), 1);
+        assert!(world.query::<Bullet>().is_empty());
+    }
+
     #[test]
     fn test_projectile_trail_system_multiple_trails() {
         let mut world = World::new();
@@ -390,6 +573,7 @@ mod tests {
 
         let mut system = ProjectileTrailSystem;
         system.run(&mut world, 0.1);
+        world.apply_deferred();
 
         // All trails should still exist
         let trails = world.query::<ProjectileTrail>();
@@ -397,6 +581,7 @@ mod tests {
 
         // Run until all trails are dead
         system.run(&mut world, 0.2);
+        world.apply_deferred();
 
         // All trails should be removed
         let trails = world.query::<ProjectileTrail>();
@@ -11,18 +11,18 @@ impl System for StunSystem {
     fn run(&mut self, world: &mut World, dt: f32) {
         let mut expired = Vec::new();
 
-        for entity in world.query::<Stunned>() {
+        for (entity, stun, velocity) in
+            world.query_mut::<(Entity, &mut Stunned, Option<&mut Velocity>)>()
+        {
             // Keep stunned entities pinned in place.
-            if let Some(velocity) = world.get_component_mut::<Velocity>(entity) {
+            if let Some(velocity) = velocity {
                 velocity.x = 0.0;
                 velocity.y = 0.0;
             }
 
-            if let Some(stun) = world.get_component_mut::<Stunned>(entity) {
-                stun.timer -= dt;
-                if !stun.is_active() {
-                    expired.push((entity, stun.fall_angle));
-                }
+            stun.timer -= dt;
+            if !stun.is_active() {
+                expired.push((entity, stun.fall_angle));
             }
         }
 
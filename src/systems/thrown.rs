@@ -2,15 +2,14 @@ use crate::collision;
 use crate::components::{
     Enemy, GameEvent, Health, Player, Position, Radius, Stunned, ThrownWeapon, Weapon, WeaponPickup,
 };
-use crate::ecs::{Entity, System, World};
+use crate::ecs::{Entity, System, Wall, World};
 use crate::math::Vec2;
+use crate::spatial::SpatialIndex;
+use crate::systems::noise::shatter_glass;
 
-/// How fast a thrown weapon travels (pixels/second).
+/// How fast a thrown weapon travels (pixels/second). Its damage and range
+/// are the weapon's own (the table's `throw`).
 pub const THROW_SPEED: f32 = 700.0;
-/// How far a thrown weapon flies before dropping to the floor (pixels).
-pub const THROW_RANGE: f32 = 550.0;
-/// Chip damage a thrown weapon deals on impact (the knockdown is the point).
-pub const THROW_DAMAGE: i32 = 15;
 /// How long an enemy stays knocked down after being hit by a thrown weapon.
 pub const STUN_DURATION: f32 = 3.0;
 /// Collision radius of a weapon in flight.
@@ -47,16 +46,17 @@ impl ThrownWeaponSystem {
 
         world.remove_component::<Weapon>(player);
 
+        let throw = weapon_type.def().throw;
         let thrown = world.spawn();
         world.add_component(
             thrown,
             ThrownWeapon::new(
                 weapon_type,
                 ammo,
-                THROW_DAMAGE,
+                throw.damage,
                 aim_dir,
                 THROW_SPEED,
-                THROW_RANGE,
+                throw.range,
             ),
         );
         world.add_component(thrown, pos);
@@ -75,30 +75,25 @@ impl ThrownWeaponSystem {
         world.add_component(pickup, Radius::new(14.0));
     }
 
-    /// First alive, not-already-stunned enemy whose body the flight path enters.
+    /// First alive, not-already-stunned enemy whose body the flight path
+    /// enters (through the spatial index, synced at the top of the run).
     fn enemy_hit_at(world: &World, point: Vec2) -> Option<Entity> {
-        for enemy in world.query::<Enemy>() {
-            let (pos, radius, health) = match (
-                world.get_component::<Position>(enemy),
-                world.get_component::<Radius>(enemy),
-                world.get_component::<Health>(enemy),
-            ) {
-                (Some(p), Some(r), Some(h)) => (*p, r.value, *h),
-                _ => continue,
-            };
-            if health.is_dead() || world.has_component::<Stunned>(enemy) {
-                continue;
-            }
-            if collision::circle_circle_collision(point, THROWN_RADIUS, pos.to_vec2(), radius) {
-                return Some(enemy);
-            }
-        }
-        None
+        SpatialIndex::of(world)
+            .within(point, THROWN_RADIUS)
+            .into_iter()
+            .find(|&enemy| {
+                world.has_component::<Enemy>(enemy)
+                    && !world.has_component::<Stunned>(enemy)
+                    && world
+                        .get_component::<Health>(enemy)
+                        .is_some_and(|h| h.is_alive())
+            })
     }
 }
 
 impl System for ThrownWeaponSystem {
     fn run(&mut self, world: &mut World, dt: f32) {
+        SpatialIndex::sync(world);
         for thrown in world.query::<ThrownWeapon>() {
             let tw = match world.get_component::<ThrownWeapon>(thrown) {
                 Some(t) => *t,
@@ -113,8 +108,7 @@ impl System for ThrownWeaponSystem {
             let new_point = Vec2::new(pos.x + tw.vx * dt, pos.y + tw.vy * dt);
             let new_pos = Position::new(new_point.x, new_point.y);
 
-            // Hit a wall? Drop where it struck.
-            let hit_wall = world.walls().iter().any(|wall| {
+            let hits = |wall: &Wall| {
                 collision::circle_rect_collision(
                     new_point,
                     THROWN_RADIUS,
@@ -123,7 +117,17 @@ impl System for ThrownWeaponSystem {
                     wall.width,
                     wall.height,
                 )
-            });
+            };
+            // Through glass: it shatters and the weapon flies on.
+            while let Some(pane) = world
+                .walls()
+                .iter()
+                .position(|wall| wall.is_glass() && hits(wall))
+            {
+                shatter_glass(world, pane);
+            }
+            // Hit a wall? Drop where it struck.
+            let hit_wall = world.walls().iter().any(hits);
             if hit_wall {
                 Self::land(world, thrown, pos, &tw);
                 continue;
@@ -131,9 +135,7 @@ impl System for ThrownWeaponSystem {
 
             // Hit an enemy? Knock them down, deal chip damage, drop the weapon.
             if let Some(enemy) = Self::enemy_hit_at(world, new_point) {
-                if let Some(health) = world.get_component_mut::<Health>(enemy) {
-                    health.take_damage(tw.damage);
-                }
+                world.update_component::<Health, _>(enemy, |h| h.take_damage(tw.damage));
                 // Knocked down sprawling along the weapon's flight direction.
                 let fall = tw.vy.atan2(tw.vx);
                 world.add_component(enemy, Stunned::with_fall(STUN_DURATION, fall));
@@ -178,7 +180,7 @@ mod tests {
     #[test]
     fn test_throw_disarms_player_and_spawns_projectile() {
         let mut world = World::new();
-        let player = player_with_weapon(&mut world, Vec2::new(100.0, 100.0), WeaponType::Shotgun);
+        let player = player_with_weapon(&mut world, Vec2::new(100.0, 100.0), WeaponType::SHOTGUN);
         world.get_component_mut::<Weapon>(player).unwrap().ammo = 3;
 
         let thrown = ThrownWeaponSystem::throw_from_player(&mut world, Vec2::new(1.0, 0.0));
@@ -188,7 +190,7 @@ mod tests {
         let projectiles = world.query::<ThrownWeapon>();
         assert_eq!(projectiles.len(), 1);
         let tw = world.get_component::<ThrownWeapon>(projectiles[0]).unwrap();
-        assert_eq!(tw.weapon_type, WeaponType::Shotgun);
+        assert_eq!(tw.weapon_type, WeaponType::SHOTGUN);
         assert_eq!(tw.ammo, 3); // the rounds fly with it
         assert_eq!(world.drain_events(), vec![GameEvent::Throw]);
     }
@@ -196,7 +198,7 @@ mod tests {
     #[test]
     fn test_thrown_weapon_lands_with_its_ammo() {
         let mut world = World::new();
-        let player = player_with_weapon(&mut world, Vec2::new(0.0, 0.0), WeaponType::Pistol);
+        let player = player_with_weapon(&mut world, Vec2::new(0.0, 0.0), WeaponType::PISTOL);
         world.get_component_mut::<Weapon>(player).unwrap().ammo = 5;
         ThrownWeaponSystem::throw_from_player(&mut world, Vec2::new(1.0, 0.0));
         world.drain_events();
@@ -211,7 +213,7 @@ mod tests {
         let pickups = world.query::<WeaponPickup>();
         assert_eq!(pickups.len(), 1);
         let p = world.get_component::<WeaponPickup>(pickups[0]).unwrap();
-        assert_eq!((p.weapon_type, p.ammo), (WeaponType::Pistol, 5));
+        assert_eq!((p.weapon_type, p.ammo), (WeaponType::PISTOL, 5));
         // Flying and landing (no enemy hit) emit nothing.
         assert!(world.drain_events().is_empty());
     }
@@ -231,10 +233,26 @@ mod tests {
         assert_eq!(world.query::<ThrownWeapon>().len(), 0);
     }
 
+    #[test]
+    fn test_a_weapon_is_thrown_with_its_own_damage_and_range() {
+        let mut world = World::new();
+        player_with_weapon(&mut world, Vec2::new(0.0, 0.0), WeaponType::KATANA);
+        ThrownWeaponSystem::throw_from_player(&mut world, Vec2::new(1.0, 0.0));
+
+        let thrown = world.query::<ThrownWeapon>()[0];
+        let tw = world.get_component::<ThrownWeapon>(thrown).unwrap();
+        let throw = WeaponType::KATANA.def().throw;
+        assert_eq!(
+            (tw.damage, tw.distance_remaining),
+            (throw.damage, throw.range)
+        );
+        assert!(throw.damage > WeaponType::PISTOL.def().throw.damage);
+    }
+
     #[test]
     fn test_thrown_weapon_stuns_enemy_and_drops() {
         let mut world = World::new();
-        player_with_weapon(&mut world, Vec2::new(0.0, 100.0), WeaponType::Pistol);
+        player_with_weapon(&mut world, Vec2::new(0.0, 100.0), WeaponType::PISTOL);
 
         // Enemy just to the right, in the throw's path.
         let enemy = world.spawn();
@@ -264,7 +282,7 @@ mod tests {
         // Enemy took chip damage.
         assert_eq!(
             world.get_component::<Health>(enemy).unwrap().current,
-            50 - THROW_DAMAGE
+            50 - WeaponType::PISTOL.def().throw.damage
         );
         // The throw and the impact were announced.
         assert_eq!(
@@ -273,10 +291,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_thrown_weapon_shatters_glass_and_flies_on() {
+        use crate::ecs::WallMaterial;
+        let mut world = World::new();
+        player_with_weapon(&mut world, Vec2::new(0.0, 100.0), WeaponType::PISTOL);
+        let pane = Wall::new(60.0, 50.0, 10.0, 100.0).with_material(WallMaterial::Glass);
+        world.insert_wall(pane);
+        let enemy = world.spawn();
+        world.add_component(enemy, Enemy);
+        world.add_component(enemy, Position::new(120.0, 100.0));
+        world.add_component(enemy, Radius::new(12.0));
+        world.add_component(enemy, Health::new(50));
+
+        ThrownWeaponSystem::throw_from_player(&mut world, Vec2::new(1.0, 0.0));
+        let mut system = ThrownWeaponSystem;
+        for _ in 0..20 {
+            system.run(&mut world, 0.016);
+            if world.has_component::<Stunned>(enemy) {
+                break;
+            }
+        }
+
+        assert!(world.walls().is_empty(), "the pane is gone");
+        assert!(
+            world.has_component::<Stunned>(enemy),
+            "and the rogue behind it down"
+        );
+        assert_eq!(
+            world.drain_events(),
+            vec![
+                GameEvent::Throw,
+                GameEvent::GlassShatter(pane),
+                GameEvent::ThrownImpact
+            ]
+        );
+    }
+
     #[test]
     fn test_thrown_weapon_lands_after_max_range() {
         let mut world = World::new();
-        player_with_weapon(&mut world, Vec2::new(0.0, 0.0), WeaponType::MachineGun);
+        player_with_weapon(&mut world, Vec2::new(0.0, 0.0), WeaponType::MACHINEGUN);
         ThrownWeaponSystem::throw_from_player(&mut world, Vec2::new(1.0, 0.0));
 
         let mut system = ThrownWeaponSystem;
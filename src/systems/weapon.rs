@@ -6,20 +6,14 @@ pub struct WeaponUpdateSystem;
 
 impl System for WeaponUpdateSystem {
     fn run(&mut self, world: &mut World, dt: f32) {
-        let entities: Vec<_> = world.query::<Weapon>();
-
-        for entity in entities {
-            if let Some(weapon) = world.get_component_mut::<Weapon>(entity) {
-                weapon.update(dt);
-            }
+        for weapon in world.query_mut::<&mut Weapon>() {
+            weapon.update(dt);
         }
 
         // The unarmed punch cooldown lives on its own small component.
-        for entity in world.query::<Fists>() {
-            if let Some(fists) = world.get_component_mut::<Fists>(entity) {
-                if fists.timer > 0.0 {
-                    fists.timer -= dt;
-                }
+        for fists in world.query_mut::<&mut Fists>() {
+            if fists.timer > 0.0 {
+                fists.timer -= dt;
             }
         }
     }
@@ -35,7 +29,7 @@ mod tests {
         let mut world = World::new();
         let entity = world.spawn();
 
-        let mut weapon = Weapon::new(WeaponType::Pistol);
+        let mut weapon = Weapon::new(WeaponType::PISTOL);
         weapon.fire(); // Start cooldown
         world.add_component(entity, weapon);
 
@@ -51,12 +45,12 @@ mod tests {
         let mut world = World::new();
 
         let e1 = world.spawn();
-        let mut w1 = Weapon::new(WeaponType::Pistol);
+        let mut w1 = Weapon::new(WeaponType::PISTOL);
         w1.fire();
         world.add_component(e1, w1);
 
         let e2 = world.spawn();
-        let mut w2 = Weapon::new(WeaponType::Shotgun);
+        let mut w2 = Weapon::new(WeaponType::SHOTGUN);
         w2.fire();
         world.add_component(e2, w2);
 
@@ -0,0 +1,352 @@
+//! Event traces: a floor attempt written down as what happened on which
+//! frame — every [`GameEvent`], scenario step firing, tutorial gate
+//! opening / release, comms line and exit opening — one line each.
+//!
+//! `tests/golden_traces.rs` plays every floor under a fixed bot seed and
+//! compares the trace against a checked-in golden file
+//! (`tests/golden/floor_NN.trace`), so a gameplay regression — a gate that
+//! never releases, a wave spawning late, a door that stays shut — fails
+//! `cargo test` with a readable [`diff`]. A recording from a bug report
+//! traces the same way ([`trace_replay`]).
+//!
+//! Line format: `<frame> <kind> <detail>`, the frame being the 1-based
+//! frame the thing happened on, `kind` one of `event`, `step`, `gate`,
+//! `comms`, `exit`, `end` (how the attempt ended: `extracted <floor id>`,
+//! `extracted surface`, `died` or `timeout`) and `attempt` (a retry after a
+//! death starts).
+
+use crate::components::GameEvent;
+use crate::levels::{floor_def, level_index_for_floor_id};
+use crate::replay::Replay;
+use crate::report::RunOptions;
+use crate::save::SaveError;
+use crate::scenario::SURFACE_EXIT;
+use crate::sim::FloorRun;
+use std::fmt::Write as _;
+
+/// Watches a [`FloorRun`] frame by frame and writes its trace.
+pub struct TraceRecorder {
+    /// Per scenario step: fired already.
+    fired: Vec<bool>,
+    exits: usize,
+    gate: bool,
+    lines: Vec<String>,
+}
+
+impl TraceRecorder {
+    /// Start watching `run` as it stands (normally just started).
+    pub fn new(run: &FloorRun) -> Self {
+        let mut recorder = TraceRecorder {
+            fired: vec![false; run.scenario.floor().scenario.len()],
+            exits: 0,
+            gate: false,
+            lines: Vec::new(),
+        };
+        recorder.observe(run);
+        recorder
+    }
+
+    fn line(&mut self, frame: usize, kind: &str, detail: impl std::fmt::Display) {
+        self.lines.push(format!("{frame:>6} {kind} {detail}"));
+    }
+
+    /// Note what the frame `run` just ran changed.
+    pub fn observe(&mut self, run: &FloorRun) {
+        let frame = run.frames;
+        let sc = &run.scenario;
+        if frame > 0 {
+            for event in run.sim.frame_events() {
+                self.line(frame, "event", format_event(event));
+            }
+        }
+        for (i, step) in sc.floor().scenario.iter().enumerate() {
+            if !self.fired[i] && sc.step_fired(step.id) {
+                self.fired[i] = true;
+                self.line(frame, "step", step.id);
+            }
+        }
+        match sc.gate_view() {
+            Some(gate) if !self.gate => {
+                self.gate = true;
+                self.line(frame, "gate", format!("{:?}", gate.input));
+            }
+            None if self.gate => {
+                self.gate = false;
+                self.line(frame, "gate", "released");
+            }
+            _ => {}
+        }
+        // A line starts playing at age zero; the feed ages it next frame.
+        for comms in sc.comms.visible().iter().filter(|l| l.age == 0.0) {
+            let text = format!("{}: {}", comms.who, comms.text);
+            self.line(frame, "comms", text);
+        }
+        let opened = sc.opened_exits();
+        for exit in &opened[self.exits.min(opened.len())..] {
+            let exit = *exit;
+            self.line(frame, "exit", exit);
+        }
+        self.exits = opened.len();
+    }
+
+    /// Close the trace with how the attempt ended.
+    pub fn finish(mut self, run: &FloorRun) -> Trace {
+        let end = match run.extracted {
+            Some(SURFACE_EXIT) => "extracted surface".to_string(),
+            Some(to) => format!("extracted {to}"),
+            None if !run.sim.player_alive() => "died".to_string(),
+            None => "timeout".to_string(),
+        };
+        self.line(run.frames, "end", end);
+        Trace {
+            floor_id: run.scenario.floor().id,
+            lines: self.lines,
+        }
+    }
+}
+
+/// Events print as their `Debug` form, except the hurting enemy's handle,
+/// which says nothing a reader can check against the floor.
+fn format_event(event: &GameEvent) -> String {
+    match event {
+        GameEvent::PlayerHurt { .. } => event.name().to_string(),
+        _ => format!("{event:?}"),
+    }
+}
+
+/// A floor attempt's trace.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trace {
+    pub floor_id: usize,
+    pub lines: Vec<String>,
+}
+
+impl Trace {
+    pub fn to_text(&self) -> String {
+        let mut out = format!("# floor {}\n", self.floor_id);
+        for line in &self.lines {
+            out.push_str(line);
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// Trace the bot ([`FloorRun::bot_frame`]) playing `level` from `seed`, the
+/// way [`crate::report::run_bot`] plays a floor: an attempt runs until the
+/// bot extracts, dies, or `max_frames` run out, and a death retries (up to
+/// `attempts`) with the RNG carried on. Every attempt's lines go in the one
+/// trace, each retry opening with an `attempt <n>` line at frame 0.
+pub fn trace_bot(level: usize, seed: u32, opts: &RunOptions) -> Trace {
+    let mut lines = Vec::new();
+    let mut seed = seed;
+    for attempt in 1..=opts.attempts.max(1) {
+        let mut run = FloorRun::start(level, seed);
+        let mut recorder = TraceRecorder::new(&run);
+        if attempt > 1 {
+            recorder
+                .lines
+                .insert(0, format!("{:>6} attempt {attempt}", 0));
+        }
+        for _ in 0..opts.max_frames {
+            run.bot_frame(opts.dt);
+            recorder.observe(&run);
+            if run.extracted.is_some() || !run.sim.player_alive() {
+                break;
+            }
+        }
+        seed = run.sim.world.rng_state();
+        let died = run.extracted.is_none() && !run.sim.player_alive();
+        let trace = recorder.finish(&run);
+        lines.extend(trace.lines);
+        if !died {
+            break;
+        }
+    }
+    Trace {
+        floor_id: floor_def(level).id,
+        lines,
+    }
+}
+
+/// Trace a recorded attempt (see [`crate::replay`]), to its last frame.
+pub fn trace_replay(replay: &Replay) -> Result<Trace, SaveError> {
+    let level = level_index_for_floor_id(replay.floor_id)
+        .ok_or(SaveError::UnknownFloor(replay.floor_id))?;
+    let mut run = FloorRun::start(level, replay.seed);
+    let mut recorder = TraceRecorder::new(&run);
+    for frame in &replay.frames {
+        run.frame(&frame.intents, frame.dt);
+        recorder.observe(&run);
+    }
+    Ok(recorder.finish(&run))
+}
+
+/// Golden file name for `level`'s trace.
+pub fn golden_name(level: usize) -> String {
+    format!("floor_{:02}.trace", floor_def(level).id)
+}
+
+/// Lines of context kept around each change in a [`diff`].
+const DIFF_CONTEXT: usize = 2;
+/// Past this many differing lines between the common head and tail, the diff
+/// skips the line matching and lists both sides.
+const DIFF_MATCH_LIMIT: usize = 4000;
+/// A diff longer than this is cut.
+const DIFF_MAX_LINES: usize = 80;
+
+/// A unified-style line diff of `expected` against `actual` (`-` expected
+/// only, `+` actual only, `@@` the expected / actual line numbers of each
+/// hunk); `None` when they are equal.
+pub fn diff(expected: &str, actual: &str) -> Option<String> {
+    if expected == actual {
+        return None;
+    }
+    let a: Vec<&str> = expected.lines().collect();
+    let b: Vec<&str> = actual.lines().collect();
+    let head = a.iter().zip(&b).take_while(|(x, y)| x == y).count();
+    let tail = a[head..]
+        .iter()
+        .rev()
+        .zip(b[head..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    let (mid_a, mid_b) = (&a[head..a.len() - tail], &b[head..b.len() - tail]);
+
+    // (tag, line in expected, line in actual): ' ' both, '-' / '+' one side.
+    let mut ops: Vec<(char, usize, usize)> = (0..head).map(|i| (' ', i, i)).collect();
+    if mid_a.len() + mid_b.len() <= DIFF_MATCH_LIMIT {
+        ops.extend(
+            match_lines(mid_a, mid_b)
+                .into_iter()
+                .map(|(t, i, j)| (t, head + i, head + j)),
+        );
+    } else {
+        ops.extend((0..mid_a.len()).map(|i| ('-', head + i, head)));
+        ops.extend((0..mid_b.len()).map(|j| ('+', head + mid_a.len(), head + j)));
+    }
+    let (off_a, off_b) = (a.len() - tail, b.len() - tail);
+    ops.extend((0..tail).map(|k| (' ', off_a + k, off_b + k)));
+
+    // Keep the changes and their context, a hunk header at every gap.
+    let keep: Vec<bool> = (0..ops.len())
+        .map(|i| {
+            let lo = i.saturating_sub(DIFF_CONTEXT);
+            let hi = (i + DIFF_CONTEXT + 1).min(ops.len());
+            ops[lo..hi].iter().any(|op| op.0 != ' ')
+        })
+        .collect();
+    let mut out = String::new();
+    let mut shown = 0;
+    let mut in_hunk = false;
+    for (i, &(tag, ia, jb)) in ops.iter().enumerate() {
+        if !keep[i] {
+            in_hunk = false;
+            continue;
+        }
+        if shown == DIFF_MAX_LINES {
+            out.push_str("...\n");
+            break;
+        }
+        if !in_hunk {
+            let _ = writeln!(out, "@@ -{} +{} @@", ia + 1, jb + 1);
+            in_hunk = true;
+        }
+        let line = if tag == '+' { b[jb] } else { a[ia] };
+        let _ = writeln!(out, "{tag}{line}");
+        shown += 1;
+    }
+    Some(out)
+}
+
+/// Longest-common-subsequence alignment of two line lists.
+fn match_lines(a: &[&str], b: &[&str]) -> Vec<(char, usize, usize)> {
+    let (n, m) = (a.len(), b.len());
+    // lcs[i][j]: common lines of a[i..] and b[j..].
+    let mut lcs = vec![0u32; (n + 1) * (m + 1)];
+    let at = |i: usize, j: usize| i * (m + 1) + j;
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[at(i, j)] = if a[i] == b[j] {
+                lcs[at(i + 1, j + 1)] + 1
+            } else {
+                lcs[at(i + 1, j)].max(lcs[at(i, j + 1)])
+            };
+        }
+    }
+    let mut ops = Vec::with_capacity(n + m);
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && a[i] == b[j] {
+            ops.push((' ', i, j));
+            i += 1;
+            j += 1;
+        } else if j == m || (i < n && lcs[at(i + 1, j)] >= lcs[at(i, j + 1)]) {
+            ops.push(('-', i, j));
+            i += 1;
+        } else {
+            ops.push(('+', i, j));
+            j += 1;
+        }
+    }
+    ops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DT: f32 = 1.0 / 60.0;
+
+    #[test]
+    fn test_trace_names_steps_comms_and_the_end() {
+        let opts = RunOptions {
+            dt: DT,
+            max_frames: 60 * 180,
+            attempts: 1,
+        };
+        let trace = trace_bot(0, 12345, &opts);
+        let text = trace.to_text();
+        assert!(text.starts_with("# floor 0\n"), "{text}");
+        let first_step = floor_def(0).scenario[0].id;
+        assert!(text.contains(&format!(" step {first_step}\n")), "{text}");
+        assert!(text.contains(" comms "), "{text}");
+        let end = trace.lines.last().unwrap();
+        assert!(end.contains(" end "), "{end}");
+        // Frame numbers never go backwards.
+        let frames: Vec<usize> = trace
+            .lines
+            .iter()
+            .map(|l| l.split_whitespace().next().unwrap().parse().unwrap())
+            .collect();
+        assert!(frames.windows(2).all(|w| w[0] <= w[1]));
+    }
+
+    #[test]
+    fn test_replay_traces_like_the_run_it_recorded() {
+        let level = 3;
+        let mut run = FloorRun::start(level, 777);
+        let mut replay = Replay::new(floor_def(level).id, 777);
+        let mut recorder = TraceRecorder::new(&run);
+        for _ in 0..600 {
+            let intents = run.bot_frame(DT);
+            replay.record(DT, intents);
+            recorder.observe(&run);
+        }
+        let live = recorder.finish(&run);
+        assert!(live.lines.iter().any(|l| l.contains(" event ")));
+        assert_eq!(trace_replay(&replay).unwrap(), live);
+    }
+
+    #[test]
+    fn test_diff_shows_the_changed_lines_in_context() {
+        assert_eq!(diff("a\nb\n", "a\nb\n"), None);
+        let expected = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n";
+        let actual = "1\n2\n3\nfour\n5\n6\n7\n8\n9\n10\n11\n";
+        let d = diff(expected, actual).unwrap();
+        assert_eq!(
+            d,
+            "@@ -2 +2 @@\n 2\n 3\n-4\n+four\n 5\n 6\n@@ -9 +9 @@\n 9\n 10\n+11\n"
+        );
+    }
+}
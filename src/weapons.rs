@@ -0,0 +1,224 @@
+//! Data-driven weapon definitions.
+//!
+//! Every weapon lives as an entry of `weapons/weapons.json`;
+//! `tools/gen_weapons.py` compiles the file into `src/weapons_data.rs` (the
+//! [`WEAPONS`] table plus a `WeaponType::<ID>` constant per entry), and a
+//! [`WeaponType`] is an index into that table. Code reads a weapon's stats
+//! from its [`WeaponDef`] rather than matching on which weapon it is, so a
+//! new gun is a new JSON entry. See docs/WEAPONS_FORMAT.md for the contract.
+
+use crate::components::WeaponType;
+pub use crate::weapons_data::WEAPONS;
+
+/// How a rogue handles the gun it carries (see
+/// [`crate::systems::combat::enemy_gun`]).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EnemyGun {
+    /// Seconds the rogue must hold the player in its sights before the
+    /// first shot: the telegraph (again after every reload).
+    pub windup: f32,
+    /// Full width (radians) of the cone each round leaves in.
+    pub spread: f32,
+    /// Rounds per trigger pull.
+    pub pellets: u32,
+    /// Seconds between trigger pulls (slower than the player's own).
+    pub cadence: f32,
+    /// Seconds to reload once the magazine runs dry.
+    pub reload: f32,
+    /// Farthest it shoots from, px.
+    pub range: f32,
+}
+
+/// What a weapon does when thrown (see
+/// [`crate::systems::thrown::ThrownWeaponSystem`]).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThrowDef {
+    /// Damage dealt on impact (the knockdown is the point).
+    pub damage: i32,
+    /// How far (px) it flies before dropping to the floor.
+    pub range: f32,
+}
+
+/// The 3D model a weapon is drawn with, in the hand and on the floor. The
+/// discriminant is the model index of robot-core.js (`GROUND_WEAPON_MODELS`,
+/// and the held-weapon models, where 0 is the fist).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WeaponModel {
+    Bar = 0,
+    Pistol = 1,
+    MachineGun = 2,
+    Shotgun = 3,
+}
+
+impl WeaponModel {
+    pub const COUNT: usize = 4;
+
+    /// The robot-core.js model index.
+    pub const fn index(self) -> u32 {
+        self as u32
+    }
+}
+
+/// Which firing / impact sound set a weapon plays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WeaponSound {
+    Gun = 0,
+    MachineGun = 1,
+    Shotgun = 2,
+    Club = 3,
+}
+
+impl WeaponSound {
+    pub const COUNT: usize = 4;
+}
+
+/// The finisher a weapon performs on a downed rogue (see
+/// [`crate::components::FinisherKind`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FinisherStyle {
+    /// One overhead blow.
+    Overhead,
+    /// One point-blank shot, while it has a round.
+    Execute,
+}
+
+/// The bare fists, for events: not in the table (nothing to pick up, drop
+/// or throw), but a punch or a pounding finisher is credited to them rather
+/// than to a weapon the player does not hold (see [`WeaponType::FISTS`]).
+static FISTS: WeaponDef = WeaponDef {
+    id: "fists",
+    name: "Fists",
+    letter: "F",
+    color: [0.9, 0.75, 0.6],
+    model: WeaponModel::Bar,
+    sound: WeaponSound::Club,
+    damage: crate::systems::combat::PUNCH_DAMAGE,
+    fire_rate: crate::game::PUNCH_COOLDOWN,
+    magazine: 0,
+    pellets: 1,
+    spread: 0.0,
+    reach: Some(crate::systems::combat::PUNCH_RANGE),
+    loudness: 0.0,
+    finisher: FinisherStyle::Overhead,
+    throw: ThrowDef {
+        damage: 0,
+        range: 0.0,
+    },
+    enemy: None,
+};
+
+/// One entry of the weapons table.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WeaponDef {
+    /// The id levels and saves name it by (`"pistol"`).
+    pub id: &'static str,
+    /// Display name (HUD, editor).
+    pub name: &'static str,
+    /// One-letter tag on the native editor's map.
+    pub letter: &'static str,
+    /// Colour on the ground and in the UI (RGB, 0..1).
+    pub color: [f32; 3],
+    pub model: WeaponModel,
+    pub sound: WeaponSound,
+    /// Damage of each round, or of a swing.
+    pub damage: i32,
+    /// Seconds between shots / swings.
+    pub fire_rate: f32,
+    /// Rounds in a full magazine. A melee weapon has none; its value here is
+    /// only a nominal cap.
+    pub magazine: i32,
+    /// Rounds that leave per shot.
+    pub pellets: u32,
+    /// Full width (radians) of the cone the rounds leave in.
+    pub spread: f32,
+    /// Reach (px, centre to centre) of a melee weapon; `None` for a gun.
+    pub reach: Option<f32>,
+    /// How far (px, on foot) a shot is heard (see [`crate::systems::noise`]).
+    pub loudness: f32,
+    pub finisher: FinisherStyle,
+    pub throw: ThrowDef,
+    /// How a rogue carrying it shoots; `None` keeps the rogue's contact hit.
+    pub enemy: Option<EnemyGun>,
+}
+
+impl WeaponType {
+    /// The bare fists: what an unarmed blow is credited to in
+    /// [`crate::components::GameEvent`]s. Never held, and not among
+    /// [`WeaponType::all`].
+    pub const FISTS: WeaponType = WeaponType(u8::MAX);
+
+    /// Every defined weapon, in table order.
+    pub fn all() -> impl Iterator<Item = WeaponType> {
+        (0..WEAPONS.len() as u8).map(WeaponType)
+    }
+
+    /// The weapon with this table id.
+    pub fn from_id(id: &str) -> Option<WeaponType> {
+        WeaponType::all().find(|w| w.id() == id)
+    }
+
+    /// Its entry in the weapons table.
+    pub fn def(self) -> &'static WeaponDef {
+        match self {
+            WeaponType::FISTS => &FISTS,
+            _ => &WEAPONS[self.0 as usize],
+        }
+    }
+
+    pub fn id(self) -> &'static str {
+        self.def().id
+    }
+
+    /// Rounds in a full magazine (see [`WeaponDef::magazine`]).
+    pub fn magazine(self) -> i32 {
+        self.def().magazine
+    }
+
+    /// Melee weapons never run dry.
+    pub fn is_melee(self) -> bool {
+        self.def().reach.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ids_round_trip_and_are_unique() {
+        for w in WeaponType::all() {
+            assert_eq!(WeaponType::from_id(w.id()), Some(w));
+        }
+        assert_eq!(WeaponType::from_id("pistol"), Some(WeaponType::PISTOL));
+        assert_eq!(WeaponType::from_id("bazooka"), None);
+    }
+
+    #[test]
+    fn test_melee_is_a_reach_and_guns_have_a_magazine() {
+        for w in WeaponType::all() {
+            let def = w.def();
+            if w.is_melee() {
+                assert!(def.reach.unwrap() > 0.0, "{}", def.id);
+                assert_eq!(def.loudness, 0.0, "{} swings silently", def.id);
+            } else {
+                assert!(def.magazine > 0 && def.pellets > 0, "{}", def.id);
+            }
+        }
+        assert!(WeaponType::KATANA.is_melee());
+        assert!(!WeaponType::UZI.is_melee());
+    }
+
+    #[test]
+    fn test_fists_stand_outside_the_table() {
+        assert!(WEAPONS.len() < u8::MAX as usize);
+        assert!(WeaponType::all().all(|w| w != WeaponType::FISTS));
+        assert_eq!(WeaponType::FISTS.id(), "fists");
+        assert_eq!(WeaponType::from_id("fists"), None, "nothing to pick up");
+        assert!(WeaponType::FISTS.is_melee());
+    }
+
+    #[test]
+    fn test_the_silenced_pistol_is_quieter_than_the_pistol() {
+        assert!(WeaponType::SILENCED_PISTOL.def().loudness < WeaponType::PISTOL.def().loudness);
+    }
+}
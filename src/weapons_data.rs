@@ -0,0 +1,149 @@
+// @generated by tools/gen_weapons.py from weapons/weapons.json — DO NOT EDIT.
+// Re-run `make gen-weapons` after editing the JSON.
+//
+// The weapons table: one entry per weapon id, in file order (the index is
+// the `WeaponType`). See docs/WEAPONS_FORMAT.md for the contract and
+// src/weapons.rs for the types.
+#![allow(clippy::all)]
+
+use crate::components::WeaponType;
+use crate::weapons::{EnemyGun, FinisherStyle, ThrowDef, WeaponDef, WeaponModel, WeaponSound};
+
+impl WeaponType {
+    pub const PISTOL: WeaponType = WeaponType(0);
+    pub const SHOTGUN: WeaponType = WeaponType(1);
+    pub const MACHINEGUN: WeaponType = WeaponType(2);
+    pub const MELEE: WeaponType = WeaponType(3);
+    pub const SILENCED_PISTOL: WeaponType = WeaponType(4);
+    pub const UZI: WeaponType = WeaponType(5);
+    pub const KATANA: WeaponType = WeaponType(6);
+}
+
+pub static WEAPONS: [WeaponDef; 7] = [
+    WeaponDef {
+        id: "pistol",
+        name: "Pistol",
+        letter: "P",
+        color: [0.9, 0.9, 0.9],
+        model: WeaponModel::Pistol,
+        sound: WeaponSound::Gun,
+        damage: 50,
+        fire_rate: 0.5,
+        magazine: 12,
+        pellets: 1,
+        spread: 0.0,
+        reach: None,
+        loudness: 220.0,
+        finisher: FinisherStyle::Execute,
+        throw: ThrowDef { damage: 15, range: 550.0 },
+        enemy: Some(EnemyGun { windup: 0.7, spread: 0.12, pellets: 1, cadence: 1.0, reload: 1.6, range: 480.0 }),
+    },
+    WeaponDef {
+        id: "shotgun",
+        name: "Shotgun",
+        letter: "S",
+        color: [1.0, 0.55, 0.1],
+        model: WeaponModel::Shotgun,
+        sound: WeaponSound::Shotgun,
+        damage: 80,
+        fire_rate: 1.0,
+        magazine: 6,
+        pellets: 1,
+        spread: 0.0,
+        reach: None,
+        loudness: 350.0,
+        finisher: FinisherStyle::Execute,
+        throw: ThrowDef { damage: 15, range: 550.0 },
+        enemy: Some(EnemyGun { windup: 0.8, spread: 0.45, pellets: 5, cadence: 1.5, reload: 2.0, range: 300.0 }),
+    },
+    WeaponDef {
+        id: "machinegun",
+        name: "Machine Gun",
+        letter: "M",
+        color: [0.2, 0.8, 1.0],
+        model: WeaponModel::MachineGun,
+        sound: WeaponSound::MachineGun,
+        damage: 30,
+        fire_rate: 0.1,
+        magazine: 30,
+        pellets: 1,
+        spread: 0.0,
+        reach: None,
+        loudness: 260.0,
+        finisher: FinisherStyle::Execute,
+        throw: ThrowDef { damage: 15, range: 550.0 },
+        enemy: Some(EnemyGun { windup: 0.9, spread: 0.3, pellets: 1, cadence: 0.2, reload: 2.2, range: 400.0 }),
+    },
+    WeaponDef {
+        id: "melee",
+        name: "Melee",
+        letter: "C",
+        color: [0.7, 0.7, 0.75],
+        model: WeaponModel::Bar,
+        sound: WeaponSound::Club,
+        damage: 100,
+        fire_rate: 0.5,
+        magazine: 999,
+        pellets: 1,
+        spread: 0.0,
+        reach: Some(70.0),
+        loudness: 0.0,
+        finisher: FinisherStyle::Overhead,
+        throw: ThrowDef { damage: 15, range: 550.0 },
+        enemy: None,
+    },
+    WeaponDef {
+        id: "silenced_pistol",
+        name: "Silenced Pistol",
+        letter: "Q",
+        color: [0.45, 0.5, 0.55],
+        model: WeaponModel::Pistol,
+        sound: WeaponSound::Gun,
+        damage: 50,
+        fire_rate: 0.6,
+        magazine: 8,
+        pellets: 1,
+        spread: 0.0,
+        reach: None,
+        loudness: 60.0,
+        finisher: FinisherStyle::Execute,
+        throw: ThrowDef { damage: 15, range: 550.0 },
+        enemy: Some(EnemyGun { windup: 0.8, spread: 0.1, pellets: 1, cadence: 1.2, reload: 1.8, range: 440.0 }),
+    },
+    WeaponDef {
+        id: "uzi",
+        name: "Uzi",
+        letter: "U",
+        color: [1.0, 0.85, 0.2],
+        model: WeaponModel::MachineGun,
+        sound: WeaponSound::MachineGun,
+        damage: 25,
+        fire_rate: 0.07,
+        magazine: 24,
+        pellets: 1,
+        spread: 0.2,
+        reach: None,
+        loudness: 240.0,
+        finisher: FinisherStyle::Execute,
+        throw: ThrowDef { damage: 15, range: 550.0 },
+        enemy: Some(EnemyGun { windup: 0.8, spread: 0.35, pellets: 1, cadence: 0.15, reload: 2.0, range: 360.0 }),
+    },
+    WeaponDef {
+        id: "katana",
+        name: "Katana",
+        letter: "K",
+        color: [0.85, 0.9, 1.0],
+        model: WeaponModel::Bar,
+        sound: WeaponSound::Club,
+        damage: 100,
+        fire_rate: 0.35,
+        magazine: 999,
+        pellets: 1,
+        spread: 0.0,
+        reach: Some(80.0),
+        loudness: 0.0,
+        finisher: FinisherStyle::Overhead,
+        throw: ThrowDef { damage: 40, range: 450.0 },
+        enemy: None,
+    },
+];
@@ -0,0 +1,77 @@
+//! Golden event traces: every floor, played by the campaign bot from a fixed
+//! seed (retrying after a death, as a headless run does), must produce
+//! exactly the checked-in trace in `tests/golden/` — every gameplay event,
+//! scenario step, gate, comms line and exit opening on the frame it happened
+//! (see `open_miami::trace`).
+//!
+//! A failure prints a line diff of the golden file against the new trace.
+//! When the change is intended (a rebalanced wave, a rewritten comms line),
+//! re-bless the files and commit them with the change:
+//!
+//! ```text
+//! UPDATE_GOLDEN=1 cargo test --test golden_traces
+//! ```
+//!
+//! Thousands of frames of float maths make a trace as exact as the
+//! platform's `libm`: another target may round one `atan2` differently and
+//! shift every later line. The files are blessed on x86_64 Linux, which is
+//! where CI runs, so the test builds there (and runs with plain
+//! `cargo test`) and nowhere else.
+
+#![cfg(all(target_arch = "x86_64", target_os = "linux"))]
+
+use open_miami::levels::LEVEL_COUNT;
+use open_miami::report::RunOptions;
+use open_miami::trace::{diff, golden_name, trace_bot};
+use std::path::PathBuf;
+
+const SEED: u32 = 12345;
+/// Three minutes per attempt, as the playthrough gate allows the campaign
+/// bot, but a single retry: enough to cover a retry carrying the RNG on.
+/// Every floor must still end in an extraction — a golden ending `died`
+/// would pin a run that never reaches the exit.
+const OPTS: RunOptions = RunOptions {
+    dt: 1.0 / 60.0,
+    max_frames: 60 * 180,
+    attempts: 2,
+};
+
+fn golden_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/golden")
+}
+
+#[test]
+fn every_floor_matches_its_golden_trace() {
+    let update = std::env::var_os("UPDATE_GOLDEN").is_some();
+    let mut failures = Vec::new();
+    for level in 0..LEVEL_COUNT {
+        let actual = trace_bot(level, SEED, &OPTS).to_text();
+        let path = golden_dir().join(golden_name(level));
+        if !actual
+            .lines()
+            .last()
+            .is_some_and(|l| l.contains(" end extracted "))
+        {
+            failures.push(format!("{}: the bot never extracted", path.display()));
+            continue;
+        }
+        if update {
+            std::fs::create_dir_all(golden_dir()).unwrap();
+            std::fs::write(&path, &actual).unwrap();
+            continue;
+        }
+        let Ok(expected) = std::fs::read_to_string(&path) else {
+            failures.push(format!("{}: missing golden file", path.display()));
+            continue;
+        };
+        if let Some(d) = diff(&expected, &actual) {
+            failures.push(format!("{}:\n{d}", path.display()));
+        }
+    }
+    assert!(
+        failures.is_empty(),
+        "traces differ from their golden files (UPDATE_GOLDEN=1 re-blesses \
+         them if intended):\n\n{}",
+        failures.join("\n")
+    );
+}
@@ -75,7 +75,7 @@ fn test_enemy_ai_chases_player() {
     let _player = spawn_player(&mut world, Vec2::new(200.0, 0.0));
     let enemy = spawn_enemy(&mut world, Vec2::new(0.0, 0.0));
 
-    let mut ai_system = AISystem::default();
+    let mut ai_system = AISystem;
     // Run multiple frames for state transition (need > 0.3s)
     for _ in 0..30 {
         ai_system.run(&mut world, 0.016);
@@ -97,7 +97,7 @@ fn test_enemy_ai_attacks_when_close() {
     let _player = spawn_player(&mut world, Vec2::new(30.0, 0.0));
     let enemy = spawn_enemy(&mut world, Vec2::new(0.0, 0.0));
 
-    let mut ai_system = AISystem::default();
+    let mut ai_system = AISystem;
     // Run multiple frames for state transition
     for _ in 0..30 {
         ai_system.run(&mut world, 0.016);
@@ -120,7 +120,7 @@ fn test_enemy_ai_idle_when_far() {
     let _player = spawn_player(&mut world, Vec2::new(1000.0, 0.0)); // Beyond 900 detection range
     let enemy = spawn_enemy(&mut world, Vec2::new(0.0, 0.0));
 
-    let mut ai_system = AISystem::default();
+    let mut ai_system = AISystem;
     ai_system.run(&mut world, 0.016);
 
     // Enemy should be idle
@@ -168,6 +168,8 @@ fn test_enemy_attacks_player() {
 
     let player = spawn_player(&mut world, Vec2::new(0.0, 0.0));
     let enemy = spawn_enemy(&mut world, Vec2::new(30.0, 0.0));
+    // An unarmed rogue: contact is its only attack.
+    world.remove_component::<Weapon>(enemy);
 
     // Set enemy to attack state
     world.get_component_mut::<AI>(enemy).unwrap().state = AIState::SurePlayerSeen;
@@ -180,6 +182,117 @@ fn test_enemy_attacks_player() {
     assert!(health.is_dead());
 }
 
+/// Run combat and bullets for `seconds`; returns the frame the player died.
+fn shoot_out(world: &mut World, seconds: f32) -> Option<usize> {
+    let dt = 1.0 / 60.0;
+    for frame in 0..(seconds / dt) as usize {
+        CombatSystem.run(world, dt);
+        BulletSystem.run(world, dt);
+        world.apply_deferred();
+        if !is_player_alive(world) {
+            return Some(frame);
+        }
+    }
+    None
+}
+
+#[test]
+fn test_armed_enemy_winds_up_then_shoots_player() {
+    let mut world = World::new();
+    let player = spawn_player(&mut world, Vec2::new(0.0, 0.0));
+    let enemy = spawn_enemy(&mut world, Vec2::new(200.0, 0.0));
+    world.get_component_mut::<AI>(enemy).unwrap().state = AIState::SurePlayerSeen;
+
+    // Out of contact reach, a pistol rogue still kills — but only after
+    // holding the player in its sights for the wind-up.
+    let windup = combat::enemy_gun(WeaponType::PISTOL).unwrap().windup;
+    let died = shoot_out(&mut world, 2.0).expect("the rogue's round lands");
+    assert!(died as f32 / 60.0 >= windup);
+    assert!(world
+        .drain_events()
+        .contains(&GameEvent::PlayerHurt { by: enemy }));
+    assert!(world.get_component::<Health>(player).unwrap().is_dead());
+}
+
+#[test]
+fn test_wall_stops_enemy_fire() {
+    let mut world = World::new();
+    spawn_player(&mut world, Vec2::new(0.0, 0.0));
+    let enemy = spawn_enemy(&mut world, Vec2::new(200.0, 0.0));
+    world.get_component_mut::<AI>(enemy).unwrap().state = AIState::SurePlayerSeen;
+    world.add_wall(90.0, -50.0, 20.0, 100.0);
+
+    assert_eq!(shoot_out(&mut world, 2.0), None);
+    assert_eq!(world.get_component::<AI>(enemy).unwrap().aim_timer, 0.0);
+}
+
+#[test]
+fn test_enemy_fire_shatters_glass_on_its_way() {
+    let mut world = World::new();
+    spawn_player(&mut world, Vec2::new(0.0, 0.0));
+    let enemy = spawn_enemy(&mut world, Vec2::new(200.0, 0.0));
+    world.get_component_mut::<AI>(enemy).unwrap().state = AIState::SurePlayerSeen;
+    world.insert_wall(Wall::new(95.0, -50.0, 10.0, 100.0).with_material(WallMaterial::Glass));
+
+    // Sight goes through the pane, so the rogue winds up as in the open;
+    // its round bursts the glass and flies on into the player.
+    assert!(shoot_out(&mut world, 2.0).is_some());
+    assert!(world.walls().is_empty());
+    let events = world.drain_events();
+    assert!(events
+        .iter()
+        .any(|e| matches!(e, GameEvent::GlassShatter(_))));
+    assert!(events.contains(&GameEvent::PlayerHurt { by: enemy }));
+}
+
+#[test]
+fn test_glass_blocks_walking() {
+    let mut world = World::new();
+    let player = spawn_player(&mut world, Vec2::new(100.0, 100.0));
+    world.insert_wall(Wall::new(150.0, 50.0, 10.0, 100.0).with_material(WallMaterial::Glass));
+    world.get_component_mut::<Velocity>(player).unwrap().x = 100.0;
+
+    MovementSystem.run(&mut world, 0.5);
+    let pos = world.get_component::<Position>(player).unwrap();
+    assert!(pos.x <= 135.001, "walked into the pane: x={}", pos.x);
+    assert_eq!(
+        world.walls().len(),
+        1,
+        "walking into glass does not break it"
+    );
+}
+
+#[test]
+fn test_gunshot_brings_a_rogue_round_the_corner() {
+    let mut world = World::new();
+    let player = spawn_player(&mut world, Vec2::new(100.0, 100.0));
+    // Out of sight behind a wall, but a short walk round its end.
+    world.add_wall(200.0, 0.0, 20.0, 150.0);
+    let rogue = spawn_enemy(&mut world, Vec2::new(300.0, 100.0));
+
+    // A punch is heard by nobody.
+    world.remove_component::<Weapon>(player);
+    fire_player_weapon(&mut world, Vec2::new(0.0, 100.0));
+    NoiseSystem.run(&mut world, 0.016);
+    assert_eq!(
+        world.get_component::<AI>(rogue).unwrap().state,
+        AIState::Unaware
+    );
+
+    world.add_component(player, Weapon::new(WeaponType::SHOTGUN));
+    assert!(!fire_player_weapon(&mut world, Vec2::new(0.0, 100.0)));
+    NoiseSystem.run(&mut world, 0.016);
+    AISystem.run(&mut world, 0.016);
+    assert_eq!(
+        world.get_component::<AI>(rogue).unwrap().state,
+        AIState::SpottedUnsure
+    );
+    assert!(
+        world.get_component::<Velocity>(rogue).unwrap().y > 0.0,
+        "heads down round the wall's end"
+    );
+}
+
 #[test]
 fn test_complete_game_scenario_player_clears_room() {
     let mut world = World::new();
@@ -231,7 +344,14 @@ fn test_melee_attack_hits_in_range() {
     let attacker_pos = Position::new(0.0, 0.0);
     let target_pos = Position::new(100.0, 0.0);
 
-    let hit = CombatSystem::process_melee(&mut world, attacker_pos, target_pos, 50, 50.0);
+    let hit = CombatSystem::process_melee(
+        &mut world,
+        attacker_pos,
+        target_pos,
+        50,
+        50.0,
+        WeaponType::MELEE,
+    );
 
     assert!(hit);
     let health = world.get_component::<Health>(enemy).unwrap();
@@ -244,7 +364,7 @@ fn test_multiple_systems_integration() {
     initialize_game(&mut world, 0);
 
     let mut movement_system = MovementSystem;
-    let mut ai_system = AISystem::default();
+    let mut ai_system = AISystem;
     let mut weapon_system = WeaponUpdateSystem;
     let mut combat_system = CombatSystem;
 
@@ -397,7 +517,7 @@ fn test_ammo_travels_with_the_weapon_through_drop_and_swap() {
     let rogue = spawn_enemy_with_type(&mut world, Vec2::new(105.0, 100.0), EnemyType::Patrolling);
     assert_eq!(
         world.get_component::<Weapon>(rogue).unwrap().weapon_type,
-        WeaponType::Shotgun
+        WeaponType::SHOTGUN
     );
     world
         .get_component_mut::<Health>(rogue)
@@ -406,27 +526,28 @@ fn test_ammo_travels_with_the_weapon_through_drop_and_swap() {
 
     let mut pickup = PickupSystem;
     pickup.run(&mut world, 1.0 / 60.0);
+    world.apply_deferred();
     let events = world.drain_events();
     assert_eq!(events, vec![GameEvent::EnemyDown]);
 
     let dropped = world.query::<WeaponPickup>();
     assert_eq!(dropped.len(), 1);
     let drop = *world.get_component::<WeaponPickup>(dropped[0]).unwrap();
-    assert_eq!(drop.weapon_type, WeaponType::Shotgun);
-    assert!(drop.ammo >= 1 && drop.ammo <= WeaponType::Shotgun.magazine());
+    assert_eq!(drop.weapon_type, WeaponType::SHOTGUN);
+    assert!(drop.ammo >= 1 && drop.ammo <= WeaponType::SHOTGUN.magazine());
 
     // E: take the shotgun with exactly its rounds; leave the 4-round pistol.
     assert_eq!(
         PickupSystem::swap_for_player(&mut world),
-        Some(WeaponType::Shotgun)
+        Some(WeaponType::SHOTGUN)
     );
     let held = *world.get_component::<Weapon>(player).unwrap();
     assert_eq!(
         (held.weapon_type, held.ammo),
-        (WeaponType::Shotgun, drop.ammo)
+        (WeaponType::SHOTGUN, drop.ammo)
     );
     let floor = *world.get_component::<WeaponPickup>(dropped[0]).unwrap();
-    assert_eq!((floor.weapon_type, floor.ammo), (WeaponType::Pistol, 4));
+    assert_eq!((floor.weapon_type, floor.ammo), (WeaponType::PISTOL, 4));
     assert_eq!(world.drain_events(), vec![GameEvent::Pickup]);
     // Nothing granted a second pickup or a refill.
     assert_eq!(world.query::<WeaponPickup>().len(), 1);
@@ -449,8 +570,8 @@ fn test_thrown_weapon_keeps_its_ammo_and_can_be_retrieved() {
     assert_eq!(
         world.drain_events(),
         vec![
-            GameEvent::PlayerFired(WeaponType::Pistol),
-            GameEvent::PlayerFired(WeaponType::Pistol)
+            GameEvent::PlayerFired(WeaponType::PISTOL),
+            GameEvent::PlayerFired(WeaponType::PISTOL)
         ]
     );
 
@@ -465,7 +586,7 @@ fn test_thrown_weapon_keeps_its_ammo_and_can_be_retrieved() {
     assert!(!fire_player_weapon(&mut world, Vec2::new(500.0, 0.0)));
     assert_eq!(
         world.drain_events(),
-        vec![GameEvent::Throw, GameEvent::PlayerFired(WeaponType::Melee)]
+        vec![GameEvent::Throw, GameEvent::PlayerFired(WeaponType::FISTS)]
     );
 
     let mut thrown = ThrownWeaponSystem;
@@ -478,17 +599,17 @@ fn test_thrown_weapon_keeps_its_ammo_and_can_be_retrieved() {
     let pickups = world.query::<WeaponPickup>();
     assert_eq!(pickups.len(), 1);
     let landed = *world.get_component::<WeaponPickup>(pickups[0]).unwrap();
-    assert_eq!((landed.weapon_type, landed.ammo), (WeaponType::Pistol, 10));
+    assert_eq!((landed.weapon_type, landed.ammo), (WeaponType::PISTOL, 10));
 
     // Walk over it and pick it up: same pistol, still 10 rounds.
     let where_it_lies = *world.get_component::<Position>(pickups[0]).unwrap();
     *world.get_component_mut::<Position>(player).unwrap() = where_it_lies;
     assert_eq!(
         PickupSystem::swap_for_player(&mut world),
-        Some(WeaponType::Pistol)
+        Some(WeaponType::PISTOL)
     );
     let held = *world.get_component::<Weapon>(player).unwrap();
-    assert_eq!((held.weapon_type, held.ammo), (WeaponType::Pistol, 10));
+    assert_eq!((held.weapon_type, held.ammo), (WeaponType::PISTOL, 10));
     assert!(world.query::<WeaponPickup>().is_empty()); // consumed (was unarmed)
 }
 
@@ -497,7 +618,7 @@ fn test_thrown_weapon_keeps_its_ammo_and_can_be_retrieved() {
 fn test_empty_gun_dry_fires_until_swapped() {
     let mut world = World::new();
     let player = spawn_player(&mut world, Vec2::new(0.0, 0.0));
-    *world.get_component_mut::<Weapon>(player).unwrap() = Weapon::with_ammo(WeaponType::Pistol, 1);
+    *world.get_component_mut::<Weapon>(player).unwrap() = Weapon::with_ammo(WeaponType::PISTOL, 1);
     fire_player_weapon(&mut world, Vec2::new(100.0, 0.0));
     world
         .get_component_mut::<Weapon>(player)
@@ -507,30 +628,30 @@ fn test_empty_gun_dry_fires_until_swapped() {
     assert_eq!(
         world.drain_events(),
         vec![
-            GameEvent::PlayerFired(WeaponType::Pistol),
+            GameEvent::PlayerFired(WeaponType::PISTOL),
             GameEvent::DryFire
         ]
     );
     assert_eq!(world.query::<Bullet>().len(), 1);
 
     // A machine gun lies here: swap and we're firing again.
-    spawn_pickup_with_ammo(&mut world, Vec2::new(0.0, 0.0), WeaponType::MachineGun, 9);
+    spawn_pickup_with_ammo(&mut world, Vec2::new(0.0, 0.0), WeaponType::MACHINEGUN, 9);
     assert_eq!(
         PickupSystem::swap_for_player(&mut world),
-        Some(WeaponType::MachineGun)
+        Some(WeaponType::MACHINEGUN)
     );
     world.drain_events();
     fire_player_weapon(&mut world, Vec2::new(100.0, 0.0));
     assert_eq!(
         world.drain_events(),
-        vec![GameEvent::PlayerFired(WeaponType::MachineGun)]
+        vec![GameEvent::PlayerFired(WeaponType::MACHINEGUN)]
     );
     assert_eq!(world.get_component::<Weapon>(player).unwrap().ammo, 8);
     // The empty pistol lies where the MG was, still empty.
     let floor = world.query::<WeaponPickup>();
     assert_eq!(floor.len(), 1);
     let p = world.get_component::<WeaponPickup>(floor[0]).unwrap();
-    assert_eq!((p.weapon_type, p.ammo), (WeaponType::Pistol, 0));
+    assert_eq!((p.weapon_type, p.ammo), (WeaponType::PISTOL, 0));
 }
 
 /// The full event flow through the headless simulation: fire -> bullet hit ->
@@ -556,7 +677,7 @@ fn test_sim_events_fire_hit_down() {
     assert_eq!(sim.enemies_alive(), 0);
     let fired = seen
         .iter()
-        .filter(|e| matches!(e, GameEvent::PlayerFired(WeaponType::Pistol)))
+        .filter(|e| matches!(e, GameEvent::PlayerFired(WeaponType::PISTOL)))
         .count();
     let hits = seen
         .iter()
@@ -564,7 +685,7 @@ fn test_sim_events_fire_hit_down() {
             matches!(
                 e,
                 GameEvent::EnemyHit {
-                    by: WeaponType::Pistol
+                    by: WeaponType::PISTOL
                 }
             )
         })
@@ -589,6 +710,6 @@ fn test_sim_events_fire_hit_down() {
     // Nothing else leaked (no pickup/throw/hurt from a static enemy).
     assert!(seen.iter().all(|e| !matches!(
         e,
-        GameEvent::Pickup | GameEvent::Throw | GameEvent::PlayerHurt
+        GameEvent::Pickup | GameEvent::Throw | GameEvent::PlayerHurt { .. }
     )));
 }
@@ -119,7 +119,7 @@ fn thin_wall_blocks_pathfinding_enemy() {
     world.add_component(enemy, Health::new(100));
     world.add_component(enemy, Rotation::new(0.0));
 
-    let mut ai_system = AISystem::default();
+    let mut ai_system = AISystem;
     let mut movement = MovementSystem;
     for _ in 0..300 {
         ai_system.run(&mut world, DT);
@@ -156,7 +156,7 @@ fn bullet_cannot_tunnel_thin_wall() {
     // Bullet placed so one 60 Hz step (800 px/s -> 13.3 px) straddles the
     // wall entirely: endpoint-only collision would fly clean through.
     let bullet = world.spawn();
-    world.add_component(bullet, Bullet::new(WeaponType::Pistol, 30));
+    world.add_component(bullet, Bullet::new(WeaponType::PISTOL, 30));
     world.add_component(bullet, Position::new(493.5, 100.0));
     world.add_component(bullet, Velocity::new(800.0, 0.0));
     world.add_component(bullet, Radius::new(2.0));
@@ -164,6 +164,7 @@ fn bullet_cannot_tunnel_thin_wall() {
     let mut bullets = BulletSystem;
     for _ in 0..120 {
         bullets.run(&mut world, DT);
+        world.apply_deferred();
         for b in world.query::<Bullet>() {
             let pos = world.get_component::<Position>(b).unwrap();
             assert!(
@@ -192,7 +193,7 @@ fn bullet_cannot_tunnel_thin_wall_full_pipeline() {
     let enemy = spawn_enemy_behind_wall(&mut world);
 
     let bullet = world.spawn();
-    world.add_component(bullet, Bullet::new(WeaponType::Pistol, 30));
+    world.add_component(bullet, Bullet::new(WeaponType::PISTOL, 30));
     world.add_component(bullet, Position::new(450.0, 100.0));
     world.add_component(bullet, Velocity::new(800.0, 0.0));
     world.add_component(bullet, Radius::new(2.0));
@@ -202,6 +203,7 @@ fn bullet_cannot_tunnel_thin_wall_full_pipeline() {
     for _ in 0..120 {
         movement.run(&mut world, DT);
         bullets.run(&mut world, DT);
+        world.apply_deferred();
         for b in world.query::<Bullet>() {
             let pos = world.get_component::<Position>(b).unwrap();
             assert!(
@@ -260,7 +262,7 @@ fn thrown_weapon_stops_on_thin_wall() {
     world.add_component(player, Player);
     world.add_component(player, Position::new(400.0, 100.0));
     world.add_component(player, Radius::new(15.0));
-    world.add_component(player, Weapon::new(WeaponType::Pistol));
+    world.add_component(player, Weapon::new(WeaponType::PISTOL));
     assert!(ThrownWeaponSystem::throw_from_player(
         &mut world,
         Vec2::new(1.0, 0.0)
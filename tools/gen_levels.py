@@ -14,9 +14,10 @@ The JSON contract is documented in docs/SCENARIO_FORMAT.md. This script also
 validates it: every `exit.to` must be an existing floor id (or "surface" =
 the end of the run),
 every zone / exit / step id referenced by a scenario must exist, speakers,
-enemy types, weapons and prop kinds (`props[].kind`, the snake_case ids of
-`PROP_NAMES` in src/props.rs) must be from the fixed sets, and no two floors
-may share an id.
+enemy types, wall materials and prop kinds (`props[].kind`, the snake_case
+ids of `PROP_NAMES` in src/props.rs) must be from the fixed sets, pickup
+weapons must be ids of the weapons table (weapons/weapons.json),
+doors need a numeric hinge and heading, and no two floors may share an id.
 """
 import json
 import os
@@ -27,9 +28,9 @@ ROOT = os.path.dirname(os.path.dirname(os.path.abspath(__file__)))
 LEVELS_DIR = os.path.join(ROOT, "levels")
 OUT_PATH = os.path.join(ROOT, "src", "levels_data.rs")
 PROPS_RS = os.path.join(ROOT, "src", "props.rs")
+WEAPONS_JSON = os.path.join(ROOT, "weapons", "weapons.json")
 
 ENEMY_TYPES = {"idle": "Idle", "wandering": "Wandering", "patrolling": "Patrolling"}
-WEAPONS = {"pistol": "Pistol", "shotgun": "Shotgun", "machinegun": "MachineGun", "melee": "Melee"}
 SPEAKERS = {"CL4-UD3", "HUNTER", "SENTINEL", "DRIFTER", "SWARM", "CORRUPTOR", "UPLINK"}
 TRIGGERS = {"start", "enter_zone", "kills", "all_dead", "timer", "exit_open", "step_done",
             "boss_dead", "extracted"}
@@ -47,6 +48,11 @@ SURFACES = {"checker": "Checker", "asphalt": "Asphalt", "marble": "Marble", "con
 SURFACE = "surface"
 # `hold.until_comms_idle` is capped at this many seconds (mirrors scenario.rs).
 HOLD_COMMS_IDLE_CAP = 20.0
+# Wall materials (`"material"` on a wall; default solid).
+WALL_MATERIALS = {"solid", "glass"}
+
+# Leaf length of a door that does not give one.
+DOOR_LENGTH = 100.0
 
 
 class Invalid(Exception):
@@ -89,6 +95,27 @@ def prop_kinds():
     return PROP_KINDS
 
 
+def load_weapon_ids():
+    """The weapon ids of the weapons table, weapons/weapons.json (validated
+    by tools/gen_weapons.py)."""
+    with open(WEAPONS_JSON, encoding="utf-8") as fh:
+        doc = json.load(fh)
+    ids = [w.get("id") for w in doc.get("weapons", []) if isinstance(w, dict)]
+    if not all(isinstance(i, str) for i in ids):
+        raise Invalid("weapons/weapons.json: every weapon needs a string id")
+    return set(ids)
+
+
+WEAPON_IDS = None
+
+
+def weapon_ids():
+    global WEAPON_IDS
+    if WEAPON_IDS is None:
+        WEAPON_IDS = load_weapon_ids()
+    return WEAPON_IDS
+
+
 def f32(v):
     """Format a number as a Rust f32 literal, deterministically."""
     v = float(v)
@@ -121,6 +148,14 @@ def rect(d, what):
     return f"Rect::new({f32(d['x'])}, {f32(d['y'])}, {f32(d['w'])}, {f32(d['h'])})"
 
 
+def wall_material(d, what):
+    """The wall's `material` (default solid) as its `FloorWall` constructor."""
+    material = d.get("material", "solid")
+    if material not in WALL_MATERIALS:
+        raise Invalid(f"{what}: unknown material {material!r} (one of {sorted(WALL_MATERIALS)})")
+    return material
+
+
 def load_floors():
     with open(os.path.join(LEVELS_DIR, "index.json"), encoding="utf-8") as fh:
         index = json.load(fh)
@@ -179,8 +214,10 @@ def validate(floors):
         for s in f["spawns"]:
             validate_spawn(s, zone_ids, f"{tag}: spawn")
         for p in f.get("pickups", []):
-            if p.get("weapon") not in WEAPONS:
+            if p.get("weapon") not in weapon_ids():
                 raise Invalid(f"{tag}: bad pickup weapon {p.get('weapon')!r}")
+        for i, d in enumerate(f.get("doors", [])):
+            validate_door(d, f"{tag}: doors[{i}]")
         for i, p in enumerate(f.get("props", [])):
             if not isinstance(p, dict) or p.get("kind") not in prop_kinds():
                 raise Invalid(f"{tag}: props[{i}]: unknown prop kind {p.get('kind') if isinstance(p, dict) else p!r}")
@@ -293,6 +330,21 @@ def validate_spawn(s, zone_ids, what):
             raise Invalid(f"{what}: unarmed must be a boolean")
 
 
+def validate_door(d, what):
+    """A swinging door: hinge `x`, `y`, shut heading `angle` (degrees) and an
+    optional leaf `length` (default DOOR_LENGTH)."""
+    if not isinstance(d, dict):
+        raise Invalid(f"{what}: must be an object")
+    for k in ("x", "y", "angle"):
+        if not isinstance(d.get(k), (int, float)):
+            raise Invalid(f"{what}: missing / non-numeric '{k}'")
+    if not isinstance(d.get("length", DOOR_LENGTH), (int, float)) or d.get("length", DOOR_LENGTH) <= 0:
+        raise Invalid(f"{what}: length must be > 0")
+    extra = set(d) - {"x", "y", "angle", "length"}
+    if extra:
+        raise Invalid(f"{what}: door takes only x/y/angle/length, got {sorted(extra)}")
+
+
 def validate_alert(payload, zone_ids, what):
     if payload == "all":
         return
@@ -492,9 +544,9 @@ def gen_floor(f, out):
         out.append(f"    {elevator(e, fid, tag + ' exit')},")
     out.append("];")
     out.append("")
-    out.append(f"static {name}_WALLS: [Rect; {len(f['walls'])}] = [")
+    out.append(f"static {name}_WALLS: [FloorWall; {len(f['walls'])}] = [")
     for w in f["walls"]:
-        out.append(f"    {rect(w, tag + ' wall')},")
+        out.append(f"    FloorWall::{wall_material(w, tag + ' wall')}({rect(w, tag + ' wall')}),")
     out.append("];")
     out.append("")
     rooms = f.get("rooms", [])
@@ -517,7 +569,14 @@ def gen_floor(f, out):
     pickups = f.get("pickups", [])
     out.append(f"static {name}_PICKUPS: [PickupDef; {len(pickups)}] = [")
     for p in pickups:
-        out.append(f"    PickupDef {{ x: {f32(p['x'])}, y: {f32(p['y'])}, weapon: WeaponType::{WEAPONS[p['weapon']]} }},")
+        out.append(f"    PickupDef {{ x: {f32(p['x'])}, y: {f32(p['y'])}, weapon: WeaponType::{p['weapon'].upper()} }},")
+    out.append("];")
+    out.append("")
+    doors = f.get("doors", [])
+    out.append(f"static {name}_DOORS: [DoorDef; {len(doors)}] = [")
+    for d in doors:
+        out.append(f"    DoorDef {{ x: {f32(d['x'])}, y: {f32(d['y'])}, angle: {f32(d['angle'])}, "
+                   f"length: {f32(d.get('length', DOOR_LENGTH))} }},")
     out.append("];")
     out.append("")
     props = f.get("props", [])
@@ -545,6 +604,7 @@ def gen_floor(f, out):
     out.append(f"    zones: &{name}_ZONES,")
     out.append(f"    spawns: &{name}_SPAWNS,")
     out.append(f"    pickups: &{name}_PICKUPS,")
+    out.append(f"    doors: &{name}_DOORS,")
     out.append(f"    props: &{name}_PROPS,")
     out.append(f"    scenario: &{name}_SCENARIO,")
     out.append(f"    surface: Surface::{SURFACES[f.get('surface', 'checker')]},")
@@ -567,9 +627,9 @@ def generate(floors):
         "",
         "use crate::components::{EnemyType, WeaponType};",
         "use crate::scenario::{",
-        "    Action, AlertTarget, ElevatorDef, ElevatorKind, FloorDef, GateDef, GateInput, HoldDef,",
-        "    LookAtDef, PickupDef, PropPlacement, Rect, RoomDef, SayDef, SpawnDef, StepDef, Surface,",
-        "    TalkDef, Trigger, ZoneDef, SURFACE_EXIT,",
+        "    Action, AlertTarget, DoorDef, ElevatorDef, ElevatorKind, FloorDef, FloorWall, GateDef,",
+        "    GateInput, HoldDef, LookAtDef, PickupDef, PropPlacement, Rect, RoomDef, SayDef, SpawnDef,",
+        "    StepDef, Surface, TalkDef, Trigger, ZoneDef, SURFACE_EXIT,",
         "};",
         "",
     ]
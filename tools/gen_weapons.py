@@ -0,0 +1,242 @@
+#!/usr/bin/env python3
+"""Generate `src/weapons_data.rs` from `weapons/weapons.json`.
+
+Python standard library only. The JSON is the weapons table: per weapon id
+its damage, fire rate, magazine, pellets and spread, melee reach, throw
+behaviour, loudness, finisher, looks and how a rogue carrying it shoots.
+This script compiles it into a static Rust table (`WEAPONS`) plus one
+`WeaponType::<ID>` constant per weapon; the types live in src/weapons.rs.
+
+Usage:
+    python3 tools/gen_weapons.py            # write src/weapons_data.rs
+    python3 tools/gen_weapons.py --check    # validate + verify the checked-in
+                                            # file is up to date (exit 1 if not)
+
+The JSON contract is documented in docs/WEAPONS_FORMAT.md:
+
+    { "weapons": [ { "id": "pistol", "name": "Pistol", "damage": 50, ... }, ... ] }
+
+  * `id`: snake_case, unique; levels name pickups by it (`pickups[].weapon`).
+  * a melee weapon has a `reach` (px) and no `magazine`; a gun has a
+    `magazine` and a `loudness` (px), and optionally `pellets`, `spread` and
+    an `enemy` block (a gun without one is never fired by a rogue).
+  * `throw` defaults to the stock throw (15 damage, 550 px).
+"""
+import json
+import os
+import re
+import sys
+
+ROOT = os.path.dirname(os.path.dirname(os.path.abspath(__file__)))
+JSON_PATH = os.path.join(ROOT, "weapons", "weapons.json")
+OUT_PATH = os.path.join(ROOT, "src", "weapons_data.rs")
+
+MODELS = {"bar": "Bar", "pistol": "Pistol", "machinegun": "MachineGun", "shotgun": "Shotgun"}
+SOUNDS = {"gun": "Gun", "machinegun": "MachineGun", "shotgun": "Shotgun", "club": "Club"}
+FINISHERS = {"overhead": "Overhead", "execute": "Execute"}
+# Nominal magazine of a melee weapon (it never runs dry).
+MELEE_MAGAZINE = 999
+# The stock throw (mirrors the old thrown.rs constants).
+DEFAULT_THROW = {"damage": 15, "range": 550}
+ENEMY_KEYS = ["windup", "spread", "pellets", "cadence", "reload", "range"]
+# `WeaponType` is a u8 index.
+MAX_WEAPONS = 255
+ID_RE = re.compile(r"^[a-z][a-z0-9_]*$")
+
+
+class Invalid(Exception):
+    pass
+
+
+def f32(v):
+    """Format a number as a Rust f32 literal, deterministically."""
+    v = float(v)
+    if v != v or v in (float("inf"), float("-inf")):
+        raise Invalid(f"non-finite number {v!r}")
+    if v == int(v) and abs(v) < 1e15:
+        return f"{int(v)}.0"
+    return repr(v)
+
+
+def rstr(s):
+    out = s.replace("\\", "\\\\").replace('"', '\\"').replace("\n", "\\n").replace("\r", "").replace("\t", "\\t")
+    return f'"{out}"'
+
+
+def number(w, key, tag, minimum=0.0, integer=False, default=None):
+    v = w.get(key, default)
+    if v is None:
+        raise Invalid(f"{tag}: missing {key}")
+    if isinstance(v, bool) or not isinstance(v, (int, float)):
+        raise Invalid(f"{tag}: {key} must be a number, got {v!r}")
+    if integer and not (isinstance(v, int) or float(v).is_integer()):
+        raise Invalid(f"{tag}: {key} must be an integer, got {v!r}")
+    if v < minimum:
+        raise Invalid(f"{tag}: {key} must be >= {minimum}, got {v!r}")
+    return int(v) if integer else v
+
+
+def load_weapons():
+    with open(JSON_PATH, encoding="utf-8") as fh:
+        doc = json.load(fh)
+    return validate(doc)
+
+
+def validate(doc):
+    """Validate the document; return the weapon entries with defaults filled."""
+    if not isinstance(doc, dict) or not isinstance(doc.get("weapons"), list):
+        raise Invalid('top level must be {"weapons": [...]}')
+    if not 0 < len(doc["weapons"]) <= MAX_WEAPONS:
+        raise Invalid(f"need 1..{MAX_WEAPONS} weapons")
+    out = []
+    seen = set()
+    for i, w in enumerate(doc["weapons"]):
+        if not isinstance(w, dict):
+            raise Invalid(f"weapons[{i}]: not an object")
+        wid = w.get("id")
+        if not isinstance(wid, str) or not ID_RE.match(wid):
+            raise Invalid(f"weapons[{i}]: id must be snake_case, got {wid!r}")
+        if wid in seen:
+            raise Invalid(f"weapons[{i}]: duplicate id {wid!r}")
+        seen.add(wid)
+        tag = wid
+        for key in ("name", "letter"):
+            if not isinstance(w.get(key), str) or not w[key]:
+                raise Invalid(f"{tag}: {key} must be a non-empty string")
+        if len(w["letter"]) != 1:
+            raise Invalid(f"{tag}: letter must be one character")
+        color = w.get("color")
+        if (not isinstance(color, list) or len(color) != 3
+                or any(isinstance(c, bool) or not isinstance(c, (int, float)) or not 0 <= c <= 1 for c in color)):
+            raise Invalid(f"{tag}: color must be [r, g, b] in 0..1")
+        if w.get("model") not in MODELS:
+            raise Invalid(f"{tag}: bad model {w.get('model')!r} (one of {', '.join(MODELS)})")
+        if w.get("sound") not in SOUNDS:
+            raise Invalid(f"{tag}: bad sound {w.get('sound')!r} (one of {', '.join(SOUNDS)})")
+        if w.get("finisher") not in FINISHERS:
+            raise Invalid(f"{tag}: bad finisher {w.get('finisher')!r} (one of {', '.join(FINISHERS)})")
+        e = {
+            "id": wid, "name": w["name"], "letter": w["letter"], "color": color,
+            "model": w["model"], "sound": w["sound"], "finisher": w["finisher"],
+            "damage": number(w, "damage", tag, integer=True),
+            "fire_rate": number(w, "fire_rate", tag),
+        }
+        melee = "reach" in w
+        if melee:
+            for key in ("magazine", "pellets", "spread", "enemy"):
+                if key in w:
+                    raise Invalid(f"{tag}: a melee weapon (it has a reach) takes no {key}")
+            e["reach"] = number(w, "reach", tag, minimum=1)
+            e["magazine"] = MELEE_MAGAZINE
+            e["pellets"] = 1
+            e["spread"] = 0
+            e["loudness"] = number(w, "loudness", tag, default=0)
+            e["enemy"] = None
+        else:
+            e["reach"] = None
+            e["magazine"] = number(w, "magazine", tag, minimum=1, integer=True)
+            e["pellets"] = number(w, "pellets", tag, minimum=1, integer=True, default=1)
+            e["spread"] = number(w, "spread", tag, default=0)
+            e["loudness"] = number(w, "loudness", tag)
+            enemy = w.get("enemy")
+            if enemy is not None:
+                if not isinstance(enemy, dict):
+                    raise Invalid(f"{tag}: enemy must be an object")
+                unknown = set(enemy) - set(ENEMY_KEYS)
+                if unknown:
+                    raise Invalid(f"{tag}: unknown enemy keys {sorted(unknown)}")
+                enemy = {k: number(enemy, k, f"{tag}: enemy", minimum=1 if k == "pellets" else 0,
+                                   integer=k == "pellets") for k in ENEMY_KEYS}
+            e["enemy"] = enemy
+        throw = w.get("throw", DEFAULT_THROW)
+        if not isinstance(throw, dict):
+            raise Invalid(f"{tag}: throw must be an object")
+        e["throw"] = {
+            "damage": number(throw, "damage", f"{tag}: throw", integer=True, default=DEFAULT_THROW["damage"]),
+            "range": number(throw, "range", f"{tag}: throw", minimum=1, default=DEFAULT_THROW["range"]),
+        }
+        out.append(e)
+    return out
+
+
+def generate(weapons):
+    out = [
+        "// @generated by tools/gen_weapons.py from weapons/weapons.json — DO NOT EDIT.",
+        "// Re-run `make gen-weapons` after editing the JSON.",
+        "//",
+        "// The weapons table: one entry per weapon id, in file order (the index is",
+        "// the `WeaponType`). See docs/WEAPONS_FORMAT.md for the contract and",
+        "// src/weapons.rs for the types.",
+        "#![allow(clippy::all)]",
+        "",
+        "use crate::components::WeaponType;",
+        "use crate::weapons::{EnemyGun, FinisherStyle, ThrowDef, WeaponDef, WeaponModel, WeaponSound};",
+        "",
+        "impl WeaponType {",
+    ]
+    for i, w in enumerate(weapons):
+        out.append(f"    pub const {w['id'].upper()}: WeaponType = WeaponType({i});")
+    out.append("}")
+    out.append("")
+    out.append(f"pub static WEAPONS: [WeaponDef; {len(weapons)}] = [")
+    for w in weapons:
+        r, g, b = (f32(c) for c in w["color"])
+        reach = f"Some({f32(w['reach'])})" if w["reach"] is not None else "None"
+        out.append("    WeaponDef {")
+        out.append(f"        id: {rstr(w['id'])},")
+        out.append(f"        name: {rstr(w['name'])},")
+        out.append(f"        letter: {rstr(w['letter'])},")
+        out.append(f"        color: [{r}, {g}, {b}],")
+        out.append(f"        model: WeaponModel::{MODELS[w['model']]},")
+        out.append(f"        sound: WeaponSound::{SOUNDS[w['sound']]},")
+        out.append(f"        damage: {w['damage']},")
+        out.append(f"        fire_rate: {f32(w['fire_rate'])},")
+        out.append(f"        magazine: {w['magazine']},")
+        out.append(f"        pellets: {w['pellets']},")
+        out.append(f"        spread: {f32(w['spread'])},")
+        out.append(f"        reach: {reach},")
+        out.append(f"        loudness: {f32(w['loudness'])},")
+        out.append(f"        finisher: FinisherStyle::{FINISHERS[w['finisher']]},")
+        out.append(f"        throw: ThrowDef {{ damage: {w['throw']['damage']}, range: {f32(w['throw']['range'])} }},")
+        if w["enemy"] is None:
+            out.append("        enemy: None,")
+        else:
+            en = w["enemy"]
+            out.append(
+                f"        enemy: Some(EnemyGun {{ windup: {f32(en['windup'])}, spread: {f32(en['spread'])}, "
+                f"pellets: {en['pellets']}, cadence: {f32(en['cadence'])}, reload: {f32(en['reload'])}, "
+                f"range: {f32(en['range'])} }}),")
+        out.append("    },")
+    out.append("];")
+    out.append("")
+    return "\n".join(out)
+
+
+def main(argv):
+    check = "--check" in argv
+    try:
+        weapons = load_weapons()
+        text = generate(weapons)
+    except (Invalid, KeyError, OSError, ValueError, json.JSONDecodeError) as e:
+        print(f"gen_weapons: error: {e}", file=sys.stderr)
+        return 1
+    if check:
+        try:
+            with open(OUT_PATH, encoding="utf-8") as fh:
+                current = fh.read()
+        except OSError:
+            current = None
+        if current != text:
+            print(f"gen_weapons: {os.path.relpath(OUT_PATH, ROOT)} is out of date — run `make gen-weapons`",
+                  file=sys.stderr)
+            return 1
+        print(f"gen_weapons: {len(weapons)} weapons valid, {os.path.relpath(OUT_PATH, ROOT)} up to date")
+        return 0
+    with open(OUT_PATH, "w", encoding="utf-8") as fh:
+        fh.write(text)
+    print(f"gen_weapons: wrote {os.path.relpath(OUT_PATH, ROOT)} ({len(weapons)} weapons)")
+    return 0
+
+
+if __name__ == "__main__":
+    sys.exit(main(sys.argv[1:]))
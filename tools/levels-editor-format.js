@@ -34,9 +34,17 @@
   const PASSIVE_LOOKS = ["idle", "wandering", "patrolling"];
   const PORTAL_KINDS = ["lift", "door", "gate"];
   const SURFACES = ["checker", "asphalt", "marble", "concrete", "grating"];
+  /* wall.material: absent = solid; glass sees through and shatters when hit */
+  const WALL_MATERIALS = ["solid", "glass"];
   /* exit.to value that ends the run (was 0 before floor 0 became the parking lot) */
   const SURFACE_TO = "surface";
+  /* weapon ids: the stock set until the page loads weapons/weapons.json
+     (setWeapons); any id the weapons table defines is a valid pickup */
   const WEAPONS = ["pistol", "shotgun", "machinegun", "melee"];
+  function setWeapons(ids) {
+    const valid = (ids || []).filter((id) => typeof id === "string" && id);
+    if (valid.length) WEAPONS.splice(0, WEAPONS.length, ...valid);
+  }
   const TRIGGER_KINDS = {
     start: [], enter_zone: ["zone"], kills: ["count"], all_dead: [],
     timer: ["seconds", "after"], exit_open: ["exit"], step_done: ["step"],
@@ -50,15 +58,18 @@
 
   const ORDER = {
     floor: ["id", "name", "theme", "accent", "flavor", "objective", "size", "surface", "entry", "exits",
-      "walls", "rooms", "zones", "spawns", "pickups", "props", "scenario"],
+      "walls", "rooms", "zones", "spawns", "pickups", "doors", "props", "scenario"],
     size: ["w", "h"],
     entry: ["x", "y", "w", "h", "label", "kind"],
     exit: ["id", "x", "y", "w", "h", "label", "to", "open", "kind"],
-    wall: ["x", "y", "w", "h"],
+    wall: ["x", "y", "w", "h", "material"],
     room: ["id", "label", "x", "y", "w", "h"],
     zone: ["id", "x", "y", "w", "h"],
     spawn: ["x", "y", "type", "walk_to", "face", "look", "group", "unarmed"],
     pickup: ["x", "y", "weapon"],
+    /* swinging doors (hinge x/y, shut angle in degrees, leaf length): kept
+       verbatim, only key-ordered */
+    door: ["x", "y", "angle", "length"],
     /* placed props (decoration; edited by the NATIVE editor in the ?viz LEVELS
        tab, not here): kept verbatim, only key-ordered */
     prop: ["kind", "x", "y", "rot", "size"],
@@ -224,6 +235,8 @@
     // placed props: this editor does not edit them (the native ?viz LEVELS
     // editor does) — pass the array through untouched
     if (Array.isArray(raw.props)) f.props = raw.props;
+    // doors likewise (hand-placed in the JSON for now)
+    if (Array.isArray(raw.doors)) f.doors = raw.doors;
     // keep unknown top-level keys so we don't destroy the other side's extras
     for (const k of Object.keys(raw)) if (!ORDER.floor.includes(k)) f[k] = raw[k];
     return f;
@@ -257,6 +270,7 @@
     if (Array.isArray(f.zones)) f.zones = f.zones.map((z) => ordered(z, ORDER.zone));
     if (Array.isArray(f.spawns)) f.spawns = f.spawns.map((s) => ordered(s, ORDER.spawn));
     if (Array.isArray(f.pickups)) f.pickups = f.pickups.map((p) => ordered(p, ORDER.pickup));
+    if (Array.isArray(f.doors)) f.doors = f.doors.map((d) => (isObj(d) ? ordered(d, ORDER.door) : d));
     if (Array.isArray(f.props)) f.props = f.props.map((p) => (isObj(p) ? ordered(p, ORDER.prop) : p));
     if (Array.isArray(f.scenario)) f.scenario = f.scenario.map((s) => {
       const st = ordered(s, ORDER.step);
@@ -358,7 +372,10 @@
         if (ctx.knownIds && ctx.knownIds.size && !ctx.knownIds.has(e.to)) warn(p + ".to", "exit \"" + e.id + "\": floor " + e.to + " is not in index.json (yet)");
       }
     });
-    (f.walls || []).forEach((w, i) => { if (!(w.w > 0 && w.h > 0)) err("walls[" + i + "]", "wall must have positive size"); });
+    (f.walls || []).forEach((w, i) => {
+      if (!(w.w > 0 && w.h > 0)) err("walls[" + i + "]", "wall must have positive size");
+      if (w.material !== undefined && !WALL_MATERIALS.includes(w.material)) err("walls[" + i + "].material", "unknown wall material " + w.material);
+    });
     (f.zones || []).forEach((z, i) => { if (!(z.w > 0 && z.h > 0)) err("zones[" + i + "]", "zone \"" + z.id + "\" must have positive size"); });
     const checkSpawn = (s, p) => {
       if (!SPAWN_TYPES.includes(s.type)) err(p, "unknown spawn type " + s.type);
@@ -450,8 +467,9 @@
 
   return {
     SPEAKERS, SPEAKER_COLORS, SPEAKER_TAGS, SPAWN_TYPES, SPAWN_LETTER, SPAWN_COLORS, WEAPONS,
-    PASSIVE_LOOKS, PORTAL_KINDS, SURFACES, SURFACE_TO,
+    PASSIVE_LOOKS, PORTAL_KINDS, SURFACES, SURFACE_TO, WALL_MATERIALS,
     TRIGGER_KINDS, ACTION_KINDS, SFX_NAMES, GATE_INPUTS, MAX_FLOOR, ORDER,
     blankFloor, normalize, canonical, stringify, validate, fileNameFor, floorLabel, pad2,
+    setWeapons,
   };
 });
@@ -113,6 +113,14 @@
     if (!r.ok) throw new Error(r.status + " " + path);
     return r.json();
   }
+  /* the weapons table (weapons/weapons.json) defines the pickup weapon ids */
+  async function loadWeapons() {
+    try { F.setWeapons(((await fetchJSON("weapons/weapons.json")).weapons || []).map((w) => w && w.id)); } catch (e) { /* keep the stock set */ }
+    const sel = $("#pickup-weapon"), cur = sel.value;
+    sel.innerHTML = "";
+    F.WEAPONS.forEach((id) => { const o = document.createElement("option"); o.textContent = id; sel.appendChild(o); });
+    if (F.WEAPONS.includes(cur)) sel.value = cur;
+  }
   async function loadIndex(keepLoaded) {
     let entries = null, dir = S.dir;
     try { entries = parseIndex(await fetchJSON(dir + "index.json")); } catch (e) { entries = null; }
@@ -1075,6 +1083,7 @@
   };
   (async () => {
     await loadFont();
+    await loadWeapons();
     setTool("select");
     updateUndoButtons();
     const ok = await loadIndex(false);